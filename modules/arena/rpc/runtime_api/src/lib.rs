@@ -0,0 +1,44 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::all)]
+
+use codec::Codec;
+use sp_core::H160;
+use sp_std::vec::Vec;
+
+pub use module_arena::{Contender, MatchRecord, MatchReport, SeasonIndex};
+
+sp_api::decl_runtime_apis! {
+	pub trait ArenaApi<BlockNumber> where
+		BlockNumber: Codec,
+	{
+		/// The final leaderboard of `season`, or the live leaderboard for the open season or `None`.
+		fn leaderboard(season: Option<SeasonIndex>) -> Vec<(H160, u32)>;
+
+		/// The matches played by `contract` during `season`.
+		fn match_history(season: SeasonIndex, contract: H160) -> Vec<MatchRecord<BlockNumber>>;
+
+		/// The current rating of `contract`.
+		fn contract_rating(contract: H160) -> u32;
+
+		/// Simulate a match between `a` and `b` without committing any state change.
+		fn dry_run_match(a: Contender, b: Contender) -> Result<MatchReport, sp_runtime::DispatchError>;
+	}
+}
@@ -0,0 +1,82 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Arena rpc interface.
+
+use jsonrpc_core::{Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use module_arena::{Contender, MatchReport};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	codec::Codec,
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor},
+};
+use std::{marker::PhantomData, sync::Arc};
+
+pub use module_arena_rpc_runtime_api::ArenaApi as ArenaRuntimeApi;
+pub use rpc_impl_ArenaApi::gen_server::ArenaApi as ArenaApiServer;
+
+/// Arena rpc interface.
+#[rpc(server)]
+pub trait ArenaApi<BlockHash> {
+	/// Simulate a match between two contenders at the given block, returning the moves and gas
+	/// used of every round and the winner. No state is changed.
+	#[rpc(name = "arena_dryRunMatch")]
+	fn dry_run_match(&self, a: Contender, b: Contender, at: Option<BlockHash>) -> Result<MatchReport>;
+}
+
+fn internal_err<T: ToString>(message: T) -> Error {
+	Error {
+		code: ErrorCode::InternalError,
+		message: message.to_string(),
+		data: None,
+	}
+}
+
+pub struct Arena<B, C> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<B, C> Arena<B, C> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<B, C> ArenaApi<<B as BlockT>::Hash> for Arena<B, C>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + 'static,
+	C::Api: ArenaRuntimeApi<B, NumberFor<B>>,
+	NumberFor<B>: Codec,
+{
+	fn dry_run_match(&self, a: Contender, b: Contender, at: Option<<B as BlockT>::Hash>) -> Result<MatchReport> {
+		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		self.client
+			.runtime_api()
+			.dry_run_match(&BlockId::Hash(hash), a, b)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))
+	}
+}
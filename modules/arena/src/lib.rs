@@ -19,15 +19,49 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use frame_support::{log, pallet_prelude::*, transactional};
+use frame_support::{
+	log,
+	pallet_prelude::*,
+	traits::{BalanceStatus, Currency, ExistenceRequirement, NamedReservableCurrency, Randomness},
+	transactional, PalletId,
+};
 use frame_system::pallet_prelude::*;
+use module_evm::{ExitError, ExitReason};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
-use sp_core::{H160, U256};
-use sp_runtime::traits::{UniqueSaturatedInto, Zero};
+use primitives::{evm::decode_revert_message, to_bytes, ReserveIdentifier};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_core::{H160, H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::{
+	traits::{AccountIdConversion, Saturating, UniqueSaturatedInto, Zero},
+	Perbill, TransactionOutcome,
+};
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
-use support::{AddressMapping, ExecutionMode, InvokeContext, EVM};
+use support::{AddressMapping, ArenaContractInfo, ArenaManager, ArenaRoundInfo, ExecutionMode, InvokeContext, EVM};
+
+mod mock;
+pub mod rating;
+mod rules;
+mod strategies;
+mod tests;
+pub mod weights;
 
 pub use module::*;
+pub use rules::{GameRules, PrisonersDilemma, RockPaperScissors, SealedBid, SealedBidState, Side};
+pub use strategies::MAX_MARKOV_ORDER;
+pub use weights::WeightInfo;
+
+pub const RESERVE_ID: ReserveIdentifier = ReserveIdentifier::Arena;
+
+/// The gas limit of the `owner()` call made to check the owner of a contract.
+pub const OWNER_CALL_GAS_LIMIT: u64 = 2_100_000;
+
+/// The storage version, 1 indexes the instances of each contract in `ContractInstances`.
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+pub type BalanceOf<T, I = ()> =
+	<<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 #[derive(Default, Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct ContractInfo {
@@ -36,18 +70,171 @@ pub struct ContractInfo {
 	wins: u32,
 }
 
+pub type SeasonIndex = u32;
+
+/// The outcome of a match for one side.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, Copy, RuntimeDebug, TypeInfo)]
+pub enum MatchOutcome {
+	Win,
+	Draw,
+	Loss,
+}
+
+impl MatchOutcome {
+	fn reverse(self) -> Self {
+		match self {
+			MatchOutcome::Win => MatchOutcome::Loss,
+			MatchOutcome::Draw => MatchOutcome::Draw,
+			MatchOutcome::Loss => MatchOutcome::Win,
+		}
+	}
+}
+
+/// A match played by a contract during a season.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+pub struct MatchRecord<BlockNumber> {
+	/// The block the match was played at.
+	pub block_number: BlockNumber,
+	/// The instance of the contract that played.
+	pub instance: u32,
+	pub opponent: Contender,
+	pub outcome: MatchOutcome,
+	/// The rating of the contract after the match.
+	pub rating: u32,
+}
+
+/// A season of the arena.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+pub struct SeasonInfo<BlockNumber> {
+	pub started_at: BlockNumber,
+	/// The block the season was closed at, `None` while it is open.
+	pub ended_at: Option<BlockNumber>,
+	/// The final leaderboard of contracts and ratings, filled when the season is closed.
+	pub leaderboard: Vec<(H160, u32)>,
+}
+
+/// A round of a simulated match.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+pub struct RoundReport {
+	/// The decoded moves of both sides.
+	pub moves: (U256, U256),
+	/// The gas used by both sides, zero for the built-in contenders.
+	pub used_gas: (u64, u64),
+}
+
+/// The outcome of a simulated match.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+pub struct MatchReport {
+	pub rounds: Vec<RoundReport>,
+	/// The winner of the match, `None` for a draw.
+	pub winner: Option<Side>,
+}
+
+/// Why a call to a contender contract failed.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+pub enum ContenderFailure {
+	/// The contract reverted, with the decoded revert message if any.
+	Reverted(Vec<u8>),
+	/// The call ran out of gas, or the contract used up its round gas budget.
+	OutOfGas,
+	/// The call exceeded the storage limit.
+	OutOfStorage,
+	/// Any other execution error.
+	Error,
+	/// The output of the call is longer than a single word.
+	InvalidOutput,
+}
+
+/// The entry stake of a registered contract.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct StakeInfo<AccountId, Balance> {
+	/// The account the stake is reserved from.
+	pub owner: AccountId,
+	/// The remaining reserved amount.
+	pub amount: Balance,
+}
+
+/// The phase of a game round.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, Copy, RuntimeDebug, TypeInfo)]
+pub enum RoundPhase {
+	/// Matches are played directly.
+	Play,
+	/// Contracts commit to their moves.
+	Commit,
+	/// The committed moves are revealed and scored.
+	Reveal,
+}
+
+/// The progress of the game round in progress.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+pub struct RoundProgress<BlockNumber> {
+	/// The block the round started at.
+	pub started_at: BlockNumber,
+	pub phase: RoundPhase,
+	/// The instances playing this round, every ordered pair of them is a match.
+	pub contenders: Vec<u32>,
+	/// The index of the next pairing to play.
+	pub cursor: u32,
+}
+
+/// A match waiting for its moves to be revealed.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+pub struct PendingMatch {
+	/// The instance id and contender of side A.
+	pub a: (u32, Contender),
+	/// The instance id and contender of side B.
+	pub b: (u32, Contender),
+	/// The moves committed by side A, `None` for built-in contenders or failed commits.
+	pub commitment_a: Option<H256>,
+	/// The moves committed by side B, `None` for built-in contenders or failed commits.
+	pub commitment_b: Option<H256>,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum Contender {
 	Contract(H160),
 	AlwaysZero,
 	Rotate,
 	Smart,
+	/// Counters the most frequent move of the opponent over its last `window` moves, or over the
+	/// whole match if `window` is zero.
+	FrequencyCounter {
+		window: u32,
+	},
+	/// Plays `first_move`, then repeats the previous move of the opponent.
+	TitForTat {
+		first_move: u32,
+	},
+	/// Predicts the next move of the opponent from what followed its last `order` moves, and
+	/// counters it. `order` is between 1 and `MAX_MARKOV_ORDER`.
+	Markov {
+		order: u8,
+	},
+	/// Plays a random move in `0..moves` drawn from the configured `Randomness`. `moves` is not
+	/// zero.
+	Random {
+		moves: u32,
+	},
 }
 
 impl Contender {
 	fn is_contract(&self) -> bool {
 		return matches!(self, Contender::Contract(_));
 	}
+
+	/// Whether the parameters of a built-in contender are valid.
+	fn is_valid(&self) -> bool {
+		match self {
+			Contender::Markov { order } => (1..=MAX_MARKOV_ORDER).contains(order),
+			Contender::Random { moves } => *moves > 0,
+			_ => true,
+		}
+	}
 }
 
 #[module_evm_utility_macro::generate_function_selector]
@@ -56,6 +243,8 @@ impl Contender {
 pub enum Action {
 	Owner = "owner()",
 	Play = "play(uint256,uint256,uint256)",
+	Commit = "commit(uint256)",
+	Reveal = "reveal(uint256)",
 }
 
 #[frame_support::pallet]
@@ -63,8 +252,8 @@ pub mod module {
 	use super::*;
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
-		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		type Event: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::Event>;
 		type EVM: EVM<Self::AccountId>;
 		type AddressMapping: AddressMapping<Self::AccountId>;
 
@@ -87,208 +276,1320 @@ pub mod module {
 		type MaxInstancesPerContender: Get<u32>;
 
 		type ContractInvoker: Get<Self::AccountId>;
+
+		/// The rules of the game played in this arena.
+		type GameRules: GameRules;
+
+		/// Play matches in commit–reveal mode. Contracts commit to the hash of all their moves of a
+		/// match, and reveal them `RevealDelay` blocks after every pairing has committed.
+		#[pallet::constant]
+		type CommitReveal: Get<bool>;
+
+		/// The number of blocks between the commit and the reveal of the moves.
+		#[pallet::constant]
+		type RevealDelay: Get<Self::BlockNumber>;
+
+		/// The gas limit of a single contender call.
+		#[pallet::constant]
+		type ContenderGasLimit: Get<u64>;
+
+		/// The error returned by the EVM when a contender call exceeds its storage limit.
+		type OutOfStorageError: Get<DispatchError>;
+
+		/// The maximum number of matches played in a block.
+		#[pallet::constant]
+		type MaxMatchesPerBlock: Get<u32>;
+
+		/// The number of matches played in `on_initialize` of every block, so a round progresses
+		/// even when no block has weight left for `on_idle`.
+		#[pallet::constant]
+		type MinMatchesPerBlock: Get<u32>;
+
+		/// The currency used for entry stakes and prizes.
+		type Currency: NamedReservableCurrency<Self::AccountId, ReserveIdentifier = ReserveIdentifier>;
+
+		/// The stake reserved from the owner when a contract is registered.
+		#[pallet::constant]
+		type EntryStake: Get<BalanceOf<Self, I>>;
+
+		/// The prize paid out of the prize pool every game round, shared among the winners.
+		#[pallet::constant]
+		type PrizePerRound: Get<BalanceOf<Self, I>>;
+
+		/// The portion of the remaining stake slashed to the prize pool when an instance is evicted.
+		#[pallet::constant]
+		type EvictionSlash: Get<Perbill>;
+
+		/// The arena's pallet id, used for deriving the prize pool account.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The number of contracts kept in the final leaderboard of a season.
+		#[pallet::constant]
+		type LeaderboardSize: Get<u32>;
+
+		/// The maximum number of match records kept per contract and season, older records are
+		/// dropped first.
+		#[pallet::constant]
+		type MaxMatchRecords: Get<u32>;
+
+		/// The gas a contract can use over all its calls in a round.
+		#[pallet::constant]
+		type RoundGasBudget: Get<u64>;
+
+		/// The number of failed calls in a round after which a contract is disqualified.
+		#[pallet::constant]
+		type MaxFailures: Get<u32>;
+
+		/// The source of randomness of the `Random` built-in contender.
+		type Randomness: Randomness<H256, Self::BlockNumber>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::error]
-	pub enum Error<T> {
+	pub enum Error<T, I = ()> {
 		QueueFull,
 		InvalidOwner,
 		AlreadyRegistered,
+		SeasonAlreadyOpen,
+		NoOpenSeason,
+		InvalidContender,
+		/// The contract is not registered.
+		NotRegistered,
+		/// The caller is not the registered owner of the contract.
+		NotOwner,
+		/// The contract has no instance playing or queued.
+		NotActive,
+		AlreadyPaused,
+		NotPaused,
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
-	pub enum Event<T: Config> {
-		InstanceRegistered { contender: Contender, id: u32 },
-		InstanceRemoved { contender: Contender, id: u32 },
-		PlayStarted { contenders: (u32, u32) },
-		PlayRoundResult { result: (U256, U256) },
-		PlayEnded { winner: Option<u32> },
-		GameResult { points: BTreeMap<u32, i32> },
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		InstanceRegistered {
+			contender: Contender,
+			id: u32,
+		},
+		InstanceRemoved {
+			contender: Contender,
+			id: u32,
+		},
+		PlayStarted {
+			contenders: (u32, u32),
+		},
+		PlayRoundResult {
+			result: (U256, U256),
+		},
+		PlayEnded {
+			winner: Option<u32>,
+		},
+		GameResult {
+			points: BTreeMap<u32, i32>,
+		},
+		/// A game round has started with a snapshot of `instances` contender instances.
+		RoundStarted {
+			instances: u32,
+		},
+		/// A contract has committed to its moves for a match.
+		MovesCommitted {
+			match_id: u32,
+			id: u32,
+			commitment: H256,
+		},
+		/// An instance failed to reveal moves matching its commitment and forfeited the match.
+		Forfeited {
+			match_id: u32,
+			id: u32,
+		},
+		/// The entry stake of a contract has been reserved.
+		StakeReserved {
+			contract: H160,
+			owner: T::AccountId,
+			amount: BalanceOf<T, I>,
+		},
+		/// A winning instance has been paid from the prize pool.
+		PrizePaid {
+			contract: H160,
+			id: u32,
+			owner: T::AccountId,
+			amount: BalanceOf<T, I>,
+		},
+		/// The stake of a contract has been slashed to the prize pool for an evicted instance.
+		StakeSlashed {
+			contract: H160,
+			id: u32,
+			owner: T::AccountId,
+			amount: BalanceOf<T, I>,
+		},
+		/// A new season has been opened.
+		SeasonOpened {
+			season: SeasonIndex,
+		},
+		/// The season has been closed and its leaderboard recorded.
+		SeasonClosed {
+			season: SeasonIndex,
+			leaderboard: Vec<(H160, u32)>,
+		},
+		/// A call to a contender contract failed.
+		ContenderFailed {
+			contract: H160,
+			reason: ContenderFailure,
+		},
+		/// The contract failed too many calls and has been removed from the arena, its remaining
+		/// stake is slashed into the prize pool.
+		Disqualified {
+			contract: H160,
+			slashed: BalanceOf<T, I>,
+		},
+		/// The contract has been withdrawn from the arena and its remaining stake unreserved.
+		Withdrawn {
+			contract: H160,
+			owner: T::AccountId,
+			amount: BalanceOf<T, I>,
+		},
+		/// The instances of the contract have been removed until it is resumed.
+		Paused {
+			contract: H160,
+		},
+		/// The paused contract has been registered again.
+		Resumed {
+			contract: H160,
+		},
+		/// The registration of a contract has moved to a new contract.
+		ContractUpgraded {
+			old: H160,
+			new: H160,
+		},
+		/// The registration and remaining stake of a contract have moved to a new owner.
+		OwnershipTransferred {
+			contract: H160,
+			from: T::AccountId,
+			to: T::AccountId,
+		},
 	}
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
-	pub struct Pallet<T>(_);
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::storage]
+	pub type ContractInfos<T: Config<I>, I: 'static = ()> = StorageMap<_, Twox64Concat, H160, ContractInfo, ValueQuery>;
+
+	#[pallet::storage]
+	pub type Contenders<T: Config<I>, I: 'static = ()> = StorageMap<_, Twox64Concat, u32, Contender, OptionQuery>;
+
+	/// The instances of each contract, at most `MaxInstancesPerContender`.
+	///
+	/// ContractInstances: map H160 => Vec<u32>
+	#[pallet::storage]
+	pub type ContractInstances<T: Config<I>, I: 'static = ()> = StorageMap<_, Twox64Concat, H160, Vec<u32>, ValueQuery>;
+
+	#[pallet::storage]
+	pub type ContenderInstancesCount<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	pub type ContenderQueue<T: Config<I>, I: 'static = ()> = StorageValue<_, Vec<Contender>, ValueQuery>;
+
+	#[pallet::storage]
+	pub type NextContenderInstanceId<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+	/// The matches committed in the current round, waiting for the reveal.
+	///
+	/// PendingMatches: map u32 => Option<PendingMatch>
+	#[pallet::storage]
+	pub type PendingMatches<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u32, PendingMatch, OptionQuery>;
+
+	#[pallet::storage]
+	pub type NextMatchId<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
 
+	/// The game round in progress.
+	///
+	/// CurrentRound: Option<RoundProgress>
 	#[pallet::storage]
-	pub type ContractInfos<T: Config> = StorageMap<_, Twox64Concat, H160, ContractInfo, ValueQuery>;
+	pub type CurrentRound<T: Config<I>, I: 'static = ()> = StorageValue<_, RoundProgress<T::BlockNumber>, OptionQuery>;
 
+	/// The points scored by the instances in the round in progress.
+	///
+	/// RoundPoints: BTreeMap<u32, i32>
 	#[pallet::storage]
-	pub type Contenders<T: Config> = StorageMap<_, Twox64Concat, u32, Contender, OptionQuery>;
+	pub type RoundPoints<T: Config<I>, I: 'static = ()> = StorageValue<_, BTreeMap<u32, i32>, ValueQuery>;
 
+	/// The block at which the pending matches are revealed.
+	///
+	/// RevealAt: Option<BlockNumber>
 	#[pallet::storage]
-	pub type ContenderInstancesCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+	pub type RevealAt<T: Config<I>, I: 'static = ()> = StorageValue<_, T::BlockNumber, OptionQuery>;
 
+	/// The entry stakes of registered contracts.
+	///
+	/// Stakes: map H160 => Option<StakeInfo>
 	#[pallet::storage]
-	pub type ContenderQueue<T: Config> = StorageValue<_, Vec<Contender>, ValueQuery>;
+	pub type Stakes<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, H160, StakeInfo<T::AccountId, BalanceOf<T, I>>, OptionQuery>;
 
+	/// The Elo ratings of the contracts that have played.
+	///
+	/// Ratings: map H160 => Option<u32>
 	#[pallet::storage]
-	pub type NextContenderInstanceId<T: Config> = StorageValue<_, u32, ValueQuery>;
+	pub type Ratings<T: Config<I>, I: 'static = ()> = CountedStorageMap<_, Twox64Concat, H160, u32, OptionQuery>;
+
+	/// The season currently open.
+	///
+	/// CurrentSeason: Option<SeasonIndex>
+	#[pallet::storage]
+	pub type CurrentSeason<T: Config<I>, I: 'static = ()> = StorageValue<_, SeasonIndex, OptionQuery>;
+
+	#[pallet::storage]
+	pub type NextSeasonIndex<T: Config<I>, I: 'static = ()> = StorageValue<_, SeasonIndex, ValueQuery>;
+
+	/// The opened seasons.
+	///
+	/// Seasons: map SeasonIndex => Option<SeasonInfo>
+	#[pallet::storage]
+	pub type Seasons<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, SeasonIndex, SeasonInfo<T::BlockNumber>, OptionQuery>;
+
+	/// The matches played by the contracts per season.
+	///
+	/// MatchRecords: double_map SeasonIndex, H160 => Vec<MatchRecord>
+	#[pallet::storage]
+	pub type MatchRecords<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		SeasonIndex,
+		Twox64Concat,
+		H160,
+		Vec<MatchRecord<T::BlockNumber>>,
+		ValueQuery,
+	>;
+
+	/// The gas used by the contracts in the current round.
+	///
+	/// RoundGasUsed: map H160 => u64
+	#[pallet::storage]
+	pub type RoundGasUsed<T: Config<I>, I: 'static = ()> = StorageMap<_, Twox64Concat, H160, u64, ValueQuery>;
+
+	/// The number of failed calls of the contracts in the current round.
+	///
+	/// Failures: map H160 => u32
+	#[pallet::storage]
+	pub type Failures<T: Config<I>, I: 'static = ()> = StorageMap<_, Twox64Concat, H160, u32, ValueQuery>;
+
+	/// The contracts paused by their owners.
+	///
+	/// Paused: map H160 => bool
+	#[pallet::storage]
+	pub type Paused<T: Config<I>, I: 'static = ()> = StorageMap<_, Twox64Concat, H160, bool, ValueQuery>;
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+	impl<T: Config<I>, I: 'static> Hooks<T::BlockNumber> for Pallet<T, I> {
 		fn on_initialize(n: T::BlockNumber) -> Weight {
-			if !(n % 10u8.into()).is_zero() {
+			let weight = if (n % 10u8.into()).is_zero() {
+				Self::start_round(n)
+			} else {
+				0
+			};
+
+			weight.saturating_add(Self::min_round_step(n))
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T, I>>() >= STORAGE_VERSION {
 				return 0;
 			}
 
-			let mut game_points = BTreeMap::<u32, i32>::new();
+			// index the instances of the contracts, the instances are bounded by the eviction of
+			// the losers at the end of each round
+			let mut count: Weight = 0;
+			for (id, contender) in Contenders::<T, I>::iter() {
+				count += 1;
+				if let Contender::Contract(contract) = contender {
+					ContractInstances::<T, I>::append(contract, id);
+				}
+			}
+			STORAGE_VERSION.put::<Pallet<T, I>>();
+
+			T::DbWeight::get().reads_writes(count.saturating_add(1), count.saturating_add(1))
+		}
+
+		fn on_idle(n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::process_round(n, remaining_weight, T::MaxMatchesPerBlock::get())
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		#[pallet::weight(T::WeightInfo::register().saturating_add(T::EVM::gas_to_weight(OWNER_CALL_GAS_LIMIT)))]
+		#[transactional]
+		pub fn register(origin: OriginFor<T>, contract: H160) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::is_active(&contract), Error::<T, I>::AlreadyRegistered);
+			ensure!(Self::contract_owner(contract)? == who, Error::<T, I>::InvalidOwner);
+
+			// an evicted contract registers again with a full stake
+			if let Some(stake) = Stakes::<T, I>::take(&contract) {
+				T::Currency::unreserve_named(&RESERVE_ID, &stake.owner, stake.amount);
+			}
+
+			let amount = T::EntryStake::get();
+			T::Currency::reserve_named(&RESERVE_ID, &who, amount)?;
+			Stakes::<T, I>::insert(
+				&contract,
+				StakeInfo {
+					owner: who.clone(),
+					amount,
+				},
+			);
+			Self::deposit_event(Event::<T, I>::StakeReserved {
+				contract,
+				owner: who,
+				amount,
+			});
+
+			Self::do_register_contender(Contender::Contract(contract))?;
+
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::register_contender(*count))]
+		#[transactional]
+		pub fn register_contender(origin: OriginFor<T>, contender: Contender, count: u32) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(contender.is_valid(), Error::<T, I>::InvalidContender);
+
+			Self::add_contender(&contender, count);
+
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::open_season())]
+		#[transactional]
+		pub fn open_season(origin: OriginFor<T>) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(!CurrentSeason::<T, I>::exists(), Error::<T, I>::SeasonAlreadyOpen);
+
+			let season = NextSeasonIndex::<T, I>::mutate(|index| {
+				let current = *index;
+				*index = index.saturating_add(1);
+				current
+			});
+			Seasons::<T, I>::insert(
+				season,
+				SeasonInfo {
+					started_at: frame_system::Pallet::<T>::block_number(),
+					ended_at: None,
+					leaderboard: vec![],
+				},
+			);
+			CurrentSeason::<T, I>::put(season);
+
+			Self::deposit_event(Event::<T, I>::SeasonOpened { season });
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::close_season(Ratings::<T, I>::count()))]
+		#[transactional]
+		pub fn close_season(origin: OriginFor<T>) -> DispatchResult {
+			ensure_root(origin)?;
+			let season = CurrentSeason::<T, I>::take().ok_or(Error::<T, I>::NoOpenSeason)?;
+
+			let leaderboard = Self::leaderboard(T::LeaderboardSize::get());
+			Seasons::<T, I>::mutate(season, |maybe_info| {
+				if let Some(info) = maybe_info {
+					info.ended_at = Some(frame_system::Pallet::<T>::block_number());
+					info.leaderboard = leaderboard.clone();
+				}
+			});
+
+			Self::deposit_event(Event::<T, I>::SeasonClosed { season, leaderboard });
+			Ok(())
+		}
+
+		/// Remove every instance of the contract and unreserve its remaining stake. The points
+		/// and wins of the contract are dropped, its rating is kept.
+		#[pallet::weight(T::WeightInfo::withdraw(T::MaxInstancesPerContender::get()))]
+		#[transactional]
+		pub fn withdraw(origin: OriginFor<T>, contract: H160) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let stake = Self::ensure_owner(&who, &contract)?;
+
+			Self::remove_instances(contract);
+			Stakes::<T, I>::remove(contract);
+			ContractInfos::<T, I>::remove(contract);
+			Failures::<T, I>::remove(contract);
+			Paused::<T, I>::remove(contract);
+
+			let unreserved = stake
+				.amount
+				.saturating_sub(T::Currency::unreserve_named(&RESERVE_ID, &who, stake.amount));
+			Self::deposit_event(Event::<T, I>::Withdrawn {
+				contract,
+				owner: who,
+				amount: unreserved,
+			});
+
+			Ok(())
+		}
+
+		/// Remove every instance of the contract, keeping its registration and stake until it is
+		/// resumed or withdrawn.
+		#[pallet::weight(T::WeightInfo::pause(T::MaxInstancesPerContender::get()))]
+		#[transactional]
+		pub fn pause(origin: OriginFor<T>, contract: H160) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_owner(&who, &contract)?;
+			ensure!(!Paused::<T, I>::get(contract), Error::<T, I>::AlreadyPaused);
+			ensure!(Self::is_active(&contract), Error::<T, I>::NotActive);
+
+			Self::remove_instances(contract);
+			Paused::<T, I>::insert(contract, true);
+			Self::deposit_event(Event::<T, I>::Paused { contract });
+
+			Ok(())
+		}
+
+		/// Register a paused contract again, as a new entry.
+		#[pallet::weight(T::WeightInfo::resume())]
+		#[transactional]
+		pub fn resume(origin: OriginFor<T>, contract: H160) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_owner(&who, &contract)?;
+			ensure!(Paused::<T, I>::take(contract), Error::<T, I>::NotPaused);
+
+			Self::do_register_contender(Contender::Contract(contract))?;
+			Self::deposit_event(Event::<T, I>::Resumed { contract });
+
+			Ok(())
+		}
+
+		/// Move the registration of `old` to `new`, a contract owned by the caller that is not
+		/// registered. The instances, points, stake and rating are kept, and so are the gas used and
+		/// failures of the current round.
+		#[pallet::weight(
+			T::WeightInfo::upgrade_contract(T::MaxInstancesPerContender::get())
+				.saturating_add(T::EVM::gas_to_weight(OWNER_CALL_GAS_LIMIT))
+		)]
+		#[transactional]
+		pub fn upgrade_contract(origin: OriginFor<T>, old: H160, new: H160) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let stake = Self::ensure_owner(&who, &old)?;
+			ensure!(
+				!ContractInfos::<T, I>::contains_key(&new) && !Stakes::<T, I>::contains_key(&new),
+				Error::<T, I>::AlreadyRegistered
+			);
+			ensure!(Self::contract_owner(new)? == who, Error::<T, I>::InvalidOwner);
+
+			let (old_contender, new_contender) = (Contender::Contract(old), Contender::Contract(new));
+			let ids = ContractInstances::<T, I>::take(old);
+			for id in ids.iter() {
+				Contenders::<T, I>::insert(id, &new_contender);
+			}
+			ContractInstances::<T, I>::insert(new, ids);
+			ContenderQueue::<T, I>::mutate(|queue| {
+				for c in queue.iter_mut().filter(|c| **c == old_contender) {
+					*c = new_contender.clone();
+				}
+			});
+			if let Ok(info) = ContractInfos::<T, I>::try_get(old) {
+				ContractInfos::<T, I>::remove(old);
+				ContractInfos::<T, I>::insert(new, info);
+			}
+			if let Some(rating) = Ratings::<T, I>::take(old) {
+				Ratings::<T, I>::insert(new, rating);
+			}
+			if Paused::<T, I>::take(old) {
+				Paused::<T, I>::insert(new, true);
+			}
+			if let Ok(used_gas) = RoundGasUsed::<T, I>::try_get(old) {
+				RoundGasUsed::<T, I>::remove(old);
+				RoundGasUsed::<T, I>::insert(new, used_gas);
+			}
+			if let Ok(failures) = Failures::<T, I>::try_get(old) {
+				Failures::<T, I>::remove(old);
+				Failures::<T, I>::insert(new, failures);
+			}
+			Stakes::<T, I>::remove(old);
+			Stakes::<T, I>::insert(new, stake);
+
+			Self::deposit_event(Event::<T, I>::ContractUpgraded { old, new });
+
+			Ok(())
+		}
+
+		/// Hand the registration of the contract and its remaining stake over to `new_owner`, who
+		/// must be the owner of the contract.
+		#[pallet::weight(
+			T::WeightInfo::transfer_ownership().saturating_add(T::EVM::gas_to_weight(OWNER_CALL_GAS_LIMIT))
+		)]
+		#[transactional]
+		pub fn transfer_ownership(origin: OriginFor<T>, contract: H160, new_owner: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let stake = Self::ensure_owner(&who, &contract)?;
+			ensure!(
+				Self::contract_owner(contract)? == new_owner,
+				Error::<T, I>::InvalidOwner
+			);
+
+			let unmoved = T::Currency::repatriate_reserved_named(
+				&RESERVE_ID,
+				&who,
+				&new_owner,
+				stake.amount,
+				BalanceStatus::Reserved,
+			)?;
+			Stakes::<T, I>::insert(
+				contract,
+				StakeInfo {
+					owner: new_owner.clone(),
+					amount: stake.amount.saturating_sub(unmoved),
+				},
+			);
+
+			Self::deposit_event(Event::<T, I>::OwnershipTransferred {
+				contract,
+				from: who,
+				to: new_owner,
+			});
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// The prize pool account, funded by slashed stakes and external deposits.
+	pub fn prize_pool_account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// Split the prize of this round equally between the top `WinnerCount` instances of staked
+	/// contracts, instances withdrawn or disqualified during the round are skipped.
+	fn pay_prizes(results: &[(u32, i32)]) {
+		let winners = results
+			.iter()
+			.filter_map(|(id, _)| match Contenders::<T, I>::get(id) {
+				Some(Contender::Contract(contract)) => {
+					Stakes::<T, I>::get(&contract).map(|stake| (*id, contract, stake.owner))
+				}
+				_ => None,
+			})
+			.take(T::WinnerCount::get() as usize)
+			.collect::<Vec<_>>();
+		if winners.is_empty() {
+			return;
+		}
+
+		let pool = Self::prize_pool_account_id();
+		let prize = T::PrizePerRound::get().min(T::Currency::free_balance(&pool));
+		let share = prize / (winners.len() as u32).into();
+		if share.is_zero() {
+			return;
+		}
+
+		for (id, contract, owner) in winners {
+			match T::Currency::transfer(&pool, &owner, share, ExistenceRequirement::AllowDeath) {
+				Ok(()) => Self::deposit_event(Event::<T, I>::PrizePaid {
+					contract,
+					id,
+					owner,
+					amount: share,
+				}),
+				Err(e) => log::warn!(target: "arena", "pay prize to {:?} failed: {:?}", contract, e),
+			}
+		}
+	}
 
-			let mut add_point = |id: u32, point: i32, contender: &Contender| {
-				if !contender.is_contract() {
+	/// Move `EvictionSlash` of the remaining stake of `contract` to the prize pool.
+	fn slash_stake(contract: &H160, id: u32) {
+		Stakes::<T, I>::mutate(contract, |maybe_stake| {
+			if let Some(stake) = maybe_stake {
+				let slash = T::EvictionSlash::get() * stake.amount;
+				if slash.is_zero() {
 					return;
 				}
-				if let Some(p) = game_points.get_mut(&id) {
-					*p = *p + point;
-				} else {
-					game_points.insert(id, point);
+				let pool = Self::prize_pool_account_id();
+				let unslashed = T::Currency::repatriate_reserved_named(
+					&RESERVE_ID,
+					&stake.owner,
+					&pool,
+					slash,
+					BalanceStatus::Free,
+				)
+				.unwrap_or(slash);
+				let slashed = slash.saturating_sub(unslashed);
+				stake.amount = stake.amount.saturating_sub(slashed);
+
+				Self::deposit_event(Event::<T, I>::StakeSlashed {
+					contract: *contract,
+					id,
+					owner: stake.owner.clone(),
+					amount: slashed,
+				});
+			}
+		});
+	}
+
+	/// The worst case weight of a single match, every contender call using the full gas limit,
+	/// plus disqualifying both contracts.
+	pub fn match_weight() -> Weight {
+		let calls = 2u64.saturating_mul(T::PlayPerRound::get().max(1).into());
+		let disqualify = T::DbWeight::get().reads_writes(
+			u64::from(T::MaxContenderInstancesCount::get()).saturating_add(4),
+			u64::from(T::MaxInstancesPerContender::get()).saturating_add(6),
+		);
+		T::EVM::gas_to_weight(T::ContenderGasLimit::get())
+			.saturating_mul(calls)
+			.saturating_add(T::DbWeight::get().reads_writes(3, 2))
+			.saturating_add(calls.saturating_mul(T::DbWeight::get().reads_writes(3, 2)))
+			.saturating_add(disqualify.saturating_mul(2))
+	}
+
+	/// The weight of finalizing a round with `count` scored instances.
+	fn finalize_weight(count: usize) -> Weight {
+		let count = (count as u64)
+			.saturating_add(T::EnqueueCount::get().saturating_mul(2).into())
+			.saturating_add(T::WinnerCount::get().into());
+		T::DbWeight::get().reads_writes(count.saturating_mul(5), count.saturating_mul(5))
+	}
+
+	/// Snapshot the current contender instances and start a new game round, unless the previous
+	/// round is still in progress.
+	fn start_round(n: T::BlockNumber) -> Weight {
+		if CurrentRound::<T, I>::exists() {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut contenders = Contenders::<T, I>::iter_keys().collect::<Vec<_>>();
+		contenders.sort_unstable();
+		let instances = contenders.len() as u32;
+		let phase = if T::CommitReveal::get() {
+			RoundPhase::Commit
+		} else {
+			RoundPhase::Play
+		};
+		CurrentRound::<T, I>::put(RoundProgress {
+			started_at: n,
+			phase,
+			contenders,
+			cursor: 0,
+		});
+		Self::deposit_event(Event::<T, I>::RoundStarted { instances });
+
+		T::DbWeight::get().reads_writes(u64::from(instances).saturating_add(1), 1)
+	}
+
+	/// Play `MinMatchesPerBlock` matches of the round in progress, and finalize it if every
+	/// pairing has been played.
+	fn min_round_step(n: T::BlockNumber) -> Weight {
+		let min_matches = T::MinMatchesPerBlock::get();
+		if min_matches.is_zero() {
+			return 0;
+		}
+
+		let db_weight = T::DbWeight::get();
+		let contenders = match CurrentRound::<T, I>::get() {
+			Some(round) => round.contenders.len(),
+			None => return db_weight.reads(1),
+		};
+		let step_weight = Self::match_weight()
+			.saturating_add(db_weight.reads(2))
+			.saturating_mul(min_matches.into())
+			.saturating_add(Self::finalize_weight(contenders))
+			.saturating_add(db_weight.reads_writes(3, 2));
+		db_weight
+			.reads(1)
+			.saturating_add(Self::process_round(n, step_weight, min_matches))
+	}
+
+	/// Play the matches of the current round until `max_matches` or the remaining weight is
+	/// reached, and finalize the round once every pairing has been played.
+	fn process_round(n: T::BlockNumber, remaining_weight: Weight, max_matches: u32) -> Weight {
+		let db_weight = T::DbWeight::get();
+		let mut used_weight = db_weight.reads(1);
+		let mut round = match CurrentRound::<T, I>::get() {
+			Some(round) => round,
+			None => return used_weight,
+		};
+		let mut game_points = RoundPoints::<T, I>::get();
+		used_weight = used_weight.saturating_add(db_weight.reads_writes(2, 2));
+
+		let match_weight = Self::match_weight();
+		let len = round.contenders.len() as u32;
+		let pairings = len.saturating_mul(len);
+		let mut matches = 0u32;
+
+		loop {
+			let finished = match round.phase {
+				RoundPhase::Play | RoundPhase::Commit => round.cursor >= pairings,
+				RoundPhase::Reveal => {
+					if RevealAt::<T, I>::get().map_or(false, |at| n < at) {
+						break;
+					}
+					used_weight = used_weight.saturating_add(db_weight.reads(1));
+					PendingMatches::<T, I>::iter_keys().next().is_none()
 				}
 			};
 
-			for (id1, contender1) in Contenders::<T>::iter() {
-				for (id2, contender2) in Contenders::<T>::iter() {
-					if contender1.is_contract() {
-						Self::deposit_event(Event::<T>::PlayStarted { contenders: (id1, id2) });
-						let winner = Self::play(&contender1, &contender2);
-						if let Some(winner) = winner {
-							if winner == &contender1 {
-								add_point(id1, 2, &contender1);
-								add_point(id2, -1, &contender2);
-								Self::deposit_event(Event::<T>::PlayEnded { winner: Some(id1) })
-							} else {
-								add_point(id1, -1, &contender1);
-								add_point(id2, 2, &contender2);
-								Self::deposit_event(Event::<T>::PlayEnded { winner: Some(id2) })
-							}
+			if finished {
+				if round.phase == RoundPhase::Commit {
+					round.phase = RoundPhase::Reveal;
+					RevealAt::<T, I>::put(n.saturating_add(T::RevealDelay::get()));
+					continue;
+				}
+
+				let finalize_weight = Self::finalize_weight(game_points.len());
+				if used_weight.saturating_add(finalize_weight) > remaining_weight {
+					break;
+				}
+				CurrentRound::<T, I>::kill();
+				RoundPoints::<T, I>::kill();
+				RoundGasUsed::<T, I>::remove_all(None);
+				Failures::<T, I>::remove_all(None);
+				RevealAt::<T, I>::kill();
+				Self::finalize_round(n, game_points);
+				return used_weight.saturating_add(finalize_weight);
+			}
+
+			if matches >= max_matches || used_weight.saturating_add(match_weight) > remaining_weight {
+				break;
+			}
+
+			if round.phase == RoundPhase::Reveal {
+				if let Some((match_id, pending)) = PendingMatches::<T, I>::iter().next() {
+					PendingMatches::<T, I>::remove(match_id);
+					Self::reveal_match(&mut game_points, match_id, pending);
+				}
+			} else {
+				let id1 = round.contenders[(round.cursor / len) as usize];
+				let id2 = round.contenders[(round.cursor % len) as usize];
+				round.cursor += 1;
+
+				let pairing = Contenders::<T, I>::get(id1).zip(Contenders::<T, I>::get(id2));
+				used_weight = used_weight.saturating_add(db_weight.reads(2));
+				match pairing {
+					Some((contender1, contender2)) if contender1.is_contract() => {
+						if round.phase == RoundPhase::Commit {
+							Self::commit_match((id1, contender1), (id2, contender2));
 						} else {
-							add_point(id1, 1, &contender1);
-							add_point(id2, 1, &contender2);
-							Self::deposit_event(Event::<T>::PlayEnded { winner: None })
+							Self::deposit_event(Event::<T, I>::PlayStarted { contenders: (id1, id2) });
+							let winner = Self::play(&contender1, &contender2);
+							Self::record_match_result(&mut game_points, (id1, &contender1), (id2, &contender2), winner);
 						}
 					}
+					_ => continue,
 				}
 			}
 
-			let block_number: u128 = n.unique_saturated_into();
-			let multipler = (block_number / 1800).saturating_sub(7200) + 1;
-			for (id, point) in game_points.iter() {
-				let contract = Contenders::<T>::get(id);
-				if let Some(Contender::Contract(contract)) = contract {
-					ContractInfos::<T>::mutate(contract, |info| {
-						if *point > 0 {
-							info.point = info.point + (*point as u128) * multipler;
+			matches += 1;
+			used_weight = used_weight.saturating_add(match_weight);
+		}
+
+		CurrentRound::<T, I>::put(round);
+		RoundPoints::<T, I>::put(game_points);
+		used_weight
+	}
+
+	fn add_point(game_points: &mut BTreeMap<u32, i32>, id: u32, point: i32, contender: &Contender) {
+		if !contender.is_contract() {
+			return;
+		}
+		if let Some(p) = game_points.get_mut(&id) {
+			*p = *p + point;
+		} else {
+			game_points.insert(id, point);
+		}
+	}
+
+	fn record_match_result(
+		game_points: &mut BTreeMap<u32, i32>,
+		(id1, contender1): (u32, &Contender),
+		(id2, contender2): (u32, &Contender),
+		winner: Option<Side>,
+	) {
+		match winner {
+			Some(Side::A) => {
+				Self::add_point(game_points, id1, 2, contender1);
+				Self::add_point(game_points, id2, -1, contender2);
+				Self::deposit_event(Event::<T, I>::PlayEnded { winner: Some(id1) })
+			}
+			Some(Side::B) => {
+				Self::add_point(game_points, id1, -1, contender1);
+				Self::add_point(game_points, id2, 2, contender2);
+				Self::deposit_event(Event::<T, I>::PlayEnded { winner: Some(id2) })
+			}
+			None => {
+				Self::add_point(game_points, id1, 1, contender1);
+				Self::add_point(game_points, id2, 1, contender2);
+				Self::deposit_event(Event::<T, I>::PlayEnded { winner: None })
+			}
+		}
+
+		Self::rate_match((id1, contender1), (id2, contender2), winner);
+	}
+
+	fn rating_of(contender: &Contender) -> u32 {
+		match contender {
+			Contender::Contract(contract) => Ratings::<T, I>::get(contract).unwrap_or(rating::INITIAL_RATING),
+			_ => rating::INITIAL_RATING,
+		}
+	}
+
+	/// Update the ratings of the contracts of a match, and record it in the open season. The
+	/// built-in contenders keep the initial rating.
+	fn rate_match((id1, contender1): (u32, &Contender), (id2, contender2): (u32, &Contender), winner: Option<Side>) {
+		if contender1 == contender2 {
+			return;
+		}
+
+		let rating1 = Self::rating_of(contender1);
+		let rating2 = Self::rating_of(contender2);
+		let (score1, outcome1) = match winner {
+			Some(Side::A) => (1000, MatchOutcome::Win),
+			Some(Side::B) => (0, MatchOutcome::Loss),
+			None => (500, MatchOutcome::Draw),
+		};
+		let season = CurrentSeason::<T, I>::get();
+
+		let update = |instance: u32, contender: &Contender, opponent: &Contender, new_rating: u32, outcome| {
+			if let Contender::Contract(contract) = contender {
+				Ratings::<T, I>::insert(contract, new_rating);
+				if let Some(season) = season {
+					MatchRecords::<T, I>::mutate(season, contract, |records| {
+						if records.len() >= T::MaxMatchRecords::get() as usize {
+							records.remove(0);
 						}
+						records.push(MatchRecord {
+							block_number: frame_system::Pallet::<T>::block_number(),
+							instance,
+							opponent: opponent.clone(),
+							outcome,
+							rating: new_rating,
+						});
 					});
 				}
 			}
+		};
 
-			Self::deposit_event(Event::GameResult {
-				points: game_points.clone(),
-			});
+		update(
+			id1,
+			contender1,
+			contender2,
+			rating::new_rating(rating1, rating2, score1),
+			outcome1,
+		);
+		update(
+			id2,
+			contender2,
+			contender1,
+			rating::new_rating(rating2, rating1, 1000 - score1),
+			outcome1.reverse(),
+		);
+	}
 
-			let mut results = game_points.into_iter().collect::<Vec<_>>();
-			results.sort_by(|(_, a), (_, b)| b.cmp(a));
+	/// The `count` best rated contracts, with their ratings.
+	pub fn leaderboard(count: u32) -> Vec<(H160, u32)> {
+		let mut ratings = Ratings::<T, I>::iter().collect::<Vec<_>>();
+		ratings.sort_by(|(a, rating_a), (b, rating_b)| rating_b.cmp(rating_a).then(a.cmp(b)));
+		ratings.truncate(count as usize);
+		ratings
+	}
 
-			ContenderQueue::<T>::mutate(|queue| {
-				let max_count = T::EnqueueCount::get() as usize;
-				if queue.len() < max_count {
-					for c in queue.iter() {
-						Self::add_contender(c, 2);
-					}
-					queue.clear();
-				} else {
-					let (to_add, new_queue) = queue.split_at(max_count);
-					for c in to_add.iter() {
-						Self::add_contender(c, 2);
+	/// The final leaderboard of a closed season, or the live one for the open season or `None`.
+	pub fn season_leaderboard(season: Option<SeasonIndex>) -> Vec<(H160, u32)> {
+		match season.and_then(Seasons::<T, I>::get) {
+			Some(SeasonInfo {
+				ended_at: Some(_),
+				leaderboard,
+				..
+			}) => leaderboard,
+			Some(_) => Self::leaderboard(T::LeaderboardSize::get()),
+			None if season.is_none() => Self::leaderboard(T::LeaderboardSize::get()),
+			None => vec![],
+		}
+	}
+
+	/// The matches of `contract` recorded in `season`.
+	pub fn match_history(season: SeasonIndex, contract: H160) -> Vec<MatchRecord<T::BlockNumber>> {
+		MatchRecords::<T, I>::get(season, contract)
+	}
+
+	/// The current rating of `contract`.
+	pub fn contract_rating(contract: H160) -> u32 {
+		Ratings::<T, I>::get(contract).unwrap_or(rating::INITIAL_RATING)
+	}
+
+	/// Apply the points of a game round, pay the prizes, dequeue contenders and evict the losers.
+	fn finalize_round(n: T::BlockNumber, game_points: BTreeMap<u32, i32>) {
+		let block_number: u128 = n.unique_saturated_into();
+		let multipler = (block_number / 1800).saturating_sub(7200) + 1;
+		for (id, point) in game_points.iter() {
+			let contract = Contenders::<T, I>::get(id);
+			if let Some(Contender::Contract(contract)) = contract {
+				ContractInfos::<T, I>::mutate(contract, |info| {
+					if *point > 0 {
+						info.point = info.point + (*point as u128) * multipler;
 					}
-					*queue = new_queue.to_vec();
+				});
+			}
+		}
+
+		Self::deposit_event(Event::GameResult {
+			points: game_points.clone(),
+		});
+
+		let mut results = game_points.into_iter().collect::<Vec<_>>();
+		results.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+		ContenderQueue::<T, I>::mutate(|queue| {
+			let max_count = T::EnqueueCount::get() as usize;
+			if queue.len() < max_count {
+				for c in queue.iter() {
+					Self::add_contender(c, 2);
 				}
-			});
+				queue.clear();
+			} else {
+				let (to_add, new_queue) = queue.split_at(max_count);
+				for c in to_add.iter() {
+					Self::add_contender(c, 2);
+				}
+				*queue = new_queue.to_vec();
+			}
+		});
+
+		Self::pay_prizes(&results);
+
+		for (winner, _) in results.iter().take(T::WinnerCount::get() as usize) {
+			let contender = Contenders::<T, I>::get(winner);
+			if let Some(contender) = contender {
+				Self::add_contender(&contender, 1);
+				if let Contender::Contract(ref contract) = contender {
+					ContractInfos::<T, I>::mutate(contract, |info| {
+						info.wins += 1;
+					});
+				}
+			}
+		}
 
-			for (winner, _) in results.iter().take(T::WinnerCount::get() as usize) {
-				let contender = Contenders::<T>::get(winner);
+		let instances_count = ContenderInstancesCount::<T, I>::get();
+		let to_remove = instances_count.saturating_sub(T::MaxContenderInstancesCount::get());
+		if to_remove > 0 {
+			// instances withdrawn or disqualified during the round are skipped
+			let mut removed = 0u32;
+			for (id, _) in results.iter().rev() {
+				if removed >= to_remove {
+					break;
+				}
+				let contender = Contenders::<T, I>::take(id);
 				if let Some(contender) = contender {
-					Self::add_contender(&contender, 1);
+					removed += 1;
 					if let Contender::Contract(ref contract) = contender {
-						ContractInfos::<T>::mutate(contract, |info| {
-							info.wins += 1;
+						ContractInfos::<T, I>::mutate(contract, |info| {
+							info.instance_count -= 1;
 						});
+						ContractInstances::<T, I>::mutate(contract, |ids| ids.retain(|i| i != id));
+						Self::slash_stake(contract, *id);
 					}
+					Self::deposit_event(Event::<T, I>::InstanceRemoved { contender, id: *id });
 				}
 			}
 
-			let instances_count = ContenderInstancesCount::<T>::get();
-			let to_remove = instances_count.saturating_sub(T::MaxContenderInstancesCount::get());
-			if to_remove > 0 {
-				for (id, _) in results.iter().rev().take(to_remove as usize) {
-					let contender = Contenders::<T>::take(id);
-					if let Some(contender) = contender {
-						if let Contender::Contract(ref contract) = contender {
-							ContractInfos::<T>::mutate(contract, |info| {
-								info.instance_count -= 1;
-							});
-						}
-						Self::deposit_event(Event::<T>::InstanceRemoved { contender, id: *id });
+			ContenderInstancesCount::<T, I>::put(instances_count - removed);
+		}
+	}
+
+	/// Commit phase: ask the contracts of a pairing for the hash of their moves.
+	fn commit_match((id1, contender1): (u32, Contender), (id2, contender2): (u32, Contender)) {
+		let match_id = NextMatchId::<T, I>::mutate(|id| {
+			let current_id = *id;
+			*id = id.wrapping_add(1);
+			current_id
+		});
+		let commitment_a = Self::contender_commit(match_id, id1, &contender1);
+		let commitment_b = Self::contender_commit(match_id, id2, &contender2);
+		PendingMatches::<T, I>::insert(
+			match_id,
+			PendingMatch {
+				a: (id1, contender1),
+				b: (id2, contender2),
+				commitment_a,
+				commitment_b,
+			},
+		);
+	}
+
+	fn contender_commit(match_id: u32, id: u32, contender: &Contender) -> Option<H256> {
+		if let Contender::Contract(contract) = contender {
+			let output = Self::call_contract(*contract, Action::Commit, &[U256::from(match_id)])?;
+			if output.len() < 32 {
+				return None;
+			}
+			let commitment = H256::from_slice(&output[0..32]);
+			Self::deposit_event(Event::<T, I>::MovesCommitted {
+				match_id,
+				id,
+				commitment,
+			});
+			Some(commitment)
+		} else {
+			None
+		}
+	}
+
+	/// Reveal phase: verify the revealed moves against the commitments and score the match.
+	/// A contract that fails to reveal moves matching its commitment forfeits the match.
+	fn reveal_match(game_points: &mut BTreeMap<u32, i32>, match_id: u32, pending: PendingMatch) {
+		let (id1, contender1) = pending.a;
+		let (id2, contender2) = pending.b;
+		Self::deposit_event(Event::<T, I>::PlayStarted { contenders: (id1, id2) });
+
+		let moves_a = Self::contender_reveal(match_id, id1, &contender1, pending.commitment_a);
+		let moves_b = Self::contender_reveal(match_id, id2, &contender2, pending.commitment_b);
+
+		let winner = match (moves_a, moves_b) {
+			(Err(()), Err(())) => {
+				Self::add_point(game_points, id1, -1, &contender1);
+				Self::add_point(game_points, id2, -1, &contender2);
+				Self::deposit_event(Event::<T, I>::PlayEnded { winner: None });
+				return;
+			}
+			(Err(()), Ok(_)) => Some(Side::B),
+			(Ok(_), Err(())) => Some(Side::A),
+			(Ok(moves_a), Ok(moves_b)) => Self::play_revealed(&contender1, moves_a, &contender2, moves_b),
+		};
+		Self::record_match_result(game_points, (id1, &contender1), (id2, &contender2), winner);
+	}
+
+	/// Returns the revealed moves of a contract, or `None` for built-in contenders whose moves are
+	/// computed during the play.
+	fn contender_reveal(
+		match_id: u32,
+		id: u32,
+		contender: &Contender,
+		commitment: Option<H256>,
+	) -> Result<Option<Vec<U256>>, ()> {
+		let contract = match contender {
+			Contender::Contract(contract) => *contract,
+			_ => return Ok(None),
+		};
+
+		let revealed = commitment.and_then(|commitment| {
+			let output = Self::call_contract(contract, Action::Reveal, &[U256::from(match_id)])?;
+			let (salt, moves) = decode_reveal(&output)?;
+			if moves.len() != T::PlayPerRound::get() as usize || commitment_of(salt, &moves) != commitment {
+				return None;
+			}
+			Some(moves)
+		});
+
+		match revealed {
+			Some(moves) => Ok(Some(moves)),
+			None => {
+				Self::deposit_event(Event::<T, I>::Forfeited { match_id, id });
+				Err(())
+			}
+		}
+	}
+
+	fn play_revealed(
+		a: &Contender,
+		moves_a: Option<Vec<U256>>,
+		b: &Contender,
+		moves_b: Option<Vec<U256>>,
+	) -> Option<Side> {
+		let (seed_a, seed_b) = Self::match_seeds(a, b);
+		let mut history_a = Vec::with_capacity(T::PlayPerRound::get() as usize);
+		let mut history_b = Vec::with_capacity(T::PlayPerRound::get() as usize);
+
+		for round in 0..T::PlayPerRound::get() {
+			let play_a = match moves_a {
+				Some(ref moves_a) => moves_a[round as usize],
+				None => Self::contender_play(a, round, &history_a, &history_b, seed_a, true).0,
+			};
+			let play_b = match moves_b {
+				Some(ref moves_b) => moves_b[round as usize],
+				None => Self::contender_play(b, round, &history_b, &history_a, seed_b, true).0,
+			};
+			history_a.push(T::GameRules::decode_move(play_a));
+			history_b.push(T::GameRules::decode_move(play_b));
+		}
+
+		let moves = history_a.into_iter().zip(history_b).collect::<Vec<_>>();
+		Self::score_match(&moves)
+	}
+
+	fn call_contract(contract: H160, action: Action, args: &[U256]) -> Option<Vec<u8>> {
+		let mut input = Into::<u32>::into(action).to_be_bytes().to_vec();
+		for arg in args {
+			input.extend_from_slice(&to_bytes(*arg));
+		}
+		Self::execute_contender(contract, input, true).0
+	}
+
+	/// Call a contender contract, returning the output of a successful call and the gas used.
+	/// A `metered` call is charged against the round gas budget of the contract, and counted
+	/// towards its disqualification if it fails. An unmetered call, as in a dry run, is given the
+	/// full `ContenderGasLimit` and leaves the round budget and failures untouched.
+	fn execute_contender(contract: H160, input: Vec<u8>, metered: bool) -> (Option<Vec<u8>>, u64) {
+		let used_gas = RoundGasUsed::<T, I>::get(contract);
+		let gas_limit = if metered {
+			T::ContenderGasLimit::get().min(T::RoundGasBudget::get().saturating_sub(used_gas))
+		} else {
+			T::ContenderGasLimit::get()
+		};
+		if gas_limit.is_zero() {
+			if metered {
+				Self::contender_failed(contract, ContenderFailure::OutOfGas);
+			}
+			return (None, 0);
+		}
+
+		let invoker = T::AddressMapping::get_default_evm_address(&T::ContractInvoker::get());
+		let result = T::EVM::execute(
+			InvokeContext {
+				contract,
+				sender: invoker,
+				origin: invoker,
+			},
+			input,
+			Default::default(),
+			gas_limit,
+			64 * 20,
+			ExecutionMode::Execute,
+		);
+
+		let (call_gas, outcome) = match result {
+			Ok(info) => {
+				let call_gas = info.used_gas.low_u64();
+				match info.exit_reason {
+					ExitReason::Succeed(_) => (call_gas, Ok(info.value)),
+					ExitReason::Revert(_) => {
+						let message = decode_revert_message(&info.value).unwrap_or_default();
+						(call_gas, Err(ContenderFailure::Reverted(message.as_bytes().to_vec())))
 					}
+					ExitReason::Error(ExitError::OutOfGas) => (call_gas, Err(ContenderFailure::OutOfGas)),
+					_ => (call_gas, Err(ContenderFailure::Error)),
 				}
+			}
+			Err(e) if e == T::OutOfStorageError::get() => (gas_limit, Err(ContenderFailure::OutOfStorage)),
+			Err(_) => (gas_limit, Err(ContenderFailure::Error)),
+		};
+		if !metered {
+			return (outcome.ok(), call_gas);
+		}
+		RoundGasUsed::<T, I>::insert(contract, used_gas.saturating_add(call_gas));
 
-				ContenderInstancesCount::<T>::put(instances_count - to_remove);
+		match outcome {
+			Ok(output) => (Some(output), call_gas),
+			Err(reason) => {
+				Self::contender_failed(contract, reason);
+				(None, call_gas)
 			}
+		}
+	}
+
+	/// Report a failed call and disqualify the contract once it reaches `MaxFailures`. Only
+	/// registered contracts are counted, so dry runs of arbitrary contracts are not affected.
+	fn contender_failed(contract: H160, reason: ContenderFailure) {
+		Self::deposit_event(Event::<T, I>::ContenderFailed { contract, reason });
 
-			0
+		if !ContractInfos::<T, I>::contains_key(contract) {
+			return;
+		}
+		let failures = Failures::<T, I>::mutate(contract, |failures| {
+			*failures = failures.saturating_add(1);
+			*failures
+		});
+		if failures >= T::MaxFailures::get() {
+			Self::disqualify(contract);
 		}
 	}
 
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		#[pallet::weight(0)]
-		#[transactional]
-		pub fn register(origin: OriginFor<T>, contract: H160) -> DispatchResult {
-			let who = ensure_signed(origin)?;
-			ensure!(
-				!ContractInfos::<T>::contains_key(&contract),
-				Error::<T>::AlreadyRegistered
-			);
+	/// Remove every instance of the contract, and slash its whole remaining stake into the prize
+	/// pool.
+	fn disqualify(contract: H160) {
+		Self::remove_instances(contract);
+		ContractInfos::<T, I>::remove(contract);
+		Paused::<T, I>::remove(contract);
+		Failures::<T, I>::remove(contract);
 
-			let input = Into::<u32>::into(Action::Owner).to_be_bytes().to_vec();
-			let info = T::EVM::execute(
-				InvokeContext {
-					contract,
-					sender: Default::default(),
-					origin: Default::default(),
-				},
-				input,
-				Default::default(),
-				2_100_000,
-				0,
-				ExecutionMode::View,
-			)?;
-			let owner = H160::decode(&mut &info.value[12..]).map_err(|_| Error::<T>::InvalidOwner)?;
-			let owner = T::AddressMapping::get_account_id(&owner);
-			ensure!(owner == who, Error::<T>::InvalidOwner);
+		let slashed = Stakes::<T, I>::take(contract).map_or_else(Zero::zero, |stake| {
+			let unslashed = T::Currency::repatriate_reserved_named(
+				&RESERVE_ID,
+				&stake.owner,
+				&Self::prize_pool_account_id(),
+				stake.amount,
+				BalanceStatus::Free,
+			)
+			.unwrap_or(stake.amount);
+			stake.amount.saturating_sub(unslashed)
+		});
 
-			Self::do_register_contender(Contender::Contract(contract))?;
+		Self::deposit_event(Event::<T, I>::Disqualified { contract, slashed });
+	}
 
-			Ok(())
+	/// Remove every instance and queue entry of the contract.
+	fn remove_instances(contract: H160) {
+		let contender = Contender::Contract(contract);
+		let ids = ContractInstances::<T, I>::take(contract);
+		for id in ids.iter() {
+			Contenders::<T, I>::remove(id);
+			Self::deposit_event(Event::<T, I>::InstanceRemoved {
+				contender: contender.clone(),
+				id: *id,
+			});
 		}
+		ContenderInstancesCount::<T, I>::mutate(|count| *count = count.saturating_sub(ids.len() as u32));
+		ContenderQueue::<T, I>::mutate(|queue| queue.retain(|c| *c != contender));
+		if ContractInfos::<T, I>::contains_key(contract) {
+			ContractInfos::<T, I>::mutate(contract, |info| info.instance_count = 0);
+		}
+	}
 
-		#[pallet::weight(0)]
-		#[transactional]
-		pub fn register_contender(origin: OriginFor<T>, contender: Contender, count: u32) -> DispatchResult {
-			ensure_root(origin)?;
+	/// Whether the contract has instances playing or queued, or is paused.
+	fn is_active(contract: &H160) -> bool {
+		Paused::<T, I>::get(contract)
+			|| ContractInfos::<T, I>::get(contract).instance_count > 0
+			|| ContenderQueue::<T, I>::get().contains(&Contender::Contract(*contract))
+	}
 
-			Self::add_contender(&contender, count);
+	/// The stake of the contract, if `who` is its registered owner.
+	fn ensure_owner(
+		who: &T::AccountId,
+		contract: &H160,
+	) -> Result<StakeInfo<T::AccountId, BalanceOf<T, I>>, DispatchError> {
+		let stake = Stakes::<T, I>::get(contract).ok_or(Error::<T, I>::NotRegistered)?;
+		ensure!(stake.owner == *who, Error::<T, I>::NotOwner);
+		Ok(stake)
+	}
 
-			Ok(())
-		}
+	/// The account of the owner returned by the `owner()` method of the contract.
+	fn contract_owner(contract: H160) -> Result<T::AccountId, DispatchError> {
+		let input = Into::<u32>::into(Action::Owner).to_be_bytes().to_vec();
+		let info = T::EVM::execute(
+			InvokeContext {
+				contract,
+				sender: Default::default(),
+				origin: Default::default(),
+			},
+			input,
+			Default::default(),
+			OWNER_CALL_GAS_LIMIT,
+			0,
+			ExecutionMode::View,
+		)?;
+		ensure!(info.value.len() >= 32, Error::<T, I>::InvalidOwner);
+		let owner = H160::decode(&mut &info.value[12..]).map_err(|_| Error::<T, I>::InvalidOwner)?;
+		Ok(T::AddressMapping::get_account_id(&owner))
 	}
-}
 
-impl<T: Config> Pallet<T> {
 	fn add_contender(contender: &Contender, mut count: u32) {
 		if let Contender::Contract(contract) = contender {
-			ContractInfos::<T>::mutate(contract, |info| {
+			ContractInfos::<T, I>::mutate(contract, |info| {
 				let max = T::MaxInstancesPerContender::get() - info.instance_count;
 				count = count.min(max);
 				info.instance_count += count;
@@ -299,32 +1600,35 @@ impl<T: Config> Pallet<T> {
 			return;
 		}
 
-		let id = NextContenderInstanceId::<T>::mutate(|id| {
+		let id = NextContenderInstanceId::<T, I>::mutate(|id| {
 			let current_id = *id;
 			*id += count;
 			current_id
 		});
 
+		if let Contender::Contract(contract) = contender {
+			ContractInstances::<T, I>::mutate(contract, |ids| ids.extend(id..id + count));
+		}
 		for i in 0..count {
 			let instance_id = id + i;
-			Contenders::<T>::insert(instance_id, &contender);
+			Contenders::<T, I>::insert(instance_id, &contender);
 
-			Self::deposit_event(Event::<T>::InstanceRegistered {
+			Self::deposit_event(Event::<T, I>::InstanceRegistered {
 				contender: contender.clone(),
 				id: instance_id,
 			})
 		}
 
-		ContenderInstancesCount::<T>::mutate(|c| *c += count);
+		ContenderInstancesCount::<T, I>::mutate(|c| *c += count);
 	}
 
 	fn do_register_contender(contender: Contender) -> DispatchResult {
-		if T::MaxContenderInstancesCount::get() > ContenderInstancesCount::<T>::get() {
+		if T::MaxContenderInstancesCount::get() > ContenderInstancesCount::<T, I>::get() {
 			Self::add_contender(&contender, 2);
 		} else {
-			ContenderQueue::<T>::try_mutate(|queue| -> DispatchResult {
+			ContenderQueue::<T, I>::try_mutate(|queue| -> DispatchResult {
 				if queue.len() >= T::MaxQueueSize::get() as usize {
-					return Err(Error::<T>::QueueFull.into());
+					return Err(Error::<T, I>::QueueFull.into());
 				}
 				queue.push(contender);
 				Ok(())
@@ -334,71 +1638,175 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	fn contender_play(contender: &Contender, round: u32, prev_play: U256, other_prev_play: U256) -> U256 {
+	/// The seeds of the `Random` contenders of each side of a match, drawn from the configured
+	/// randomness.
+	fn match_seeds(a: &Contender, b: &Contender) -> (H256, H256) {
+		let (seed, _) = T::Randomness::random(&(b"arena/match", a, b).encode());
+		(
+			keccak_256(&(seed, Side::A).encode()).into(),
+			keccak_256(&(seed, Side::B).encode()).into(),
+		)
+	}
+
+	/// The raw move of a contender and the gas it used, given the decoded moves played so far by
+	/// the contender and its opponent.
+	fn contender_play(
+		contender: &Contender,
+		round: u32,
+		own: &[U256],
+		other: &[U256],
+		seed: H256,
+		metered: bool,
+	) -> (U256, u64) {
+		let prev_play = own.last().copied().unwrap_or_default();
+		let other_prev_play = other.last().copied().unwrap_or_default();
 		match contender {
 			Contender::Contract(contract) => {
 				let mut input = Into::<u32>::into(Action::Play).to_be_bytes().to_vec();
 				input.extend_from_slice(U256::from(round).encode().as_slice());
 				input.extend_from_slice(prev_play.encode().as_slice());
 				input.extend_from_slice(other_prev_play.encode().as_slice());
-				let invoker = T::AddressMapping::get_default_evm_address(&T::ContractInvoker::get());
-				let info = T::EVM::execute(
-					InvokeContext {
-						contract: contract.clone(),
-						sender: invoker,
-						origin: invoker,
-					},
-					input,
-					Default::default(),
-					10_000_000,
-					64 * 20,
-					ExecutionMode::Execute,
-				);
-
-				if let Ok(info) = info {
-					log::warn!(target: "arena", "info2 {:?}", info.value);
-					U256::from(info.value.as_slice())
-				} else {
-					U256::zero()
+
+				let (output, used_gas) = Self::execute_contender(*contract, input, metered);
+				match output {
+					Some(output) if output.len() > 32 => {
+						if metered {
+							Self::contender_failed(*contract, ContenderFailure::InvalidOutput);
+						}
+						(U256::zero(), used_gas)
+					}
+					Some(output) => (U256::from(output.as_slice()), used_gas),
+					None => (U256::zero(), used_gas),
 				}
 			}
-			Contender::AlwaysZero => U256::zero(),
-			Contender::Rotate => U256::from(round),
-			Contender::Smart => other_prev_play.div_mod(3u32.into()).1 + U256::from(2u32),
+			Contender::AlwaysZero => (U256::zero(), 0),
+			Contender::Rotate => (U256::from(round), 0),
+			Contender::Smart => (other_prev_play.div_mod(3u32.into()).1 + U256::from(2u32), 0),
+			Contender::FrequencyCounter { window } => {
+				(strategies::frequency_counter::<T::GameRules>(other, *window), 0)
+			}
+			Contender::TitForTat { first_move } => (strategies::tit_for_tat(other, *first_move), 0),
+			Contender::Markov { order } => (strategies::markov::<T::GameRules>(other, *order), 0),
+			Contender::Random { moves } => (strategies::random(seed, round, *moves), 0),
 		}
 	}
 
-	fn play<'a>(a: &'a Contender, b: &'a Contender) -> Option<&'a Contender> {
-		let mut prev_play_a = U256::zero();
-		let mut prev_play_b = U256::zero();
-		let mut point_a = 0u32;
-		let mut point_b = 0u32;
+	fn play(a: &Contender, b: &Contender) -> Option<Side> {
+		Self::simulate_match(a, b, true).winner
+	}
+
+	/// Play a match, reporting the moves and gas used of every round. The calls of a `metered`
+	/// match are charged against the round gas budget of the contracts.
+	fn simulate_match(a: &Contender, b: &Contender, metered: bool) -> MatchReport {
+		let (seed_a, seed_b) = Self::match_seeds(a, b);
+		let mut history_a = Vec::with_capacity(T::PlayPerRound::get() as usize);
+		let mut history_b = Vec::with_capacity(T::PlayPerRound::get() as usize);
+		let mut rounds = Vec::with_capacity(T::PlayPerRound::get() as usize);
 
 		for round in 0..T::PlayPerRound::get() {
-			let play_a = Self::contender_play(a, round, prev_play_a, prev_play_b);
-			let play_b = Self::contender_play(b, round, prev_play_b, prev_play_a);
+			let (play_a, used_gas_a) = Self::contender_play(a, round, &history_a, &history_b, seed_a, metered);
+			let (play_b, used_gas_b) = Self::contender_play(b, round, &history_b, &history_a, seed_b, metered);
+			let play_a = T::GameRules::decode_move(play_a);
+			let play_b = T::GameRules::decode_move(play_b);
+			history_a.push(play_a);
+			history_b.push(play_b);
+			rounds.push(RoundReport {
+				moves: (play_a, play_b),
+				used_gas: (used_gas_a, used_gas_b),
+			});
+		}
+
+		let moves = rounds.iter().map(|round| round.moves).collect::<Vec<_>>();
+		let winner = Self::score_match(&moves);
+		MatchReport { rounds, winner }
+	}
 
-			Self::deposit_event(Event::<T>::PlayRoundResult {
-				result: (play_a, play_b),
+	/// Simulate a match between two contenders without committing any state change. The round gas
+	/// budget and failures of the contracts are ignored.
+	pub fn dry_run_match(a: Contender, b: Contender) -> MatchReport {
+		frame_support::storage::with_transaction(|| TransactionOutcome::Rollback(Self::simulate_match(&a, &b, false)))
+	}
+
+	/// Score the decoded moves of a match with the game rules.
+	fn score_match(moves: &[(U256, U256)]) -> Option<Side> {
+		let mut point_a = 0u32;
+		let mut point_b = 0u32;
+		let mut state = <T::GameRules as GameRules>::MatchState::default();
+
+		for (round, (play_a, play_b)) in moves.iter().enumerate() {
+			Self::deposit_event(Event::<T, I>::PlayRoundResult {
+				result: (*play_a, *play_b),
 			});
 
-			prev_play_a = play_a;
-			prev_play_b = play_b;
-			let play_a: u8 = play_a.div_mod(3.into()).1.unique_saturated_into();
-			let play_b: u8 = play_b.div_mod(3.into()).1.unique_saturated_into();
-			match (play_a, play_b) {
-				(0, 1) | (1, 2) | (2, 0) => point_a += 1,
-				(0, 2) | (2, 1) | (1, 0) => point_b += 1,
-				_ => {}
-			}
+			let (gained_a, gained_b) = T::GameRules::score_round(&mut state, round as u32, *play_a, *play_b);
+			point_a = point_a.saturating_add(gained_a);
+			point_b = point_b.saturating_add(gained_b);
 		}
 
-		if point_a > point_b {
-			Some(a)
-		} else if point_a < point_b {
-			Some(b)
-		} else {
-			None
-		}
+		T::GameRules::match_winner(&state, point_a, point_b)
+	}
+}
+
+impl<T: Config<I>, I: 'static> ArenaManager for Pallet<T, I> {
+	fn contract_info(contract: H160) -> Option<ArenaContractInfo> {
+		let info = ContractInfos::<T, I>::try_get(contract).ok()?;
+		Some(ArenaContractInfo {
+			points: info.point,
+			instances: info.instance_count,
+			wins: info.wins,
+			rating: Self::contract_rating(contract),
+		})
+	}
+
+	fn leaderboard(count: u32) -> Vec<(H160, u32)> {
+		Self::leaderboard(count)
+	}
+
+	fn rated_contracts() -> u32 {
+		Ratings::<T, I>::count()
+	}
+
+	fn current_round() -> Option<ArenaRoundInfo> {
+		CurrentRound::<T, I>::get().map(|round| ArenaRoundInfo {
+			started_at: round.started_at.unique_saturated_into(),
+			phase: match round.phase {
+				RoundPhase::Play => 0,
+				RoundPhase::Commit => 1,
+				RoundPhase::Reveal => 2,
+			},
+			instances: round.contenders.len() as u32,
+			progress: round.cursor,
+		})
+	}
+
+	fn queue_length() -> u32 {
+		ContenderQueue::<T, I>::decode_len().unwrap_or_default() as u32
+	}
+}
+
+/// The commitment of a sequence of moves: `keccak256(abi.encodePacked(salt, moves))`.
+pub fn commitment_of(salt: H256, moves: &[U256]) -> H256 {
+	let mut data = salt.as_bytes().to_vec();
+	for m in moves {
+		data.extend_from_slice(&to_bytes(*m));
+	}
+	H256::from(keccak_256(&data))
+}
+
+/// Decode the output of `reveal(uint256) returns (bytes32 salt, uint256[] moves)`.
+fn decode_reveal(output: &[u8]) -> Option<(H256, Vec<U256>)> {
+	let word =
+		|offset: usize| -> Option<U256> { output.get(offset..offset.checked_add(32)?).map(U256::from_big_endian) };
+	let as_usize = |v: U256| -> Option<usize> { (v <= U256::from(u32::MAX)).then(|| v.as_usize()) };
+
+	let salt = H256::from_slice(output.get(0..32)?);
+	let offset = as_usize(word(32)?)?;
+	let len = as_usize(word(offset)?)?;
+	if len > output.len() / 32 {
+		return None;
 	}
+	let moves = (0..len)
+		.map(|i| word(offset + 32 + i * 32))
+		.collect::<Option<Vec<_>>>()?;
+	Some((salt, moves))
 }
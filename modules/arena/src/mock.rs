@@ -0,0 +1,409 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Mocks for the arena module.
+
+#![cfg(test)]
+
+use super::*;
+use crate as arena;
+use frame_support::{
+	construct_runtime,
+	instances::Instance1,
+	parameter_types,
+	traits::{ConstU128, ConstU32, ConstU64, Everything, Randomness},
+};
+use module_evm::{ExitError, ExitReason, ExitRevert, ExitSucceed};
+use primitives::{
+	evm::{CallInfo, EvmAddress},
+	Balance,
+};
+use sp_core::{crypto::AccountId32, H256};
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, Hash, IdentityLookup},
+	DispatchError, ModuleError,
+};
+use sp_std::cell::RefCell;
+use support::mocks::MockAddressMapping;
+
+pub type AccountId = AccountId32;
+pub type BlockNumber = u64;
+
+pub fn alice() -> AccountId {
+	MockAddressMapping::get_account_id(&EvmAddress::from_low_u64_be(1))
+}
+
+pub fn bob() -> AccountId {
+	MockAddressMapping::get_account_id(&EvmAddress::from_low_u64_be(2))
+}
+
+/// A contract that always plays `mv`.
+pub fn contract(mv: u8) -> H160 {
+	H160::from_low_u64_be(0x1000 + mv as u64)
+}
+
+/// A contract that commits to always playing `mv`, but reveals `mv + 1`.
+pub fn cheater(mv: u8) -> H160 {
+	H160::from_low_u64_be(0x1080 + mv as u64)
+}
+
+/// A contract whose calls revert with a message.
+pub fn reverting() -> H160 {
+	H160::from_low_u64_be(0x2000)
+}
+
+/// A contract whose calls use up all the gas, except for `owner()`.
+pub fn gas_guzzler() -> H160 {
+	H160::from_low_u64_be(0x2001)
+}
+
+/// A contract whose calls run out of storage, except for `owner()`.
+pub fn storage_hog() -> H160 {
+	H160::from_low_u64_be(0x2002)
+}
+
+/// A contract whose `play(...)` returns two words.
+pub fn wide_output() -> H160 {
+	H160::from_low_u64_be(0x2003)
+}
+
+/// A contract whose `owner()` returns less than a word.
+pub fn short_owner() -> H160 {
+	H160::from_low_u64_be(0x2004)
+}
+
+pub const SALT: H256 = H256::repeat_byte(0x55);
+
+thread_local! {
+	static CONTRACT_OWNERS: RefCell<BTreeMap<H160, H160>> = RefCell::new(BTreeMap::new());
+}
+
+pub fn set_contract_owner(contract: H160, owner: &AccountId) {
+	let owner = MockAddressMapping::get_evm_address(owner).unwrap();
+	CONTRACT_OWNERS.with(|v| v.borrow_mut().insert(contract, owner));
+}
+
+/// A mocked EVM: `owner()` returns the registered owner and `play(...)` always returns the last
+/// byte of the contract address, masked with `0x7f`. Contracts with the high bit set cheat in
+/// `reveal(uint256)`.
+pub struct MockEVM;
+impl EVM<AccountId> for MockEVM {
+	type Balance = Balance;
+
+	fn execute(
+		context: InvokeContext,
+		input: Vec<u8>,
+		_value: Balance,
+		gas_limit: u64,
+		_storage_limit: u32,
+		_mode: ExecutionMode,
+	) -> Result<CallInfo, DispatchError> {
+		let selector = u32::from_be_bytes(input[0..4].try_into().unwrap());
+		if selector != Into::<u32>::into(Action::Owner) {
+			if context.contract == reverting() {
+				// Error("bad move")
+				let mut output = 0x08c379a0u32.to_be_bytes().to_vec();
+				output.extend_from_slice(&primitives::to_bytes(0x20u32));
+				output.extend_from_slice(&primitives::to_bytes(8u32));
+				output.extend_from_slice(&[b"bad move".as_slice(), &[0u8; 24]].concat());
+				return Ok(CallInfo {
+					exit_reason: ExitReason::Revert(ExitRevert::Reverted),
+					value: output,
+					used_gas: U256::from(200),
+					used_storage: 0,
+					logs: vec![],
+				});
+			}
+			if context.contract == storage_hog() {
+				return Err(OutOfStorageError::get());
+			}
+			if context.contract == wide_output() {
+				return Ok(CallInfo {
+					exit_reason: ExitReason::Succeed(ExitSucceed::Returned),
+					value: vec![1u8; 64],
+					used_gas: U256::from(500),
+					used_storage: 0,
+					logs: vec![],
+				});
+			}
+			if context.contract == gas_guzzler() {
+				return Ok(CallInfo {
+					exit_reason: ExitReason::Error(ExitError::OutOfGas),
+					value: vec![],
+					used_gas: U256::from(gas_limit),
+					used_storage: 0,
+					logs: vec![],
+				});
+			}
+		}
+		if context.contract == short_owner() {
+			return Ok(CallInfo {
+				exit_reason: ExitReason::Succeed(ExitSucceed::Returned),
+				value: vec![0u8; 8],
+				used_gas: Default::default(),
+				used_storage: 0,
+				logs: vec![],
+			});
+		}
+		let mut value = [0u8; 32];
+		let mut used_gas = U256::zero();
+		match Action::try_from(selector).map_err(|_| DispatchError::Other("unknown selector"))? {
+			Action::Owner => {
+				let owner = CONTRACT_OWNERS
+					.with(|v| v.borrow().get(&context.contract).cloned())
+					.ok_or(DispatchError::Other("no contract"))?;
+				value[12..].copy_from_slice(owner.as_bytes());
+			}
+			Action::Play => {
+				if gas_limit < 500 {
+					return Ok(CallInfo {
+						exit_reason: ExitReason::Error(ExitError::OutOfGas),
+						value: vec![],
+						used_gas: U256::from(gas_limit),
+						used_storage: 0,
+						logs: vec![],
+					});
+				}
+				value[31] = context.contract[19] & 0x7f;
+				used_gas = U256::from(500);
+			}
+			Action::Commit => {
+				let moves = vec![U256::from(context.contract[19] & 0x7f); PlayPerRound::get() as usize];
+				value.copy_from_slice(commitment_of(SALT, &moves).as_bytes());
+			}
+			Action::Reveal => {
+				let cheat = context.contract[19] >> 7;
+				let mv = U256::from((context.contract[19] & 0x7f) + cheat);
+				let mut output = SALT.as_bytes().to_vec();
+				output.extend_from_slice(&primitives::to_bytes(0x40u32));
+				output.extend_from_slice(&primitives::to_bytes(PlayPerRound::get()));
+				for _ in 0..PlayPerRound::get() {
+					output.extend_from_slice(&primitives::to_bytes(mv));
+				}
+				return Ok(CallInfo {
+					exit_reason: ExitReason::Succeed(ExitSucceed::Returned),
+					value: output,
+					used_gas: Default::default(),
+					used_storage: 0,
+					logs: vec![],
+				});
+			}
+		}
+		Ok(CallInfo {
+			exit_reason: ExitReason::Succeed(ExitSucceed::Returned),
+			value: value.to_vec(),
+			used_gas,
+			used_storage: 0,
+			logs: vec![],
+		})
+	}
+
+	fn get_origin() -> Option<AccountId> {
+		None
+	}
+
+	fn set_origin(_origin: AccountId) {}
+
+	fn gas_to_weight(gas: u64) -> Weight {
+		gas
+	}
+}
+
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = Everything;
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = frame_system::Pallet<Runtime>;
+	type MaxLocks = ();
+	type MaxReserves = ConstU32<50>;
+	type ReserveIdentifier = ReserveIdentifier;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const PlayPerRound: u32 = 5;
+	pub ContractInvoker: AccountId = AccountId32::new([0xff; 32]);
+	pub const ArenaPalletId: PalletId = PalletId(*b"aca/arna");
+	pub const DilemmaArenaPalletId: PalletId = PalletId(*b"aca/arnd");
+	pub const EvictionSlash: Perbill = Perbill::from_percent(10);
+	pub static CommitReveal: bool = false;
+	pub static RoundGasBudget: u64 = 1_000_000;
+	pub static MinMatchesPerBlock: u32 = 0;
+	pub static RandomSeed: H256 = H256::zero();
+	pub OutOfStorageError: DispatchError = DispatchError::Module(ModuleError {
+		index: 180,
+		error: 13,
+		message: Some("OutOfStorage"),
+	});
+}
+
+/// A randomness source mixing the `RandomSeed` with the subject.
+pub struct MockRandomness;
+impl Randomness<H256, BlockNumber> for MockRandomness {
+	fn random(subject: &[u8]) -> (H256, BlockNumber) {
+		(
+			BlakeTwo256::hash_of(&(RandomSeed::get(), subject)),
+			System::block_number(),
+		)
+	}
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type EVM = MockEVM;
+	type AddressMapping = MockAddressMapping;
+	type PlayPerRound = PlayPerRound;
+	type MaxContenderInstancesCount = ConstU32<4>;
+	type MaxQueueSize = ConstU32<10>;
+	type WinnerCount = ConstU32<1>;
+	type EnqueueCount = ConstU32<2>;
+	type MaxInstancesPerContender = ConstU32<6>;
+	type ContractInvoker = ContractInvoker;
+	type GameRules = RockPaperScissors;
+	type CommitReveal = CommitReveal;
+	type RevealDelay = ConstU64<2>;
+	type ContenderGasLimit = ConstU64<1_000>;
+	type OutOfStorageError = OutOfStorageError;
+	type MaxMatchesPerBlock = ConstU32<5>;
+	type MinMatchesPerBlock = MinMatchesPerBlock;
+	type Currency = Balances;
+	type EntryStake = ConstU128<1_000>;
+	type PrizePerRound = ConstU128<100>;
+	type EvictionSlash = EvictionSlash;
+	type PalletId = ArenaPalletId;
+	type LeaderboardSize = ConstU32<10>;
+	type MaxMatchRecords = ConstU32<3>;
+	type RoundGasBudget = RoundGasBudget;
+	type MaxFailures = ConstU32<3>;
+	type Randomness = MockRandomness;
+	type WeightInfo = ();
+}
+
+impl Config<Instance1> for Runtime {
+	type Event = Event;
+	type EVM = MockEVM;
+	type AddressMapping = MockAddressMapping;
+	type PlayPerRound = PlayPerRound;
+	type MaxContenderInstancesCount = ConstU32<4>;
+	type MaxQueueSize = ConstU32<10>;
+	type WinnerCount = ConstU32<1>;
+	type EnqueueCount = ConstU32<2>;
+	type MaxInstancesPerContender = ConstU32<6>;
+	type ContractInvoker = ContractInvoker;
+	type GameRules = PrisonersDilemma;
+	type CommitReveal = CommitReveal;
+	type RevealDelay = ConstU64<2>;
+	type ContenderGasLimit = ConstU64<1_000>;
+	type OutOfStorageError = OutOfStorageError;
+	type MaxMatchesPerBlock = ConstU32<5>;
+	type MinMatchesPerBlock = MinMatchesPerBlock;
+	type Currency = Balances;
+	type EntryStake = ConstU128<1_000>;
+	type PrizePerRound = ConstU128<100>;
+	type EvictionSlash = EvictionSlash;
+	type PalletId = DilemmaArenaPalletId;
+	type LeaderboardSize = ConstU32<10>;
+	type MaxMatchRecords = ConstU32<3>;
+	type RoundGasBudget = RoundGasBudget;
+	type MaxFailures = ConstU32<3>;
+	type Randomness = MockRandomness;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Arena: arena::{Pallet, Call, Storage, Event<T>},
+		DilemmaArena: arena::<Instance1>::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+pub struct ExtBuilder {
+	balances: Vec<(AccountId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			balances: vec![
+				(alice(), 10_000),
+				(bob(), 10_000),
+				(Arena::prize_pool_account_id(), 10_000),
+			],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: self.balances,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		CONTRACT_OWNERS.with(|v| v.borrow_mut().clear());
+		CommitReveal::set(false);
+		RoundGasBudget::set(1_000_000);
+		RandomSeed::set(H256::zero());
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -0,0 +1,59 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Elo rating of the arena contracts.
+//!
+//! The expected score follows the logistic curve `1 / (1 + 10^(-d / 400))` of the rating
+//! difference `d`, looked up from a table in permille and linearly interpolated.
+
+/// The rating of a contract that has not played yet, and of the built-in contenders.
+pub const INITIAL_RATING: u32 = 1500;
+
+/// The maximum rating change of a single match.
+pub const K_FACTOR: i64 = 32;
+
+/// The expected score of the higher rated side in permille, for every 25 points of difference.
+const EXPECTED_SCORE: [u32; 33] = [
+	500, 536, 571, 606, 640, 673, 703, 733, 760, 785, 808, 830, 849, 867, 882, 896, 909, 920, 930, 939, 947, 954, 960,
+	965, 969, 973, 977, 980, 983, 985, 987, 989, 990,
+];
+
+const STEP: u32 = 25;
+
+/// The expected score of `a` against `b` in permille.
+pub fn expected_score(a: u32, b: u32) -> u32 {
+	let diff = if a >= b { a - b } else { b - a };
+	let diff = diff.min(STEP * (EXPECTED_SCORE.len() as u32 - 1));
+	let index = (diff / STEP) as usize;
+	let low = EXPECTED_SCORE[index];
+	let high = EXPECTED_SCORE[(index + 1).min(EXPECTED_SCORE.len() - 1)];
+	let expected = low + (high - low) * (diff % STEP) / STEP;
+
+	if a >= b {
+		expected
+	} else {
+		1000 - expected
+	}
+}
+
+/// The new rating of `a` after scoring `score` permille against `b`, i.e. 1000 for a win, 500
+/// for a draw and 0 for a loss.
+pub fn new_rating(a: u32, b: u32, score: u32) -> u32 {
+	let delta = K_FACTOR * (i64::from(score) - i64::from(expected_score(a, b))) / 1000;
+	(i64::from(a) + delta).clamp(0, i64::from(u32::MAX)) as u32
+}
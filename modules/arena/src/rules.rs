@@ -0,0 +1,159 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Game rules of an arena.
+//!
+//! A `GameRules` implementation decides how the raw output of a contender is turned into a move,
+//! how a round is scored and who wins a match. Every arena instance is configured with one set of
+//! rules, so the same scheduling, queue and eviction logic can host different games.
+
+use codec::{Decode, Encode};
+use frame_support::traits::Get;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_core::U256;
+use sp_runtime::traits::UniqueSaturatedInto;
+use sp_std::marker::PhantomData;
+
+/// The side of a match.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Debug, Eq, PartialEq, Clone, Copy, TypeInfo)]
+pub enum Side {
+	A,
+	B,
+}
+
+pub trait GameRules {
+	/// State carried between the rounds of a single match.
+	type MatchState: Default;
+
+	/// Decode the raw value returned by a contender into a move.
+	fn decode_move(raw: U256) -> U256;
+
+	/// The move that does best against `predicted`, used by the built-in contenders that predict
+	/// the next move of their opponent.
+	fn counter_move(predicted: U256) -> U256;
+
+	/// Score a round, returns the points gained by `a` and `b`.
+	fn score_round(state: &mut Self::MatchState, round: u32, a: U256, b: U256) -> (u32, u32);
+
+	/// Decide the winner of a match from the accumulated points. Returns `None` on a draw.
+	fn match_winner(_state: &Self::MatchState, points_a: u32, points_b: u32) -> Option<Side> {
+		match points_a.cmp(&points_b) {
+			sp_std::cmp::Ordering::Greater => Some(Side::A),
+			sp_std::cmp::Ordering::Less => Some(Side::B),
+			sp_std::cmp::Ordering::Equal => None,
+		}
+	}
+}
+
+/// Rock-paper-scissors: moves are taken modulo 3, and `0` beats `1`, `1` beats `2`, `2` beats `0`.
+pub struct RockPaperScissors;
+impl GameRules for RockPaperScissors {
+	type MatchState = ();
+
+	fn decode_move(raw: U256) -> U256 {
+		raw % 3
+	}
+
+	fn counter_move(predicted: U256) -> U256 {
+		(predicted + 2) % 3
+	}
+
+	fn score_round(_state: &mut (), _round: u32, a: U256, b: U256) -> (u32, u32) {
+		let a: u8 = a.unique_saturated_into();
+		let b: u8 = b.unique_saturated_into();
+		match (a, b) {
+			(0, 1) | (1, 2) | (2, 0) => (1, 0),
+			(0, 2) | (2, 1) | (1, 0) => (0, 1),
+			_ => (0, 0),
+		}
+	}
+}
+
+/// Iterated prisoner's dilemma: an even move cooperates and an odd move defects.
+///
+/// Mutual cooperation pays 3 each, mutual defection 1 each, and a defector against a cooperator
+/// gets 5 while the cooperator gets nothing.
+pub struct PrisonersDilemma;
+impl GameRules for PrisonersDilemma {
+	type MatchState = ();
+
+	fn decode_move(raw: U256) -> U256 {
+		raw % 2
+	}
+
+	fn counter_move(_predicted: U256) -> U256 {
+		// defecting is the best response to either move
+		U256::one()
+	}
+
+	fn score_round(_state: &mut (), _round: u32, a: U256, b: U256) -> (u32, u32) {
+		match (a.is_zero(), b.is_zero()) {
+			(true, true) => (3, 3),
+			(true, false) => (0, 5),
+			(false, true) => (5, 0),
+			(false, false) => (1, 1),
+		}
+	}
+}
+
+/// Sealed-bid game: both contenders start a match with `Budget` and bid every round. The higher
+/// bid wins the round and every bid is spent, bids over the remaining budget are capped.
+pub struct SealedBid<Budget>(PhantomData<Budget>);
+
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
+pub struct SealedBidState {
+	pub spent_a: u32,
+	pub spent_b: u32,
+}
+
+impl<Budget: Get<u32>> GameRules for SealedBid<Budget> {
+	type MatchState = SealedBidState;
+
+	fn decode_move(raw: U256) -> U256 {
+		let bid: u32 = raw.unique_saturated_into();
+		bid.min(Budget::get()).into()
+	}
+
+	fn counter_move(predicted: U256) -> U256 {
+		let bid: u32 = predicted.unique_saturated_into();
+		bid.saturating_add(1).min(Budget::get()).into()
+	}
+
+	fn score_round(state: &mut SealedBidState, _round: u32, a: U256, b: U256) -> (u32, u32) {
+		let bid_a = a.low_u32().min(Budget::get().saturating_sub(state.spent_a));
+		let bid_b = b.low_u32().min(Budget::get().saturating_sub(state.spent_b));
+		state.spent_a = state.spent_a.saturating_add(bid_a);
+		state.spent_b = state.spent_b.saturating_add(bid_b);
+		match bid_a.cmp(&bid_b) {
+			sp_std::cmp::Ordering::Greater => (1, 0),
+			sp_std::cmp::Ordering::Less => (0, 1),
+			sp_std::cmp::Ordering::Equal => (0, 0),
+		}
+	}
+
+	fn match_winner(state: &SealedBidState, points_a: u32, points_b: u32) -> Option<Side> {
+		// ties on rounds won go to the contender with more budget left
+		match (points_a, state.spent_b).cmp(&(points_b, state.spent_a)) {
+			sp_std::cmp::Ordering::Greater => Some(Side::A),
+			sp_std::cmp::Ordering::Less => Some(Side::B),
+			sp_std::cmp::Ordering::Equal => None,
+		}
+	}
+}
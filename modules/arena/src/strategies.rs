@@ -0,0 +1,86 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Built-in reference strategies.
+//!
+//! The strategies only see the decoded moves played so far in the match, and rely on
+//! `GameRules::counter_move` to answer the move they predict, so they can spar in any game.
+
+use crate::rules::GameRules;
+use codec::Encode;
+use sp_core::{H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+
+/// The maximum number of previous moves a Markov contender conditions its prediction on.
+pub const MAX_MARKOV_ORDER: u8 = 4;
+
+/// The most frequent move of `moves`, ties go to the move played last.
+pub fn most_frequent(moves: &[U256]) -> Option<U256> {
+	let mut counts = BTreeMap::<U256, (u32, usize)>::new();
+	for (index, mv) in moves.iter().enumerate() {
+		let entry = counts.entry(*mv).or_default();
+		entry.0 = entry.0.saturating_add(1);
+		entry.1 = index;
+	}
+	counts
+		.into_iter()
+		.max_by_key(|(_, count_and_last)| *count_and_last)
+		.map(|(mv, _)| mv)
+}
+
+/// Counter the most frequent move of the opponent over its last `window` moves, or over the whole
+/// match if `window` is zero.
+pub fn frequency_counter<R: GameRules>(other: &[U256], window: u32) -> U256 {
+	let start = match window {
+		0 => 0,
+		window => other.len().saturating_sub(window as usize),
+	};
+	R::counter_move(most_frequent(&other[start..]).unwrap_or_default())
+}
+
+/// Play `first_move`, then repeat the previous move of the opponent.
+pub fn tit_for_tat(other: &[U256], first_move: u32) -> U256 {
+	other.last().copied().unwrap_or_else(|| first_move.into())
+}
+
+/// Predict the next move of the opponent from the moves that followed its last `order` moves
+/// earlier in the match, and counter it. Falls back to countering its most frequent move while the
+/// pattern has not been seen yet.
+pub fn markov<R: GameRules>(other: &[U256], order: u8) -> U256 {
+	let order = order.clamp(1, MAX_MARKOV_ORDER) as usize;
+	let predicted = if other.len() > order {
+		let context = &other[other.len() - order..];
+		let followers = other
+			.windows(order + 1)
+			.filter(|window| &window[..order] == context)
+			.map(|window| window[order])
+			.collect::<Vec<_>>();
+		most_frequent(&followers)
+	} else {
+		None
+	};
+	R::counter_move(predicted.or_else(|| most_frequent(other)).unwrap_or_default())
+}
+
+/// A move in `0..moves` drawn from the match `seed`, or zero if `moves` is zero.
+pub fn random(seed: H256, round: u32, moves: u32) -> U256 {
+	if moves == 0 {
+		return U256::zero();
+	}
+	U256::from(&keccak_256(&(seed, round).encode())[..]) % moves
+}
@@ -0,0 +1,1063 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Unit tests for the arena module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, instances::Instance1, traits::Hooks};
+use mock::{Event, *};
+use primitives::Balance;
+use sp_runtime::traits::BadOrigin;
+
+fn free_balance(who: &AccountId) -> Balance {
+	Balances::free_balance(who)
+}
+
+fn reserved_balance(who: &AccountId) -> Balance {
+	Balances::reserved_balance(who)
+}
+
+fn run_to_block(n: BlockNumber) {
+	while System::block_number() < n {
+		let b = System::block_number() + 1;
+		System::set_block_number(b);
+		Arena::on_initialize(b);
+		Arena::on_idle(b, u64::MAX);
+	}
+}
+
+fn has_game_result() -> bool {
+	System::events()
+		.iter()
+		.any(|r| matches!(r.event, Event::Arena(crate::Event::GameResult { .. })))
+}
+
+fn register(who: AccountId, contract: H160) -> DispatchResult {
+	set_contract_owner(contract, &who);
+	Arena::register(Origin::signed(who), contract)
+}
+
+#[test]
+fn register_reserves_entry_stake() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(register(alice(), contract(0)));
+		System::assert_has_event(Event::Arena(crate::Event::StakeReserved {
+			contract: contract(0),
+			owner: alice(),
+			amount: 1_000,
+		}));
+		assert_eq!(free_balance(&alice()), 9_000);
+		assert_eq!(reserved_balance(&alice()), 1_000);
+		assert_eq!(
+			Stakes::<Runtime>::get(contract(0)),
+			Some(StakeInfo {
+				owner: alice(),
+				amount: 1_000
+			})
+		);
+		assert_eq!(ContenderInstancesCount::<Runtime>::get(), 2);
+
+		assert_noop!(
+			Arena::register(Origin::signed(alice()), contract(0)),
+			Error::<Runtime>::AlreadyRegistered
+		);
+	});
+}
+
+#[test]
+fn register_fails_for_non_owner() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_contract_owner(contract(0), &alice());
+		assert_noop!(
+			Arena::register(Origin::signed(bob()), contract(0)),
+			Error::<Runtime>::InvalidOwner
+		);
+		assert_eq!(reserved_balance(&bob()), 0);
+	});
+}
+
+#[test]
+fn register_fails_for_short_owner_output() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Arena::register(Origin::signed(alice()), short_owner()),
+			Error::<Runtime>::InvalidOwner
+		);
+	});
+}
+
+#[test]
+fn game_round_pays_prizes_and_slashes_evicted() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(register(alice(), contract(0)));
+		assert_ok!(register(bob(), contract(1)));
+		assert_eq!(ContenderInstancesCount::<Runtime>::get(), 4);
+
+		run_to_block(13);
+		assert_eq!(CurrentRound::<Runtime>::get(), None);
+
+		// contract(0) always beats contract(1), instance 0 is the single winner
+		System::assert_has_event(Event::Arena(crate::Event::PrizePaid {
+			contract: contract(0),
+			id: 0,
+			owner: alice(),
+			amount: 100,
+		}));
+		// the winner gets another instance, so the last instance of contract(1) is evicted
+		System::assert_has_event(Event::Arena(crate::Event::StakeSlashed {
+			contract: contract(1),
+			id: 3,
+			owner: bob(),
+			amount: 100,
+		}));
+		assert_eq!(Contenders::<Runtime>::get(3), None);
+		assert_eq!(ContenderInstancesCount::<Runtime>::get(), 4);
+
+		assert_eq!(free_balance(&alice()), 9_100);
+		assert_eq!(reserved_balance(&alice()), 1_000);
+		assert_eq!(free_balance(&bob()), 9_000);
+		assert_eq!(reserved_balance(&bob()), 900);
+		assert_eq!(Stakes::<Runtime>::get(contract(1)).unwrap().amount, 900);
+		assert_eq!(free_balance(&Arena::prize_pool_account_id()), 10_000);
+		assert_eq!(RoundGasUsed::<Runtime>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn prize_is_capped_by_prize_pool() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(register(alice(), contract(0)));
+		assert_ok!(register(bob(), contract(1)));
+		assert_ok!(Balances::transfer(
+			Origin::signed(Arena::prize_pool_account_id()),
+			bob(),
+			9_960
+		));
+
+		run_to_block(13);
+		assert_eq!(CurrentRound::<Runtime>::get(), None);
+
+		System::assert_has_event(Event::Arena(crate::Event::PrizePaid {
+			contract: contract(0),
+			id: 0,
+			owner: alice(),
+			amount: 40,
+		}));
+		assert_eq!(free_balance(&alice()), 9_040);
+	});
+}
+
+#[test]
+fn prize_skips_withdrawn_instances() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(register(alice(), contract(0)));
+		assert_ok!(register(bob(), contract(1)));
+
+		System::set_block_number(10);
+		Arena::on_initialize(10);
+		Arena::on_idle(10, u64::MAX);
+		// instance 0 of contract(0) leads the round when it is withdrawn
+		assert_ok!(Arena::withdraw(Origin::signed(alice()), contract(0)));
+
+		run_to_block(13);
+		assert_eq!(CurrentRound::<Runtime>::get(), None);
+
+		// the best instance of a staked contract wins the prize
+		System::assert_has_event(Event::Arena(crate::Event::PrizePaid {
+			contract: contract(1),
+			id: 2,
+			owner: bob(),
+			amount: 100,
+		}));
+		assert_eq!(free_balance(&alice()), 10_000);
+		assert_eq!(free_balance(&bob()), 9_100);
+	});
+}
+
+#[test]
+fn rock_paper_scissors_rules() {
+	assert_eq!(RockPaperScissors::decode_move(5.into()), 2.into());
+	assert_eq!(RockPaperScissors::score_round(&mut (), 0, 0.into(), 1.into()), (1, 0));
+	assert_eq!(RockPaperScissors::score_round(&mut (), 0, 0.into(), 2.into()), (0, 1));
+	assert_eq!(RockPaperScissors::score_round(&mut (), 0, 1.into(), 1.into()), (0, 0));
+	assert_eq!(RockPaperScissors::match_winner(&(), 3, 2), Some(Side::A));
+	assert_eq!(RockPaperScissors::match_winner(&(), 2, 2), None);
+}
+
+#[test]
+fn prisoners_dilemma_rules() {
+	assert_eq!(PrisonersDilemma::decode_move(7.into()), 1.into());
+	assert_eq!(PrisonersDilemma::score_round(&mut (), 0, 0.into(), 0.into()), (3, 3));
+	assert_eq!(PrisonersDilemma::score_round(&mut (), 0, 0.into(), 1.into()), (0, 5));
+	assert_eq!(PrisonersDilemma::score_round(&mut (), 0, 1.into(), 0.into()), (5, 0));
+	assert_eq!(PrisonersDilemma::score_round(&mut (), 0, 1.into(), 1.into()), (1, 1));
+}
+
+#[test]
+fn sealed_bid_rules() {
+	type Rules = SealedBid<frame_support::traits::ConstU32<10>>;
+	let mut state = SealedBidState::default();
+	assert_eq!(Rules::decode_move(100.into()), 10.into());
+	assert_eq!(Rules::score_round(&mut state, 0, 8.into(), 3.into()), (1, 0));
+	// only 2 left for a
+	assert_eq!(Rules::score_round(&mut state, 1, 5.into(), 3.into()), (0, 1));
+	assert_eq!(Rules::score_round(&mut state, 2, 0.into(), 0.into()), (0, 0));
+	assert_eq!(
+		state,
+		SealedBidState {
+			spent_a: 10,
+			spent_b: 6
+		}
+	);
+	// rounds are tied, b has more budget left
+	assert_eq!(Rules::match_winner(&state, 1, 1), Some(Side::B));
+}
+
+#[test]
+fn instances_use_their_own_rules_and_storage() {
+	ExtBuilder::default().build().execute_with(|| {
+		// contract(0) cooperates and contract(1) defects
+		assert_ok!(register(alice(), contract(0)));
+		set_contract_owner(contract(1), &bob());
+		assert_ok!(DilemmaArena::register(Origin::signed(bob()), contract(1)));
+		assert_ok!(DilemmaArena::register(Origin::signed(alice()), contract(0)));
+		assert_eq!(ContenderInstancesCount::<Runtime>::get(), 2);
+		assert_eq!(ContenderInstancesCount::<Runtime, Instance1>::get(), 4);
+
+		let a = Contender::Contract(contract(0));
+		let b = Contender::Contract(contract(1));
+		// the defector wins the prisoner's dilemma, but loses rock-paper-scissors
+		assert_eq!(DilemmaArena::play(&a, &b), Some(Side::B));
+		assert_eq!(Arena::play(&a, &b), Some(Side::A));
+	});
+}
+
+#[test]
+fn decode_reveal_works() {
+	let moves = vec![U256::from(1), U256::from(2)];
+	let mut output = SALT.as_bytes().to_vec();
+	output.extend_from_slice(&primitives::to_bytes(0x40u32));
+	output.extend_from_slice(&primitives::to_bytes(2u32));
+	output.extend_from_slice(&primitives::to_bytes(1u32));
+	output.extend_from_slice(&primitives::to_bytes(2u32));
+	assert_eq!(decode_reveal(&output), Some((SALT, moves)));
+
+	// truncated
+	assert_eq!(decode_reveal(&output[..output.len() - 1]), None);
+	// invalid offset
+	output[63] = 0xff;
+	assert_eq!(decode_reveal(&output), None);
+}
+
+#[test]
+fn commit_reveal_forfeits_mismatched_reveals() {
+	ExtBuilder::default().build().execute_with(|| {
+		CommitReveal::set(true);
+		assert_ok!(register(alice(), contract(0)));
+		assert_ok!(register(bob(), cheater(0)));
+
+		// 16 pairings commit in 4 blocks, then wait for the reveal
+		run_to_block(13);
+		assert_eq!(PendingMatches::<Runtime>::iter().count(), 16);
+		assert_eq!(RevealAt::<Runtime>::get(), Some(15));
+		let (match_id, pending) = PendingMatches::<Runtime>::iter()
+			.find(|(_, m)| m.a.0 == 0 && m.b.0 == 2)
+			.unwrap();
+		assert_eq!(pending.commitment_a, Some(commitment_of(SALT, &[U256::zero(); 5])));
+		assert_eq!(pending.commitment_b, Some(commitment_of(SALT, &[U256::zero(); 5])));
+		System::assert_has_event(Event::Arena(crate::Event::MovesCommitted {
+			match_id,
+			id: 0,
+			commitment: commitment_of(SALT, &[U256::zero(); 5]),
+		}));
+
+		run_to_block(14);
+		assert_eq!(PendingMatches::<Runtime>::iter().count(), 16);
+		assert!(!has_game_result());
+
+		run_to_block(17);
+		assert_eq!(PendingMatches::<Runtime>::iter().count(), 1);
+		assert!(!has_game_result());
+
+		run_to_block(18);
+		assert_eq!(PendingMatches::<Runtime>::iter().count(), 0);
+		assert_eq!(RevealAt::<Runtime>::get(), None);
+		assert_eq!(CurrentRound::<Runtime>::get(), None);
+		assert!(has_game_result());
+
+		System::assert_has_event(Event::Arena(crate::Event::Forfeited { match_id, id: 2 }));
+		System::assert_has_event(Event::Arena(crate::Event::PrizePaid {
+			contract: contract(0),
+			id: 0,
+			owner: alice(),
+			amount: 100,
+		}));
+		System::assert_has_event(Event::Arena(crate::Event::StakeSlashed {
+			contract: cheater(0),
+			id: 3,
+			owner: bob(),
+			amount: 100,
+		}));
+	});
+}
+
+#[test]
+fn matches_are_spread_across_blocks() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(register(alice(), contract(0)));
+		assert_ok!(register(bob(), contract(1)));
+
+		System::set_block_number(10);
+		assert_eq!(Arena::on_initialize(10), 0);
+		System::assert_last_event(Event::Arena(crate::Event::RoundStarted { instances: 4 }));
+		assert_eq!(CurrentRound::<Runtime>::get().unwrap().contenders, vec![0, 1, 2, 3]);
+
+		// bounded by the remaining weight
+		let match_weight = Arena::match_weight();
+		assert_eq!(match_weight, 10_000);
+		assert_eq!(Arena::on_idle(10, 2 * match_weight), 2 * match_weight);
+		assert_eq!(CurrentRound::<Runtime>::get().unwrap().cursor, 2);
+
+		// bounded by `MaxMatchesPerBlock`
+		assert_eq!(Arena::on_idle(10, u64::MAX), 5 * match_weight);
+		assert_eq!(CurrentRound::<Runtime>::get().unwrap().cursor, 7);
+		assert_eq!(RoundPoints::<Runtime>::get().len(), 4);
+		assert!(!has_game_result());
+
+		// a new round is not started while one is in progress
+		Arena::on_initialize(20);
+		assert_eq!(CurrentRound::<Runtime>::get().unwrap().started_at, 10);
+
+		run_to_block(11);
+		assert_eq!(CurrentRound::<Runtime>::get().unwrap().cursor, 12);
+		run_to_block(12);
+		assert_eq!(CurrentRound::<Runtime>::get(), None);
+		assert_eq!(RoundPoints::<Runtime>::get(), BTreeMap::new());
+		assert!(has_game_result());
+	});
+}
+
+#[test]
+fn rounds_progress_in_on_initialize() {
+	ExtBuilder::default().build().execute_with(|| {
+		MinMatchesPerBlock::set(2);
+		assert_ok!(register(alice(), contract(0)));
+		assert_ok!(register(bob(), contract(1)));
+
+		// the blocks are full, `on_idle` is never called
+		for n in 1..=10 {
+			System::set_block_number(n);
+			Arena::on_initialize(n);
+		}
+		assert_eq!(CurrentRound::<Runtime>::get().unwrap().cursor, 2);
+
+		System::set_block_number(11);
+		assert_eq!(Arena::on_initialize(11), 2 * Arena::match_weight());
+		assert_eq!(CurrentRound::<Runtime>::get().unwrap().cursor, 4);
+
+		for n in 12..=17 {
+			System::set_block_number(n);
+			Arena::on_initialize(n);
+		}
+		assert_eq!(CurrentRound::<Runtime>::get(), None);
+		assert!(has_game_result());
+	});
+}
+
+#[test]
+fn elo_rating_works() {
+	assert_eq!(rating::expected_score(1500, 1500), 500);
+	assert_eq!(rating::expected_score(1900, 1500), 909);
+	assert_eq!(rating::expected_score(1500, 1900), 91);
+	assert_eq!(rating::expected_score(1510, 1500), 514);
+	assert_eq!(rating::expected_score(3000, 0), 990);
+
+	assert_eq!(rating::new_rating(1500, 1500, 1000), 1516);
+	assert_eq!(rating::new_rating(1500, 1500, 500), 1500);
+	assert_eq!(rating::new_rating(1500, 1500, 0), 1484);
+	assert_eq!(rating::new_rating(1516, 1484, 1000), 1530);
+	assert_eq!(rating::new_rating(1484, 1516, 0), 1470);
+	assert_eq!(rating::new_rating(1900, 1500, 0), 1871);
+	assert_eq!(rating::new_rating(10, 3000, 0), 10);
+}
+
+#[test]
+fn open_and_close_season_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(Arena::open_season(Origin::signed(alice())), BadOrigin);
+		assert_noop!(Arena::close_season(Origin::root()), Error::<Runtime>::NoOpenSeason);
+
+		System::set_block_number(5);
+		assert_ok!(Arena::open_season(Origin::root()));
+		System::assert_last_event(Event::Arena(crate::Event::SeasonOpened { season: 0 }));
+		assert_eq!(CurrentSeason::<Runtime>::get(), Some(0));
+		assert_eq!(
+			Seasons::<Runtime>::get(0),
+			Some(SeasonInfo {
+				started_at: 5,
+				ended_at: None,
+				leaderboard: vec![],
+			})
+		);
+		assert_noop!(Arena::open_season(Origin::root()), Error::<Runtime>::SeasonAlreadyOpen);
+
+		System::set_block_number(8);
+		assert_ok!(Arena::close_season(Origin::root()));
+		System::assert_last_event(Event::Arena(crate::Event::SeasonClosed {
+			season: 0,
+			leaderboard: vec![],
+		}));
+		assert_eq!(CurrentSeason::<Runtime>::get(), None);
+		assert_eq!(Seasons::<Runtime>::get(0).unwrap().ended_at, Some(8));
+
+		assert_ok!(Arena::open_season(Origin::root()));
+		assert_eq!(CurrentSeason::<Runtime>::get(), Some(1));
+		assert_eq!(NextSeasonIndex::<Runtime>::get(), 2);
+	});
+}
+
+#[test]
+fn matches_update_ratings_and_season_history() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(register(alice(), contract(0)));
+		assert_ok!(register(bob(), contract(1)));
+		assert_ok!(Arena::open_season(Origin::root()));
+
+		run_to_block(13);
+
+		// contract(0) won all 8 matches against contract(1)
+		let rating = Arena::contract_rating(contract(0));
+		assert!(rating > rating::INITIAL_RATING);
+		assert_eq!(rating + Arena::contract_rating(contract(1)), 2 * rating::INITIAL_RATING);
+		assert_eq!(
+			Arena::leaderboard(10),
+			vec![
+				(contract(0), rating),
+				(contract(1), 2 * rating::INITIAL_RATING - rating)
+			]
+		);
+		assert_eq!(Arena::leaderboard(1), vec![(contract(0), rating)]);
+		assert_eq!(Arena::contract_rating(contract(2)), rating::INITIAL_RATING);
+
+		// only the last `MaxMatchRecords` matches are kept
+		let history = Arena::match_history(0, contract(0));
+		assert_eq!(history.len(), 3);
+		assert!(history
+			.iter()
+			.all(|record| record.outcome == MatchOutcome::Win && record.opponent == Contender::Contract(contract(1))));
+		assert_eq!(history[2].rating, rating);
+		assert!(Arena::match_history(0, contract(1))
+			.iter()
+			.all(|record| record.outcome == MatchOutcome::Loss));
+
+		// the open season reports the live leaderboard
+		assert_eq!(Arena::season_leaderboard(Some(0)), Arena::leaderboard(10));
+		assert_ok!(Arena::close_season(Origin::root()));
+		assert_eq!(Arena::season_leaderboard(Some(0)), Arena::leaderboard(10));
+		assert_eq!(Arena::season_leaderboard(Some(1)), vec![]);
+
+		// matches are not recorded without an open season, but are still rated
+		run_to_block(23);
+		assert!(Arena::contract_rating(contract(0)) > rating);
+		assert_eq!(Arena::match_history(0, contract(0)), history);
+		assert_eq!(Arena::season_leaderboard(Some(0)).first(), Some(&(contract(0), rating)));
+	});
+}
+
+#[test]
+fn dry_run_match_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let report = Arena::dry_run_match(Contender::Contract(contract(0)), Contender::Rotate);
+		assert_eq!(report.winner, Some(Side::A));
+		assert_eq!(
+			report.rounds,
+			vec![
+				RoundReport {
+					moves: (0.into(), 0.into()),
+					used_gas: (500, 0),
+				},
+				RoundReport {
+					moves: (0.into(), 1.into()),
+					used_gas: (500, 0),
+				},
+				RoundReport {
+					moves: (0.into(), 2.into()),
+					used_gas: (500, 0),
+				},
+				RoundReport {
+					moves: (0.into(), 0.into()),
+					used_gas: (500, 0),
+				},
+				RoundReport {
+					moves: (0.into(), 1.into()),
+					used_gas: (500, 0),
+				},
+			]
+		);
+
+		// nothing is committed
+		assert_eq!(System::events(), vec![]);
+		assert_eq!(Ratings::<Runtime>::iter().count(), 0);
+
+		let report = DilemmaArena::dry_run_match(Contender::AlwaysZero, Contender::Contract(contract(1)));
+		assert_eq!(report.winner, Some(Side::B));
+		assert_eq!(report.rounds.len(), 5);
+		assert!(report.rounds.iter().all(|round| round.used_gas == (0, 500)));
+	});
+}
+
+#[test]
+fn reverting_contract_is_disqualified() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(register(alice(), contract(0)));
+		assert_ok!(register(bob(), reverting()));
+		assert_eq!(ContenderInstancesCount::<Runtime>::get(), 4);
+
+		System::set_block_number(10);
+		Arena::on_initialize(10);
+		Arena::on_idle(10, u64::MAX);
+
+		System::assert_has_event(Event::Arena(crate::Event::ContenderFailed {
+			contract: reverting(),
+			reason: ContenderFailure::Reverted(b"bad move".to_vec()),
+		}));
+		System::assert_has_event(Event::Arena(crate::Event::Disqualified {
+			contract: reverting(),
+			slashed: 1_000,
+		}));
+		assert_eq!(ContenderInstancesCount::<Runtime>::get(), 2);
+		assert!(Contenders::<Runtime>::iter_values().all(|c| c == Contender::Contract(contract(0))));
+		assert!(!ContractInfos::<Runtime>::contains_key(reverting()));
+		assert_eq!(Stakes::<Runtime>::get(reverting()), None);
+		assert_eq!(Failures::<Runtime>::get(reverting()), 0);
+		assert_eq!(reserved_balance(&bob()), 0);
+		assert_eq!(free_balance(&bob()), 9_000);
+		assert_eq!(free_balance(&Arena::prize_pool_account_id()), 11_000);
+
+		// the round goes on without the disqualified contract
+		run_to_block(13);
+		assert_eq!(CurrentRound::<Runtime>::get(), None);
+		assert!(has_game_result());
+	});
+}
+
+#[test]
+fn failures_are_counted_per_round() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(register(alice(), contract(0)));
+		assert_ok!(register(bob(), contract(1)));
+		Failures::<Runtime>::insert(contract(0), 2);
+
+		run_to_block(13);
+		assert!(has_game_result());
+		assert_eq!(Failures::<Runtime>::get(contract(0)), 0);
+		assert!(ContractInfos::<Runtime>::contains_key(contract(0)));
+	});
+}
+
+#[test]
+fn wide_output_is_counted_as_failure() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(register(bob(), wide_output()));
+
+		assert_eq!(
+			Arena::play(&Contender::Contract(wide_output()), &Contender::AlwaysZero),
+			None
+		);
+		System::assert_has_event(Event::Arena(crate::Event::ContenderFailed {
+			contract: wide_output(),
+			reason: ContenderFailure::InvalidOutput,
+		}));
+		System::assert_has_event(Event::Arena(crate::Event::Disqualified {
+			contract: wide_output(),
+			slashed: 1_000,
+		}));
+		assert!(!ContractInfos::<Runtime>::contains_key(wide_output()));
+	});
+}
+
+#[test]
+fn out_of_gas_calls_are_charged_and_counted() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(register(bob(), gas_guzzler()));
+
+		assert_eq!(
+			Arena::play(&Contender::Contract(gas_guzzler()), &Contender::AlwaysZero),
+			None
+		);
+		System::assert_has_event(Event::Arena(crate::Event::ContenderFailed {
+			contract: gas_guzzler(),
+			reason: ContenderFailure::OutOfGas,
+		}));
+		System::assert_has_event(Event::Arena(crate::Event::Disqualified {
+			contract: gas_guzzler(),
+			slashed: 1_000,
+		}));
+		// every call of the match used the full gas limit
+		assert_eq!(RoundGasUsed::<Runtime>::get(gas_guzzler()), 5_000);
+	});
+}
+
+#[test]
+fn out_of_storage_calls_are_charged_and_counted() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(register(bob(), storage_hog()));
+
+		assert_eq!(
+			Arena::play(&Contender::Contract(storage_hog()), &Contender::AlwaysZero),
+			None
+		);
+		System::assert_has_event(Event::Arena(crate::Event::ContenderFailed {
+			contract: storage_hog(),
+			reason: ContenderFailure::OutOfStorage,
+		}));
+		System::assert_has_event(Event::Arena(crate::Event::Disqualified {
+			contract: storage_hog(),
+			slashed: 1_000,
+		}));
+		assert_eq!(RoundGasUsed::<Runtime>::get(storage_hog()), 5_000);
+	});
+}
+
+#[test]
+fn round_gas_budget_is_enforced() {
+	ExtBuilder::default().build().execute_with(|| {
+		RoundGasBudget::set(1_200);
+		assert_ok!(register(alice(), contract(0)));
+
+		assert_eq!(
+			Arena::play(&Contender::Contract(contract(0)), &Contender::AlwaysZero),
+			None
+		);
+		// two calls fit in the budget, the third runs out of gas and the rest are not made
+		assert_eq!(RoundGasUsed::<Runtime>::get(contract(0)), 1_200);
+		System::assert_has_event(Event::Arena(crate::Event::Disqualified {
+			contract: contract(0),
+			slashed: 1_000,
+		}));
+
+		// with enough budget every call succeeds
+		RoundGasBudget::set(1_000_000);
+		assert_ok!(register(alice(), contract(1)));
+		assert_eq!(
+			Arena::play(&Contender::Contract(contract(1)), &Contender::AlwaysZero),
+			Some(Side::B)
+		);
+		assert_eq!(RoundGasUsed::<Runtime>::get(contract(1)), 2_500);
+		assert_eq!(Failures::<Runtime>::get(contract(1)), 0);
+	});
+}
+
+#[test]
+fn dry_run_ignores_round_gas_budget() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(register(alice(), contract(1)));
+		RoundGasUsed::<Runtime>::insert(contract(1), 1_000_000);
+		Failures::<Runtime>::insert(contract(1), 2);
+
+		let report = Arena::dry_run_match(Contender::Contract(contract(1)), Contender::AlwaysZero);
+		assert_eq!(report.winner, Some(Side::B));
+		assert!(report.rounds.iter().all(|round| round.moves == (1.into(), 0.into())));
+		assert!(report.rounds.iter().all(|round| round.used_gas == (500, 0)));
+
+		assert_eq!(RoundGasUsed::<Runtime>::get(contract(1)), 1_000_000);
+		assert_eq!(Failures::<Runtime>::get(contract(1)), 2);
+		assert!(ContractInfos::<Runtime>::contains_key(contract(1)));
+	});
+}
+
+fn report_moves(report: &MatchReport) -> Vec<(u32, u32)> {
+	report
+		.rounds
+		.iter()
+		.map(|round| (round.moves.0.as_u32(), round.moves.1.as_u32()))
+		.collect()
+}
+
+#[test]
+fn register_contender_validates_parameters() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Arena::register_contender(Origin::root(), Contender::Markov { order: 0 }, 1),
+			Error::<Runtime>::InvalidContender
+		);
+		assert_noop!(
+			Arena::register_contender(
+				Origin::root(),
+				Contender::Markov {
+					order: MAX_MARKOV_ORDER + 1
+				},
+				1
+			),
+			Error::<Runtime>::InvalidContender
+		);
+		assert_noop!(
+			Arena::register_contender(Origin::root(), Contender::Random { moves: 0 }, 1),
+			Error::<Runtime>::InvalidContender
+		);
+
+		assert_ok!(Arena::register_contender(
+			Origin::root(),
+			Contender::Markov {
+				order: MAX_MARKOV_ORDER
+			},
+			1
+		));
+		assert_ok!(Arena::register_contender(
+			Origin::root(),
+			Contender::FrequencyCounter { window: 0 },
+			1
+		));
+		assert_ok!(Arena::register_contender(
+			Origin::root(),
+			Contender::TitForTat { first_move: 1 },
+			1
+		));
+		assert_ok!(Arena::register_contender(
+			Origin::root(),
+			Contender::Random { moves: 3 },
+			1
+		));
+		assert_eq!(ContenderInstancesCount::<Runtime>::get(), 4);
+	});
+}
+
+#[test]
+fn frequency_counter_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let report = Arena::dry_run_match(
+			Contender::Contract(contract(1)),
+			Contender::FrequencyCounter { window: 0 },
+		);
+		assert_eq!(report_moves(&report), vec![(1, 2), (1, 0), (1, 0), (1, 0), (1, 0)]);
+		assert_eq!(report.winner, Some(Side::B));
+
+		// only looks at the last move, so it counters the previous move of `Rotate`
+		let report = Arena::dry_run_match(Contender::Rotate, Contender::FrequencyCounter { window: 1 });
+		assert_eq!(report_moves(&report), vec![(0, 2), (1, 2), (2, 0), (0, 1), (1, 2)]);
+
+		// defects against anything in the prisoner's dilemma
+		let report = DilemmaArena::dry_run_match(Contender::AlwaysZero, Contender::FrequencyCounter { window: 0 });
+		assert!(report.rounds.iter().all(|round| round.moves == (0.into(), 1.into())));
+		assert_eq!(report.winner, Some(Side::B));
+	});
+}
+
+#[test]
+fn tit_for_tat_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let report = Arena::dry_run_match(Contender::Rotate, Contender::TitForTat { first_move: 1 });
+		assert_eq!(report_moves(&report), vec![(0, 1), (1, 0), (2, 1), (0, 2), (1, 0)]);
+
+		let report = DilemmaArena::dry_run_match(
+			Contender::TitForTat { first_move: 0 },
+			Contender::TitForTat { first_move: 1 },
+		);
+		assert_eq!(report_moves(&report), vec![(0, 1), (1, 0), (0, 1), (1, 0), (0, 1)]);
+	});
+}
+
+#[test]
+fn markov_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		// falls back to the most frequent move, then predicts `Rotate` from its last move
+		let report = Arena::dry_run_match(Contender::Rotate, Contender::Markov { order: 1 });
+		assert_eq!(report_moves(&report), vec![(0, 2), (1, 2), (2, 0), (0, 1), (1, 0)]);
+
+		let report = Arena::dry_run_match(Contender::Contract(contract(2)), Contender::Markov { order: 2 });
+		assert_eq!(report_moves(&report), vec![(2, 2), (2, 1), (2, 1), (2, 1), (2, 1)]);
+		assert_eq!(report.winner, Some(Side::B));
+	});
+}
+
+#[test]
+fn random_contender_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let a = Contender::Random { moves: 3 };
+		let b = Contender::Random { moves: 2 };
+
+		let report = Arena::dry_run_match(a.clone(), b.clone());
+		assert!(report
+			.rounds
+			.iter()
+			.all(|round| round.moves.0 < 3.into() && round.moves.1 < 2.into()));
+		assert_eq!(Arena::dry_run_match(a.clone(), b.clone()), report);
+
+		RandomSeed::set(H256::repeat_byte(1));
+		assert_ne!(Arena::dry_run_match(a, b), report);
+	});
+}
+
+#[test]
+fn withdraw_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(register(alice(), contract(0)));
+		assert_ok!(register(bob(), contract(1)));
+		set_contract_owner(contract(2), &alice());
+		assert_ok!(Arena::register(Origin::signed(alice()), contract(2)));
+		assert_eq!(ContenderQueue::<Runtime>::get(), vec![Contender::Contract(contract(2))]);
+		assert_eq!(reserved_balance(&alice()), 2_000);
+
+		assert_noop!(
+			Arena::withdraw(Origin::signed(alice()), contract(3)),
+			Error::<Runtime>::NotRegistered
+		);
+		assert_noop!(
+			Arena::withdraw(Origin::signed(bob()), contract(0)),
+			Error::<Runtime>::NotOwner
+		);
+
+		assert_ok!(Arena::withdraw(Origin::signed(alice()), contract(0)));
+		System::assert_last_event(Event::Arena(crate::Event::Withdrawn {
+			contract: contract(0),
+			owner: alice(),
+			amount: 1_000,
+		}));
+		assert_eq!(ContenderInstancesCount::<Runtime>::get(), 2);
+		assert_eq!(Contenders::<Runtime>::iter().count(), 2);
+		assert!(!ContractInfos::<Runtime>::contains_key(contract(0)));
+		assert!(!ContractInstances::<Runtime>::contains_key(contract(0)));
+		assert_eq!(ContractInstances::<Runtime>::get(contract(1)), vec![2, 3]);
+		assert_eq!(Stakes::<Runtime>::get(contract(0)), None);
+
+		// queued entries are dropped too
+		assert_ok!(Arena::withdraw(Origin::signed(alice()), contract(2)));
+		assert_eq!(ContenderQueue::<Runtime>::get(), vec![]);
+		assert_eq!(free_balance(&alice()), 10_000);
+		assert_eq!(reserved_balance(&alice()), 0);
+
+		// and can register again
+		assert_ok!(Arena::register(Origin::signed(alice()), contract(0)));
+		assert_eq!(ContenderInstancesCount::<Runtime>::get(), 4);
+		assert_eq!(ContractInstances::<Runtime>::get(contract(0)), vec![4, 5]);
+	});
+}
+
+#[test]
+fn withdraw_during_round_keeps_instances_count() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(register(alice(), contract(0)));
+		assert_ok!(register(bob(), contract(1)));
+
+		run_to_block(10);
+		assert!(CurrentRound::<Runtime>::exists());
+		assert_ok!(Arena::withdraw(Origin::signed(bob()), contract(1)));
+		assert_eq!(free_balance(&bob()), 10_000);
+
+		run_to_block(13);
+		assert!(has_game_result());
+		assert_eq!(
+			ContenderInstancesCount::<Runtime>::get(),
+			Contenders::<Runtime>::iter().count() as u32
+		);
+		assert_eq!(ContractInfos::<Runtime>::get(contract(0)).instance_count, 3);
+		assert_eq!(ContractInstances::<Runtime>::get(contract(0)).len(), 3);
+		assert!(!ContractInstances::<Runtime>::contains_key(contract(1)));
+	});
+}
+
+#[test]
+fn pause_and_resume_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(register(alice(), contract(0)));
+		assert_ok!(register(bob(), contract(1)));
+
+		assert_noop!(
+			Arena::pause(Origin::signed(bob()), contract(0)),
+			Error::<Runtime>::NotOwner
+		);
+		assert_noop!(
+			Arena::resume(Origin::signed(alice()), contract(0)),
+			Error::<Runtime>::NotPaused
+		);
+
+		assert_ok!(Arena::pause(Origin::signed(alice()), contract(0)));
+		System::assert_last_event(Event::Arena(crate::Event::Paused { contract: contract(0) }));
+		assert!(Paused::<Runtime>::get(contract(0)));
+		assert_eq!(ContenderInstancesCount::<Runtime>::get(), 2);
+		assert_eq!(ContractInfos::<Runtime>::get(contract(0)).instance_count, 0);
+		assert!(!ContractInstances::<Runtime>::contains_key(contract(0)));
+		assert_eq!(reserved_balance(&alice()), 1_000);
+
+		assert_noop!(
+			Arena::pause(Origin::signed(alice()), contract(0)),
+			Error::<Runtime>::AlreadyPaused
+		);
+		assert_noop!(
+			Arena::register(Origin::signed(alice()), contract(0)),
+			Error::<Runtime>::AlreadyRegistered
+		);
+
+		assert_ok!(Arena::resume(Origin::signed(alice()), contract(0)));
+		System::assert_last_event(Event::Arena(crate::Event::Resumed { contract: contract(0) }));
+		assert!(!Paused::<Runtime>::contains_key(contract(0)));
+		assert_eq!(ContenderInstancesCount::<Runtime>::get(), 4);
+		assert_eq!(ContractInfos::<Runtime>::get(contract(0)).instance_count, 2);
+	});
+}
+
+#[test]
+fn evicted_contract_can_register_again() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(register(alice(), contract(0)));
+		assert_ok!(register(bob(), contract(1)));
+
+		// contract(1) loses an instance every round
+		run_to_block(23);
+		assert_eq!(ContractInfos::<Runtime>::get(contract(1)).instance_count, 0);
+		assert!(ContractInstances::<Runtime>::get(contract(1)).is_empty());
+		assert_eq!(Stakes::<Runtime>::get(contract(1)).unwrap().amount, 810);
+		assert_eq!(reserved_balance(&bob()), 810);
+
+		assert_noop!(
+			Arena::pause(Origin::signed(bob()), contract(1)),
+			Error::<Runtime>::NotActive
+		);
+
+		// the remaining stake is released and a full stake reserved
+		assert_ok!(Arena::register(Origin::signed(bob()), contract(1)));
+		assert_eq!(free_balance(&bob()), 8_810);
+		assert_eq!(reserved_balance(&bob()), 1_000);
+		assert_eq!(Stakes::<Runtime>::get(contract(1)).unwrap().amount, 1_000);
+		assert_eq!(ContenderQueue::<Runtime>::get(), vec![Contender::Contract(contract(1))]);
+	});
+}
+
+#[test]
+fn upgrade_contract_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(register(alice(), contract(0)));
+		assert_ok!(register(bob(), contract(1)));
+		Ratings::<Runtime>::insert(contract(0), 1_600);
+		Failures::<Runtime>::insert(contract(0), 2);
+		RoundGasUsed::<Runtime>::insert(contract(0), 1_500);
+		set_contract_owner(contract(3), &alice());
+		set_contract_owner(contract(4), &bob());
+
+		assert_noop!(
+			Arena::upgrade_contract(Origin::signed(bob()), contract(0), contract(3)),
+			Error::<Runtime>::NotOwner
+		);
+		assert_noop!(
+			Arena::upgrade_contract(Origin::signed(alice()), contract(0), contract(1)),
+			Error::<Runtime>::AlreadyRegistered
+		);
+		assert_noop!(
+			Arena::upgrade_contract(Origin::signed(alice()), contract(0), contract(4)),
+			Error::<Runtime>::InvalidOwner
+		);
+
+		assert_ok!(Arena::upgrade_contract(
+			Origin::signed(alice()),
+			contract(0),
+			contract(3)
+		));
+		System::assert_last_event(Event::Arena(crate::Event::ContractUpgraded {
+			old: contract(0),
+			new: contract(3),
+		}));
+		assert_eq!(Contenders::<Runtime>::get(0), Some(Contender::Contract(contract(3))));
+		assert_eq!(Contenders::<Runtime>::get(1), Some(Contender::Contract(contract(3))));
+		assert_eq!(ContenderInstancesCount::<Runtime>::get(), 4);
+		assert!(!ContractInfos::<Runtime>::contains_key(contract(0)));
+		assert_eq!(ContractInfos::<Runtime>::get(contract(3)).instance_count, 2);
+		assert!(!ContractInstances::<Runtime>::contains_key(contract(0)));
+		assert_eq!(ContractInstances::<Runtime>::get(contract(3)), vec![0, 1]);
+		assert_eq!(Stakes::<Runtime>::get(contract(0)), None);
+		assert_eq!(
+			Stakes::<Runtime>::get(contract(3)),
+			Some(StakeInfo {
+				owner: alice(),
+				amount: 1_000
+			})
+		);
+		assert_eq!(Ratings::<Runtime>::get(contract(0)), None);
+		assert_eq!(Ratings::<Runtime>::get(contract(3)), Some(1_600));
+		assert_eq!(Failures::<Runtime>::get(contract(0)), 0);
+		assert_eq!(Failures::<Runtime>::get(contract(3)), 2);
+		assert_eq!(RoundGasUsed::<Runtime>::get(contract(0)), 0);
+		assert_eq!(RoundGasUsed::<Runtime>::get(contract(3)), 1_500);
+		assert_eq!(reserved_balance(&alice()), 1_000);
+
+		// the old contract is free to register again
+		assert_ok!(Arena::register(Origin::signed(alice()), contract(0)));
+	});
+}
+
+#[test]
+fn transfer_ownership_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(register(alice(), contract(0)));
+
+		assert_noop!(
+			Arena::transfer_ownership(Origin::signed(bob()), contract(0), bob()),
+			Error::<Runtime>::NotOwner
+		);
+		// bob does not own the contract yet
+		assert_noop!(
+			Arena::transfer_ownership(Origin::signed(alice()), contract(0), bob()),
+			Error::<Runtime>::InvalidOwner
+		);
+
+		set_contract_owner(contract(0), &bob());
+		assert_ok!(Arena::transfer_ownership(Origin::signed(alice()), contract(0), bob()));
+		System::assert_last_event(Event::Arena(crate::Event::OwnershipTransferred {
+			contract: contract(0),
+			from: alice(),
+			to: bob(),
+		}));
+		assert_eq!(
+			Stakes::<Runtime>::get(contract(0)),
+			Some(StakeInfo {
+				owner: bob(),
+				amount: 1_000
+			})
+		);
+		assert_eq!(free_balance(&alice()), 9_000);
+		assert_eq!(reserved_balance(&alice()), 0);
+		assert_eq!(free_balance(&bob()), 10_000);
+		assert_eq!(reserved_balance(&bob()), 1_000);
+
+		assert_ok!(Arena::withdraw(Origin::signed(bob()), contract(0)));
+		assert_eq!(free_balance(&bob()), 11_000);
+	});
+}
+
+#[test]
+fn on_runtime_upgrade_indexes_contract_instances() {
+	ExtBuilder::default().build().execute_with(|| {
+		Contenders::<Runtime>::insert(0, Contender::Contract(contract(0)));
+		Contenders::<Runtime>::insert(1, Contender::Contract(contract(1)));
+		Contenders::<Runtime>::insert(2, Contender::Contract(contract(0)));
+		StorageVersion::new(0).put::<Arena>();
+
+		Arena::on_runtime_upgrade();
+		let mut ids = ContractInstances::<Runtime>::get(contract(0));
+		ids.sort_unstable();
+		assert_eq!(ids, vec![0, 2]);
+		assert_eq!(ContractInstances::<Runtime>::get(contract(1)), vec![1]);
+		assert_eq!(StorageVersion::get::<Arena>(), 1);
+
+		// runs only once
+		Arena::on_runtime_upgrade();
+		assert_eq!(ContractInstances::<Runtime>::get(contract(1)), vec![1]);
+	});
+}
@@ -35,7 +35,7 @@ use sp_runtime::{
 	traits::{AccountIdConversion, IdentityLookup, One as OneT},
 };
 use sp_std::cell::RefCell;
-use support::mocks::MockStableAsset;
+use support::mocks::{MockEVM, MockStableAsset};
 pub use support::Price;
 
 pub type AccountId = u128;
@@ -179,6 +179,12 @@ impl module_dex::Config for Runtime {
 	type ExtendedProvisioningBlocks = ConstU64<0>;
 	type StableAsset = MockStableAsset<CurrencyId, Balance, AccountId, BlockNumber>;
 	type OnLiquidityPoolUpdated = ();
+	type PositionNFT = ();
+	type MaxInitializedTicks = ConstU32<16>;
+	type MaxLimitOrdersPerAccount = ConstU32<8>;
+	type LimitOrderKeeperFee = ();
+	type MaxRouteSplits = ConstU32<4>;
+	type EVM = MockEVM;
 }
 
 thread_local! {
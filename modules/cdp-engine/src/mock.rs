@@ -35,7 +35,7 @@ use sp_runtime::{
 	traits::{AccountIdConversion, IdentityLookup, One as OneT},
 };
 use sp_std::cell::RefCell;
-use support::mocks::MockStableAsset;
+use support::mocks::{MockEVM, MockStableAsset};
 use support::{AuctionManager, EmergencyShutdown};
 
 pub type AccountId = u128;
@@ -262,6 +262,12 @@ impl dex::Config for Runtime {
 	type ListingOrigin = EnsureSignedBy<One, AccountId>;
 	type ExtendedProvisioningBlocks = ConstU64<0>;
 	type OnLiquidityPoolUpdated = ();
+	type PositionNFT = ();
+	type MaxInitializedTicks = ConstU32<16>;
+	type MaxLimitOrdersPerAccount = ConstU32<8>;
+	type LimitOrderKeeperFee = ();
+	type MaxRouteSplits = ConstU32<4>;
+	type EVM = MockEVM;
 }
 
 impl pallet_timestamp::Config for Runtime {
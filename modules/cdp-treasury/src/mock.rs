@@ -35,6 +35,7 @@ use primitives::{DexShare, TokenSymbol, TradingPair};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
 use sp_std::cell::RefCell;
+use support::mocks::MockEVM;
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
@@ -150,6 +151,12 @@ impl module_dex::Config for Runtime {
 	type ListingOrigin = EnsureSignedBy<One, AccountId>;
 	type ExtendedProvisioningBlocks = ConstU64<0>;
 	type OnLiquidityPoolUpdated = ();
+	type PositionNFT = ();
+	type MaxInitializedTicks = ConstU32<16>;
+	type MaxLimitOrdersPerAccount = ConstU32<8>;
+	type LimitOrderKeeperFee = ();
+	type MaxRouteSplits = ConstU32<4>;
+	type EVM = MockEVM;
 }
 
 thread_local! {
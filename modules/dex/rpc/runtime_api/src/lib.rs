@@ -0,0 +1,45 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::all)]
+
+use codec::Codec;
+use primitives::{Balance, CurrencyId};
+use sp_std::vec::Vec;
+
+pub use module_dex::{LimitOrder, LimitOrderId, RouteHop, RouteQuote};
+
+sp_api::decl_runtime_apis! {
+	pub trait DexApi<AccountId, BlockNumber> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// The open limit orders of `who`.
+		fn open_limit_orders(who: AccountId) -> Vec<(LimitOrderId, LimitOrder<AccountId, BlockNumber>)>;
+
+		/// The best routes to swap `supply_amount` of `supply_currency_id` to `target_currency_id`,
+		/// split across at most `max_splits` routes. Empty if the amount can't be swapped.
+		fn get_best_routes(
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			supply_amount: Balance,
+			max_splits: u32,
+		) -> Vec<RouteQuote>;
+	}
+}
@@ -0,0 +1,744 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Concentrated liquidity pools.
+//!
+//! The math refers to the design of Uniswap V3. The price of the pool is
+//! the amount of `trading_pair.second()` per `trading_pair.first()`, it is
+//! tracked as a Q64.96 square root and discretized into ticks, the price at
+//! tick `i` is `1.0001^i`. Liquidity is provided into a tick range and is only
+//! active when the current price is inside the range, so a range entirely
+//! above or below the current price is a single-sided range order.
+
+use super::*;
+use sp_core::U512;
+
+/// The minimum tick, the price at it is about `2^-128`.
+pub const MIN_TICK: i32 = -887272;
+/// The maximum tick, the price at it is about `2^128`.
+pub const MAX_TICK: i32 = -MIN_TICK;
+
+/// State of a concentrated liquidity pool.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub struct ConcentratedPool {
+	/// The current sqrt price, as a Q64.96 number.
+	pub sqrt_price: U256,
+	/// The current tick.
+	pub tick: i32,
+	/// Ticks of positions must be multiples of the spacing.
+	pub tick_spacing: u32,
+	/// The liquidity of positions in range.
+	pub liquidity: u128,
+	/// Fee growth per unit of liquidity of the two currencies, as Q128.128
+	/// numbers.
+	pub fee_growth_global: (U256, U256),
+}
+
+/// State of an initialized tick.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default, MaxEncodedLen, TypeInfo)]
+pub struct TickInfo {
+	/// The total liquidity of positions referencing the tick.
+	pub liquidity_gross: u128,
+	/// The liquidity change when the price crosses the tick from left to
+	/// right.
+	pub liquidity_net: i128,
+	/// Fee growth on the other side of the tick from the current tick, as
+	/// Q128.128 numbers.
+	pub fee_growth_outside: (U256, U256),
+}
+
+/// A liquidity position in a concentrated liquidity pool.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub struct Position<ClassId, TokenId> {
+	/// The trading pair of the pool.
+	pub trading_pair: TradingPair,
+	/// The lower tick of the range.
+	pub tick_lower: i32,
+	/// The upper tick of the range.
+	pub tick_upper: i32,
+	/// The liquidity of the position.
+	pub liquidity: u128,
+	/// Fee growth inside the range as of the last update.
+	pub fee_growth_inside_last: (U256, U256),
+	/// Fees accrued to the position and not collected yet.
+	pub tokens_owed: (Balance, Balance),
+	/// The NFT represents the ownership of the position.
+	pub nft: (ClassId, TokenId),
+}
+
+/// The result of a swap step within a single tick range.
+#[derive(Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub struct SwapStep {
+	/// The sqrt price after the step.
+	pub sqrt_price_next: U256,
+	/// The amount to be swapped in, excluding the fee.
+	pub amount_in: U256,
+	/// The amount to be received.
+	pub amount_out: U256,
+	/// The fee charged from the amount in.
+	pub fee_amount: U256,
+}
+
+fn q96() -> U256 {
+	U256::one() << 96
+}
+
+fn q128() -> U256 {
+	U256::one() << 128
+}
+
+/// The sqrt price of `MIN_TICK`.
+pub fn min_sqrt_price() -> U256 {
+	U256::from(4_295_128_739u64)
+}
+
+/// The sqrt price of `MAX_TICK`.
+pub fn max_sqrt_price() -> U256 {
+	U256([0x5d951d5263988d26, 0xefd1fc6a50648849, 0xfffd8963, 0])
+}
+
+/// Calculate `a * b / denominator` with full precision, rounding down.
+pub fn mul_div(a: U256, b: U256, denominator: U256) -> Option<U256> {
+	if denominator.is_zero() {
+		return None;
+	}
+	U256::try_from(a.full_mul(b) / U512::from(denominator)).ok()
+}
+
+/// Calculate `a * b / denominator` with full precision, rounding up.
+pub fn mul_div_rounding_up(a: U256, b: U256, denominator: U256) -> Option<U256> {
+	if denominator.is_zero() {
+		return None;
+	}
+	let (quotient, remainder) = a.full_mul(b).div_mod(U512::from(denominator));
+	let quotient = U256::try_from(quotient).ok()?;
+	if remainder.is_zero() {
+		Some(quotient)
+	} else {
+		quotient.checked_add(U256::one())
+	}
+}
+
+fn div_rounding_up(a: U256, b: U256) -> Option<U256> {
+	if b.is_zero() {
+		return None;
+	}
+	let (quotient, remainder) = a.div_mod(b);
+	if remainder.is_zero() {
+		Some(quotient)
+	} else {
+		quotient.checked_add(U256::one())
+	}
+}
+
+fn wrapping_sub(a: U256, b: U256) -> U256 {
+	a.overflowing_sub(b).0
+}
+
+fn wrapping_add(a: U256, b: U256) -> U256 {
+	a.overflowing_add(b).0
+}
+
+/// Add a signed liquidity delta to the liquidity.
+pub fn add_liquidity_delta(liquidity: u128, delta: i128) -> Option<u128> {
+	if delta < 0 {
+		liquidity.checked_sub(delta.unsigned_abs())
+	} else {
+		liquidity.checked_add(delta.unsigned_abs())
+	}
+}
+
+/// Get the sqrt price at the tick, as a Q64.96 number. The tick must be in
+/// `[MIN_TICK, MAX_TICK]`.
+pub fn sqrt_price_at_tick(tick: i32) -> U256 {
+	const FACTORS: [u128; 19] = [
+		0xfff97272373d413259a46990580e213a,
+		0xfff2e50f5f656932ef12357cf3c7fdcc,
+		0xffe5caca7e10e4e61c3624eaa0941cd0,
+		0xffcb9843d60f6159c9db58835c926644,
+		0xff973b41fa98c081472e6896dfb254c0,
+		0xff2ea16466c96a3843ec78b326b52861,
+		0xfe5dee046a99a2a811c461f1969c3053,
+		0xfcbe86c7900a88aedcffc83b479aa3a4,
+		0xf987a7253ac413176f2b074cf7815e54,
+		0xf3392b0822b70005940c7a398e4b70f3,
+		0xe7159475a2c29b7443b29c7fa6e889d9,
+		0xd097f3bdfd2022b8845ad8f792aa5825,
+		0xa9f746462d870fdf8a65dc1f90e061e5,
+		0x70d869a156d2a1b890bb3df62baf32f7,
+		0x31be135f97d08fd981231505542fcfa6,
+		0x9aa508b5b7a84e1c677de54f3e99bc9,
+		0x5d6af8dedb81196699c329225ee604,
+		0x2216e584f5fa1ea926041bedfe98,
+		0x48a170391f7dc42444e8fa2,
+	];
+
+	let abs_tick = tick.unsigned_abs();
+	// ratio = 1.0001^(-abs_tick / 2), as a Q128.128 number
+	let mut ratio = if abs_tick & 0x1 != 0 {
+		U256::from(0xfffcb933bd6fad37aa2d162d1a594001u128)
+	} else {
+		q128()
+	};
+	for (i, factor) in FACTORS.iter().enumerate() {
+		if abs_tick & (0x2 << i) != 0 {
+			ratio = (ratio * U256::from(*factor)) >> 128;
+		}
+	}
+	if tick > 0 {
+		ratio = U256::max_value() / ratio;
+	}
+
+	// round up to Q64.96 so that `tick_at_sqrt_price(sqrt_price_at_tick(tick)) == tick`
+	let (sqrt_price, remainder) = (ratio >> 32, ratio & U256::from(u32::MAX));
+	if remainder.is_zero() {
+		sqrt_price
+	} else {
+		sqrt_price + U256::one()
+	}
+}
+
+/// Get the greatest tick whose sqrt price is not greater than `sqrt_price`.
+pub fn tick_at_sqrt_price(sqrt_price: U256) -> i32 {
+	let (mut low, mut high) = (MIN_TICK, MAX_TICK);
+	while low < high {
+		// round up to make progress when `high == low + 1`
+		let mid = low + (high - low + 1) / 2;
+		if sqrt_price_at_tick(mid) <= sqrt_price {
+			low = mid;
+		} else {
+			high = mid - 1;
+		}
+	}
+	low
+}
+
+/// Get the amount of `trading_pair.first()` between the two sqrt prices for
+/// the liquidity.
+pub fn amount_0_delta(sqrt_price_a: U256, sqrt_price_b: U256, liquidity: u128, round_up: bool) -> Option<U256> {
+	let (sqrt_price_lower, sqrt_price_upper) = if sqrt_price_a > sqrt_price_b {
+		(sqrt_price_b, sqrt_price_a)
+	} else {
+		(sqrt_price_a, sqrt_price_b)
+	};
+	if sqrt_price_lower.is_zero() {
+		return None;
+	}
+
+	let numerator_1 = U256::from(liquidity) << 96;
+	let numerator_2 = sqrt_price_upper - sqrt_price_lower;
+	if round_up {
+		div_rounding_up(
+			mul_div_rounding_up(numerator_1, numerator_2, sqrt_price_upper)?,
+			sqrt_price_lower,
+		)
+	} else {
+		mul_div(numerator_1, numerator_2, sqrt_price_upper)?.checked_div(sqrt_price_lower)
+	}
+}
+
+/// Get the amount of `trading_pair.second()` between the two sqrt prices for
+/// the liquidity.
+pub fn amount_1_delta(sqrt_price_a: U256, sqrt_price_b: U256, liquidity: u128, round_up: bool) -> Option<U256> {
+	let (sqrt_price_lower, sqrt_price_upper) = if sqrt_price_a > sqrt_price_b {
+		(sqrt_price_b, sqrt_price_a)
+	} else {
+		(sqrt_price_a, sqrt_price_b)
+	};
+
+	if round_up {
+		mul_div_rounding_up(U256::from(liquidity), sqrt_price_upper - sqrt_price_lower, q96())
+	} else {
+		mul_div(U256::from(liquidity), sqrt_price_upper - sqrt_price_lower, q96())
+	}
+}
+
+/// Get the next sqrt price by adding or removing `amount` of
+/// `trading_pair.first()`, rounding up to ensure the pool is not underpaid.
+fn next_sqrt_price_from_amount_0(sqrt_price: U256, liquidity: u128, amount: U256, add: bool) -> Option<U256> {
+	if amount.is_zero() {
+		return Some(sqrt_price);
+	}
+
+	let numerator = U512::from(U256::from(liquidity) << 96);
+	let product = amount.full_mul(sqrt_price);
+	let denominator = if add {
+		numerator.checked_add(product)?
+	} else {
+		numerator.checked_sub(product).filter(|d| !d.is_zero())?
+	};
+
+	let (quotient, remainder) = numerator.checked_mul(U512::from(sqrt_price))?.div_mod(denominator);
+	let quotient = U256::try_from(quotient).ok()?;
+	if remainder.is_zero() {
+		Some(quotient)
+	} else {
+		quotient.checked_add(U256::one())
+	}
+}
+
+/// Get the next sqrt price by adding or removing `amount` of
+/// `trading_pair.second()`, rounding down to ensure the pool is not underpaid.
+fn next_sqrt_price_from_amount_1(sqrt_price: U256, liquidity: u128, amount: U256, add: bool) -> Option<U256> {
+	if add {
+		let quotient = mul_div(amount, q96(), U256::from(liquidity))?;
+		sqrt_price.checked_add(quotient)
+	} else {
+		let quotient = mul_div_rounding_up(amount, q96(), U256::from(liquidity))?;
+		sqrt_price.checked_sub(quotient).filter(|p| !p.is_zero())
+	}
+}
+
+/// Compute the result of swapping from `sqrt_price_current` towards
+/// `sqrt_price_target`, until either the target is reached or
+/// `amount_remaining` is used up.
+pub fn compute_swap_step(
+	sqrt_price_current: U256,
+	sqrt_price_target: U256,
+	liquidity: u128,
+	amount_remaining: U256,
+	is_exact_supply: bool,
+	(fee_numerator, fee_denominator): (u32, u32),
+) -> Option<SwapStep> {
+	let zero_for_one = sqrt_price_current >= sqrt_price_target;
+	let fee_rest = U256::from(fee_denominator.checked_sub(fee_numerator).filter(|r| *r > 0)?);
+
+	let mut amount_in = U256::zero();
+	let mut amount_out = U256::zero();
+	let sqrt_price_next = if is_exact_supply {
+		let amount_remaining_less_fee = mul_div(amount_remaining, fee_rest, U256::from(fee_denominator))?;
+		amount_in = if zero_for_one {
+			amount_0_delta(sqrt_price_target, sqrt_price_current, liquidity, true)?
+		} else {
+			amount_1_delta(sqrt_price_current, sqrt_price_target, liquidity, true)?
+		};
+		if amount_remaining_less_fee >= amount_in {
+			sqrt_price_target
+		} else if zero_for_one {
+			next_sqrt_price_from_amount_0(sqrt_price_current, liquidity, amount_remaining_less_fee, true)?
+		} else {
+			next_sqrt_price_from_amount_1(sqrt_price_current, liquidity, amount_remaining_less_fee, true)?
+		}
+	} else {
+		amount_out = if zero_for_one {
+			amount_1_delta(sqrt_price_target, sqrt_price_current, liquidity, false)?
+		} else {
+			amount_0_delta(sqrt_price_current, sqrt_price_target, liquidity, false)?
+		};
+		if amount_remaining >= amount_out {
+			sqrt_price_target
+		} else if zero_for_one {
+			next_sqrt_price_from_amount_1(sqrt_price_current, liquidity, amount_remaining, false)?
+		} else {
+			next_sqrt_price_from_amount_0(sqrt_price_current, liquidity, amount_remaining, false)?
+		}
+	};
+
+	let reach_target = sqrt_price_next == sqrt_price_target;
+	if zero_for_one {
+		if !(reach_target && is_exact_supply) {
+			amount_in = amount_0_delta(sqrt_price_next, sqrt_price_current, liquidity, true)?;
+		}
+		if !(reach_target && !is_exact_supply) {
+			amount_out = amount_1_delta(sqrt_price_next, sqrt_price_current, liquidity, false)?;
+		}
+	} else {
+		if !(reach_target && is_exact_supply) {
+			amount_in = amount_1_delta(sqrt_price_current, sqrt_price_next, liquidity, true)?;
+		}
+		if !(reach_target && !is_exact_supply) {
+			amount_out = amount_0_delta(sqrt_price_current, sqrt_price_next, liquidity, false)?;
+		}
+	}
+
+	// cap the output amount to not exceed the remaining output amount
+	if !is_exact_supply && amount_out > amount_remaining {
+		amount_out = amount_remaining;
+	}
+
+	let fee_amount = if is_exact_supply && !reach_target {
+		// take the remainder of the maximum input as fee
+		amount_remaining.checked_sub(amount_in)?
+	} else {
+		mul_div_rounding_up(amount_in, U256::from(fee_numerator), fee_rest)?
+	};
+
+	Some(SwapStep {
+		sqrt_price_next,
+		amount_in,
+		amount_out,
+		fee_amount,
+	})
+}
+
+/// Get the maximum liquidity can be provided into the range by the amounts.
+pub fn liquidity_for_amounts(
+	sqrt_price: U256,
+	sqrt_price_lower: U256,
+	sqrt_price_upper: U256,
+	amount_0: Balance,
+	amount_1: Balance,
+) -> Option<u128> {
+	let liquidity_for_amount_0 = |sqrt_price_a: U256, sqrt_price_b: U256| -> Option<U256> {
+		let intermediate = mul_div(sqrt_price_a, sqrt_price_b, q96())?;
+		mul_div(
+			U256::from(amount_0),
+			intermediate,
+			sqrt_price_b.checked_sub(sqrt_price_a)?,
+		)
+	};
+	let liquidity_for_amount_1 = |sqrt_price_a: U256, sqrt_price_b: U256| -> Option<U256> {
+		mul_div(U256::from(amount_1), q96(), sqrt_price_b.checked_sub(sqrt_price_a)?)
+	};
+
+	let liquidity = if sqrt_price <= sqrt_price_lower {
+		liquidity_for_amount_0(sqrt_price_lower, sqrt_price_upper)?
+	} else if sqrt_price < sqrt_price_upper {
+		liquidity_for_amount_0(sqrt_price, sqrt_price_upper)?.min(liquidity_for_amount_1(sqrt_price_lower, sqrt_price)?)
+	} else {
+		liquidity_for_amount_1(sqrt_price_lower, sqrt_price_upper)?
+	};
+	TryInto::<u128>::try_into(liquidity).ok()
+}
+
+/// Get the fee growth inside the range of the ticks.
+pub fn fee_growth_inside(
+	tick_lower: i32,
+	lower: &TickInfo,
+	tick_upper: i32,
+	upper: &TickInfo,
+	tick_current: i32,
+	fee_growth_global: (U256, U256),
+) -> (U256, U256) {
+	let inside = |global: U256, lower_outside: U256, upper_outside: U256| -> U256 {
+		let below = if tick_current >= tick_lower {
+			lower_outside
+		} else {
+			wrapping_sub(global, lower_outside)
+		};
+		let above = if tick_current < tick_upper {
+			upper_outside
+		} else {
+			wrapping_sub(global, upper_outside)
+		};
+		wrapping_sub(wrapping_sub(global, below), above)
+	};
+
+	(
+		inside(
+			fee_growth_global.0,
+			lower.fee_growth_outside.0,
+			upper.fee_growth_outside.0,
+		),
+		inside(
+			fee_growth_global.1,
+			lower.fee_growth_outside.1,
+			upper.fee_growth_outside.1,
+		),
+	)
+}
+
+fn to_balance(amount: U256) -> sp_std::result::Result<Balance, ArithmeticError> {
+	TryInto::<Balance>::try_into(amount).map_err(|_| ArithmeticError::Overflow)
+}
+
+fn saturated_balance(amount: U256) -> Balance {
+	TryInto::<Balance>::try_into(amount).unwrap_or(Balance::MAX)
+}
+
+impl<T: Config> Pallet<T> {
+	/// Ensure the tick range is valid for the pool.
+	pub(crate) fn ensure_valid_tick_range(pool: &ConcentratedPool, tick_lower: i32, tick_upper: i32) -> DispatchResult {
+		let tick_spacing = pool.tick_spacing as i32;
+		ensure!(
+			tick_lower < tick_upper
+				&& tick_lower >= MIN_TICK
+				&& tick_upper <= MAX_TICK
+				&& tick_lower % tick_spacing == 0
+				&& tick_upper % tick_spacing == 0,
+			Error::<T>::InvalidTickRange
+		);
+		Ok(())
+	}
+
+	/// Get the virtual reserves of the concentrated liquidity pool, which
+	/// is the equivalent constant product pool of the liquidity in range.
+	pub(crate) fn concentrated_virtual_reserves(pool: &ConcentratedPool) -> (Balance, Balance) {
+		let liquidity = U256::from(pool.liquidity);
+		let reserve_0 = mul_div(liquidity, q96(), pool.sqrt_price).map_or(Balance::MAX, saturated_balance);
+		let reserve_1 = mul_div(liquidity, pool.sqrt_price, q96()).map_or(Balance::MAX, saturated_balance);
+		(reserve_0, reserve_1)
+	}
+
+	/// Swap in the concentrated liquidity pool of the trading pair.
+	///
+	/// Swap the exact `amount` of `supply_currency_id` if `is_exact_supply`,
+	/// otherwise swap to get the exact `amount` of the other currency.
+	/// The state is only updated if `commit`, so that this can be used for
+	/// quoting. Returns `(supply_amount, target_amount)`.
+	pub(crate) fn concentrated_swap(
+		trading_pair: TradingPair,
+		supply_currency_id: CurrencyId,
+		amount: Balance,
+		is_exact_supply: bool,
+		commit: bool,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		let mut pool = Self::concentrated_pools(trading_pair).ok_or(Error::<T>::ConcentratedPoolNotFound)?;
+		let zero_for_one = supply_currency_id == trading_pair.first();
+		let exchange_fee = Self::get_exchange_fee(trading_pair);
+		let protocol_fee_share = Self::protocol_fee().map(|(_, share)| share).unwrap_or_default();
+		let initialized_ticks = Self::initialized_ticks(trading_pair);
+		let sqrt_price_limit = if zero_for_one {
+			min_sqrt_price() + U256::one()
+		} else {
+			max_sqrt_price() - U256::one()
+		};
+
+		let mut amount_remaining = U256::from(amount);
+		let mut amount_calculated = U256::zero();
+		let mut protocol_fee: Balance = Zero::zero();
+		let mut fee_growth_global = if zero_for_one {
+			pool.fee_growth_global.0
+		} else {
+			pool.fee_growth_global.1
+		};
+		let mut crossed_ticks: Vec<(i32, TickInfo)> = vec![];
+
+		while !amount_remaining.is_zero() && pool.sqrt_price != sqrt_price_limit {
+			let sqrt_price_start = pool.sqrt_price;
+
+			// the next initialized tick in the swap direction
+			let index = initialized_ticks.partition_point(|tick| *tick <= pool.tick);
+			let tick_next = if zero_for_one {
+				index.checked_sub(1).map_or(MIN_TICK, |i| initialized_ticks[i])
+			} else {
+				initialized_ticks.get(index).copied().unwrap_or(MAX_TICK)
+			};
+			let sqrt_price_next = sqrt_price_at_tick(tick_next);
+			let sqrt_price_target = if zero_for_one {
+				sqrt_price_next.max(sqrt_price_limit)
+			} else {
+				sqrt_price_next.min(sqrt_price_limit)
+			};
+
+			let step = compute_swap_step(
+				pool.sqrt_price,
+				sqrt_price_target,
+				pool.liquidity,
+				amount_remaining,
+				is_exact_supply,
+				exchange_fee,
+			)
+			.ok_or(ArithmeticError::Overflow)?;
+			pool.sqrt_price = step.sqrt_price_next;
+
+			if is_exact_supply {
+				amount_remaining = amount_remaining
+					.checked_sub(step.amount_in.saturating_add(step.fee_amount))
+					.ok_or(ArithmeticError::Underflow)?;
+				amount_calculated = amount_calculated
+					.checked_add(step.amount_out)
+					.ok_or(ArithmeticError::Overflow)?;
+			} else {
+				amount_remaining = amount_remaining
+					.checked_sub(step.amount_out)
+					.ok_or(ArithmeticError::Underflow)?;
+				amount_calculated = amount_calculated
+					.checked_add(step.amount_in.saturating_add(step.fee_amount))
+					.ok_or(ArithmeticError::Overflow)?;
+			}
+
+			// the protocol fee is taken from the exchange fee and the rest goes to the liquidity in range
+			let fee_amount = to_balance(step.fee_amount)?;
+			let step_protocol_fee = protocol_fee_share.saturating_mul_int(fee_amount);
+			protocol_fee = protocol_fee.saturating_add(step_protocol_fee);
+			if pool.liquidity > 0 {
+				let fee_growth = mul_div(
+					U256::from(fee_amount.saturating_sub(step_protocol_fee)),
+					q128(),
+					U256::from(pool.liquidity),
+				)
+				.ok_or(ArithmeticError::Overflow)?;
+				fee_growth_global = wrapping_add(fee_growth_global, fee_growth);
+			}
+
+			if pool.sqrt_price == sqrt_price_next {
+				if initialized_ticks.binary_search(&tick_next).is_ok() {
+					let mut tick_info = Self::concentrated_ticks(trading_pair, tick_next).unwrap_or_default();
+					let fee_growth_global = if zero_for_one {
+						(fee_growth_global, pool.fee_growth_global.1)
+					} else {
+						(pool.fee_growth_global.0, fee_growth_global)
+					};
+					tick_info.fee_growth_outside = (
+						wrapping_sub(fee_growth_global.0, tick_info.fee_growth_outside.0),
+						wrapping_sub(fee_growth_global.1, tick_info.fee_growth_outside.1),
+					);
+					let liquidity_net = if zero_for_one {
+						tick_info.liquidity_net.checked_neg().ok_or(ArithmeticError::Overflow)?
+					} else {
+						tick_info.liquidity_net
+					};
+					pool.liquidity =
+						add_liquidity_delta(pool.liquidity, liquidity_net).ok_or(ArithmeticError::Overflow)?;
+					crossed_ticks.push((tick_next, tick_info));
+				}
+				pool.tick = if zero_for_one { tick_next - 1 } else { tick_next };
+			} else if pool.sqrt_price != sqrt_price_start {
+				pool.tick = tick_at_sqrt_price(pool.sqrt_price);
+			}
+		}
+
+		ensure!(amount_remaining.is_zero(), Error::<T>::InsufficientLiquidity);
+		let amount_calculated = to_balance(amount_calculated)?;
+
+		if commit {
+			if zero_for_one {
+				pool.fee_growth_global.0 = fee_growth_global;
+			} else {
+				pool.fee_growth_global.1 = fee_growth_global;
+			}
+			ConcentratedPools::<T>::insert(trading_pair, pool);
+			for (tick, tick_info) in crossed_ticks {
+				ConcentratedTicks::<T>::insert(trading_pair, tick, tick_info);
+			}
+			if !protocol_fee.is_zero() {
+				AccruedProtocolFees::<T>::try_mutate(supply_currency_id, |accrued| -> DispatchResult {
+					*accrued = accrued.checked_add(protocol_fee).ok_or(ArithmeticError::Overflow)?;
+					Ok(())
+				})?;
+			}
+		}
+
+		if is_exact_supply {
+			Ok((amount, amount_calculated))
+		} else {
+			Ok((amount_calculated, amount))
+		}
+	}
+
+	/// Add or remove liquidity of the position, and accrue the fees to the
+	/// position. Returns the amounts of the two currencies to be paid by the
+	/// provider when adding liquidity, or to be returned to the provider when
+	/// removing liquidity.
+	pub(crate) fn modify_concentrated_position(
+		position: &mut Position<PositionClassIdOf<T>, PositionTokenIdOf<T>>,
+		liquidity_delta: i128,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		let trading_pair = position.trading_pair;
+		let (tick_lower, tick_upper) = (position.tick_lower, position.tick_upper);
+		let mut pool = Self::concentrated_pools(trading_pair).ok_or(Error::<T>::ConcentratedPoolNotFound)?;
+		let mut initialized_ticks = Self::initialized_ticks(trading_pair);
+
+		// update the ticks
+		let mut update_tick = |tick: i32, is_upper: bool| -> sp_std::result::Result<TickInfo, DispatchError> {
+			let mut tick_info = Self::concentrated_ticks(trading_pair, tick).unwrap_or_default();
+			let liquidity_gross_before = tick_info.liquidity_gross;
+			tick_info.liquidity_gross = add_liquidity_delta(liquidity_gross_before, liquidity_delta)
+				.ok_or(Error::<T>::InvalidLiquidityIncrement)?;
+			tick_info.liquidity_net = if is_upper {
+				tick_info.liquidity_net.checked_sub(liquidity_delta)
+			} else {
+				tick_info.liquidity_net.checked_add(liquidity_delta)
+			}
+			.ok_or(ArithmeticError::Overflow)?;
+
+			if liquidity_gross_before.is_zero() && !tick_info.liquidity_gross.is_zero() {
+				// by convention, assume all fee growth before the tick was initialized happened below the tick
+				if tick <= pool.tick {
+					tick_info.fee_growth_outside = pool.fee_growth_global;
+				}
+				if let Err(index) = initialized_ticks.binary_search(&tick) {
+					initialized_ticks
+						.try_insert(index, tick)
+						.map_err(|_| Error::<T>::TooManyInitializedTicks)?;
+				}
+			}
+			Ok(tick_info)
+		};
+		let lower = update_tick(tick_lower, false)?;
+		let upper = update_tick(tick_upper, true)?;
+
+		// accrue the fees to the position
+		let fee_growth_inside = fee_growth_inside(
+			tick_lower,
+			&lower,
+			tick_upper,
+			&upper,
+			pool.tick,
+			pool.fee_growth_global,
+		);
+		let position_liquidity = U256::from(position.liquidity);
+		let fees_owed = |inside: U256, inside_last: U256| -> Balance {
+			mul_div(wrapping_sub(inside, inside_last), position_liquidity, q128()).map_or(0, saturated_balance)
+		};
+		position.tokens_owed = (
+			position
+				.tokens_owed
+				.0
+				.saturating_add(fees_owed(fee_growth_inside.0, position.fee_growth_inside_last.0)),
+			position
+				.tokens_owed
+				.1
+				.saturating_add(fees_owed(fee_growth_inside.1, position.fee_growth_inside_last.1)),
+		);
+		position.fee_growth_inside_last = fee_growth_inside;
+		position.liquidity =
+			add_liquidity_delta(position.liquidity, liquidity_delta).ok_or(Error::<T>::InvalidLiquidityIncrement)?;
+
+		// calculate the amounts, round up when adding liquidity to ensure the pool is not underpaid
+		let round_up = liquidity_delta > 0;
+		let liquidity = liquidity_delta.unsigned_abs();
+		let (sqrt_price_lower, sqrt_price_upper) = (sqrt_price_at_tick(tick_lower), sqrt_price_at_tick(tick_upper));
+		let (amount_0, amount_1) = if pool.tick < tick_lower {
+			(
+				amount_0_delta(sqrt_price_lower, sqrt_price_upper, liquidity, round_up),
+				Some(U256::zero()),
+			)
+		} else if pool.tick < tick_upper {
+			pool.liquidity = add_liquidity_delta(pool.liquidity, liquidity_delta).ok_or(ArithmeticError::Overflow)?;
+			(
+				amount_0_delta(pool.sqrt_price, sqrt_price_upper, liquidity, round_up),
+				amount_1_delta(sqrt_price_lower, pool.sqrt_price, liquidity, round_up),
+			)
+		} else {
+			(
+				Some(U256::zero()),
+				amount_1_delta(sqrt_price_lower, sqrt_price_upper, liquidity, round_up),
+			)
+		};
+		let amount_0 = to_balance(amount_0.ok_or(ArithmeticError::Overflow)?)?;
+		let amount_1 = to_balance(amount_1.ok_or(ArithmeticError::Overflow)?)?;
+
+		// clear the ticks no longer referenced by any position
+		for (tick, tick_info) in [(tick_lower, lower), (tick_upper, upper)] {
+			if tick_info.liquidity_gross.is_zero() {
+				ConcentratedTicks::<T>::remove(trading_pair, tick);
+				if let Ok(index) = initialized_ticks.binary_search(&tick) {
+					initialized_ticks.remove(index);
+				}
+			} else {
+				ConcentratedTicks::<T>::insert(trading_pair, tick, tick_info);
+			}
+		}
+		InitializedTicks::<T>::insert(trading_pair, initialized_ticks);
+		ConcentratedPools::<T>::insert(trading_pair, pool);
+
+		Ok((amount_0, amount_1))
+	}
+}
@@ -0,0 +1,197 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Flash swap.
+//!
+//! A flash swap sends the borrowed amount out of the liquidity pool of an
+//! enabled trading pair to the borrower contract, calls back
+//! `onFlashSwap(address,address,uint256,uint256,bytes)` of the contract with
+//! the borrowed currency, the repay currency, the borrowed amount, the repay
+//! amount and the data of the borrower, and then collects the repay amount from
+//! the borrower. The borrowed amount is repaid either in the same currency plus
+//! the exchange fee, or in the other currency of the trading pair at the price
+//! of swapping for it. The liquidity pool is locked during the callback, and
+//! the flash swap reverts if the callback fails or the repayment can't be
+//! collected.
+
+use super::*;
+
+/// The selector of `onFlashSwap(address,address,uint256,uint256,bytes)`.
+const ON_FLASH_SWAP_SELECTOR: [u8; 4] = [0xbc, 0xbc, 0x5e, 0x55];
+
+impl<T: Config> Pallet<T> {
+	/// Flash swap `borrow_amount` of `borrow_currency_id` out of the liquidity
+	/// pool of `borrow_currency_id` and `other_currency_id` to `who`, the
+	/// account of the contract called back in `context`. The repayment is
+	/// collected in `repay_currency_id`, either currency of the trading pair.
+	///
+	/// Returns the repay amount and the gas used by the callback.
+	#[transactional]
+	pub fn do_flash_swap(
+		who: &T::AccountId,
+		context: InvokeContext,
+		borrow_currency_id: CurrencyId,
+		other_currency_id: CurrencyId,
+		repay_currency_id: CurrencyId,
+		borrow_amount: Balance,
+		data: Vec<u8>,
+		gas_limit: u64,
+		storage_limit: u32,
+	) -> Result<(Balance, u64), DispatchError> {
+		let trading_pair = TradingPair::from_currency_ids(borrow_currency_id, other_currency_id)
+			.ok_or(Error::<T>::InvalidCurrencyId)?;
+		ensure!(
+			repay_currency_id == borrow_currency_id || repay_currency_id == other_currency_id,
+			Error::<T>::InvalidCurrencyId
+		);
+		ensure!(
+			matches!(
+				Self::trading_pair_statuses(trading_pair),
+				TradingPairStatus::<_, _>::Enabled
+			),
+			Error::<T>::MustBeEnabled
+		);
+		ensure!(
+			!ConcentratedPools::<T>::contains_key(trading_pair),
+			Error::<T>::ConcentratedPoolAlreadyExists
+		);
+		ensure!(!Self::flash_swap_locked(trading_pair), Error::<T>::LiquidityPoolLocked);
+		ensure!(!borrow_amount.is_zero(), Error::<T>::ZeroTargetAmount);
+
+		let (borrow_pool, other_pool) = Self::get_liquidity(borrow_currency_id, other_currency_id);
+		ensure!(borrow_amount < borrow_pool, Error::<T>::InsufficientLiquidity);
+		let repay_amount = if repay_currency_id == borrow_currency_id {
+			Self::get_flash_loan_repay_amount(trading_pair, borrow_amount)
+		} else {
+			Self::get_supply_amount(
+				other_pool,
+				borrow_pool,
+				borrow_amount,
+				Self::get_exchange_fee(trading_pair),
+			)
+		};
+		ensure!(!repay_amount.is_zero(), Error::<T>::ZeroSupplyAmount);
+
+		let input = Self::on_flash_swap_input(
+			borrow_currency_id,
+			repay_currency_id,
+			borrow_amount,
+			repay_amount,
+			&data,
+		)?;
+
+		// lock the liquidity pool until the borrowed amount is repaid
+		FlashSwapLocks::<T>::insert(trading_pair, true);
+		T::Currency::transfer(borrow_currency_id, &Self::account_id(), who, borrow_amount)?;
+		let info = T::EVM::execute(
+			context,
+			input,
+			Default::default(),
+			gas_limit,
+			storage_limit,
+			ExecutionMode::Execute,
+		)?;
+		ensure!(info.exit_reason.is_succeed(), Error::<T>::FlashSwapCallbackFailed);
+		FlashSwapLocks::<T>::remove(trading_pair);
+
+		T::Currency::transfer(repay_currency_id, who, &Self::account_id(), repay_amount)?;
+		if repay_currency_id == borrow_currency_id {
+			Self::_flash_loan_repaid(
+				trading_pair,
+				borrow_currency_id,
+				repay_amount.saturating_sub(borrow_amount),
+			)?;
+		} else {
+			Self::_swap(repay_currency_id, borrow_currency_id, repay_amount, borrow_amount)?;
+		}
+
+		Self::deposit_event(Event::FlashSwap {
+			borrower: who.clone(),
+			borrow_currency_id,
+			repay_currency_id,
+			borrow_amount,
+			repay_amount,
+		});
+		Ok((repay_amount, info.used_gas.low_u64()))
+	}
+
+	/// Get the amount to repay in the same currency for the borrowed amount,
+	/// the exchange fee is charged on the repay amount as if it were swapped.
+	fn get_flash_loan_repay_amount(trading_pair: TradingPair, borrow_amount: Balance) -> Balance {
+		let (fee_numerator, fee_denominator) = Self::get_exchange_fee(trading_pair);
+		let numerator: U256 = U256::from(borrow_amount).saturating_mul(U256::from(fee_denominator));
+		let denominator: U256 = U256::from(fee_denominator.saturating_sub(fee_numerator));
+
+		// round up so that the pool never loses the remainder
+		numerator
+			.checked_add(denominator.saturating_sub(U256::one()))
+			.and_then(|n| n.checked_div(denominator))
+			.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+			.unwrap_or_else(Zero::zero)
+	}
+
+	/// Add the exchange fee of the flash loan repaid in the same currency to the
+	/// liquidity pool, the protocol fee is taken from it and kept out of the pool.
+	fn _flash_loan_repaid(trading_pair: TradingPair, currency_id: CurrencyId, fee: Balance) -> DispatchResult {
+		let protocol_fee = Self::protocol_fee()
+			.map(|(_, share)| share.saturating_mul_int(fee))
+			.unwrap_or_else(Zero::zero);
+		if !protocol_fee.is_zero() {
+			AccruedProtocolFees::<T>::try_mutate(currency_id, |accrued| -> DispatchResult {
+				*accrued = accrued.checked_add(protocol_fee).ok_or(ArithmeticError::Overflow)?;
+				Ok(())
+			})?;
+		}
+		let fee = fee.saturating_sub(protocol_fee);
+
+		Self::try_mutate_liquidity_pool(&trading_pair, |(pool_0, pool_1)| -> DispatchResult {
+			let pool = if currency_id == trading_pair.first() {
+				pool_0
+			} else {
+				pool_1
+			};
+			*pool = pool.checked_add(fee).ok_or(ArithmeticError::Overflow)?;
+			Ok(())
+		})
+	}
+
+	/// The ABI encoded input of the `onFlashSwap` callback.
+	fn on_flash_swap_input(
+		borrow_currency_id: CurrencyId,
+		repay_currency_id: CurrencyId,
+		borrow_amount: Balance,
+		repay_amount: Balance,
+		data: &[u8],
+	) -> Result<Vec<u8>, DispatchError> {
+		let mut input = ON_FLASH_SWAP_SELECTOR.to_vec();
+		for currency_id in [borrow_currency_id, repay_currency_id] {
+			let address = T::Erc20InfoMapping::encode_evm_address(currency_id).ok_or(Error::<T>::InvalidCurrencyId)?;
+			input.extend_from_slice(&[0u8; 12]);
+			input.extend_from_slice(address.as_bytes());
+		}
+		input.extend_from_slice(&to_bytes(borrow_amount));
+		input.extend_from_slice(&to_bytes(repay_amount));
+		// the offset of `data` after the 5 head words, then its length and content
+		// padded to 32 bytes
+		input.extend_from_slice(&to_bytes(5u32 * 32));
+		input.extend_from_slice(&to_bytes(data.len() as u32));
+		input.extend_from_slice(data);
+		input.resize(input.len() + (32 - data.len() % 32) % 32, 0);
+		Ok(input)
+	}
+}
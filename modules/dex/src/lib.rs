@@ -26,6 +26,23 @@
 //! liquidation by auction when the liquidity is sufficient. And providing
 //! market making liquidity for DEX will also receive stable currency as
 //! additional reward for its participation in the CDP liquidation.
+//!
+//! A trading pair can also swap through a concentrated liquidity pool instead
+//! of the constant product pool, whose design refers to Uniswap V3. The
+//! liquidity is provided into tick ranges as positions, each position is
+//! represented by an NFT and accrues the exchange fee while in range.
+//!
+//! Besides market swaps, traders can place limit orders which escrow the
+//! supply amount and are filled against the liquidity pools once the pool
+//! price reaches the limit price.
+//!
+//! The router searches the routes through both the liquidity pools and the
+//! stable-asset pools, and large trades can be split across several routes to
+//! reduce the price impact.
+//!
+//! EVM contracts can flash swap from the liquidity pools through the DEX
+//! precompile, borrowing the output before paying for it within the same
+//! transaction.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::too_many_arguments)]
@@ -34,12 +51,12 @@
 #![allow(clippy::collapsible_if)]
 
 use codec::MaxEncodedLen;
-use frame_support::{log, pallet_prelude::*, transactional, PalletId};
+use frame_support::{log, pallet_prelude::*, traits::ConstU32, transactional, PalletId};
 use frame_system::pallet_prelude::*;
 use nutsfinance_stable_asset::traits::StableAsset;
 use nutsfinance_stable_asset::{PoolTokenIndex, StableAssetPoolId};
 use orml_traits::{Happened, MultiCurrency, MultiCurrencyExtended};
-use primitives::{Balance, CurrencyId, TradingPair};
+use primitives::{to_bytes, Balance, CurrencyId, TradingPair};
 use scale_info::TypeInfo;
 use sp_core::{H160, U256};
 use sp_runtime::{
@@ -47,15 +64,36 @@ use sp_runtime::{
 	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug, SaturatedConversion,
 };
 use sp_std::{prelude::*, vec};
-use support::{DEXIncentives, DEXManager, Erc20InfoMapping, ExchangeRate, Ratio, StableAssetDEX, SwapLimit};
+use support::{
+	DEXIncentives, DEXManager, Erc20InfoMapping, ExchangeRate, ExecutionMode, InvokeContext, PositionNFT, Ratio,
+	StableAssetDEX, SwapLimit, EVM,
+};
 
+pub mod concentrated;
+mod flash_swap;
+mod limit_order;
 mod mock;
+mod router;
 mod tests;
 pub mod weights;
 
+pub use concentrated::{ConcentratedPool, Position, TickInfo};
+pub use limit_order::{LimitOrder, LimitOrderId};
 pub use module::*;
+pub use router::{RouteHop, RouteQuote};
 pub use weights::WeightInfo;
 
+/// The maximum tick spacing of concentrated liquidity pools.
+pub const MAX_TICK_SPACING: u32 = 16384;
+
+/// The id of concentrated liquidity positions.
+pub type PositionId = u64;
+
+pub type PositionClassIdOf<T> =
+	<<T as Config>::PositionNFT as PositionNFT<<T as frame_system::Config>::AccountId>>::ClassId;
+pub type PositionTokenIdOf<T> =
+	<<T as Config>::PositionNFT as PositionNFT<<T as frame_system::Config>::AccountId>>::TokenId;
+
 /// Parameters of TradingPair in Provisioning status
 #[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
 pub struct ProvisioningParameters<Balance, BlockNumber> {
@@ -100,7 +138,7 @@ pub mod module {
 		/// Currency for transfer currencies
 		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
 
-		/// Trading fee rate
+		/// Default trading fee rate, used by trading pairs without fee tier set
 		/// The first item of the tuple is the numerator of the fee rate, second
 		/// item is the denominator, fee_rate = numerator / denominator,
 		/// use (u32, u32) over `Rate` type to minimize internal division
@@ -143,6 +181,30 @@ pub mod module {
 
 		/// Event handler which calls when update liquidity pool.
 		type OnLiquidityPoolUpdated: Happened<(TradingPair, Balance, Balance)>;
+
+		/// NFT to represent the ownership of concentrated liquidity positions.
+		type PositionNFT: PositionNFT<Self::AccountId>;
+
+		/// The maximum number of initialized ticks of a concentrated liquidity
+		/// pool.
+		#[pallet::constant]
+		type MaxInitializedTicks: Get<u32>;
+
+		/// The maximum number of open limit orders of an account.
+		#[pallet::constant]
+		type MaxLimitOrdersPerAccount: Get<u32>;
+
+		/// The share of the target amount paid to the keeper who fills a limit
+		/// order.
+		#[pallet::constant]
+		type LimitOrderKeeperFee: Get<Ratio>;
+
+		/// The maximum number of routes a swap can be split across.
+		#[pallet::constant]
+		type MaxRouteSplits: Get<u32>;
+
+		/// EVM used to call back the borrower contract of a flash swap.
+		type EVM: EVM<Self::AccountId>;
 	}
 
 	#[pallet::error]
@@ -191,6 +253,52 @@ pub mod module {
 		InvalidTradingPath,
 		/// Not allowed to refund provision
 		NotAllowedRefund,
+		/// The exchange fee rate is invalid
+		InvalidExchangeFee,
+		/// The share of protocol fee is invalid
+		InvalidProtocolFeeShare,
+		/// The receiver of protocol fee is not set
+		ProtocolFeeNotSet,
+		/// The concentrated liquidity pool of trading pair is not found
+		ConcentratedPoolNotFound,
+		/// The trading pair already has a concentrated liquidity pool
+		ConcentratedPoolAlreadyExists,
+		/// The constant product liquidity pool of trading pair is not empty
+		LiquidityPoolNotEmpty,
+		/// The tick spacing is invalid
+		InvalidTickSpacing,
+		/// The tick is invalid
+		InvalidTick,
+		/// The tick range is invalid
+		InvalidTickRange,
+		/// The initialized ticks of concentrated liquidity pool exceed the limit
+		TooManyInitializedTicks,
+		/// The NFT class of positions is not set
+		PositionNFTClassNotSet,
+		/// The position is not found
+		PositionNotFound,
+		/// The caller is not the owner of position
+		NotPositionOwner,
+		/// The limit price is invalid
+		InvalidLimitPrice,
+		/// The limit order is expired
+		LimitOrderExpired,
+		/// The open limit orders of account exceed the limit
+		TooManyLimitOrders,
+		/// The limit order is not found
+		LimitOrderNotFound,
+		/// The caller is not the owner of limit order
+		NotLimitOrderOwner,
+		/// The limit order can not be filled at the limit price
+		LimitOrderNotFillable,
+		/// The route is invalid
+		InvalidRoute,
+		/// The swap is split across too many routes
+		TooManyRoutes,
+		/// The liquidity pool is locked by a flash swap
+		LiquidityPoolLocked,
+		/// The callback of the flash swap borrower failed
+		FlashSwapCallbackFailed,
 	}
 
 	#[pallet::event]
@@ -255,6 +363,102 @@ pub mod module {
 			accumulated_provision_0: Balance,
 			accumulated_provision_1: Balance,
 		},
+		/// The fee tier of trading pair updated.
+		TradingPairFeeUpdated {
+			trading_pair: TradingPair,
+			fee: Option<(u32, u32)>,
+		},
+		/// The receiver and share of protocol fee updated.
+		ProtocolFeeUpdated { receiver: T::AccountId, share: Ratio },
+		/// Accrued protocol fee claimed.
+		ProtocolFeeClaimed {
+			receiver: T::AccountId,
+			currency_id: CurrencyId,
+			amount: Balance,
+		},
+		/// Concentrated liquidity pool created.
+		ConcentratedPoolCreated {
+			trading_pair: TradingPair,
+			tick_spacing: u32,
+			tick: i32,
+		},
+		/// The NFT class of positions updated.
+		PositionNFTClassUpdated { class_id: PositionClassIdOf<T> },
+		/// Concentrated liquidity position opened.
+		PositionOpened {
+			who: T::AccountId,
+			position_id: PositionId,
+			trading_pair: TradingPair,
+			tick_lower: i32,
+			tick_upper: i32,
+			liquidity: u128,
+			amount_0: Balance,
+			amount_1: Balance,
+		},
+		/// Liquidity of position decreased.
+		PositionDecreased {
+			who: T::AccountId,
+			position_id: PositionId,
+			liquidity: u128,
+			amount_0: Balance,
+			amount_1: Balance,
+		},
+		/// Fees of position collected.
+		PositionFeesCollected {
+			who: T::AccountId,
+			position_id: PositionId,
+			fee_0: Balance,
+			fee_1: Balance,
+		},
+		/// Position closed and its NFT burned.
+		PositionClosed { who: T::AccountId, position_id: PositionId },
+		/// Limit order placed.
+		LimitOrderPlaced {
+			order_id: LimitOrderId,
+			owner: T::AccountId,
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			supply_amount: Balance,
+			limit_price: ExchangeRate,
+			expiry: T::BlockNumber,
+		},
+		/// Limit order filled, fully or partially.
+		LimitOrderFilled {
+			order_id: LimitOrderId,
+			owner: T::AccountId,
+			supply_amount: Balance,
+			target_amount: Balance,
+			keeper: Option<T::AccountId>,
+			keeper_fee: Balance,
+		},
+		/// Limit order cancelled and the remaining supply amount refunded.
+		LimitOrderCancelled {
+			order_id: LimitOrderId,
+			owner: T::AccountId,
+			refund_amount: Balance,
+		},
+		/// Limit order expired and the remaining supply amount refunded.
+		LimitOrderExpired {
+			order_id: LimitOrderId,
+			owner: T::AccountId,
+			refund_amount: Balance,
+		},
+		/// Swap split across routes.
+		SwapWithRoutes {
+			trader: T::AccountId,
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			supply_amount: Balance,
+			target_amount: Balance,
+		},
+		/// Flash swap repaid by the borrower.
+		FlashSwap {
+			borrower: T::AccountId,
+			borrow_currency_id: CurrencyId,
+			repay_currency_id: CurrencyId,
+			borrow_amount: Balance,
+			repay_amount: Balance,
+		},
 	}
 
 	/// Liquidity pool for TradingPair.
@@ -272,6 +476,29 @@ pub mod module {
 	pub type TradingPairStatuses<T: Config> =
 		StorageMap<_, Twox64Concat, TradingPair, TradingPairStatus<Balance, T::BlockNumber>, ValueQuery>;
 
+	/// Exchange fee tier of TradingPair, `GetExchangeFee` is used if not set.
+	///
+	/// TradingPairFees: map TradingPair => Option<(u32, u32)>
+	#[pallet::storage]
+	#[pallet::getter(fn trading_pair_fees)]
+	pub type TradingPairFees<T: Config> = StorageMap<_, Twox64Concat, TradingPair, (u32, u32), OptionQuery>;
+
+	/// The receiver of protocol fee and the share of exchange fee charged as
+	/// protocol fee. Protocol fee is switched off if not set or the share is
+	/// zero.
+	///
+	/// ProtocolFee: Option<(AccountId, Ratio)>
+	#[pallet::storage]
+	#[pallet::getter(fn protocol_fee)]
+	pub type ProtocolFee<T: Config> = StorageValue<_, (T::AccountId, Ratio), OptionQuery>;
+
+	/// Protocol fee accrued in the DEX account and not claimed yet.
+	///
+	/// AccruedProtocolFees: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn accrued_protocol_fees)]
+	pub type AccruedProtocolFees<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
 	/// Provision of TradingPair by AccountId.
 	///
 	/// ProvisioningPool: double_map TradingPair, AccountId => (Balance,
@@ -289,6 +516,90 @@ pub mod module {
 	pub type InitialShareExchangeRates<T: Config> =
 		StorageMap<_, Twox64Concat, TradingPair, (ExchangeRate, ExchangeRate), ValueQuery>;
 
+	/// Concentrated liquidity pool for TradingPair, the trading pair swaps
+	/// through it instead of `LiquidityPool` if exists.
+	///
+	/// ConcentratedPools: map TradingPair => Option<ConcentratedPool>
+	#[pallet::storage]
+	#[pallet::getter(fn concentrated_pools)]
+	pub type ConcentratedPools<T: Config> = StorageMap<_, Twox64Concat, TradingPair, ConcentratedPool, OptionQuery>;
+
+	/// Initialized ticks of concentrated liquidity pool.
+	///
+	/// ConcentratedTicks: double_map TradingPair, i32 => Option<TickInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn concentrated_ticks)]
+	pub type ConcentratedTicks<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, TradingPair, Twox64Concat, i32, TickInfo, OptionQuery>;
+
+	/// The sorted initialized ticks of concentrated liquidity pool.
+	///
+	/// InitializedTicks: map TradingPair => BoundedVec<i32, MaxInitializedTicks>
+	#[pallet::storage]
+	#[pallet::getter(fn initialized_ticks)]
+	pub type InitializedTicks<T: Config> =
+		StorageMap<_, Twox64Concat, TradingPair, BoundedVec<i32, T::MaxInitializedTicks>, ValueQuery>;
+
+	/// Concentrated liquidity positions.
+	///
+	/// Positions: map PositionId => Option<Position>
+	#[pallet::storage]
+	#[pallet::getter(fn positions)]
+	pub type Positions<T: Config> =
+		StorageMap<_, Twox64Concat, PositionId, Position<PositionClassIdOf<T>, PositionTokenIdOf<T>>, OptionQuery>;
+
+	/// The id of the next position.
+	///
+	/// NextPositionId: PositionId
+	#[pallet::storage]
+	#[pallet::getter(fn next_position_id)]
+	pub type NextPositionId<T: Config> = StorageValue<_, PositionId, ValueQuery>;
+
+	/// The NFT class of position NFTs.
+	///
+	/// PositionNFTClass: Option<ClassId>
+	#[pallet::storage]
+	#[pallet::getter(fn position_nft_class)]
+	pub type PositionNFTClass<T: Config> = StorageValue<_, PositionClassIdOf<T>, OptionQuery>;
+
+	/// Open limit orders.
+	///
+	/// LimitOrders: map LimitOrderId => Option<LimitOrder>
+	#[pallet::storage]
+	#[pallet::getter(fn limit_orders)]
+	pub type LimitOrders<T: Config> =
+		StorageMap<_, Twox64Concat, LimitOrderId, LimitOrder<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	/// The id of the next limit order.
+	///
+	/// NextLimitOrderId: LimitOrderId
+	#[pallet::storage]
+	#[pallet::getter(fn next_limit_order_id)]
+	pub type NextLimitOrderId<T: Config> = StorageValue<_, LimitOrderId, ValueQuery>;
+
+	/// The open limit orders of account.
+	///
+	/// AccountLimitOrders: map AccountId => BoundedVec<LimitOrderId, MaxLimitOrdersPerAccount>
+	#[pallet::storage]
+	#[pallet::getter(fn account_limit_orders)]
+	pub type AccountLimitOrders<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BoundedVec<LimitOrderId, T::MaxLimitOrdersPerAccount>, ValueQuery>;
+
+	/// The last limit order key iterated by `on_idle`, the next iteration starts
+	/// after it.
+	///
+	/// LastLimitOrderKeyIterated: BoundedVec<u8, 128>
+	#[pallet::storage]
+	#[pallet::getter(fn last_limit_order_key_iterated)]
+	pub type LastLimitOrderKeyIterated<T: Config> = StorageValue<_, BoundedVec<u8, ConstU32<128>>, ValueQuery>;
+
+	/// The liquidity pools locked by the flash swaps in progress.
+	///
+	/// FlashSwapLocks: map TradingPair => bool
+	#[pallet::storage]
+	#[pallet::getter(fn flash_swap_locked)]
+	pub type FlashSwapLocks<T: Config> = StorageMap<_, Twox64Concat, TradingPair, bool, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub initial_listing_trading_pairs: Vec<(TradingPair, (Balance, Balance), (Balance, Balance), T::BlockNumber)>,
@@ -357,7 +668,11 @@ pub mod module {
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::process_limit_orders(remaining_weight)
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -545,6 +860,10 @@ pub mod module {
 					&& ProvisioningPool::<T>::iter_prefix(trading_pair).next().is_none(),
 				Error::<T>::NotAllowedList
 			);
+			ensure!(
+				!ConcentratedPools::<T>::contains_key(trading_pair),
+				Error::<T>::ConcentratedPoolAlreadyExists
+			);
 
 			let check_asset_registry = |currency_id: CurrencyId| match currency_id {
 				CurrencyId::Erc20(_) | CurrencyId::ForeignAsset(_) | CurrencyId::StableAssetPoolToken(_) => {
@@ -760,6 +1079,223 @@ pub mod module {
 			Ok(())
 		}
 
+		/// Set the exchange fee tier of a trading pair.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `fee`: (numerator, denominator) of the fee rate, `None` means using the default
+		///   `GetExchangeFee`.
+		#[pallet::weight((<T as Config>::WeightInfo::set_trading_pair_fee(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_trading_pair_fee(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			fee: Option<(u32, u32)>,
+		) -> DispatchResult {
+			T::ListingOrigin::ensure_origin(origin)?;
+			let trading_pair =
+				TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+			if let Some((fee_numerator, fee_denominator)) = fee {
+				ensure!(
+					!fee_denominator.is_zero() && fee_numerator < fee_denominator,
+					Error::<T>::InvalidExchangeFee
+				);
+			}
+
+			TradingPairFees::<T>::set(trading_pair, fee);
+			Self::deposit_event(Event::TradingPairFeeUpdated { trading_pair, fee });
+			Ok(())
+		}
+
+		/// Set the receiver of protocol fee and the share of exchange fee charged as
+		/// protocol fee. Zero share switches the protocol fee off.
+		///
+		/// - `receiver`: the account to receive the protocol fee.
+		/// - `share`: the share of exchange fee, must not be greater than one.
+		#[pallet::weight((<T as Config>::WeightInfo::set_protocol_fee(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_protocol_fee(origin: OriginFor<T>, receiver: T::AccountId, share: Ratio) -> DispatchResult {
+			T::ListingOrigin::ensure_origin(origin)?;
+			ensure!(share <= Ratio::one(), Error::<T>::InvalidProtocolFeeShare);
+
+			ProtocolFee::<T>::put((receiver.clone(), share));
+			Self::deposit_event(Event::ProtocolFeeUpdated { receiver, share });
+			Ok(())
+		}
+
+		/// Transfer the accrued protocol fee of the currency to the protocol fee receiver.
+		///
+		/// - `currency_id`: currency id of the accrued protocol fee.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_protocol_fee())]
+		#[transactional]
+		pub fn claim_protocol_fee(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			let (receiver, _) = Self::protocol_fee().ok_or(Error::<T>::ProtocolFeeNotSet)?;
+
+			let amount = AccruedProtocolFees::<T>::take(currency_id);
+			if !amount.is_zero() {
+				T::Currency::transfer(currency_id, &Self::account_id(), &receiver, amount)?;
+				Self::deposit_event(Event::ProtocolFeeClaimed {
+					receiver,
+					currency_id,
+					amount,
+				});
+			}
+			Ok(())
+		}
+
+		/// Create a concentrated liquidity pool for an Enabled trading pair without constant
+		/// product liquidity, the trading pair swaps through it afterwards.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `tick_spacing`: ticks of positions must be multiples of it.
+		/// - `initial_tick`: the tick of the initial price, the price is the amount of
+		///   `trading_pair.second()` per `trading_pair.first()`.
+		#[pallet::weight((<T as Config>::WeightInfo::create_concentrated_pool(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn create_concentrated_pool(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			tick_spacing: u32,
+			initial_tick: i32,
+		) -> DispatchResult {
+			T::ListingOrigin::ensure_origin(origin)?;
+			let trading_pair =
+				TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+			ensure!(
+				matches!(
+					Self::trading_pair_statuses(trading_pair),
+					TradingPairStatus::<_, _>::Enabled
+				),
+				Error::<T>::MustBeEnabled
+			);
+			ensure!(
+				!ConcentratedPools::<T>::contains_key(trading_pair),
+				Error::<T>::ConcentratedPoolAlreadyExists
+			);
+			ensure!(
+				Self::liquidity_pool(trading_pair) == Default::default()
+					&& T::Currency::total_issuance(trading_pair.dex_share_currency_id()).is_zero(),
+				Error::<T>::LiquidityPoolNotEmpty
+			);
+			ensure!(
+				!tick_spacing.is_zero() && tick_spacing <= MAX_TICK_SPACING,
+				Error::<T>::InvalidTickSpacing
+			);
+			ensure!(
+				initial_tick > concentrated::MIN_TICK && initial_tick < concentrated::MAX_TICK,
+				Error::<T>::InvalidTick
+			);
+
+			ConcentratedPools::<T>::insert(
+				trading_pair,
+				ConcentratedPool {
+					sqrt_price: concentrated::sqrt_price_at_tick(initial_tick),
+					tick: initial_tick,
+					tick_spacing,
+					liquidity: Zero::zero(),
+					fee_growth_global: Default::default(),
+				},
+			);
+			Self::deposit_event(Event::ConcentratedPoolCreated {
+				trading_pair,
+				tick_spacing,
+				tick: initial_tick,
+			});
+			Ok(())
+		}
+
+		/// Set the NFT class of position NFTs. The class must be mintable, and neither burnable nor
+		/// have mutable properties, so a position NFT can't be burned while the position holds
+		/// liquidity. The deposits of position NFTs are paid by the position owners.
+		///
+		/// - `class_id`: the NFT class id.
+		#[pallet::weight((<T as Config>::WeightInfo::set_position_nft_class(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_position_nft_class(origin: OriginFor<T>, class_id: PositionClassIdOf<T>) -> DispatchResult {
+			T::ListingOrigin::ensure_origin(origin)?;
+			T::PositionNFT::ensure_position_class(class_id)?;
+			PositionNFTClass::<T>::put(class_id);
+			Self::deposit_event(Event::PositionNFTClassUpdated { class_id });
+			Ok(())
+		}
+
+		/// Open a position in the tick range of the concentrated liquidity pool, and mint a
+		/// position NFT to the caller. A range entirely above or below the current price is a
+		/// range order which only takes one of the currencies.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `tick_lower`: the lower tick of the range.
+		/// - `tick_upper`: the upper tick of the range.
+		/// - `max_amount_a`: maximum amount of currency_id_a is allowed to inject to the position.
+		/// - `max_amount_b`: maximum amount of currency_id_b is allowed to inject to the position.
+		/// - `min_liquidity`: minimum acceptable liquidity of the position.
+		#[pallet::weight(<T as Config>::WeightInfo::open_position())]
+		#[transactional]
+		pub fn open_position(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			tick_lower: i32,
+			tick_upper: i32,
+			#[pallet::compact] max_amount_a: Balance,
+			#[pallet::compact] max_amount_b: Balance,
+			#[pallet::compact] min_liquidity: u128,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_open_position(
+				&who,
+				currency_id_a,
+				currency_id_b,
+				tick_lower,
+				tick_upper,
+				max_amount_a,
+				max_amount_b,
+				min_liquidity,
+			)?;
+			Ok(())
+		}
+
+		/// Decrease the liquidity of the position owned by the caller. The position is closed
+		/// and its NFT is burned when all liquidity is removed, and the uncollected fees are
+		/// collected.
+		///
+		/// - `position_id`: the position id.
+		/// - `liquidity`: liquidity amount to remove.
+		/// - `min_withdrawn_0`: minimum acceptable withdrawn amount of `trading_pair.first()`.
+		/// - `min_withdrawn_1`: minimum acceptable withdrawn amount of `trading_pair.second()`.
+		#[pallet::weight(<T as Config>::WeightInfo::decrease_position())]
+		#[transactional]
+		pub fn decrease_position(
+			origin: OriginFor<T>,
+			position_id: PositionId,
+			#[pallet::compact] liquidity: u128,
+			#[pallet::compact] min_withdrawn_0: Balance,
+			#[pallet::compact] min_withdrawn_1: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_decrease_position(&who, position_id, liquidity, min_withdrawn_0, min_withdrawn_1)
+		}
+
+		/// Collect the fees accrued to the position owned by the caller.
+		///
+		/// - `position_id`: the position id.
+		#[pallet::weight(<T as Config>::WeightInfo::collect_position_fees())]
+		#[transactional]
+		pub fn collect_position_fees(origin: OriginFor<T>, position_id: PositionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Positions::<T>::try_mutate(position_id, |maybe_position| -> DispatchResult {
+				let position = maybe_position.as_mut().ok_or(Error::<T>::PositionNotFound)?;
+				Self::ensure_position_owner(&who, position)?;
+				Self::modify_concentrated_position(position, Zero::zero())?;
+				Self::do_collect_position_fees(&who, position_id, position)
+			})
+		}
+
 		/// Refund provision if the provision has already aborted.
 		///
 		/// - `owner`: founder account.
@@ -851,6 +1387,151 @@ pub mod module {
 
 			Ok(())
 		}
+
+		/// Place a limit order, the supply amount is escrowed until the order is filled,
+		/// cancelled or expired.
+		///
+		/// - `supply_currency_id`: the currency to supply.
+		/// - `target_currency_id`: the currency to receive.
+		/// - `supply_amount`: the amount of supply currency.
+		/// - `limit_price`: the minimum target amount received per supply amount.
+		/// - `expiry`: the order can not be filled after this block.
+		#[pallet::weight(<T as Config>::WeightInfo::place_limit_order())]
+		#[transactional]
+		pub fn place_limit_order(
+			origin: OriginFor<T>,
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			#[pallet::compact] supply_amount: Balance,
+			limit_price: ExchangeRate,
+			expiry: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let trading_pair = TradingPair::from_currency_ids(supply_currency_id, target_currency_id)
+				.ok_or(Error::<T>::InvalidCurrencyId)?;
+			ensure!(
+				matches!(
+					Self::trading_pair_statuses(trading_pair),
+					TradingPairStatus::<_, _>::Enabled
+				),
+				Error::<T>::MustBeEnabled
+			);
+			ensure!(!supply_amount.is_zero(), Error::<T>::ZeroSupplyAmount);
+			ensure!(!limit_price.is_zero(), Error::<T>::InvalidLimitPrice);
+			ensure!(
+				expiry > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::LimitOrderExpired
+			);
+
+			let order_id =
+				NextLimitOrderId::<T>::try_mutate(|id| -> sp_std::result::Result<LimitOrderId, DispatchError> {
+					let current_id = *id;
+					*id = id.checked_add(One::one()).ok_or(ArithmeticError::Overflow)?;
+					Ok(current_id)
+				})?;
+			AccountLimitOrders::<T>::try_mutate(&who, |order_ids| {
+				order_ids.try_push(order_id).map_err(|_| Error::<T>::TooManyLimitOrders)
+			})?;
+			T::Currency::transfer(supply_currency_id, &who, &Self::account_id(), supply_amount)?;
+			LimitOrders::<T>::insert(
+				order_id,
+				LimitOrder {
+					owner: who.clone(),
+					supply_currency_id,
+					target_currency_id,
+					remaining_supply_amount: supply_amount,
+					limit_price,
+					expiry,
+				},
+			);
+
+			Self::deposit_event(Event::LimitOrderPlaced {
+				order_id,
+				owner: who,
+				supply_currency_id,
+				target_currency_id,
+				supply_amount,
+				limit_price,
+				expiry,
+			});
+			Ok(())
+		}
+
+		/// Cancel the limit order and refund the remaining supply amount. The owner can
+		/// cancel the order at any time, and anyone can refund the order after it expired.
+		///
+		/// - `order_id`: the limit order id.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_limit_order())]
+		#[transactional]
+		pub fn cancel_limit_order(origin: OriginFor<T>, order_id: LimitOrderId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let order = Self::limit_orders(order_id).ok_or(Error::<T>::LimitOrderNotFound)?;
+			let expired = frame_system::Pallet::<T>::block_number() > order.expiry;
+			ensure!(who == order.owner || expired, Error::<T>::NotLimitOrderOwner);
+
+			Self::do_remove_limit_order(order_id, &order, expired)
+		}
+
+		/// Fill the limit order as much as possible at the limit price, the caller earns the
+		/// keeper fee.
+		///
+		/// - `order_id`: the limit order id.
+		#[pallet::weight(<T as Config>::WeightInfo::fill_limit_order())]
+		#[transactional]
+		pub fn fill_limit_order(origin: OriginFor<T>, order_id: LimitOrderId) -> DispatchResult {
+			let keeper = ensure_signed(origin)?;
+			Self::do_fill_limit_order(order_id, Some(&keeper))?;
+			Ok(())
+		}
+
+		/// Swap the exact supply amount split across the routes, the routes can go through
+		/// both the liquidity pools and the stable-asset pools.
+		///
+		/// - `supply_currency_id`: the currency to supply.
+		/// - `target_currency_id`: the currency to receive.
+		/// - `routes`: the routes and the supply amount of each route.
+		/// - `min_target_amount`: acceptable minimum target amount of all routes.
+		#[pallet::weight(<T as Config>::WeightInfo::swap_with_routes(
+			routes.iter().map(|(route, _)| route.len() as u32).sum()
+		))]
+		#[transactional]
+		pub fn swap_with_routes(
+			origin: OriginFor<T>,
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			routes: Vec<(Vec<RouteHop>, Balance)>,
+			#[pallet::compact] min_target_amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!routes.is_empty(), Error::<T>::InvalidRoute);
+			ensure!(
+				routes.len() <= T::MaxRouteSplits::get().saturated_into(),
+				Error::<T>::TooManyRoutes
+			);
+
+			let mut supply_amount: Balance = Zero::zero();
+			let mut target_amount: Balance = Zero::zero();
+			for (route, route_supply_amount) in routes.iter() {
+				Self::ensure_valid_route(route, supply_currency_id, target_currency_id)?;
+				let route_target_amount = Self::do_swap_by_route(&who, route, *route_supply_amount)?;
+				supply_amount = supply_amount
+					.checked_add(*route_supply_amount)
+					.ok_or(ArithmeticError::Overflow)?;
+				target_amount = target_amount
+					.checked_add(route_target_amount)
+					.ok_or(ArithmeticError::Overflow)?;
+			}
+			ensure!(target_amount >= min_target_amount, Error::<T>::InsufficientTargetAmount);
+
+			Self::deposit_event(Event::SwapWithRoutes {
+				trader: who,
+				supply_currency_id,
+				target_currency_id,
+				supply_amount,
+				target_amount,
+			});
+			Ok(())
+		}
 	}
 }
 
@@ -859,10 +1540,15 @@ impl<T: Config> Pallet<T> {
 		T::PalletId::get().into_account()
 	}
 
-	fn try_mutate_liquidity_pool<R, E>(
+	fn try_mutate_liquidity_pool<R, E: From<Error<T>>>(
 		trading_pair: &TradingPair,
 		f: impl FnOnce((&mut Balance, &mut Balance)) -> sp_std::result::Result<R, E>,
 	) -> sp_std::result::Result<R, E> {
+		// the liquidity pool can't be updated during the callback of a flash swap
+		if Self::flash_swap_locked(trading_pair) {
+			return Err(Error::<T>::LiquidityPoolLocked.into());
+		}
+
 		LiquidityPool::<T>::try_mutate(trading_pair, |(pool_0, pool_1)| -> sp_std::result::Result<R, E> {
 			let old_pool_0 = *pool_0;
 			let old_pool_1 = *pool_1;
@@ -1015,6 +1701,10 @@ impl<T: Config> Pallet<T> {
 			),
 			Error::<T>::MustBeEnabled,
 		);
+		ensure!(
+			!ConcentratedPools::<T>::contains_key(trading_pair),
+			Error::<T>::ConcentratedPoolAlreadyExists
+		);
 
 		ensure!(
 			!max_amount_a.is_zero() && !max_amount_b.is_zero(),
@@ -1184,9 +1874,14 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
+	/// Get the liquidity of the trading pair, it is the virtual reserves of
+	/// the liquidity in range for concentrated liquidity pool.
 	fn get_liquidity(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
 		if let Some(trading_pair) = TradingPair::from_currency_ids(currency_id_a, currency_id_b) {
-			let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
+			let (pool_0, pool_1) = match Self::concentrated_pools(trading_pair) {
+				Some(pool) => Self::concentrated_virtual_reserves(&pool),
+				None => Self::liquidity_pool(trading_pair),
+			};
 			if currency_id_a == trading_pair.first() {
 				(pool_0, pool_1)
 			} else {
@@ -1197,12 +1892,21 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Get the exchange fee rate of the trading pair.
+	pub fn get_exchange_fee(trading_pair: TradingPair) -> (u32, u32) {
+		Self::trading_pair_fees(trading_pair).unwrap_or_else(T::GetExchangeFee::get)
+	}
+
 	/// Get how much target amount will be got for specific supply amount.
-	fn get_target_amount(supply_pool: Balance, target_pool: Balance, supply_amount: Balance) -> Balance {
+	fn get_target_amount(
+		supply_pool: Balance,
+		target_pool: Balance,
+		supply_amount: Balance,
+		(fee_numerator, fee_denominator): (u32, u32),
+	) -> Balance {
 		if supply_amount.is_zero() || supply_pool.is_zero() || target_pool.is_zero() {
 			Zero::zero()
 		} else {
-			let (fee_numerator, fee_denominator) = T::GetExchangeFee::get();
 			let supply_amount_with_fee: U256 =
 				U256::from(supply_amount).saturating_mul(U256::from(fee_denominator.saturating_sub(fee_numerator)));
 			let numerator: U256 = supply_amount_with_fee.saturating_mul(U256::from(target_pool));
@@ -1218,11 +1922,15 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Get how much supply amount will be paid for specific target amount.
-	fn get_supply_amount(supply_pool: Balance, target_pool: Balance, target_amount: Balance) -> Balance {
+	fn get_supply_amount(
+		supply_pool: Balance,
+		target_pool: Balance,
+		target_amount: Balance,
+		(fee_numerator, fee_denominator): (u32, u32),
+	) -> Balance {
 		if target_amount.is_zero() || supply_pool.is_zero() || target_pool.is_zero() {
 			Zero::zero()
 		} else {
-			let (fee_numerator, fee_denominator) = T::GetExchangeFee::get();
 			let numerator: U256 = U256::from(supply_pool)
 				.saturating_mul(U256::from(target_amount))
 				.saturating_mul(U256::from(fee_denominator));
@@ -1259,12 +1967,21 @@ impl<T: Config> Pallet<T> {
 				),
 				Error::<T>::MustBeEnabled
 			);
-			let (supply_pool, target_pool) = Self::get_liquidity(path[i], path[i + 1]);
-			ensure!(
-				!supply_pool.is_zero() && !target_pool.is_zero(),
-				Error::<T>::InsufficientLiquidity
-			);
-			let target_amount = Self::get_target_amount(supply_pool, target_pool, target_amounts[i]);
+			let target_amount = if ConcentratedPools::<T>::contains_key(trading_pair) {
+				Self::concentrated_swap(trading_pair, path[i], target_amounts[i], true, false)?.1
+			} else {
+				let (supply_pool, target_pool) = Self::get_liquidity(path[i], path[i + 1]);
+				ensure!(
+					!supply_pool.is_zero() && !target_pool.is_zero(),
+					Error::<T>::InsufficientLiquidity
+				);
+				Self::get_target_amount(
+					supply_pool,
+					target_pool,
+					target_amounts[i],
+					Self::get_exchange_fee(trading_pair),
+				)
+			};
 			ensure!(!target_amount.is_zero(), Error::<T>::ZeroTargetAmount);
 
 			target_amounts[i + 1] = target_amount;
@@ -1295,12 +2012,21 @@ impl<T: Config> Pallet<T> {
 				),
 				Error::<T>::MustBeEnabled
 			);
-			let (supply_pool, target_pool) = Self::get_liquidity(path[i - 1], path[i]);
-			ensure!(
-				!supply_pool.is_zero() && !target_pool.is_zero(),
-				Error::<T>::InsufficientLiquidity
-			);
-			let supply_amount = Self::get_supply_amount(supply_pool, target_pool, supply_amounts[i]);
+			let supply_amount = if ConcentratedPools::<T>::contains_key(trading_pair) {
+				Self::concentrated_swap(trading_pair, path[i - 1], supply_amounts[i], false, false)?.0
+			} else {
+				let (supply_pool, target_pool) = Self::get_liquidity(path[i - 1], path[i]);
+				ensure!(
+					!supply_pool.is_zero() && !target_pool.is_zero(),
+					Error::<T>::InsufficientLiquidity
+				);
+				Self::get_supply_amount(
+					supply_pool,
+					target_pool,
+					supply_amounts[i],
+					Self::get_exchange_fee(trading_pair),
+				)
+			};
 			ensure!(!supply_amount.is_zero(), Error::<T>::ZeroSupplyAmount);
 
 			supply_amounts[i - 1] = supply_amount;
@@ -1328,6 +2054,16 @@ impl<T: Config> Pallet<T> {
 		target_decrement: Balance,
 	) -> DispatchResult {
 		if let Some(trading_pair) = TradingPair::from_currency_ids(supply_currency_id, target_currency_id) {
+			// the protocol fee is taken from the exchange fee and kept out of the pool
+			let protocol_fee = Self::get_protocol_fee(trading_pair, supply_increment);
+			if !protocol_fee.is_zero() {
+				AccruedProtocolFees::<T>::try_mutate(supply_currency_id, |accrued| -> DispatchResult {
+					*accrued = accrued.checked_add(protocol_fee).ok_or(ArithmeticError::Overflow)?;
+					Ok(())
+				})?;
+			}
+			let supply_increment = supply_increment.saturating_sub(protocol_fee);
+
 			Self::try_mutate_liquidity_pool(&trading_pair, |(pool_0, pool_1)| -> DispatchResult {
 				let invariant_before_swap: U256 = U256::from(*pool_0).saturating_mul(U256::from(*pool_1));
 
@@ -1351,22 +2087,214 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	fn _swap_by_path(path: &[CurrencyId], amounts: &[Balance]) -> DispatchResult {
+	/// Get the protocol fee charged from the exchange fee of the supply amount.
+	fn get_protocol_fee(trading_pair: TradingPair, supply_amount: Balance) -> Balance {
+		match Self::protocol_fee() {
+			Some((_, share)) if !share.is_zero() => {
+				let (fee_numerator, fee_denominator) = Self::get_exchange_fee(trading_pair);
+				let exchange_fee: Balance = U256::from(supply_amount)
+					.saturating_mul(U256::from(fee_numerator))
+					.checked_div(U256::from(fee_denominator))
+					.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+					.unwrap_or_else(Zero::zero);
+				share.saturating_mul_int(exchange_fee)
+			}
+			_ => Zero::zero(),
+		}
+	}
+
+	/// Swap by the amounts of the path, `is_exact_supply` is the mode of
+	/// the amounts calculated, the concentrated liquidity pools in the path
+	/// replay the swaps in the same mode.
+	fn _swap_by_path(path: &[CurrencyId], amounts: &[Balance], is_exact_supply: bool) -> DispatchResult {
 		let mut i: usize = 0;
 		while i + 1 < path.len() {
 			let (supply_currency_id, target_currency_id) = (path[i], path[i + 1]);
 			let (supply_increment, target_decrement) = (amounts[i], amounts[i + 1]);
-			Self::_swap(
-				supply_currency_id,
-				target_currency_id,
-				supply_increment,
-				target_decrement,
-			)?;
+			match TradingPair::from_currency_ids(supply_currency_id, target_currency_id) {
+				Some(trading_pair) if ConcentratedPools::<T>::contains_key(trading_pair) => {
+					let amount = if is_exact_supply {
+						supply_increment
+					} else {
+						target_decrement
+					};
+					let swapped =
+						Self::concentrated_swap(trading_pair, supply_currency_id, amount, is_exact_supply, true)?;
+					ensure!(
+						swapped == (supply_increment, target_decrement),
+						Error::<T>::InvariantCheckFailed
+					);
+				}
+				_ => Self::_swap(
+					supply_currency_id,
+					target_currency_id,
+					supply_increment,
+					target_decrement,
+				)?,
+			}
 			i += 1;
 		}
 		Ok(())
 	}
 
+	fn ensure_position_owner(
+		who: &T::AccountId,
+		position: &Position<PositionClassIdOf<T>, PositionTokenIdOf<T>>,
+	) -> DispatchResult {
+		let (class_id, token_id) = position.nft;
+		ensure!(
+			T::PositionNFT::owner(class_id, token_id).as_ref() == Some(who),
+			Error::<T>::NotPositionOwner
+		);
+		Ok(())
+	}
+
+	fn do_open_position(
+		who: &T::AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		tick_lower: i32,
+		tick_upper: i32,
+		max_amount_a: Balance,
+		max_amount_b: Balance,
+		min_liquidity: u128,
+	) -> sp_std::result::Result<PositionId, DispatchError> {
+		let trading_pair =
+			TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+		ensure!(
+			matches!(
+				Self::trading_pair_statuses(trading_pair),
+				TradingPairStatus::<_, _>::Enabled
+			),
+			Error::<T>::MustBeEnabled,
+		);
+		let pool = Self::concentrated_pools(trading_pair).ok_or(Error::<T>::ConcentratedPoolNotFound)?;
+		Self::ensure_valid_tick_range(&pool, tick_lower, tick_upper)?;
+		let class_id = Self::position_nft_class().ok_or(Error::<T>::PositionNFTClassNotSet)?;
+
+		let (max_amount_0, max_amount_1) = if currency_id_a == trading_pair.first() {
+			(max_amount_a, max_amount_b)
+		} else {
+			(max_amount_b, max_amount_a)
+		};
+		let liquidity = concentrated::liquidity_for_amounts(
+			pool.sqrt_price,
+			concentrated::sqrt_price_at_tick(tick_lower),
+			concentrated::sqrt_price_at_tick(tick_upper),
+			max_amount_0,
+			max_amount_1,
+		)
+		.ok_or(ArithmeticError::Overflow)?;
+		let liquidity_delta = i128::try_from(liquidity).map_err(|_| ArithmeticError::Overflow)?;
+		ensure!(!liquidity.is_zero(), Error::<T>::InvalidLiquidityIncrement);
+		ensure!(liquidity >= min_liquidity, Error::<T>::UnacceptableShareIncrement);
+
+		let token_id = T::PositionNFT::mint(class_id, who)?;
+		let position_id = NextPositionId::<T>::try_mutate(|id| -> sp_std::result::Result<PositionId, DispatchError> {
+			let current_id = *id;
+			*id = id.checked_add(One::one()).ok_or(ArithmeticError::Overflow)?;
+			Ok(current_id)
+		})?;
+		let mut position = Position {
+			trading_pair,
+			tick_lower,
+			tick_upper,
+			liquidity: Zero::zero(),
+			fee_growth_inside_last: Default::default(),
+			tokens_owed: Default::default(),
+			nft: (class_id, token_id),
+		};
+		let (amount_0, amount_1) = Self::modify_concentrated_position(&mut position, liquidity_delta)?;
+		ensure!(
+			amount_0 <= max_amount_0 && amount_1 <= max_amount_1,
+			Error::<T>::InvalidLiquidityIncrement
+		);
+
+		let module_account_id = Self::account_id();
+		T::Currency::transfer(trading_pair.first(), who, &module_account_id, amount_0)?;
+		T::Currency::transfer(trading_pair.second(), who, &module_account_id, amount_1)?;
+		Positions::<T>::insert(position_id, position);
+
+		Self::deposit_event(Event::PositionOpened {
+			who: who.clone(),
+			position_id,
+			trading_pair,
+			tick_lower,
+			tick_upper,
+			liquidity,
+			amount_0,
+			amount_1,
+		});
+		Ok(position_id)
+	}
+
+	fn do_decrease_position(
+		who: &T::AccountId,
+		position_id: PositionId,
+		liquidity: u128,
+		min_withdrawn_0: Balance,
+		min_withdrawn_1: Balance,
+	) -> DispatchResult {
+		Positions::<T>::try_mutate_exists(position_id, |maybe_position| -> DispatchResult {
+			let position = maybe_position.as_mut().ok_or(Error::<T>::PositionNotFound)?;
+			Self::ensure_position_owner(who, position)?;
+			ensure!(
+				!liquidity.is_zero() && liquidity <= position.liquidity,
+				Error::<T>::InvalidLiquidityIncrement
+			);
+			let liquidity_delta = i128::try_from(liquidity).map_err(|_| ArithmeticError::Overflow)?;
+
+			let (amount_0, amount_1) = Self::modify_concentrated_position(position, -liquidity_delta)?;
+			ensure!(
+				amount_0 >= min_withdrawn_0 && amount_1 >= min_withdrawn_1,
+				Error::<T>::UnacceptableLiquidityWithdrawn
+			);
+
+			let module_account_id = Self::account_id();
+			T::Currency::transfer(position.trading_pair.first(), &module_account_id, who, amount_0)?;
+			T::Currency::transfer(position.trading_pair.second(), &module_account_id, who, amount_1)?;
+			Self::deposit_event(Event::PositionDecreased {
+				who: who.clone(),
+				position_id,
+				liquidity,
+				amount_0,
+				amount_1,
+			});
+
+			if position.liquidity.is_zero() {
+				Self::do_collect_position_fees(who, position_id, position)?;
+				let (class_id, token_id) = position.nft;
+				T::PositionNFT::burn(class_id, token_id)?;
+				*maybe_position = None;
+				Self::deposit_event(Event::PositionClosed {
+					who: who.clone(),
+					position_id,
+				});
+			}
+			Ok(())
+		})
+	}
+
+	fn do_collect_position_fees(
+		who: &T::AccountId,
+		position_id: PositionId,
+		position: &mut Position<PositionClassIdOf<T>, PositionTokenIdOf<T>>,
+	) -> DispatchResult {
+		let (fee_0, fee_1) = sp_std::mem::take(&mut position.tokens_owed);
+		if !fee_0.is_zero() || !fee_1.is_zero() {
+			let module_account_id = Self::account_id();
+			T::Currency::transfer(position.trading_pair.first(), &module_account_id, who, fee_0)?;
+			T::Currency::transfer(position.trading_pair.second(), &module_account_id, who, fee_1)?;
+			Self::deposit_event(Event::PositionFeesCollected {
+				who: who.clone(),
+				position_id,
+				fee_0,
+				fee_1,
+			});
+		}
+		Ok(())
+	}
+
 	/// Ensured atomic.
 	#[transactional]
 	fn do_swap_with_exact_supply(
@@ -1384,7 +2312,7 @@ impl<T: Config> Pallet<T> {
 		let actual_target_amount = amounts[amounts.len() - 1];
 
 		T::Currency::transfer(path[0], who, &module_account_id, supply_amount)?;
-		Self::_swap_by_path(path, &amounts)?;
+		Self::_swap_by_path(path, &amounts, true)?;
 		T::Currency::transfer(path[path.len() - 1], &module_account_id, who, actual_target_amount)?;
 
 		Self::deposit_event(Event::Swap {
@@ -1409,7 +2337,7 @@ impl<T: Config> Pallet<T> {
 		let actual_supply_amount = amounts[0];
 
 		T::Currency::transfer(path[0], who, &module_account_id, actual_supply_amount)?;
-		Self::_swap_by_path(path, &amounts)?;
+		Self::_swap_by_path(path, &amounts, false)?;
 		T::Currency::transfer(path[path.len() - 1], &module_account_id, who, target_amount)?;
 
 		Self::deposit_event(Event::Swap {
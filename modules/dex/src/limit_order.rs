@@ -0,0 +1,241 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Limit orders.
+//!
+//! The supply amount of a limit order is escrowed in the DEX account. The
+//! order is filled by swapping through the liquidity pool of the trading pair
+//! when the pool price reaches the limit price, either by keepers who earn
+//! the keeper fee or in `on_idle`. An order can be partially filled, the
+//! filled amount is the largest amount whose average price is not worse than
+//! the limit price.
+
+use super::*;
+
+/// The maximum steps to search the fillable amount of a partially fillable
+/// limit order.
+const FILL_SEARCH_STEPS: u32 = 32;
+
+/// The id of limit orders.
+pub type LimitOrderId = u64;
+
+/// Limit order to swap the escrowed supply currency to the target currency.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub struct LimitOrder<AccountId, BlockNumber> {
+	/// The owner of the order.
+	pub owner: AccountId,
+	/// The currency to supply.
+	pub supply_currency_id: CurrencyId,
+	/// The currency to receive.
+	pub target_currency_id: CurrencyId,
+	/// The escrowed supply amount not filled yet.
+	pub remaining_supply_amount: Balance,
+	/// The minimum target amount received per supply amount.
+	pub limit_price: ExchangeRate,
+	/// The order can not be filled after this block.
+	pub expiry: BlockNumber,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Get the open limit orders of the account.
+	pub fn open_limit_orders(who: &T::AccountId) -> Vec<(LimitOrderId, LimitOrder<T::AccountId, T::BlockNumber>)> {
+		Self::account_limit_orders(who)
+			.into_iter()
+			.filter_map(|order_id| Self::limit_orders(order_id).map(|order| (order_id, order)))
+			.collect()
+	}
+
+	/// Get the largest supply amount of the order can be filled at the limit
+	/// price after charging the keeper fee from the target amount, and the
+	/// target amount of it.
+	pub(crate) fn limit_order_fill_amounts(
+		order: &LimitOrder<T::AccountId, T::BlockNumber>,
+		keeper_fee_rate: Ratio,
+	) -> Option<(Balance, Balance)> {
+		let path = [order.supply_currency_id, order.target_currency_id];
+		let quote = |supply_amount: Balance| -> Option<Balance> {
+			let target_amount = Self::get_target_amounts(&path, supply_amount).ok()?[1];
+			let received_amount = target_amount.saturating_sub(keeper_fee_rate.saturating_mul_int(target_amount));
+			let price = ExchangeRate::checked_from_rational(received_amount, supply_amount)?;
+			if price >= order.limit_price {
+				Some(target_amount)
+			} else {
+				None
+			}
+		};
+
+		if let Some(target_amount) = quote(order.remaining_supply_amount) {
+			return Some((order.remaining_supply_amount, target_amount));
+		}
+
+		// the average price gets worse as the supply amount grows, search the largest
+		// acceptable supply amount below the remaining supply amount.
+		let mut fill_amounts = None;
+		let (mut low, mut high) = (One::one(), order.remaining_supply_amount);
+		for _ in 0..FILL_SEARCH_STEPS {
+			if low >= high {
+				break;
+			}
+			let mid = low + (high - low) / 2;
+			match quote(mid) {
+				Some(target_amount) => {
+					fill_amounts = Some((mid, target_amount));
+					low = mid + 1;
+				}
+				None => high = mid,
+			}
+		}
+		fill_amounts
+	}
+
+	/// Fill the order as much as possible at the limit price, the keeper fee
+	/// is paid to `keeper` if any. Returns the filled supply amount.
+	#[transactional]
+	pub(crate) fn do_fill_limit_order(
+		order_id: LimitOrderId,
+		keeper: Option<&T::AccountId>,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		LimitOrders::<T>::try_mutate_exists(
+			order_id,
+			|maybe_order| -> sp_std::result::Result<Balance, DispatchError> {
+				let order = maybe_order.as_mut().ok_or(Error::<T>::LimitOrderNotFound)?;
+				ensure!(
+					frame_system::Pallet::<T>::block_number() <= order.expiry,
+					Error::<T>::LimitOrderExpired
+				);
+
+				let keeper_fee_rate = if keeper.is_some() {
+					T::LimitOrderKeeperFee::get()
+				} else {
+					Zero::zero()
+				};
+				let (supply_amount, target_amount) =
+					Self::limit_order_fill_amounts(order, keeper_fee_rate).ok_or(Error::<T>::LimitOrderNotFillable)?;
+				let path = [order.supply_currency_id, order.target_currency_id];
+				Self::_swap_by_path(&path, &[supply_amount, target_amount], true)?;
+
+				// the supply amount has been escrowed in the DEX account
+				let module_account_id = Self::account_id();
+				let keeper_fee = keeper_fee_rate.saturating_mul_int(target_amount);
+				let received_amount = target_amount.saturating_sub(keeper_fee);
+				T::Currency::transfer(
+					order.target_currency_id,
+					&module_account_id,
+					&order.owner,
+					received_amount,
+				)?;
+				if let Some(keeper) = keeper {
+					T::Currency::transfer(order.target_currency_id, &module_account_id, keeper, keeper_fee)?;
+				}
+
+				order.remaining_supply_amount = order.remaining_supply_amount.saturating_sub(supply_amount);
+				let owner = order.owner.clone();
+				if order.remaining_supply_amount.is_zero() {
+					*maybe_order = None;
+					AccountLimitOrders::<T>::mutate(&owner, |order_ids| order_ids.retain(|id| *id != order_id));
+				}
+
+				Self::deposit_event(Event::LimitOrderFilled {
+					order_id,
+					owner,
+					supply_amount,
+					target_amount: received_amount,
+					keeper: keeper.cloned(),
+					keeper_fee,
+				});
+				Ok(supply_amount)
+			},
+		)
+	}
+
+	/// Remove the order and refund the remaining supply amount to the owner.
+	pub(crate) fn do_remove_limit_order(
+		order_id: LimitOrderId,
+		order: &LimitOrder<T::AccountId, T::BlockNumber>,
+		expired: bool,
+	) -> DispatchResult {
+		T::Currency::transfer(
+			order.supply_currency_id,
+			&Self::account_id(),
+			&order.owner,
+			order.remaining_supply_amount,
+		)?;
+		LimitOrders::<T>::remove(order_id);
+		AccountLimitOrders::<T>::mutate(&order.owner, |order_ids| order_ids.retain(|id| *id != order_id));
+
+		if expired {
+			Self::deposit_event(Event::LimitOrderExpired {
+				order_id,
+				owner: order.owner.clone(),
+				refund_amount: order.remaining_supply_amount,
+			});
+		} else {
+			Self::deposit_event(Event::LimitOrderCancelled {
+				order_id,
+				owner: order.owner.clone(),
+				refund_amount: order.remaining_supply_amount,
+			});
+		}
+		Ok(())
+	}
+
+	/// Check the limit orders from the last iterated one within the weight,
+	/// fill the fillable orders and refund the expired orders.
+	pub(crate) fn process_limit_orders(remaining_weight: Weight) -> Weight {
+		let base_weight = T::DbWeight::get().reads_writes(1, 1);
+		let weight_per_order = <T as Config>::WeightInfo::fill_limit_order();
+		let max_orders = remaining_weight.saturating_sub(base_weight) / weight_per_order.max(1);
+		if max_orders.is_zero() {
+			return 0;
+		}
+
+		let last_key = Self::last_limit_order_key_iterated();
+		let mut iterator = if last_key.is_empty() {
+			LimitOrders::<T>::iter_keys()
+		} else {
+			LimitOrders::<T>::iter_keys_from(last_key.into_inner())
+		};
+		let order_ids: Vec<LimitOrderId> = iterator.by_ref().take(max_orders as usize).collect();
+
+		// start from the beginning next time if all orders are iterated
+		let next_key: BoundedVec<u8, ConstU32<128>> = if (order_ids.len() as u64) < max_orders {
+			Default::default()
+		} else {
+			iterator.last_raw_key().to_vec().try_into().unwrap_or_default()
+		};
+		LastLimitOrderKeyIterated::<T>::put(next_key);
+
+		let now = frame_system::Pallet::<T>::block_number();
+		for order_id in order_ids.iter() {
+			if let Some(order) = Self::limit_orders(order_id) {
+				let res = if now > order.expiry {
+					Self::do_remove_limit_order(*order_id, &order, true)
+				} else {
+					Self::do_fill_limit_order(*order_id, None).map(|_| ())
+				};
+				log::debug!(
+					target: "dex",
+					"process limit order {:?}: {:?}",
+					order_id, res,
+				);
+			}
+		}
+
+		base_weight.saturating_add(weight_per_order.saturating_mul(order_ids.len() as Weight))
+	}
+}
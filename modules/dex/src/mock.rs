@@ -26,14 +26,15 @@ use frame_support::{
 	traits::{ConstU32, ConstU64, Everything, Nothing},
 };
 use frame_system::EnsureSignedBy;
+use module_evm_utility::evm::{ExitReason, ExitRevert, ExitSucceed};
 use nutsfinance_stable_asset::{
 	PoolTokenIndex, RedeemProportionResult, StableAssetPoolId, StableAssetPoolInfo, SwapResult,
 };
 use orml_traits::{parameter_type_with_key, MultiReservableCurrency};
-use primitives::{Amount, TokenSymbol};
+use primitives::{evm::CallInfo, Amount, TokenSymbol};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
-use sp_std::cell::RefCell;
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap};
 use support::mocks::MockErc20InfoMapping;
 
 pub type BlockNumber = u64;
@@ -120,6 +121,7 @@ ord_parameter_types! {
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (1, 100);
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
+	pub LimitOrderKeeperFee: Ratio = Ratio::saturating_from_rational(1, 100);
 }
 
 thread_local! {
@@ -136,6 +138,108 @@ impl Happened<(TradingPair, Balance, Balance)> for MockOnLiquidityPoolUpdated {
 	}
 }
 
+thread_local! {
+	pub static POSITION_NFTS: RefCell<BTreeMap<(u32, u64), AccountId>> = RefCell::new(BTreeMap::new());
+	pub static NEXT_POSITION_TOKEN_ID: RefCell<u64> = RefCell::new(0);
+}
+
+/// A position NFT class whose properties can be changed, rejected by `MockPositionNFT`.
+pub const MUTABLE_POSITION_CLASS: u32 = 1;
+
+pub struct MockPositionNFT;
+impl PositionNFT<AccountId> for MockPositionNFT {
+	type ClassId = u32;
+	type TokenId = u64;
+
+	fn ensure_position_class(class_id: u32) -> DispatchResult {
+		if class_id == MUTABLE_POSITION_CLASS {
+			return Err(DispatchError::Other("mutable position NFT class"));
+		}
+		Ok(())
+	}
+
+	fn mint(class_id: u32, to: &AccountId) -> sp_std::result::Result<u64, DispatchError> {
+		let token_id = NEXT_POSITION_TOKEN_ID.with(|v| {
+			let token_id = *v.borrow();
+			*v.borrow_mut() = token_id + 1;
+			token_id
+		});
+		POSITION_NFTS.with(|v| v.borrow_mut().insert((class_id, token_id), *to));
+		Ok(token_id)
+	}
+
+	fn burn(class_id: u32, token_id: u64) -> DispatchResult {
+		POSITION_NFTS
+			.with(|v| v.borrow_mut().remove(&(class_id, token_id)))
+			.map(|_| ())
+			.ok_or(DispatchError::Other("position NFT not found"))
+	}
+
+	fn owner(class_id: u32, token_id: u64) -> Option<AccountId> {
+		POSITION_NFTS.with(|v| v.borrow().get(&(class_id, token_id)).copied())
+	}
+}
+
+/// The behaviour of the `onFlashSwap` callback of `MockEVM`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FlashSwapCallback {
+	/// Succeed and leave the repay amount in the borrower account.
+	Succeed,
+	/// Revert the callback.
+	Revert,
+	/// Swap through the liquidity pool of AUSD and DOT during the callback.
+	Reenter,
+}
+
+thread_local! {
+	pub static FLASH_SWAP_CALLBACK: RefCell<FlashSwapCallback> = RefCell::new(FlashSwapCallback::Succeed);
+	pub static FLASH_SWAP_CALLBACK_INPUT: RefCell<Vec<u8>> = RefCell::new(vec![]);
+	pub static REENTRANT_SWAP_RESULT: RefCell<Option<DispatchResult>> = RefCell::new(None);
+}
+
+pub struct MockEVM;
+impl EVM<AccountId> for MockEVM {
+	type Balance = Balance;
+
+	fn execute(
+		_context: InvokeContext,
+		input: Vec<u8>,
+		_value: Balance,
+		gas_limit: u64,
+		_storage_limit: u32,
+		_mode: ExecutionMode,
+	) -> Result<CallInfo, DispatchError> {
+		FLASH_SWAP_CALLBACK_INPUT.with(|v| *v.borrow_mut() = input);
+		let exit_reason = match FLASH_SWAP_CALLBACK.with(|v| *v.borrow()) {
+			FlashSwapCallback::Succeed => ExitReason::Succeed(ExitSucceed::Returned),
+			FlashSwapCallback::Revert => ExitReason::Revert(ExitRevert::Reverted),
+			FlashSwapCallback::Reenter => {
+				let result = DexModule::swap_with_exact_supply(Origin::signed(ALICE), vec![AUSD, DOT], 1_000, 0);
+				REENTRANT_SWAP_RESULT.with(|v| *v.borrow_mut() = Some(result));
+				ExitReason::Succeed(ExitSucceed::Returned)
+			}
+		};
+
+		Ok(CallInfo {
+			exit_reason,
+			value: vec![],
+			used_gas: U256::from(gas_limit / 2),
+			used_storage: 0,
+			logs: vec![],
+		})
+	}
+
+	fn get_origin() -> Option<AccountId> {
+		None
+	}
+
+	fn set_origin(_origin: AccountId) {}
+
+	fn gas_to_weight(gas: u64) -> Weight {
+		gas
+	}
+}
+
 impl Config for Runtime {
 	type Event = Event;
 	type Currency = Tokens;
@@ -149,6 +253,12 @@ impl Config for Runtime {
 	type ExtendedProvisioningBlocks = ConstU64<2000>;
 	type OnLiquidityPoolUpdated = MockOnLiquidityPoolUpdated;
 	type StableAsset = MockStableAsset;
+	type PositionNFT = MockPositionNFT;
+	type MaxInitializedTicks = ConstU32<16>;
+	type MaxLimitOrdersPerAccount = ConstU32<2>;
+	type LimitOrderKeeperFee = LimitOrderKeeperFee;
+	type MaxRouteSplits = ConstU32<2>;
+	type EVM = MockEVM;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -242,7 +352,7 @@ impl StableAsset for MockStableAsset {
 	type BlockNumber = BlockNumber;
 
 	fn pool_count() -> StableAssetPoolId {
-		unimplemented!()
+		1
 	}
 
 	fn pool(
@@ -263,7 +373,7 @@ impl StableAsset for MockStableAsset {
 			a_block: 1,
 			future_a: 100,
 			future_a_block: 1,
-			balances: vec![0, 0],
+			balances: vec![1_000_000, 1_000_000],
 			fee_recipient: 0,
 			account_id: 1,
 			yield_recipient: 2,
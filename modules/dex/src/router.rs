@@ -0,0 +1,355 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Swap router.
+//!
+//! A route is a chain of hops through the liquidity pools of enabled trading
+//! pairs and the stable-asset pools, containing at most `TradingPathLimit`
+//! currencies. `get_best_routes` searches the routes from the supply currency
+//! to the target currency and splits the supply amount across the routes which
+//! don't share any pool, allocating it in parts to the route with the best
+//! marginal output. The quotes are meant to be queried off-chain, and
+//! `swap_with_routes` executes the chosen routes.
+
+use super::*;
+
+/// The number of parts the supply amount is split into when allocating it
+/// across routes.
+const ROUTE_SPLIT_PARTS: u32 = 20;
+
+/// The maximum steps to search the target amount of the exact supply amount of
+/// a stable-asset pool.
+const STABLE_ASSET_SEARCH_STEPS: u32 = 128;
+
+/// A hop of a swap route.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub enum RouteHop {
+	/// Swap the first currency to the second one through the liquidity pool of
+	/// the trading pair.
+	Dex(CurrencyId, CurrencyId),
+	/// Swap the asset at the first index to the asset at the second index
+	/// through the stable-asset pool.
+	StableAsset(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex),
+}
+
+/// The quote of a route.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct RouteQuote {
+	/// The hops of the route.
+	pub route: Vec<RouteHop>,
+	/// The supply amount allocated to the route.
+	pub supply_amount: Balance,
+	/// The target amount expected from the route.
+	pub target_amount: Balance,
+}
+
+/// The pool a hop swaps through, routes sharing a pool affect the quotes of
+/// each other.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RoutePool {
+	Dex(TradingPair),
+	StableAsset(StableAssetPoolId),
+}
+
+impl<T: Config> Pallet<T> {
+	/// Get the currencies swapped by the hop.
+	fn route_hop_currencies(hop: &RouteHop) -> Option<(CurrencyId, CurrencyId)> {
+		match *hop {
+			RouteHop::Dex(supply_currency_id, target_currency_id) => Some((supply_currency_id, target_currency_id)),
+			RouteHop::StableAsset(pool_id, supply_index, target_index) => {
+				let pool_info = T::StableAsset::pool(pool_id)?;
+				let supply_currency_id = *pool_info.assets.get(supply_index as usize)?;
+				let target_currency_id = *pool_info.assets.get(target_index as usize)?;
+				Some((supply_currency_id, target_currency_id))
+			}
+		}
+	}
+
+	fn route_hop_pool(hop: &RouteHop) -> Option<RoutePool> {
+		match *hop {
+			RouteHop::Dex(currency_id_a, currency_id_b) => {
+				TradingPair::from_currency_ids(currency_id_a, currency_id_b).map(RoutePool::Dex)
+			}
+			RouteHop::StableAsset(pool_id, _, _) => Some(RoutePool::StableAsset(pool_id)),
+		}
+	}
+
+	/// Ensure the route swaps `supply_currency_id` to `target_currency_id` and
+	/// contains at most `TradingPathLimit` currencies.
+	pub(crate) fn ensure_valid_route(
+		route: &[RouteHop],
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+	) -> DispatchResult {
+		ensure!(
+			!route.is_empty() && route.len() < T::TradingPathLimit::get().saturated_into(),
+			Error::<T>::InvalidTradingPathLength
+		);
+		let mut currency_id = supply_currency_id;
+		for hop in route {
+			let (hop_supply_currency_id, hop_target_currency_id) =
+				Self::route_hop_currencies(hop).ok_or(Error::<T>::InvalidRoute)?;
+			ensure!(hop_supply_currency_id == currency_id, Error::<T>::InvalidRoute);
+			currency_id = hop_target_currency_id;
+		}
+		ensure!(currency_id == target_currency_id, Error::<T>::InvalidRoute);
+		Ok(())
+	}
+
+	/// Get the target amount of the exact supply amount swapped through the
+	/// stable-asset pool, which is the largest target amount whose required
+	/// supply amount doesn't exceed the exact supply amount.
+	fn get_stable_asset_target_amount(
+		pool_id: StableAssetPoolId,
+		supply_index: PoolTokenIndex,
+		target_index: PoolTokenIndex,
+		supply_amount: Balance,
+	) -> Option<Balance> {
+		let pool_info = T::StableAsset::pool(pool_id)?;
+		let target_balance = *pool_info.balances.get(target_index as usize)?;
+		let enough_supply = |target_amount: Balance| {
+			T::StableAsset::get_swap_amount_exact(pool_id, supply_index, target_index, target_amount)
+				.map_or(false, |result| result.dx <= supply_amount)
+		};
+
+		let mut target_amount = None;
+		let (mut low, mut high) = (One::one(), target_balance);
+		for _ in 0..STABLE_ASSET_SEARCH_STEPS {
+			if low > high {
+				break;
+			}
+			let mid = low + (high - low) / 2;
+			if enough_supply(mid) {
+				target_amount = Some(mid);
+				low = mid + 1;
+			} else {
+				high = mid - 1;
+			}
+		}
+		target_amount
+	}
+
+	/// Get the target amount of the supply amount swapped through the route.
+	pub fn get_route_target_amount(route: &[RouteHop], supply_amount: Balance) -> Option<Balance> {
+		route.iter().try_fold(supply_amount, |amount, hop| {
+			let target_amount = match *hop {
+				RouteHop::Dex(supply_currency_id, target_currency_id) => {
+					Self::get_target_amounts(&[supply_currency_id, target_currency_id], amount).ok()?[1]
+				}
+				RouteHop::StableAsset(pool_id, supply_index, target_index) => {
+					Self::get_stable_asset_target_amount(pool_id, supply_index, target_index, amount)?
+				}
+			};
+			if target_amount.is_zero() {
+				None
+			} else {
+				Some(target_amount)
+			}
+		})
+	}
+
+	/// Get all routes from `supply_currency_id` to `target_currency_id` through
+	/// the enabled trading pairs and the stable-asset pools.
+	fn get_routes(supply_currency_id: CurrencyId, target_currency_id: CurrencyId) -> Vec<Vec<RouteHop>> {
+		let mut hops: Vec<(CurrencyId, CurrencyId, RouteHop)> = vec![];
+		for (trading_pair, status) in TradingPairStatuses::<T>::iter() {
+			if matches!(status, TradingPairStatus::<_, _>::Enabled) {
+				let (currency_id_0, currency_id_1) = (trading_pair.first(), trading_pair.second());
+				hops.push((
+					currency_id_0,
+					currency_id_1,
+					RouteHop::Dex(currency_id_0, currency_id_1),
+				));
+				hops.push((
+					currency_id_1,
+					currency_id_0,
+					RouteHop::Dex(currency_id_1, currency_id_0),
+				));
+			}
+		}
+		for pool_id in 0..T::StableAsset::pool_count() {
+			if let Some(pool_info) = T::StableAsset::pool(pool_id) {
+				for (i, supply_asset) in pool_info.assets.iter().enumerate() {
+					for (j, target_asset) in pool_info.assets.iter().enumerate() {
+						if i != j {
+							hops.push((
+								*supply_asset,
+								*target_asset,
+								RouteHop::StableAsset(pool_id, i as PoolTokenIndex, j as PoolTokenIndex),
+							));
+						}
+					}
+				}
+			}
+		}
+
+		// depth first search of the routes without repeated currency
+		let max_hops: usize = T::TradingPathLimit::get().saturating_sub(1).saturated_into();
+		let mut routes = vec![];
+		let mut route: Vec<RouteHop> = vec![];
+		let mut visited = vec![supply_currency_id];
+		let mut next_hop_indexes = vec![0usize];
+		while let Some(next_hop_index) = next_hop_indexes.last_mut() {
+			let currency_id = visited[visited.len() - 1];
+			match hops[*next_hop_index..]
+				.iter()
+				.position(|(hop_supply, hop_target, _)| *hop_supply == currency_id && !visited.contains(hop_target))
+			{
+				Some(offset) => {
+					let (_, hop_target, hop) = hops[*next_hop_index + offset];
+					*next_hop_index += offset + 1;
+					if hop_target == target_currency_id {
+						let mut found = route.clone();
+						found.push(hop);
+						routes.push(found);
+					} else if route.len() + 1 < max_hops {
+						route.push(hop);
+						visited.push(hop_target);
+						next_hop_indexes.push(0);
+					}
+				}
+				None => {
+					next_hop_indexes.pop();
+					visited.pop();
+					route.pop();
+				}
+			}
+		}
+		routes
+	}
+
+	/// Get the best routes to swap the exact supply amount, the supply amount
+	/// is split across at most `max_splits` routes which don't share any pool.
+	/// Returns empty if the supply amount can't be swapped.
+	pub fn get_best_routes(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: Balance,
+		max_splits: u32,
+	) -> Vec<RouteQuote> {
+		let max_splits = max_splits.min(T::MaxRouteSplits::get()).max(1) as usize;
+		if supply_amount.is_zero() || supply_currency_id == target_currency_id {
+			return vec![];
+		}
+
+		let mut candidates: Vec<(Vec<RouteHop>, Balance)> = Self::get_routes(supply_currency_id, target_currency_id)
+			.into_iter()
+			.filter_map(|route| Self::get_route_target_amount(&route, supply_amount).map(|amount| (route, amount)))
+			.collect();
+		candidates.sort_by_key(|(_, target_amount)| sp_std::cmp::Reverse(*target_amount));
+
+		// select the best routes which don't share any pool
+		let mut used_pools: Vec<RoutePool> = vec![];
+		let mut selected: Vec<Vec<RouteHop>> = vec![];
+		for (route, _) in candidates {
+			if selected.len() >= max_splits {
+				break;
+			}
+			let pools: Option<Vec<RoutePool>> = route.iter().map(Self::route_hop_pool).collect();
+			if let Some(pools) = pools {
+				if pools.iter().all(|pool| !used_pools.contains(pool)) {
+					used_pools.extend(pools);
+					selected.push(route);
+				}
+			}
+		}
+
+		// allocate the supply amount in parts to the route with the best marginal output
+		let parts = if selected.len() > 1 { ROUTE_SPLIT_PARTS } else { 1 };
+		let part_amount = supply_amount / Balance::from(parts);
+		let mut allocations: Vec<(Balance, Balance)> = vec![(Zero::zero(), Zero::zero()); selected.len()];
+		for part in 0..parts {
+			let amount = if part == parts - 1 {
+				supply_amount.saturating_sub(part_amount.saturating_mul(Balance::from(parts - 1)))
+			} else {
+				part_amount
+			};
+			if amount.is_zero() {
+				continue;
+			}
+
+			let best = selected
+				.iter()
+				.zip(allocations.iter())
+				.enumerate()
+				.filter_map(|(index, (route, (allocated, output)))| {
+					Self::get_route_target_amount(route, allocated.saturating_add(amount))
+						.map(|new_output| (index, new_output, new_output.saturating_sub(*output)))
+				})
+				.max_by_key(|(_, _, gain)| *gain);
+			match best {
+				Some((index, new_output, _)) => {
+					let allocation = &mut allocations[index];
+					allocation.0 = allocation.0.saturating_add(amount);
+					allocation.1 = new_output;
+				}
+				None => return vec![],
+			}
+		}
+
+		selected
+			.into_iter()
+			.zip(allocations)
+			.filter(|(_, (allocated, _))| !allocated.is_zero())
+			.map(|(route, (supply_amount, target_amount))| RouteQuote {
+				route,
+				supply_amount,
+				target_amount,
+			})
+			.collect()
+	}
+
+	/// Swap the supply amount of each route hop by hop, the consecutive DEX
+	/// hops are swapped by path. Returns the target amount.
+	pub(crate) fn do_swap_by_route(
+		who: &T::AccountId,
+		route: &[RouteHop],
+		supply_amount: Balance,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		let mut amount = supply_amount;
+		let mut dex_path: Vec<CurrencyId> = vec![];
+		for hop in route {
+			match *hop {
+				RouteHop::Dex(supply_currency_id, target_currency_id) => {
+					if dex_path.is_empty() {
+						dex_path.push(supply_currency_id);
+					}
+					dex_path.push(target_currency_id);
+				}
+				RouteHop::StableAsset(pool_id, supply_index, target_index) => {
+					if !dex_path.is_empty() {
+						amount = Self::do_swap_with_exact_supply(who, &dex_path, amount, Zero::zero())?;
+						dex_path.clear();
+					}
+					let (_, target_amount) = <Self as StableAssetDEX<_, _, _>>::swap(
+						who,
+						pool_id,
+						supply_index,
+						target_index,
+						SwapLimit::ExactSupply(amount, Zero::zero()),
+					)?;
+					amount = target_amount;
+				}
+			}
+		}
+		if !dex_path.is_empty() {
+			amount = Self::do_swap_with_exact_supply(who, &dex_path, amount, Zero::zero())?;
+		}
+		Ok(amount)
+	}
+}
@@ -23,8 +23,9 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{
-	AUSDBTCPair, AUSDDOTPair, DOTBTCPair, DexModule, Event, ExtBuilder, ListingOrigin, Origin, Runtime, System, Tokens,
-	ACA, ALICE, AUSD, AUSD_DOT_POOL_RECORD, BOB, BTC, DOT,
+	AUSDBTCPair, AUSDDOTPair, AccountId, DOTBTCPair, DexModule, Event, ExtBuilder, FlashSwapCallback, GetExchangeFee,
+	ListingOrigin, MockPositionNFT, Origin, Runtime, System, Tokens, ACA, ALICE, AUSD, AUSD_DOT_POOL_RECORD, BOB, BTC,
+	DOT, FLASH_SWAP_CALLBACK, FLASH_SWAP_CALLBACK_INPUT, MUTABLE_POSITION_CLASS, REENTRANT_SWAP_RESULT,
 };
 use orml_traits::MultiReservableCurrency;
 use sp_core::H160;
@@ -727,6 +728,157 @@ fn disable_trading_pair_work() {
 	});
 }
 
+#[test]
+fn set_trading_pair_fee_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (50000, 10000));
+
+			assert_noop!(
+				DexModule::set_trading_pair_fee(Origin::signed(ALICE), AUSD, DOT, Some((5, 1000))),
+				BadOrigin
+			);
+			assert_noop!(
+				DexModule::set_trading_pair_fee(Origin::signed(ListingOrigin::get()), AUSD, AUSD, Some((5, 1000))),
+				Error::<Runtime>::InvalidCurrencyId
+			);
+			assert_noop!(
+				DexModule::set_trading_pair_fee(Origin::signed(ListingOrigin::get()), AUSD, DOT, Some((1, 0))),
+				Error::<Runtime>::InvalidExchangeFee
+			);
+			assert_noop!(
+				DexModule::set_trading_pair_fee(Origin::signed(ListingOrigin::get()), AUSD, DOT, Some((100, 100))),
+				Error::<Runtime>::InvalidExchangeFee
+			);
+
+			assert_eq!(DexModule::get_exchange_fee(AUSDDOTPair::get()), GetExchangeFee::get());
+			assert_eq!(
+				DexModule::get_target_amounts(&[DOT, AUSD], 10000),
+				Ok(vec![10000, 24874])
+			);
+
+			assert_ok!(DexModule::set_trading_pair_fee(
+				Origin::signed(ListingOrigin::get()),
+				DOT,
+				AUSD,
+				Some((5, 1000))
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::TradingPairFeeUpdated {
+				trading_pair: AUSDDOTPair::get(),
+				fee: Some((5, 1000)),
+			}));
+			assert_eq!(DexModule::trading_pair_fees(AUSDDOTPair::get()), Some((5, 1000)));
+			assert_eq!(DexModule::get_exchange_fee(AUSDDOTPair::get()), (5, 1000));
+			assert_eq!(DexModule::get_exchange_fee(AUSDBTCPair::get()), GetExchangeFee::get());
+			assert_eq!(
+				DexModule::get_target_amounts(&[DOT, AUSD], 10000),
+				Ok(vec![10000, 24937])
+			);
+			assert_eq!(
+				DexModule::get_supply_amounts(&[DOT, AUSD], 24937),
+				Ok(vec![10000, 24937])
+			);
+
+			assert_ok!(DexModule::set_trading_pair_fee(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				None
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::TradingPairFeeUpdated {
+				trading_pair: AUSDDOTPair::get(),
+				fee: None,
+			}));
+			assert_eq!(DexModule::trading_pair_fees(AUSDDOTPair::get()), None);
+			assert_eq!(
+				DexModule::get_target_amounts(&[DOT, AUSD], 10000),
+				Ok(vec![10000, 24874])
+			);
+		});
+}
+
+#[test]
+fn protocol_fee_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let treasury: AccountId = 10;
+
+			assert_noop!(
+				DexModule::set_protocol_fee(Origin::signed(ALICE), treasury, Ratio::saturating_from_rational(1, 2)),
+				BadOrigin
+			);
+			assert_noop!(
+				DexModule::set_protocol_fee(
+					Origin::signed(ListingOrigin::get()),
+					treasury,
+					Ratio::saturating_from_rational(3, 2)
+				),
+				Error::<Runtime>::InvalidProtocolFeeShare
+			);
+			assert_noop!(
+				DexModule::claim_protocol_fee(Origin::signed(BOB), DOT),
+				Error::<Runtime>::ProtocolFeeNotSet
+			);
+
+			assert_ok!(DexModule::set_protocol_fee(
+				Origin::signed(ListingOrigin::get()),
+				treasury,
+				Ratio::saturating_from_rational(1, 2)
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::ProtocolFeeUpdated {
+				receiver: treasury,
+				share: Ratio::saturating_from_rational(1, 2),
+			}));
+			assert_eq!(
+				DexModule::protocol_fee(),
+				Some((treasury, Ratio::saturating_from_rational(1, 2)))
+			);
+
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				500_000,
+				100_000,
+				0,
+				false,
+			));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (500_000, 100_000));
+
+			// the protocol fee does not change the quote
+			assert_ok!(DexModule::do_swap_with_exact_supply(&BOB, &[DOT, AUSD], 10_000, 0));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (454_960, 109_950));
+			assert_eq!(DexModule::accrued_protocol_fees(DOT), 50);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 110_000);
+
+			assert_ok!(DexModule::claim_protocol_fee(Origin::signed(BOB), DOT));
+			System::assert_last_event(Event::DexModule(crate::Event::ProtocolFeeClaimed {
+				receiver: treasury,
+				currency_id: DOT,
+				amount: 50,
+			}));
+			assert_eq!(DexModule::accrued_protocol_fees(DOT), 0);
+			assert_eq!(Tokens::free_balance(DOT, &treasury), 50);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 109_950);
+
+			// switch off the protocol fee
+			assert_ok!(DexModule::set_protocol_fee(
+				Origin::signed(ListingOrigin::get()),
+				treasury,
+				Ratio::zero()
+			));
+			assert_ok!(DexModule::do_swap_with_exact_supply(&BOB, &[DOT, AUSD], 10_000, 0));
+			assert_eq!(DexModule::accrued_protocol_fees(DOT), 0);
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT).1, 119_950);
+		});
+}
+
 #[test]
 fn on_liquidity_pool_updated_work() {
 	ExtBuilder::default()
@@ -957,26 +1109,47 @@ fn get_liquidity_work() {
 #[test]
 fn get_target_amount_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_eq!(DexModule::get_target_amount(10000, 0, 1000), 0);
-		assert_eq!(DexModule::get_target_amount(0, 20000, 1000), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 0), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 1, 1000000), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 10000), 9949);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000), 1801);
+		assert_eq!(DexModule::get_target_amount(10000, 0, 1000, GetExchangeFee::get()), 0);
+		assert_eq!(DexModule::get_target_amount(0, 20000, 1000, GetExchangeFee::get()), 0);
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 0, GetExchangeFee::get()), 0);
+		assert_eq!(
+			DexModule::get_target_amount(10000, 1, 1000000, GetExchangeFee::get()),
+			0
+		);
+		assert_eq!(
+			DexModule::get_target_amount(10000, 20000, 10000, GetExchangeFee::get()),
+			9949
+		);
+		assert_eq!(
+			DexModule::get_target_amount(10000, 20000, 1000, GetExchangeFee::get()),
+			1801
+		);
 	});
 }
 
 #[test]
 fn get_supply_amount_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_eq!(DexModule::get_supply_amount(10000, 0, 1000), 0);
-		assert_eq!(DexModule::get_supply_amount(0, 20000, 1000), 0);
-		assert_eq!(DexModule::get_supply_amount(10000, 20000, 0), 0);
-		assert_eq!(DexModule::get_supply_amount(10000, 1, 1), 0);
-		assert_eq!(DexModule::get_supply_amount(10000, 20000, 9949), 9999);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 9999), 9949);
-		assert_eq!(DexModule::get_supply_amount(10000, 20000, 1801), 1000);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000), 1801);
+		assert_eq!(DexModule::get_supply_amount(10000, 0, 1000, GetExchangeFee::get()), 0);
+		assert_eq!(DexModule::get_supply_amount(0, 20000, 1000, GetExchangeFee::get()), 0);
+		assert_eq!(DexModule::get_supply_amount(10000, 20000, 0, GetExchangeFee::get()), 0);
+		assert_eq!(DexModule::get_supply_amount(10000, 1, 1, GetExchangeFee::get()), 0);
+		assert_eq!(
+			DexModule::get_supply_amount(10000, 20000, 9949, GetExchangeFee::get()),
+			9999
+		);
+		assert_eq!(
+			DexModule::get_target_amount(10000, 20000, 9999, GetExchangeFee::get()),
+			9949
+		);
+		assert_eq!(
+			DexModule::get_supply_amount(10000, 20000, 1801, GetExchangeFee::get()),
+			1000
+		);
+		assert_eq!(
+			DexModule::get_target_amount(10000, 20000, 1000, GetExchangeFee::get()),
+			1801
+		);
 	});
 }
 
@@ -1038,7 +1211,8 @@ fn calculate_amount_for_big_number_work() {
 			DexModule::get_supply_amount(
 				171_000_000_000_000_000_000_000,
 				56_000_000_000_000_000_000_000,
-				1_000_000_000_000_000_000_000
+				1_000_000_000_000_000_000_000,
+				GetExchangeFee::get()
 			),
 			3_140_495_867_768_595_041_323
 		);
@@ -1046,7 +1220,8 @@ fn calculate_amount_for_big_number_work() {
 			DexModule::get_target_amount(
 				171_000_000_000_000_000_000_000,
 				56_000_000_000_000_000_000_000,
-				3_140_495_867_768_595_041_323
+				3_140_495_867_768_595_041_323,
+				GetExchangeFee::get()
 			),
 			1_000_000_000_000_000_000_000
 		);
@@ -1131,9 +1306,9 @@ fn _swap_by_path_work() {
 
 			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (50000, 10000));
 			assert_eq!(DexModule::get_liquidity(AUSD, BTC), (100000, 10));
-			assert_ok!(DexModule::_swap_by_path(&[DOT, AUSD], &[10000, 25000]));
+			assert_ok!(DexModule::_swap_by_path(&[DOT, AUSD], &[10000, 25000], true));
 			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (25000, 20000));
-			assert_ok!(DexModule::_swap_by_path(&[DOT, AUSD, BTC], &[100000, 20000, 1]));
+			assert_ok!(DexModule::_swap_by_path(&[DOT, AUSD, BTC], &[100000, 20000, 1], true));
 			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (5000, 120000));
 			assert_eq!(DexModule::get_liquidity(AUSD, BTC), (120000, 9));
 		});
@@ -1942,3 +2117,1142 @@ fn stable_asset_swap_work() {
 		);
 	});
 }
+
+#[test]
+fn create_concentrated_pool_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(
+				DexModule::create_concentrated_pool(Origin::signed(ALICE), AUSD, DOT, 60, 0),
+				BadOrigin
+			);
+			assert_noop!(
+				DexModule::create_concentrated_pool(Origin::signed(ListingOrigin::get()), AUSD, ACA, 60, 0),
+				Error::<Runtime>::MustBeEnabled
+			);
+			assert_noop!(
+				DexModule::create_concentrated_pool(Origin::signed(ListingOrigin::get()), AUSD, DOT, 0, 0),
+				Error::<Runtime>::InvalidTickSpacing
+			);
+			assert_noop!(
+				DexModule::create_concentrated_pool(
+					Origin::signed(ListingOrigin::get()),
+					AUSD,
+					DOT,
+					MAX_TICK_SPACING + 1,
+					0
+				),
+				Error::<Runtime>::InvalidTickSpacing
+			);
+			assert_noop!(
+				DexModule::create_concentrated_pool(
+					Origin::signed(ListingOrigin::get()),
+					AUSD,
+					DOT,
+					60,
+					concentrated::MIN_TICK
+				),
+				Error::<Runtime>::InvalidTick
+			);
+
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				BTC,
+				1_000_000,
+				2_000_000,
+				0,
+				false,
+			));
+			assert_noop!(
+				DexModule::create_concentrated_pool(Origin::signed(ListingOrigin::get()), AUSD, BTC, 60, 0),
+				Error::<Runtime>::LiquidityPoolNotEmpty
+			);
+
+			assert_ok!(DexModule::create_concentrated_pool(
+				Origin::signed(ListingOrigin::get()),
+				DOT,
+				AUSD,
+				60,
+				-23028
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::ConcentratedPoolCreated {
+				trading_pair: AUSDDOTPair::get(),
+				tick_spacing: 60,
+				tick: -23028,
+			}));
+			assert_eq!(
+				DexModule::concentrated_pools(AUSDDOTPair::get()),
+				Some(ConcentratedPool {
+					sqrt_price: U256::from_dec_str("25052894984021797146183221489").unwrap(),
+					tick: -23028,
+					tick_spacing: 60,
+					liquidity: 0,
+					fee_growth_global: Default::default(),
+				})
+			);
+			assert_eq!(DexModule::get_liquidity_pool(AUSD, DOT), (0, 0));
+
+			assert_noop!(
+				DexModule::create_concentrated_pool(Origin::signed(ListingOrigin::get()), AUSD, DOT, 60, 0),
+				Error::<Runtime>::ConcentratedPoolAlreadyExists
+			);
+			assert_noop!(
+				DexModule::add_liquidity(Origin::signed(ALICE), AUSD, DOT, 1_000_000, 2_000_000, 0, false),
+				Error::<Runtime>::ConcentratedPoolAlreadyExists
+			);
+		});
+}
+
+#[test]
+fn open_position_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(DexModule::create_concentrated_pool(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				60,
+				0
+			));
+
+			assert_noop!(
+				DexModule::open_position(Origin::signed(ALICE), AUSD, DOT, -600, 600, 1_000_000, 1_000_000, 0),
+				Error::<Runtime>::PositionNFTClassNotSet
+			);
+			assert_noop!(DexModule::set_position_nft_class(Origin::signed(ALICE), 0), BadOrigin);
+			assert_noop!(
+				DexModule::set_position_nft_class(Origin::signed(ListingOrigin::get()), MUTABLE_POSITION_CLASS),
+				DispatchError::Other("mutable position NFT class")
+			);
+			assert_ok!(DexModule::set_position_nft_class(
+				Origin::signed(ListingOrigin::get()),
+				0
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::PositionNFTClassUpdated { class_id: 0 }));
+
+			assert_noop!(
+				DexModule::open_position(Origin::signed(ALICE), DOT, BTC, -600, 600, 1_000_000, 1_000_000, 0),
+				Error::<Runtime>::ConcentratedPoolNotFound
+			);
+			assert_noop!(
+				DexModule::open_position(Origin::signed(ALICE), AUSD, DOT, -610, 600, 1_000_000, 1_000_000, 0),
+				Error::<Runtime>::InvalidTickRange
+			);
+			assert_noop!(
+				DexModule::open_position(Origin::signed(ALICE), AUSD, DOT, 600, -600, 1_000_000, 1_000_000, 0),
+				Error::<Runtime>::InvalidTickRange
+			);
+			assert_noop!(
+				DexModule::open_position(Origin::signed(ALICE), AUSD, DOT, -600, 600, 1_000_000, 0, 0),
+				Error::<Runtime>::InvalidLiquidityIncrement
+			);
+			assert_noop!(
+				DexModule::open_position(
+					Origin::signed(ALICE),
+					AUSD,
+					DOT,
+					-600,
+					600,
+					1_000_000,
+					1_000_000,
+					33_837_500
+				),
+				Error::<Runtime>::UnacceptableShareIncrement
+			);
+
+			assert_ok!(DexModule::open_position(
+				Origin::signed(ALICE),
+				DOT,
+				AUSD,
+				-600,
+				600,
+				1_000_000,
+				1_000_000,
+				33_837_499
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::PositionOpened {
+				who: ALICE,
+				position_id: 0,
+				trading_pair: AUSDDOTPair::get(),
+				tick_lower: -600,
+				tick_upper: 600,
+				liquidity: 33_837_499,
+				amount_0: 1_000_000,
+				amount_1: 1_000_000,
+			}));
+			assert_eq!(DexModule::next_position_id(), 1);
+			assert_eq!(
+				DexModule::positions(0),
+				Some(Position {
+					trading_pair: AUSDDOTPair::get(),
+					tick_lower: -600,
+					tick_upper: 600,
+					liquidity: 33_837_499,
+					fee_growth_inside_last: Default::default(),
+					tokens_owed: (0, 0),
+					nft: (0, 0),
+				})
+			);
+			assert_eq!(MockPositionNFT::owner(0, 0), Some(ALICE));
+			assert_eq!(
+				DexModule::initialized_ticks(AUSDDOTPair::get()).into_inner(),
+				vec![-600, 600]
+			);
+			assert_eq!(
+				DexModule::concentrated_ticks(AUSDDOTPair::get(), -600),
+				Some(TickInfo {
+					liquidity_gross: 33_837_499,
+					liquidity_net: 33_837_499,
+					fee_growth_outside: Default::default(),
+				})
+			);
+			assert_eq!(
+				DexModule::concentrated_ticks(AUSDDOTPair::get(), 600),
+				Some(TickInfo {
+					liquidity_gross: 33_837_499,
+					liquidity_net: -33_837_499,
+					fee_growth_outside: Default::default(),
+				})
+			);
+			assert_eq!(DexModule::get_liquidity_pool(AUSD, DOT), (33_837_499, 33_837_499));
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 1_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 1_000_000);
+			assert_eq!(Tokens::free_balance(AUSD, &ALICE), 999_999_999_999_000_000);
+		});
+}
+
+#[test]
+fn concentrated_swap_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(DexModule::create_concentrated_pool(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				60,
+				0
+			));
+			assert_ok!(DexModule::set_position_nft_class(
+				Origin::signed(ListingOrigin::get()),
+				0
+			));
+			assert_eq!(
+				DexModule::get_swap_amount(&[AUSD, DOT], SwapLimit::ExactSupply(10_000, 0)),
+				None
+			);
+
+			assert_ok!(DexModule::open_position(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				-600,
+				600,
+				1_000_000,
+				1_000_000,
+				0
+			));
+			assert_eq!(
+				DexModule::get_swap_amount(&[AUSD, DOT], SwapLimit::ExactSupply(10_000, 0)),
+				Some((10_000, 9_897))
+			);
+			assert_eq!(
+				DexModule::swap_with_specific_path(&BOB, &[AUSD, DOT], SwapLimit::ExactSupply(10_000, 0)),
+				Ok((10_000, 9_897))
+			);
+			assert_eq!(DexModule::concentrated_pools(AUSDDOTPair::get()).unwrap().tick, -6);
+
+			assert_eq!(
+				DexModule::get_swap_amount(&[DOT, AUSD], SwapLimit::ExactTarget(Balance::MAX, 5_000)),
+				Some((5_049, 5_000))
+			);
+			assert_eq!(
+				DexModule::swap_with_specific_path(&BOB, &[DOT, AUSD], SwapLimit::ExactTarget(Balance::MAX, 5_000)),
+				Ok((5_049, 5_000))
+			);
+			assert_eq!(DexModule::concentrated_pools(AUSDDOTPair::get()).unwrap().tick, -3);
+
+			// swap through the concentrated liquidity pool and the constant product pool
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				BTC,
+				1_000_000,
+				2_000_000,
+				0,
+				false,
+			));
+			assert_eq!(
+				DexModule::swap_with_specific_path(
+					&BOB,
+					&[DOT, AUSD, BTC],
+					SwapLimit::ExactTarget(Balance::MAX, 10_000)
+				),
+				Ok((5_128, 10_000))
+			);
+			assert_eq!(DexModule::get_liquidity(AUSD, BTC), (1_005_076, 1_990_000));
+			assert_eq!(DexModule::concentrated_pools(AUSDDOTPair::get()).unwrap().tick, 0);
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 2_005_000);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 1_000_280);
+
+			// the liquidity out of the range is not enough
+			assert_noop!(
+				DexModule::swap_with_specific_path(&BOB, &[AUSD, DOT], SwapLimit::ExactSupply(2_000_000, 0)),
+				Error::<Runtime>::InsufficientLiquidity
+			);
+
+			assert_noop!(
+				DexModule::collect_position_fees(Origin::signed(BOB), 0),
+				Error::<Runtime>::NotPositionOwner
+			);
+			assert_noop!(
+				DexModule::collect_position_fees(Origin::signed(ALICE), 1),
+				Error::<Runtime>::PositionNotFound
+			);
+			let alice_ausd = Tokens::free_balance(AUSD, &ALICE);
+			let alice_dot = Tokens::free_balance(DOT, &ALICE);
+			assert_ok!(DexModule::collect_position_fees(Origin::signed(ALICE), 0));
+			System::assert_last_event(Event::DexModule(crate::Event::PositionFeesCollected {
+				who: ALICE,
+				position_id: 0,
+				fee_0: 99,
+				fee_1: 102,
+			}));
+			assert_eq!(Tokens::free_balance(AUSD, &ALICE), alice_ausd + 99);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), alice_dot + 102);
+			assert_eq!(DexModule::positions(0).unwrap().tokens_owed, (0, 0));
+		});
+}
+
+#[test]
+fn range_order_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(DexModule::create_concentrated_pool(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				60,
+				0
+			));
+			assert_ok!(DexModule::set_position_nft_class(
+				Origin::signed(ListingOrigin::get()),
+				0
+			));
+			assert_ok!(DexModule::open_position(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				-600,
+				600,
+				1_000_000,
+				1_000_000,
+				0
+			));
+
+			// the range above the current price only takes AUSD
+			assert_ok!(DexModule::open_position(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				60,
+				120,
+				100_000,
+				0,
+				0
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::PositionOpened {
+				who: ALICE,
+				position_id: 1,
+				trading_pair: AUSDDOTPair::get(),
+				tick_lower: 60,
+				tick_upper: 120,
+				liquidity: 33_485_325,
+				amount_0: 100_000,
+				amount_1: 0,
+			}));
+			assert_eq!(
+				DexModule::initialized_ticks(AUSDDOTPair::get()).into_inner(),
+				vec![-600, 60, 120, 600]
+			);
+
+			// the price crosses the range and fills the range order
+			assert_eq!(
+				DexModule::swap_with_specific_path(&BOB, &[DOT, AUSD], SwapLimit::ExactSupply(400_000, 0)),
+				Ok((400_000, 392_541))
+			);
+			assert_eq!(DexModule::concentrated_pools(AUSDDOTPair::get()).unwrap().tick, 173);
+
+			assert_noop!(
+				DexModule::decrease_position(Origin::signed(BOB), 1, 33_485_325, 0, 0),
+				Error::<Runtime>::NotPositionOwner
+			);
+			assert_noop!(
+				DexModule::decrease_position(Origin::signed(ALICE), 1, 33_485_326, 0, 0),
+				Error::<Runtime>::InvalidLiquidityIncrement
+			);
+			assert_noop!(
+				DexModule::decrease_position(Origin::signed(ALICE), 1, 33_485_325, 1, 0),
+				Error::<Runtime>::UnacceptableLiquidityWithdrawn
+			);
+
+			let alice_ausd = Tokens::free_balance(AUSD, &ALICE);
+			let alice_dot = Tokens::free_balance(DOT, &ALICE);
+			assert_ok!(DexModule::decrease_position(
+				Origin::signed(ALICE),
+				1,
+				33_485_325,
+				0,
+				100_904
+			));
+			System::assert_has_event(Event::DexModule(crate::Event::PositionDecreased {
+				who: ALICE,
+				position_id: 1,
+				liquidity: 33_485_325,
+				amount_0: 0,
+				amount_1: 100_904,
+			}));
+			System::assert_has_event(Event::DexModule(crate::Event::PositionFeesCollected {
+				who: ALICE,
+				position_id: 1,
+				fee_0: 0,
+				fee_1: 1_019,
+			}));
+			System::assert_last_event(Event::DexModule(crate::Event::PositionClosed {
+				who: ALICE,
+				position_id: 1,
+			}));
+			assert_eq!(Tokens::free_balance(AUSD, &ALICE), alice_ausd);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), alice_dot + 100_904 + 1_019);
+			assert_eq!(DexModule::positions(1), None);
+			assert_eq!(MockPositionNFT::owner(0, 1), None);
+			assert_eq!(
+				DexModule::initialized_ticks(AUSDDOTPair::get()).into_inner(),
+				vec![-600, 600]
+			);
+			assert_eq!(DexModule::concentrated_ticks(AUSDDOTPair::get(), 60), None);
+			assert_eq!(
+				DexModule::concentrated_pools(AUSDDOTPair::get()).unwrap().liquidity,
+				33_837_499
+			);
+		});
+}
+
+#[test]
+fn place_limit_order_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let limit_price = ExchangeRate::saturating_from_rational(19, 10);
+
+			assert_noop!(
+				DexModule::place_limit_order(Origin::signed(BOB), AUSD, AUSD, 10_000, limit_price, 100),
+				Error::<Runtime>::InvalidCurrencyId
+			);
+			assert_noop!(
+				DexModule::place_limit_order(Origin::signed(BOB), ACA, AUSD, 10_000, limit_price, 100),
+				Error::<Runtime>::MustBeEnabled
+			);
+			assert_noop!(
+				DexModule::place_limit_order(Origin::signed(BOB), AUSD, DOT, 0, limit_price, 100),
+				Error::<Runtime>::ZeroSupplyAmount
+			);
+			assert_noop!(
+				DexModule::place_limit_order(Origin::signed(BOB), AUSD, DOT, 10_000, ExchangeRate::zero(), 100),
+				Error::<Runtime>::InvalidLimitPrice
+			);
+			assert_noop!(
+				DexModule::place_limit_order(Origin::signed(BOB), AUSD, DOT, 10_000, limit_price, 1),
+				Error::<Runtime>::LimitOrderExpired
+			);
+
+			let bob_ausd = Tokens::free_balance(AUSD, &BOB);
+			assert_ok!(DexModule::place_limit_order(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				10_000,
+				limit_price,
+				100
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::LimitOrderPlaced {
+				order_id: 0,
+				owner: BOB,
+				supply_currency_id: AUSD,
+				target_currency_id: DOT,
+				supply_amount: 10_000,
+				limit_price,
+				expiry: 100,
+			}));
+			assert_eq!(
+				DexModule::limit_orders(0),
+				Some(LimitOrder {
+					owner: BOB,
+					supply_currency_id: AUSD,
+					target_currency_id: DOT,
+					remaining_supply_amount: 10_000,
+					limit_price,
+					expiry: 100,
+				})
+			);
+			assert_eq!(DexModule::next_limit_order_id(), 1);
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), bob_ausd - 10_000);
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 10_000);
+
+			assert_ok!(DexModule::place_limit_order(
+				Origin::signed(BOB),
+				DOT,
+				AUSD,
+				20_000,
+				limit_price,
+				100
+			));
+			assert_eq!(DexModule::account_limit_orders(BOB).into_inner(), vec![0, 1]);
+			assert_eq!(
+				DexModule::open_limit_orders(&BOB)
+					.into_iter()
+					.map(|(order_id, order)| (order_id, order.remaining_supply_amount))
+					.collect::<Vec<_>>(),
+				vec![(0, 10_000), (1, 20_000)]
+			);
+			assert_noop!(
+				DexModule::place_limit_order(Origin::signed(BOB), AUSD, DOT, 10_000, limit_price, 100),
+				Error::<Runtime>::TooManyLimitOrders
+			);
+		});
+}
+
+#[test]
+fn fill_limit_order_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.initialize_added_liquidity_pools(ALICE)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let limit_price = ExchangeRate::saturating_from_rational(19, 10);
+			assert_noop!(
+				DexModule::fill_limit_order(Origin::signed(ALICE), 0),
+				Error::<Runtime>::LimitOrderNotFound
+			);
+
+			assert_ok!(DexModule::place_limit_order(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				10_000,
+				limit_price,
+				100
+			));
+			assert_ok!(DexModule::place_limit_order(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				10_000,
+				ExchangeRate::saturating_from_rational(2, 1),
+				100
+			));
+			assert_noop!(
+				DexModule::fill_limit_order(Origin::signed(ALICE), 1),
+				Error::<Runtime>::LimitOrderNotFillable
+			);
+
+			let bob_dot = Tokens::free_balance(DOT, &BOB);
+			let alice_dot = Tokens::free_balance(DOT, &ALICE);
+			assert_ok!(DexModule::fill_limit_order(Origin::signed(ALICE), 0));
+			System::assert_last_event(Event::DexModule(crate::Event::LimitOrderFilled {
+				order_id: 0,
+				owner: BOB,
+				supply_amount: 10_000,
+				target_amount: 19_409,
+				keeper: Some(ALICE),
+				keeper_fee: 196,
+			}));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (1_010_000, 1_980_395));
+			assert_eq!(Tokens::free_balance(DOT, &BOB), bob_dot + 19_409);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), alice_dot + 196);
+			assert_eq!(DexModule::limit_orders(0), None);
+			assert_eq!(DexModule::account_limit_orders(BOB).into_inner(), vec![1]);
+
+			System::set_block_number(101);
+			assert_noop!(
+				DexModule::fill_limit_order(Origin::signed(ALICE), 1),
+				Error::<Runtime>::LimitOrderExpired
+			);
+		});
+}
+
+#[test]
+fn partially_fill_limit_order_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.initialize_added_liquidity_pools(ALICE)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let limit_price = ExchangeRate::saturating_from_rational(19, 10);
+			assert_ok!(DexModule::place_limit_order(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				100_000,
+				limit_price,
+				100
+			));
+
+			// only the supply amount whose average price is not worse than the limit price is filled
+			assert_ok!(DexModule::fill_limit_order(Origin::signed(ALICE), 0));
+			System::assert_last_event(Event::DexModule(crate::Event::LimitOrderFilled {
+				order_id: 0,
+				owner: BOB,
+				supply_amount: 31_995,
+				target_amount: 60_791,
+				keeper: Some(ALICE),
+				keeper_fee: 614,
+			}));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (1_031_995, 1_938_595));
+			assert_eq!(DexModule::limit_orders(0).unwrap().remaining_supply_amount, 68_005);
+			assert_eq!(DexModule::account_limit_orders(BOB).into_inner(), vec![0]);
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 68_005);
+
+			// the pool price no longer reaches the limit price
+			assert_noop!(
+				DexModule::fill_limit_order(Origin::signed(ALICE), 0),
+				Error::<Runtime>::LimitOrderNotFillable
+			);
+		});
+}
+
+#[test]
+fn cancel_limit_order_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let limit_price = ExchangeRate::saturating_from_rational(19, 10);
+			let bob_ausd = Tokens::free_balance(AUSD, &BOB);
+			assert_ok!(DexModule::place_limit_order(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				10_000,
+				limit_price,
+				100
+			));
+			assert_ok!(DexModule::place_limit_order(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				20_000,
+				limit_price,
+				10
+			));
+
+			assert_noop!(
+				DexModule::cancel_limit_order(Origin::signed(ALICE), 0),
+				Error::<Runtime>::NotLimitOrderOwner
+			);
+			assert_ok!(DexModule::cancel_limit_order(Origin::signed(BOB), 0));
+			System::assert_last_event(Event::DexModule(crate::Event::LimitOrderCancelled {
+				order_id: 0,
+				owner: BOB,
+				refund_amount: 10_000,
+			}));
+			assert_eq!(DexModule::limit_orders(0), None);
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), bob_ausd - 20_000);
+			assert_noop!(
+				DexModule::cancel_limit_order(Origin::signed(BOB), 0),
+				Error::<Runtime>::LimitOrderNotFound
+			);
+
+			// anyone can refund the expired order
+			assert_noop!(
+				DexModule::cancel_limit_order(Origin::signed(ALICE), 1),
+				Error::<Runtime>::NotLimitOrderOwner
+			);
+			System::set_block_number(11);
+			assert_ok!(DexModule::cancel_limit_order(Origin::signed(ALICE), 1));
+			System::assert_last_event(Event::DexModule(crate::Event::LimitOrderExpired {
+				order_id: 1,
+				owner: BOB,
+				refund_amount: 20_000,
+			}));
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), bob_ausd);
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 0);
+			assert!(DexModule::account_limit_orders(BOB).is_empty());
+		});
+}
+
+#[test]
+fn process_limit_orders_on_idle_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.initialize_added_liquidity_pools(ALICE)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let order_weight = <() as WeightInfo>::fill_limit_order();
+			assert_ok!(DexModule::place_limit_order(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				10_000,
+				ExchangeRate::saturating_from_rational(19, 10),
+				100
+			));
+			assert_ok!(DexModule::place_limit_order(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				20_000,
+				ExchangeRate::saturating_from_rational(2, 1),
+				5
+			));
+
+			// not enough weight to process any order
+			assert_eq!(DexModule::on_idle(2, order_weight - 1), 0);
+			assert_eq!(DexModule::open_limit_orders(&BOB).len(), 2);
+
+			// the orders are processed across blocks within the weight
+			assert_eq!(DexModule::on_idle(2, order_weight), order_weight);
+			assert!(!DexModule::last_limit_order_key_iterated().is_empty());
+			assert_eq!(DexModule::on_idle(2, order_weight), order_weight);
+
+			// the fillable order is filled without keeper fee, the other one stays open
+			assert_eq!(DexModule::limit_orders(0), None);
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (1_010_000, 1_980_395));
+			System::assert_has_event(Event::DexModule(crate::Event::LimitOrderFilled {
+				order_id: 0,
+				owner: BOB,
+				supply_amount: 10_000,
+				target_amount: 19_605,
+				keeper: None,
+				keeper_fee: 0,
+			}));
+			assert!(DexModule::limit_orders(1).is_some());
+
+			// the expired order is refunded
+			let bob_ausd = Tokens::free_balance(AUSD, &BOB);
+			System::set_block_number(6);
+			// the iteration restarts from the first order after reaching the end
+			DexModule::on_idle(6, u64::MAX);
+			DexModule::on_idle(6, u64::MAX);
+			System::assert_last_event(Event::DexModule(crate::Event::LimitOrderExpired {
+				order_id: 1,
+				owner: BOB,
+				refund_amount: 20_000,
+			}));
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), bob_ausd + 20_000);
+			assert!(DexModule::open_limit_orders(&BOB).is_empty());
+			assert!(DexModule::last_limit_order_key_iterated().is_empty());
+		});
+}
+
+#[test]
+fn get_best_routes_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			for (currency_id_a, currency_id_b) in [(AUSD, DOT), (AUSD, BTC), (DOT, BTC)] {
+				assert_ok!(DexModule::add_liquidity(
+					Origin::signed(ALICE),
+					currency_id_a,
+					currency_id_b,
+					1_000_000,
+					1_000_000,
+					0,
+					false,
+				));
+			}
+
+			assert_eq!(
+				DexModule::get_best_routes(AUSD, DOT, 300_000, 1),
+				vec![RouteQuote {
+					route: vec![RouteHop::Dex(AUSD, DOT)],
+					supply_amount: 300_000,
+					target_amount: 228_989,
+				}]
+			);
+
+			// splitting the supply amount across the routes without shared pool gets more
+			let split_routes = vec![
+				RouteQuote {
+					route: vec![RouteHop::Dex(AUSD, DOT)],
+					supply_amount: 195_000,
+					target_amount: 161_812,
+				},
+				RouteQuote {
+					route: vec![RouteHop::Dex(AUSD, BTC), RouteHop::Dex(BTC, DOT)],
+					supply_amount: 105_000,
+					target_amount: 85_270,
+				},
+			];
+			assert_eq!(DexModule::get_best_routes(AUSD, DOT, 300_000, 2), split_routes);
+			assert_eq!(DexModule::get_best_routes(AUSD, DOT, 300_000, 5), split_routes);
+
+			// the stable-asset pool gives the best price
+			assert_eq!(
+				DexModule::get_best_routes(BTC, DOT, 100_000, 1),
+				vec![RouteQuote {
+					route: vec![RouteHop::StableAsset(0, 0, 1)],
+					supply_amount: 100_000,
+					target_amount: 100_000,
+				}]
+			);
+
+			assert_eq!(DexModule::get_best_routes(AUSD, ACA, 300_000, 2), vec![]);
+			assert_eq!(DexModule::get_best_routes(AUSD, DOT, 0, 2), vec![]);
+		});
+}
+
+#[test]
+fn swap_with_routes_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			for (currency_id_a, currency_id_b) in [(AUSD, DOT), (AUSD, BTC), (DOT, BTC)] {
+				assert_ok!(DexModule::add_liquidity(
+					Origin::signed(ALICE),
+					currency_id_a,
+					currency_id_b,
+					1_000_000,
+					1_000_000,
+					0,
+					false,
+				));
+			}
+			let direct_route = vec![RouteHop::Dex(AUSD, DOT)];
+			let btc_route = vec![RouteHop::Dex(AUSD, BTC), RouteHop::Dex(BTC, DOT)];
+
+			assert_noop!(
+				DexModule::swap_with_routes(Origin::signed(BOB), AUSD, DOT, vec![], 0),
+				Error::<Runtime>::InvalidRoute
+			);
+			assert_noop!(
+				DexModule::swap_with_routes(
+					Origin::signed(BOB),
+					AUSD,
+					DOT,
+					vec![
+						(direct_route.clone(), 1_000),
+						(btc_route.clone(), 1_000),
+						(direct_route.clone(), 1_000)
+					],
+					0
+				),
+				Error::<Runtime>::TooManyRoutes
+			);
+			assert_noop!(
+				DexModule::swap_with_routes(
+					Origin::signed(BOB),
+					AUSD,
+					DOT,
+					vec![(vec![RouteHop::Dex(AUSD, BTC)], 1_000)],
+					0
+				),
+				Error::<Runtime>::InvalidRoute
+			);
+			assert_noop!(
+				DexModule::swap_with_routes(
+					Origin::signed(BOB),
+					AUSD,
+					DOT,
+					vec![(vec![RouteHop::Dex(DOT, BTC), RouteHop::Dex(BTC, DOT)], 1_000)],
+					0
+				),
+				Error::<Runtime>::InvalidRoute
+			);
+			assert_noop!(
+				DexModule::swap_with_routes(
+					Origin::signed(BOB),
+					AUSD,
+					DOT,
+					vec![(
+						vec![
+							RouteHop::Dex(AUSD, BTC),
+							RouteHop::StableAsset(0, 0, 1),
+							RouteHop::Dex(DOT, BTC)
+						],
+						1_000
+					)],
+					0
+				),
+				Error::<Runtime>::InvalidTradingPathLength
+			);
+			assert_noop!(
+				DexModule::swap_with_routes(
+					Origin::signed(BOB),
+					AUSD,
+					DOT,
+					vec![(direct_route.clone(), 195_000), (btc_route.clone(), 105_000)],
+					247_083
+				),
+				Error::<Runtime>::InsufficientTargetAmount
+			);
+
+			let bob_ausd = Tokens::free_balance(AUSD, &BOB);
+			let bob_dot = Tokens::free_balance(DOT, &BOB);
+			assert_ok!(DexModule::swap_with_routes(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				vec![(direct_route, 195_000), (btc_route, 105_000)],
+				247_082
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::SwapWithRoutes {
+				trader: BOB,
+				supply_currency_id: AUSD,
+				target_currency_id: DOT,
+				supply_amount: 300_000,
+				target_amount: 247_082,
+			}));
+			System::assert_has_event(Event::DexModule(crate::Event::Swap {
+				trader: BOB,
+				path: vec![AUSD, BTC, DOT],
+				liquidity_changes: vec![105_000, 94_161, 85_270],
+			}));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (1_195_000, 838_188));
+			assert_eq!(DexModule::get_liquidity(AUSD, BTC), (1_105_000, 905_839));
+			assert_eq!(DexModule::get_liquidity(DOT, BTC), (914_730, 1_094_161));
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), bob_ausd - 300_000);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), bob_dot + 247_082);
+
+			// the route goes through the stable-asset pool
+			assert_ok!(DexModule::swap_with_routes(
+				Origin::signed(BOB),
+				BTC,
+				DOT,
+				vec![(vec![RouteHop::StableAsset(0, 0, 1)], 100_000)],
+				100_000
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::SwapWithRoutes {
+				trader: BOB,
+				supply_currency_id: BTC,
+				target_currency_id: DOT,
+				supply_amount: 100_000,
+				target_amount: 100_000,
+			}));
+		});
+}
+
+fn borrower_context() -> InvokeContext {
+	InvokeContext {
+		contract: H160::from_low_u64_be(BOB as u64),
+		sender: Default::default(),
+		origin: Default::default(),
+	}
+}
+
+#[test]
+fn flash_swap_repaid_in_same_currency_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				1_000_000,
+				1_000_000,
+				0,
+				false,
+			));
+
+			let bob_ausd = Tokens::free_balance(AUSD, &BOB);
+			assert_eq!(
+				DexModule::do_flash_swap(
+					&BOB,
+					borrower_context(),
+					AUSD,
+					DOT,
+					AUSD,
+					10_000,
+					b"acala".to_vec(),
+					1_000,
+					0
+				),
+				Ok((10_102, 500))
+			);
+			System::assert_last_event(Event::DexModule(crate::Event::FlashSwap {
+				borrower: BOB,
+				borrow_currency_id: AUSD,
+				repay_currency_id: AUSD,
+				borrow_amount: 10_000,
+				repay_amount: 10_102,
+			}));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (1_000_102, 1_000_000));
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), bob_ausd - 102);
+			assert!(!DexModule::flash_swap_locked(AUSDDOTPair::get()));
+
+			let input = FLASH_SWAP_CALLBACK_INPUT.with(|v| v.borrow().clone());
+			assert_eq!(input.len(), 4 + 8 * 32);
+			assert_eq!(input[0..4], [0xbc, 0xbc, 0x5e, 0x55]);
+			assert_eq!(input[16..36], *H160::try_from(AUSD).unwrap().as_bytes());
+			assert_eq!(input[48..68], *H160::try_from(AUSD).unwrap().as_bytes());
+			assert_eq!(input[68..100], to_bytes(10_000u128));
+			assert_eq!(input[100..132], to_bytes(10_102u128));
+			assert_eq!(input[132..164], to_bytes(160u32));
+			assert_eq!(input[164..196], to_bytes(5u32));
+			assert_eq!(input[196..201], *b"acala");
+		});
+}
+
+#[test]
+fn flash_swap_repaid_in_other_currency_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				1_000_000,
+				1_000_000,
+				0,
+				false,
+			));
+
+			let bob_ausd = Tokens::free_balance(AUSD, &BOB);
+			let bob_dot = Tokens::free_balance(DOT, &BOB);
+			assert_eq!(
+				DexModule::do_flash_swap(&BOB, borrower_context(), DOT, AUSD, AUSD, 10_000, vec![], 1_000, 0),
+				Ok((10_204, 500))
+			);
+			System::assert_last_event(Event::DexModule(crate::Event::FlashSwap {
+				borrower: BOB,
+				borrow_currency_id: DOT,
+				repay_currency_id: AUSD,
+				borrow_amount: 10_000,
+				repay_amount: 10_204,
+			}));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (1_010_204, 990_000));
+			assert_eq!(AUSD_DOT_POOL_RECORD.with(|v| *v.borrow()), (1_010_204, 990_000));
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), bob_ausd - 10_204);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), bob_dot + 10_000);
+			assert_eq!(FLASH_SWAP_CALLBACK_INPUT.with(|v| v.borrow().len()), 4 + 7 * 32);
+		});
+}
+
+#[test]
+fn flash_swap_failed() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				1_000_000,
+				1_000_000,
+				0,
+				false,
+			));
+
+			assert_noop!(
+				DexModule::do_flash_swap(&BOB, borrower_context(), AUSD, DOT, BTC, 10_000, vec![], 1_000, 0),
+				Error::<Runtime>::InvalidCurrencyId
+			);
+			assert_noop!(
+				DexModule::do_flash_swap(&BOB, borrower_context(), AUSD, ACA, AUSD, 10_000, vec![], 1_000, 0),
+				Error::<Runtime>::MustBeEnabled
+			);
+			assert_noop!(
+				DexModule::do_flash_swap(&BOB, borrower_context(), AUSD, DOT, AUSD, 1_000_000, vec![], 1_000, 0),
+				Error::<Runtime>::InsufficientLiquidity
+			);
+
+			// the borrower can't repay
+			assert_noop!(
+				DexModule::do_flash_swap(&3, borrower_context(), AUSD, DOT, AUSD, 10_000, vec![], 1_000, 0),
+				orml_tokens::Error::<Runtime>::BalanceTooLow
+			);
+
+			FLASH_SWAP_CALLBACK.with(|v| *v.borrow_mut() = FlashSwapCallback::Revert);
+			assert_noop!(
+				DexModule::do_flash_swap(&BOB, borrower_context(), AUSD, DOT, AUSD, 10_000, vec![], 1_000, 0),
+				Error::<Runtime>::FlashSwapCallbackFailed
+			);
+		});
+}
+
+#[test]
+fn flash_swap_locks_liquidity_pool_during_callback() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				1_000_000,
+				1_000_000,
+				0,
+				false,
+			));
+
+			FLASH_SWAP_CALLBACK.with(|v| *v.borrow_mut() = FlashSwapCallback::Reenter);
+			assert_ok!(DexModule::do_flash_swap(
+				&BOB,
+				borrower_context(),
+				AUSD,
+				DOT,
+				AUSD,
+				10_000,
+				vec![],
+				1_000,
+				0
+			));
+			assert_eq!(
+				REENTRANT_SWAP_RESULT.with(|v| v.borrow().clone()),
+				Some(Err(Error::<Runtime>::LiquidityPoolLocked.into()))
+			);
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (1_000_102, 1_000_000));
+
+			// the liquidity pool is unlocked after the flash swap
+			assert!(!DexModule::flash_swap_locked(AUSDDOTPair::get()));
+			assert_ok!(DexModule::swap_with_exact_supply(
+				Origin::signed(ALICE),
+				vec![AUSD, DOT],
+				1_000,
+				0
+			));
+
+			// the liquidity pool can't be flash swapped again during the callback
+			FlashSwapLocks::<Runtime>::insert(AUSDDOTPair::get(), true);
+			assert_noop!(
+				DexModule::do_flash_swap(&BOB, borrower_context(), DOT, AUSD, AUSD, 10_000, vec![], 1_000, 0),
+				Error::<Runtime>::LiquidityPoolLocked
+			);
+			assert_noop!(
+				DexModule::add_liquidity(Origin::signed(ALICE), AUSD, DOT, 1_000, 1_000, 0, false),
+				Error::<Runtime>::LiquidityPoolLocked
+			);
+		});
+}
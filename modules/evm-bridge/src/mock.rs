@@ -115,8 +115,11 @@ impl module_evm::Config for Runtime {
 
 	type DeveloperDeposit = ConstU128<1000>;
 	type PublicationFee = ConstU128<200>;
+	type ContractInactivityPeriod = ConstU64<100>;
+	type UpgradeDelay = ConstU64<10>;
 	type TreasuryAccount = TreasuryAccount;
 	type FreePublicationOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
+	type VerifierOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
 
 	type Runner = module_evm::runner::stack::Runner<Self>;
 	type FindAuthor = ();
@@ -19,7 +19,10 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::all)]
 
-use primitives::evm::{AccessListItem, CallInfo, CreateInfo, EstimateResourcesRequest};
+use primitives::evm::{
+	tracing::{Trace, TracerConfig},
+	AccessListInfo, AccessListItem, CallInfo, ContractVerification, CreateInfo, EstimateResourcesRequest,
+};
 use sp_core::H160;
 use sp_runtime::{
 	codec::Codec,
@@ -28,6 +31,8 @@ use sp_runtime::{
 use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
+	/// Version 2 adds `create_access_list`, version 3 adds `contract_verification`.
+	#[api_version(3)]
 	pub trait EVMRuntimeRPCApi<Balance> where
 		Balance: Codec + MaybeDisplay + MaybeFromStr,
 	{
@@ -53,5 +58,48 @@ sp_api::decl_runtime_apis! {
 		) -> Result<CreateInfo, sp_runtime::DispatchError>;
 
 		fn get_estimate_resources_request(data: Vec<u8>) -> Result<EstimateResourcesRequest, sp_runtime::DispatchError>;
+
+		/// Estimate a call, or a create if `to` is `None`, and generate its access list and storage deposits.
+		///
+		/// Since version 2.
+		fn create_access_list(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+		) -> Result<AccessListInfo, sp_runtime::DispatchError>;
+
+		/// The verification submitted for the current code of a contract.
+		///
+		/// Since version 3.
+		fn contract_verification(address: H160) -> Option<ContractVerification>;
+	}
+
+	/// Re-execute EVM calls and trace them. Served by runtimes built with the `tracing` feature.
+	pub trait EVMTraceApi<Balance> where
+		Balance: Codec + MaybeDisplay + MaybeFromStr,
+	{
+		/// Trace the extrinsic at `transaction_index` of `extrinsics`, after applying the
+		/// extrinsics before it. The block must be initialized first.
+		fn trace_extrinsic(
+			extrinsics: Vec<Block::Extrinsic>,
+			transaction_index: u32,
+			tracer: TracerConfig,
+		) -> Result<Trace, sp_runtime::DispatchError>;
+
+		/// Trace a call, or a create if `to` is `None`.
+		fn trace_call(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			tracer: TracerConfig,
+		) -> Result<Trace, sp_runtime::DispatchError>;
 	}
 }
@@ -16,10 +16,14 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use primitives::evm::AccessListItem;
+use primitives::evm::{
+	tracing::{OpcodeConfig, TracerConfig},
+	AccessListItem,
+};
 use serde::{Deserialize, Serialize};
-use sp_core::{Bytes, H160, U256};
+use sp_core::{Bytes, H160, H256, U256};
 use sp_rpc::number::NumberOrHex;
+use std::collections::BTreeMap;
 
 /// Call request
 #[derive(Debug, Default, PartialEq, Deserialize, Clone)]
@@ -53,3 +57,88 @@ pub struct EstimateResourcesResponse {
 	/// Adjusted weight fee
 	pub weight_fee: U256,
 }
+
+/// Storage deposit change of a contract
+#[derive(Debug, Eq, PartialEq, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageDepositResponse {
+	/// Contract
+	pub address: H160,
+	/// Change of the storage used by the contract, in bytes
+	pub storage: i32,
+	/// Storage used by the contract after the call, in bytes
+	pub total_storage: u32,
+	/// Deposit reserved for the contract, or refunded to the caller if `storage` is negative
+	pub deposit: U256,
+}
+
+/// CreateAccessList response
+#[derive(Debug, Eq, PartialEq, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAccessListResponse {
+	/// Accessed accounts and storage slots
+	pub access_list: Vec<AccessListItem>,
+	/// Used gas with the access list applied
+	pub gas_used: U256,
+	/// Used storage
+	pub storage: i32,
+	/// Storage deposit changes
+	pub storage_deposits: Vec<StorageDepositResponse>,
+	/// Reason of the failure of the call
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
+/// VerifyContract request
+#[derive(Debug, PartialEq, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyContractRequest {
+	/// Contract
+	pub address: H160,
+	/// Name of the contract, as `<file>:<contract>`
+	pub contract_name: String,
+	/// Contents of the sources, by file name
+	pub sources: BTreeMap<String, String>,
+}
+
+/// VerifyContract response
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyContractResponse {
+	/// Code hash of the submitted verification
+	pub code_hash: H256,
+	/// Whether the sources, or the compiled metadata, match the submitted source hash
+	pub source_matched: bool,
+	/// Whether the sources match and the compiled code matches the code hash
+	pub verified: bool,
+}
+
+/// Trace options of `debug_traceTransaction` and `debug_traceCall`
+#[derive(Debug, Default, PartialEq, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceOptions {
+	/// `callTracer` to trace the call frames, the struct logger otherwise
+	pub tracer: Option<String>,
+	/// Do not record the stack of the struct logs
+	pub disable_stack: Option<bool>,
+	/// Do not record the storage of the struct logs
+	pub disable_storage: Option<bool>,
+	/// Record the memory of the struct logs
+	pub enable_memory: Option<bool>,
+}
+
+impl TraceOptions {
+	/// The tracer to run, `None` if the tracer is not supported.
+	pub fn tracer_config(&self) -> Option<TracerConfig> {
+		match self.tracer.as_deref() {
+			None => Some(TracerConfig::OpcodeTracer(OpcodeConfig {
+				disable_stack: self.disable_stack.unwrap_or_default(),
+				disable_storage: self.disable_storage.unwrap_or_default(),
+				enable_memory: self.enable_memory.unwrap_or_default(),
+			})),
+			Some("callTracer") => Some(TracerConfig::CallTracer),
+			Some(_) => None,
+		}
+	}
+}
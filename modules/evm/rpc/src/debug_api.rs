@@ -0,0 +1,45 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! EVM debug rpc interface.
+
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use primitives::evm::tracing::Trace;
+
+pub use rpc_impl_EVMDebugApi::gen_server::EVMDebugApi as EVMDebugApiServer;
+
+use crate::call_request::{CallRequest, TraceOptions};
+
+/// EVM debug rpc interface.
+#[rpc(server)]
+pub trait EVMDebugApi<BlockHash> {
+	/// Re-execute the extrinsic `extrinsic_hash` of the block `block_hash` and trace its EVM
+	/// execution.
+	#[rpc(name = "debug_traceTransaction")]
+	fn trace_transaction(
+		&self,
+		extrinsic_hash: BlockHash,
+		block_hash: BlockHash,
+		options: Option<TraceOptions>,
+	) -> Result<Trace>;
+
+	/// Trace a call executed on top of the state of the block `at`.
+	#[rpc(name = "debug_traceCall")]
+	fn trace_call(&self, _: CallRequest, options: Option<TraceOptions>, at: Option<BlockHash>) -> Result<Trace>;
+}
@@ -24,7 +24,9 @@ use sp_core::{Bytes, H160};
 
 pub use rpc_impl_EVMApi::gen_server::EVMApi as EVMApiServer;
 
-use crate::call_request::{CallRequest, EstimateResourcesResponse};
+use crate::call_request::{
+	CallRequest, CreateAccessListResponse, EstimateResourcesResponse, VerifyContractRequest, VerifyContractResponse,
+};
 
 /// EVM rpc interface.
 #[rpc(server)]
@@ -41,4 +43,14 @@ pub trait EVMApi<BlockHash> {
 		unsigned_extrinsic: Bytes,
 		at: Option<BlockHash>,
 	) -> Result<EstimateResourcesResponse>;
+
+	/// Generate the access list of a call, with the storage deposit it reserves for each contract.
+	#[rpc(name = "evm_createAccessList", alias("eth_createAccessList"))]
+	fn create_access_list(&self, _: CallRequest, at: Option<BlockHash>) -> Result<CreateAccessListResponse>;
+
+	/// Recompile the sources of a contract with the local solc of the submitted compiler version,
+	/// and check them against the verification submitted for the current code of the contract.
+	/// The result is recorded on-chain by the verifier origin.
+	#[rpc(name = "evm_verifyContract")]
+	fn verify_contract(&self, _: VerifyContractRequest, at: Option<BlockHash>) -> Result<VerifyContractResponse>;
 }
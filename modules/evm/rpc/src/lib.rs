@@ -22,27 +22,34 @@ use frame_support::log;
 use jsonrpc_core::{Error, ErrorCode, Result, Value};
 use pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi;
 use rustc_hex::ToHex;
+use sc_client_api::BlockBackend;
 use sc_rpc_api::DenyUnsafe;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
-use sp_core::{Bytes, Decode, H160, U256};
+use sp_core::{keccak_256, Bytes, Decode, H160, H256, U256};
 use sp_rpc::number::NumberOrHex;
 use sp_runtime::{
 	codec::Codec,
 	generic::BlockId,
-	traits::{self, Block as BlockT, MaybeDisplay, MaybeFromStr},
-	SaturatedConversion,
+	traits::{self, Block as BlockT, Hash, Header as HeaderT, MaybeDisplay, MaybeFromStr},
 };
 use std::{marker::PhantomData, sync::Arc};
 
-use call_request::{CallRequest, EstimateResourcesResponse};
+use call_request::{
+	CallRequest, CreateAccessListResponse, EstimateResourcesResponse, StorageDepositResponse, TraceOptions,
+	VerifyContractRequest, VerifyContractResponse,
+};
 pub use module_evm::{ExitError, ExitReason};
-pub use module_evm_rpc_runtime_api::EVMRuntimeRPCApi;
+pub use module_evm_rpc_runtime_api::{EVMRuntimeRPCApi, EVMTraceApi};
+use primitives::evm::{decode_revert_message, tracing::Trace};
 
+pub use crate::debug_api::{EVMDebugApi as EVMDebugApiT, EVMDebugApiServer};
 pub use crate::evm_api::{EVMApi as EVMApiT, EVMApiServer};
 
 mod call_request;
+mod debug_api;
 mod evm_api;
+mod verifier;
 
 fn internal_err<T: ToString>(message: T) -> Error {
 	Error {
@@ -88,36 +95,17 @@ fn error_on_execution_failure(reason: &ExitReason, data: &[u8]) -> Result<()> {
 	}
 }
 
-fn decode_revert_message(data: &[u8]) -> Option<String> {
-	// A minimum size of error function selector (4) + offset (32) + string length
-	// (32) should contain a utf-8 encoded revert reason.
-	let msg_start: usize = 68;
-	if data.len() > msg_start {
-		let message_len = U256::from(&data[36..msg_start]).saturated_into::<usize>();
-		let msg_end = msg_start.checked_add(message_len)?;
-
-		if data.len() < msg_end {
-			return None;
-		}
-		let body: &[u8] = &data[msg_start..msg_end];
-		if let Ok(reason) = std::str::from_utf8(body) {
-			return Some(reason.to_string());
-		}
-	}
-	None
-}
-
 pub struct EVMApi<B, C, Balance> {
 	client: Arc<C>,
-	_deny_unsafe: DenyUnsafe,
+	deny_unsafe: DenyUnsafe,
 	_marker: PhantomData<(B, Balance)>,
 }
 
 impl<B, C, Balance> EVMApi<B, C, Balance> {
-	pub fn new(client: Arc<C>, _deny_unsafe: DenyUnsafe) -> Self {
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
 		Self {
 			client,
-			_deny_unsafe,
+			deny_unsafe,
 			_marker: Default::default(),
 		}
 	}
@@ -442,29 +430,284 @@ where
 			weight_fee: adjusted_weight_fee.into(),
 		})
 	}
+
+	fn create_access_list(
+		&self,
+		request: CallRequest,
+		at: Option<<B as BlockT>::Hash>,
+	) -> Result<CreateAccessListResponse> {
+		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		log::debug!(target: "evm", "rpc create access list, request: {:?}", request);
+
+		let api = self.client.runtime_api();
+		let supported = api
+			.has_api_with::<dyn EVMRuntimeRPCApi<B, Balance>, _>(&BlockId::Hash(hash), |version| version >= 2)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+		if !supported {
+			return Err(internal_err("create access list is not supported by the runtime"));
+		}
+
+		let CallRequest {
+			from,
+			to,
+			gas_limit,
+			storage_limit,
+			value,
+			data,
+			access_list,
+		} = request;
+
+		let gas_limit = gas_limit.unwrap_or(MAX_GAS_LIMIT);
+		let storage_limit = storage_limit.unwrap_or(MAX_STROAGE_LIMIT);
+		let data = data.map(|d| d.0).unwrap_or_default();
+
+		let balance_value = if let Some(value) = value {
+			to_u128(value).and_then(|v| TryInto::<Balance>::try_into(v).map_err(|_| ()))
+		} else {
+			Ok(Default::default())
+		};
+
+		let balance_value = balance_value.map_err(|_| Error {
+			code: ErrorCode::InvalidParams,
+			message: format!("Invalid parameter value: {:?}", value),
+			data: None,
+		})?;
+
+		let info = api
+			.create_access_list(
+				&BlockId::Hash(hash),
+				from.unwrap_or_default(),
+				to,
+				data,
+				balance_value,
+				gas_limit,
+				storage_limit,
+				access_list,
+			)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+
+		log::debug!(
+			target: "evm",
+			"rpc create access list, info.exit_reason: {:?}, info.access_list: {:?}",
+			info.exit_reason, info.access_list,
+		);
+
+		Ok(CreateAccessListResponse {
+			access_list: info.access_list,
+			gas_used: info.used_gas,
+			storage: info.used_storage,
+			storage_deposits: info
+				.storage_deposits
+				.into_iter()
+				.map(|deposit| StorageDepositResponse {
+					address: deposit.address,
+					storage: deposit.storage,
+					total_storage: deposit.total_storage,
+					deposit: deposit.deposit.into(),
+				})
+				.collect(),
+			error: error_on_execution_failure(&info.exit_reason, &info.output)
+				.err()
+				.map(|err| err.message),
+		})
+	}
+
+	fn verify_contract(
+		&self,
+		request: VerifyContractRequest,
+		at: Option<<B as BlockT>::Hash>,
+	) -> Result<VerifyContractResponse> {
+		// runs the local solc
+		self.deny_unsafe.check_if_safe()?;
+
+		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		log::debug!(target: "evm", "rpc verify contract, request: {:?}", request);
+
+		let api = self.client.runtime_api();
+		let supported = api
+			.has_api_with::<dyn EVMRuntimeRPCApi<B, Balance>, _>(&BlockId::Hash(hash), |version| version >= 3)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+		if !supported {
+			return Err(internal_err("contract verification is not supported by the runtime"));
+		}
+
+		let verification = api
+			.contract_verification(&BlockId::Hash(hash), request.address)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.ok_or_else(|| Error {
+				code: ErrorCode::InvalidParams,
+				message: format!("No verification submitted for the code of {:?}", request.address),
+				data: None,
+			})?;
+		let compiler_version = String::from_utf8(verification.compiler_version)
+			.map_err(|_| internal_err("invalid compiler version of the verification"))?;
+
+		let compiled = verifier::compile(
+			&compiler_version,
+			&verification.settings,
+			&request.sources,
+			&request.contract_name,
+		)
+		.map_err(internal_err)?;
+
+		// the source hash is the hash of the metadata, or of the source if there is only one
+		let source_hash = verification.source_hash.to_fixed_bytes();
+		let source_matched = keccak_256(compiled.metadata.as_bytes()) == source_hash
+			|| (request.sources.len() == 1
+				&& request
+					.sources
+					.values()
+					.any(|source| keccak_256(source.as_bytes()) == source_hash));
+		let verified = source_matched && H256::from(keccak_256(&compiled.code)) == verification.code_hash;
+
+		log::debug!(
+			target: "evm",
+			"rpc verify contract, address: {:?}, source_matched: {:?}, verified: {:?}",
+			request.address, source_matched, verified,
+		);
+
+		Ok(VerifyContractResponse {
+			code_hash: verification.code_hash,
+			source_matched,
+			verified,
+		})
+	}
+}
+
+pub struct EVMDebugApi<B, C, Balance> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+	_marker: PhantomData<(B, Balance)>,
 }
 
-#[test]
-fn decode_revert_message_should_work() {
-	use sp_core::bytes::from_hex;
-	assert_eq!(decode_revert_message(&[]), None);
-
-	let data = from_hex("0x8c379a00000000000000000000000000000000000000000000000000000000000000020").unwrap();
-	assert_eq!(decode_revert_message(&data), None);
-
-	let data = from_hex("0x8c379a00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000d6572726f72206d65737361676").unwrap();
-	assert_eq!(decode_revert_message(&data), None);
-
-	let data = from_hex("0x8c379a00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000d6572726f72206d65737361676500000000000000000000000000000000000000").unwrap();
-	assert_eq!(decode_revert_message(&data), Some("error message".into()));
-
-	// ensures we protect against msg_start + message_len overflow
-	let data = from_hex("0x9850188c1837189a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000018d618571827182618f718220618d6185718371836161876").unwrap();
-	assert_eq!(decode_revert_message(&data), None);
-	// ensures we protect against msg_start + message_len overflow
-	let data = from_hex("0x9860189818501818188c181818371818189a00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000181818d6181818571818182718181826181818f71818182206181818d61818185718181837181818361618181876").unwrap();
-	assert_eq!(decode_revert_message(&data), None);
-	// ensures we protect against msg_start + message_len overflow
-	let data = from_hex("0x98640818c3187918a0000000000000000000000000000000000000000000000000000000000000001820000000000000000000000000000000000000000000000000000000000000000d186518721872186f18721820186d18651873187318611867186500000000000000000000000000000000000000").unwrap();
-	assert_eq!(decode_revert_message(&data), None);
+impl<B, C, Balance> EVMDebugApi<B, C, Balance> {
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		Self {
+			client,
+			deny_unsafe,
+			_marker: Default::default(),
+		}
+	}
+}
+
+fn invalid_params<T: ToString>(message: T) -> Error {
+	Error {
+		code: ErrorCode::InvalidParams,
+		message: message.to_string(),
+		data: None,
+	}
+}
+
+impl<B, C, Balance> EVMDebugApiT<<B as BlockT>::Hash> for EVMDebugApi<B, C, Balance>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B> + Send + Sync + 'static,
+	C::Api: EVMTraceApi<B, Balance>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
+{
+	fn trace_transaction(
+		&self,
+		extrinsic_hash: <B as BlockT>::Hash,
+		block_hash: <B as BlockT>::Hash,
+		options: Option<TraceOptions>,
+	) -> Result<Trace> {
+		// re-executing a block is expensive
+		self.deny_unsafe.check_if_safe()?;
+
+		let tracer = options
+			.unwrap_or_default()
+			.tracer_config()
+			.ok_or_else(|| invalid_params("Invalid parameter options, unsupported tracer"))?;
+
+		let block_id = BlockId::Hash(block_hash);
+		let header = self
+			.client
+			.header(block_id)
+			.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+			.ok_or_else(|| invalid_params(format!("Unknown block: {:?}", block_hash)))?;
+		let extrinsics = self
+			.client
+			.block_body(&block_id)
+			.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+			.ok_or_else(|| invalid_params(format!("Unknown block body: {:?}", block_hash)))?;
+		let transaction_index = extrinsics
+			.iter()
+			.position(|extrinsic| <<B as BlockT>::Header as HeaderT>::Hashing::hash_of(extrinsic) == extrinsic_hash)
+			.ok_or_else(|| invalid_params(format!("Unknown extrinsic: {:?}", extrinsic_hash)))?;
+
+		log::debug!(
+			target: "evm",
+			"rpc trace transaction, block: {:?}, index: {:?}",
+			block_hash, transaction_index,
+		);
+
+		// replay the block on top of its parent
+		let parent_id = BlockId::Hash(*header.parent_hash());
+		let api = self.client.runtime_api();
+		api.initialize_block(&parent_id, &header)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+		api.trace_extrinsic(&parent_id, extrinsics, transaction_index as u32, tracer)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))
+	}
+
+	fn trace_call(
+		&self,
+		request: CallRequest,
+		options: Option<TraceOptions>,
+		at: Option<<B as BlockT>::Hash>,
+	) -> Result<Trace> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		let tracer = options
+			.unwrap_or_default()
+			.tracer_config()
+			.ok_or_else(|| invalid_params("Invalid parameter options, unsupported tracer"))?;
+
+		log::debug!(target: "evm", "rpc trace call, request: {:?}", request);
+
+		let CallRequest {
+			from,
+			to,
+			gas_limit,
+			storage_limit,
+			value,
+			data,
+			access_list,
+		} = request;
+
+		let gas_limit = gas_limit.unwrap_or(MAX_GAS_LIMIT);
+		let storage_limit = storage_limit.unwrap_or(MAX_STROAGE_LIMIT);
+		let data = data.map(|d| d.0).unwrap_or_default();
+
+		let balance_value = if let Some(value) = value {
+			to_u128(value).and_then(|v| TryInto::<Balance>::try_into(v).map_err(|_| ()))
+		} else {
+			Ok(Default::default())
+		};
+
+		let balance_value =
+			balance_value.map_err(|_| invalid_params(format!("Invalid parameter value: {:?}", value)))?;
+
+		self.client
+			.runtime_api()
+			.trace_call(
+				&BlockId::Hash(hash),
+				from.unwrap_or_default(),
+				to,
+				data,
+				balance_value,
+				gas_limit,
+				storage_limit,
+				access_list,
+				tracer,
+			)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))
+	}
 }
@@ -0,0 +1,183 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Recompile contract sources with a locally installed `solc`, to verify the submitted
+//! verifications of contracts.
+//!
+//! The `solc` binary of a compiler version `<version>+commit.<commit>` is looked up as
+//! `solc-<version>` in `PATH`, then as `solc` if it reports the same version. Contracts with
+//! immutables are not supported, their deployed code differs from the compiled one.
+
+use rustc_hex::FromHex;
+use serde_json::{json, Map, Value};
+use std::{
+	collections::BTreeMap,
+	io::Write,
+	process::{Command, Stdio},
+};
+
+/// The output of the compilation of a contract.
+#[derive(Debug, PartialEq)]
+pub struct CompiledContract {
+	/// The deployed code
+	pub code: Vec<u8>,
+	/// The metadata, which references the sources by their hashes
+	pub metadata: String,
+}
+
+/// Compile the contract `contract_name`, as `<file>:<contract>`, of `sources` with the solc of
+/// `version` and the `settings` of the solc standard JSON input.
+pub fn compile(
+	version: &str,
+	settings: &[u8],
+	sources: &BTreeMap<String, String>,
+	contract_name: &str,
+) -> Result<CompiledContract, String> {
+	let (file, name) = contract_name
+		.rsplit_once(':')
+		.ok_or("invalid contract name, expected <file>:<contract>")?;
+	let input = standard_json_input(settings, sources, file, name)?;
+
+	let mut child = Command::new(find_solc(version)?)
+		.arg("--standard-json")
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()
+		.map_err(|e| format!("failed to run solc: {}", e))?;
+	child
+		.stdin
+		.take()
+		.expect("stdin is piped; qed")
+		.write_all(input.to_string().as_bytes())
+		.map_err(|e| format!("failed to write the solc input: {}", e))?;
+	let output = child
+		.wait_with_output()
+		.map_err(|e| format!("failed to run solc: {}", e))?;
+	let output: Value = serde_json::from_slice(&output.stdout).map_err(|e| format!("invalid solc output: {}", e))?;
+
+	if let Some(error) = output["errors"]
+		.as_array()
+		.and_then(|errors| errors.iter().find(|error| error["severity"] == "error"))
+	{
+		return Err(format!(
+			"compilation failed: {}",
+			error["formattedMessage"].as_str().unwrap_or_default()
+		));
+	}
+
+	let contract = &output["contracts"][file][name];
+	let code = contract["evm"]["deployedBytecode"]["object"]
+		.as_str()
+		.ok_or_else(|| format!("contract {} not found in the sources", contract_name))?;
+	let metadata = contract["metadata"]
+		.as_str()
+		.ok_or_else(|| format!("no metadata for contract {}", contract_name))?;
+
+	Ok(CompiledContract {
+		code: code.from_hex().map_err(|e| format!("invalid deployed code: {}", e))?,
+		metadata: metadata.to_string(),
+	})
+}
+
+/// The solc standard JSON input, selecting the deployed code and the metadata of the contract.
+fn standard_json_input(
+	settings: &[u8],
+	sources: &BTreeMap<String, String>,
+	file: &str,
+	name: &str,
+) -> Result<Value, String> {
+	let mut settings: Value = if settings.is_empty() {
+		json!({})
+	} else {
+		serde_json::from_slice(settings).map_err(|e| format!("invalid settings: {}", e))?
+	};
+	settings
+		.as_object_mut()
+		.ok_or("invalid settings, expected an object")?
+		.insert(
+			"outputSelection".to_string(),
+			json!({ file: { name: ["evm.deployedBytecode.object", "metadata"] } }),
+		);
+
+	let sources = sources
+		.iter()
+		.map(|(file, content)| (file.clone(), json!({ "content": content })))
+		.collect::<Map<_, _>>();
+
+	Ok(json!({
+		"language": "Solidity",
+		"sources": sources,
+		"settings": settings,
+	}))
+}
+
+/// The solc binary reporting `version`.
+fn find_solc(version: &str) -> Result<String, String> {
+	let release = version.split('+').next().unwrap_or(version);
+	[format!("solc-{}", release), "solc".to_string()]
+		.into_iter()
+		.find(|bin| {
+			Command::new(bin).arg("--version").output().map_or(false, |output| {
+				output.status.success() && String::from_utf8_lossy(&output.stdout).contains(version)
+			})
+		})
+		.ok_or_else(|| format!("solc {} is not installed", version))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn standard_json_input_works() {
+		let sources = BTreeMap::from([("Test.sol".to_string(), "contract Test {}".to_string())]);
+		let input = standard_json_input(
+			br#"{"optimizer":{"enabled":true,"runs":200},"outputSelection":{"*":{"*":["abi"]}}}"#,
+			&sources,
+			"Test.sol",
+			"Test",
+		)
+		.unwrap();
+		assert_eq!(
+			input,
+			json!({
+				"language": "Solidity",
+				"sources": { "Test.sol": { "content": "contract Test {}" } },
+				"settings": {
+					"optimizer": { "enabled": true, "runs": 200 },
+					"outputSelection": { "Test.sol": { "Test": ["evm.deployedBytecode.object", "metadata"] } },
+				},
+			})
+		);
+
+		assert_eq!(
+			standard_json_input(b"", &sources, "Test.sol", "Test").unwrap()["settings"],
+			json!({ "outputSelection": { "Test.sol": { "Test": ["evm.deployedBytecode.object", "metadata"] } } })
+		);
+		assert!(standard_json_input(b"[]", &sources, "Test.sol", "Test").is_err());
+	}
+
+	#[test]
+	fn compile_rejects_invalid_contract_name() {
+		assert_eq!(
+			compile("0.8.2+commit.661d1103", b"", &BTreeMap::new(), "Test"),
+			Err("invalid contract name, expected <file>:<contract>".to_string())
+		);
+	}
+}
@@ -22,11 +22,19 @@
 pub mod mock;
 
 use crate::{
-	code_hash, evm::Runtime as EVMRuntime, module::*, runner::Runner, Context, StackExecutor, StackSubstateMetadata,
-	SubstrateStackState,
+	code_hash,
+	evm::Runtime as EVMRuntime,
+	module::*,
+	precompiles::{
+		Bls12381G1Add, Bls12381G1Msm, Bls12381G2Add, Bls12381G2Msm, Bls12381MapG1, Bls12381MapG2, Bls12381Pairing,
+		P256Verify, Precompile,
+	},
+	runner::Runner,
+	Context, ExitSucceed, StackExecutor, StackSubstateMetadata, SubstrateStackState,
 };
 use frame_support::{assert_ok, BoundedVec};
 use hex::FromHex;
+use hex_literal::hex;
 use mock::*;
 use module_support::mocks::MockAddressMapping;
 use module_support::AddressMapping;
@@ -244,6 +252,142 @@ evm_create!(ballot_deploy);
 evm_call!(ballot_delegate);
 evm_call!(ballot_vote);
 
+fn precompile_context() -> Context {
+	Context {
+		address: Default::default(),
+		caller: Default::default(),
+		apparent_value: Default::default(),
+	}
+}
+
+fn bls12_381_g1_add(b: &mut Bencher) {
+	// G + G
+	let input = hex! {"
+		0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb
+		0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1
+		0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb
+		0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1
+	"};
+
+	let resp = b
+		.bench(|| Bls12381G1Add::execute(&input, None, &precompile_context(), false))
+		.unwrap();
+	assert_eq!(resp.exit_status, ExitSucceed::Returned);
+}
+
+fn bls12_381_g1_msm(b: &mut Bencher) {
+	// 2 * G + 1 * G
+	let input = hex! {"
+		0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb
+		0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1
+		00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0f
+		c3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4
+		fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e10000000000000000000000000000000000000000000000000000000000000001
+	"};
+
+	let resp = b
+		.bench(|| Bls12381G1Msm::execute(&input, None, &precompile_context(), false))
+		.unwrap();
+	assert_eq!(resp.exit_status, ExitSucceed::Returned);
+}
+
+fn bls12_381_g2_add(b: &mut Bencher) {
+	// G + G
+	let input = hex! {"
+		00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8
+		0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e
+		000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801
+		000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be
+		00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8
+		0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e
+		000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801
+		000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be
+	"};
+
+	let resp = b
+		.bench(|| Bls12381G2Add::execute(&input, None, &precompile_context(), false))
+		.unwrap();
+	assert_eq!(resp.exit_status, ExitSucceed::Returned);
+}
+
+fn bls12_381_g2_msm(b: &mut Bencher) {
+	// 2 * G + 1 * G
+	let input = hex! {"
+		00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8
+		0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e
+		000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801
+		000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be
+		000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000024aa2b2f08f0a91260805272dc51051
+		c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb80000000000000000000000000000000013e02b6052719f607dacd3a088274f65
+		596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351a
+		adfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99
+		cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be0000000000000000000000000000000000000000000000000000000000000001
+	"};
+
+	let resp = b
+		.bench(|| Bls12381G2Msm::execute(&input, None, &precompile_context(), false))
+		.unwrap();
+	assert_eq!(resp.exit_status, ExitSucceed::Returned);
+}
+
+fn bls12_381_pairing(b: &mut Bencher) {
+	// e(G1, G2) * e(-G1, G2)
+	let input = hex! {"
+		0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb
+		0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1
+		00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8
+		0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e
+		000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801
+		000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be
+		0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb
+		00000000000000000000000000000000114d1d6855d545a8aa7d76c8cf2e21f267816aef1db507c96655b9d5caac42364e6f38ba0ecb751bad54dcd6b939c2ca
+		00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8
+		0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e
+		000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801
+		000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be
+	"};
+
+	let resp = b
+		.bench(|| Bls12381Pairing::execute(&input, None, &precompile_context(), false))
+		.unwrap();
+	assert_eq!(resp.output[31], 1);
+}
+
+fn bls12_381_map_g1(b: &mut Bencher) {
+	let mut input = [0u8; 64];
+	input[16..].copy_from_slice(&[0x11u8; 48]);
+
+	let resp = b
+		.bench(|| Bls12381MapG1::execute(&input, None, &precompile_context(), false))
+		.unwrap();
+	assert_eq!(resp.exit_status, ExitSucceed::Returned);
+}
+
+fn bls12_381_map_g2(b: &mut Bencher) {
+	let mut input = [0u8; 128];
+	input[16..64].copy_from_slice(&[0x11u8; 48]);
+	input[80..].copy_from_slice(&[0x12u8; 48]);
+
+	let resp = b
+		.bench(|| Bls12381MapG2::execute(&input, None, &precompile_context(), false))
+		.unwrap();
+	assert_eq!(resp.exit_status, ExitSucceed::Returned);
+}
+
+fn p256_verify(b: &mut Bencher) {
+	// hash | r | s | x | y
+	let input = hex! {"
+		4cee90eb86eaa050036147a12d49004b6b9c72bd725d39d4785011fe190f0b4da73bd4903f0ce3b639bbbf6e8e80d16931ff4bcf5993d58468e8fb19086e8cac
+		36dbcd03009df8c59286b162af3bd7fcc0450c9aa81be5d10d312af6c66b1d604aebd3099c618202fcfe16ae7770b0c49ab5eadf74b754204a3bb6060e44eff3
+		7618b065f9832de4ca6ca971a7a1adc826d0f7c00181a5fb2ddf79ae00b4e10e
+	"};
+
+	let resp = b
+		.bench(|| P256Verify::execute(&input, None, &precompile_context(), false))
+		.unwrap();
+	assert_eq!(resp.output[31], 1);
+}
+
 benches!(
 	empty_deploy,
 	empty_noop,
@@ -257,5 +401,13 @@ benches!(
 	storage_store_many,
 	ballot_deploy,
 	ballot_delegate,
-	ballot_vote
+	ballot_vote,
+	bls12_381_g1_add,
+	bls12_381_g1_msm,
+	bls12_381_g2_add,
+	bls12_381_g2_msm,
+	bls12_381_pairing,
+	bls12_381_map_g1,
+	bls12_381_map_g2,
+	p256_verify
 );
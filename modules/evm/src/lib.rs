@@ -37,7 +37,7 @@ use frame_support::{
 	pallet_prelude::*,
 	parameter_types,
 	traits::{
-		BalanceStatus, Currency, EnsureOneOf, EnsureOrigin, ExistenceRequirement, FindAuthor, Get,
+		BalanceStatus, Currency, EnsureOneOf, EnsureOrigin, ExistenceRequirement, FindAuthor, Get, IsSubType,
 		NamedReservableCurrency, OnKilledAccount,
 	},
 	transactional,
@@ -52,15 +52,15 @@ pub use module_evm_utility::{
 	Account,
 };
 pub use module_support::{
-	AddressMapping, DispatchableTask, EVMManager, ExecutionMode, IdleScheduler, InvokeContext, TransactionPayment,
-	EVM as EVMTrait,
+	AddressMapping, DispatchableTask, EVMManager, ExecutionMode, IdleScheduler, InvokeContext, TransactionFeeSponsor,
+	TransactionPayment, EVM as EVMTrait,
 };
 pub use orml_traits::{currency::TransferAll, MultiCurrency};
 use primitive_types::{H160, H256, U256};
 pub use primitives::{
 	evm::{
-		convert_decimals_from_evm, convert_decimals_to_evm, CallInfo, CreateInfo, EvmAddress, ExecutionInfo, Vicinity,
-		MIRRORED_NFT_ADDRESS_START, MIRRORED_TOKENS_ADDRESS_START,
+		convert_decimals_from_evm, convert_decimals_to_evm, CallInfo, ContractVerification, CreateInfo, EvmAddress,
+		ExecutionInfo, VerificationStatus, Vicinity, MIRRORED_NFT_ADDRESS_START, MIRRORED_TOKENS_ADDRESS_START,
 	},
 	task::TaskResult,
 	Balance, CurrencyId, ReserveIdentifier,
@@ -77,6 +77,7 @@ use sp_runtime::{
 };
 use sp_std::{cmp, collections::btree_map::BTreeMap, fmt::Debug, marker::PhantomData, prelude::*};
 
+pub mod migrations;
 pub mod precompiles;
 pub mod runner;
 
@@ -98,6 +99,8 @@ pub type NegativeImbalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
 pub const RESERVE_ID_STORAGE_DEPOSIT: ReserveIdentifier = ReserveIdentifier::EvmStorageDeposit;
 pub const RESERVE_ID_DEVELOPER_DEPOSIT: ReserveIdentifier = ReserveIdentifier::EvmDeveloperDeposit;
+pub const RESERVE_ID_SPONSOR_BUDGET: ReserveIdentifier = ReserveIdentifier::EvmSponsorBudget;
+pub const RESERVE_ID_VERIFICATION_DEPOSIT: ReserveIdentifier = ReserveIdentifier::EvmVerificationDeposit;
 
 // Initially based on London hard fork configuration.
 static ACALA_CONFIG: EvmConfig = EvmConfig {
@@ -153,6 +156,19 @@ fn call_weight<T: Config>(gas: u64) -> Weight {
 		.saturating_add(T::GasToWeight::convert(gas.saturating_sub(BASE_CALL_GAS)))
 }
 
+/// Helper method to calculate the weight `batch_call` takes besides its calls: mapping the caller
+/// and reserving the storage deposit shared by the batch.
+fn batch_call_base_weight<T: Config>() -> Weight {
+	<T as frame_system::Config>::DbWeight::get().reads_writes(2, 2)
+}
+
+/// Helper method to calculate `batch_call` weight.
+fn batch_call_weight<T: Config>(calls: &[BatchCallItem<BalanceOf<T>>]) -> Weight {
+	calls.iter().fold(batch_call_base_weight::<T>(), |weight, call| {
+		weight.saturating_add(call_weight::<T>(call.gas_limit))
+	})
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -160,6 +176,12 @@ pub mod module {
 	parameter_types! {
 		// Contract max code size.
 		pub const MaxCodeSize: u32 = 60 * 1024;
+		// Max size of the compiler version of a contract verification.
+		pub const MaxCompilerVersionSize: u32 = 64;
+		// Max size of the compiler settings of a contract verification.
+		pub const MaxCompilerSettingsSize: u32 = 4 * 1024;
+		// Max number of calls in a batch call.
+		pub const MaxBatchCalls: u32 = 64;
 	}
 
 	/// EVM module trait
@@ -229,11 +251,23 @@ pub mod module {
 		#[pallet::constant]
 		type PublicationFee: Get<BalanceOf<Self>>;
 
+		/// The number of blocks an unpublished contract can go without being called before anyone
+		/// can reclaim it. Zero disables the reclamation.
+		#[pallet::constant]
+		type ContractInactivityPeriod: Get<Self::BlockNumber>;
+
+		/// The number of blocks a proposed code upgrade must wait before it can be enacted.
+		#[pallet::constant]
+		type UpgradeDelay: Get<Self::BlockNumber>;
+
 		#[pallet::constant]
 		type TreasuryAccount: Get<Self::AccountId>;
 
 		type FreePublicationOrigin: EnsureOrigin<Self::Origin>;
 
+		/// The origin which records the result of a contract verification.
+		type VerifierOrigin: EnsureOrigin<Self::Origin>;
+
 		/// EVM execution runner.
 		type Runner: Runner<Self>;
 
@@ -275,6 +309,57 @@ pub mod module {
 		pub ref_count: u32,
 	}
 
+	#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+	pub struct UpgradeProposal<BlockNumber> {
+		/// The hash of the proposed code, referenced in `CodeInfos` until the proposal is enacted
+		/// or cancelled.
+		pub code_hash: H256,
+		/// The block from which the upgrade can be enacted.
+		pub enact_after: BlockNumber,
+		/// The proposer, whose storage deposit of the proposed code is reserved until the
+		/// proposal is enacted or cancelled.
+		pub proposer: EvmAddress,
+		/// The size of the proposed code the storage deposit is reserved for.
+		pub deposit: u32,
+	}
+
+	#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+	pub struct SponsorshipLimits<Balance> {
+		/// The maximum transaction fee the sponsor pays for a single call.
+		pub max_fee_per_call: Balance,
+		/// The maximum storage limit of a single call, its storage deposit is paid by the sponsor.
+		pub max_storage_limit: u32,
+	}
+
+	#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+	pub struct SponsorSpending<Balance, BlockNumber> {
+		/// The maximum amount the sponsor budget pays in a period.
+		pub max_spend_per_period: Balance,
+		/// The length of a period in blocks.
+		pub period: BlockNumber,
+		/// The block the current period started at.
+		pub period_start: BlockNumber,
+		/// The amount paid from the sponsor budget in the current period.
+		pub spent: Balance,
+	}
+
+	#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+	pub struct BatchCallItem<Balance> {
+		/// The contract address to call.
+		pub target: EvmAddress,
+		/// The data supplied for the call.
+		pub input: Vec<u8>,
+		/// The amount sent for payable calls.
+		#[codec(compact)]
+		pub value: Balance,
+		/// The maximum gas the call can use.
+		#[codec(compact)]
+		pub gas_limit: u64,
+		/// The storage limit added to the storage limit shared by the batch.
+		#[codec(compact)]
+		pub storage_limit: u32,
+	}
+
 	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, Default)]
 	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 	/// Account definition used for genesis block construction.
@@ -306,6 +391,69 @@ pub mod module {
 	#[pallet::getter(fn contract_storage_sizes)]
 	pub type ContractStorageSizes<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, u32, ValueQuery>;
 
+	/// The block at which an unpublished contract was last called or updated.
+	///
+	/// ContractLastAccessed: map EvmAddress => Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn contract_last_accessed)]
+	pub type ContractLastAccessed<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, T::BlockNumber, OptionQuery>;
+
+	/// The block from which the accesses of unpublished contracts are tracked. The contracts
+	/// created before it are treated as last accessed at this block until they are accessed.
+	///
+	/// ContractAccessTrackedSince: BlockNumber
+	#[pallet::storage]
+	#[pallet::getter(fn contract_access_tracked_since)]
+	pub type ContractAccessTrackedSince<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// The pending code upgrades of contracts.
+	///
+	/// UpgradeProposals: map EvmAddress => Option<UpgradeProposal<BlockNumber>>
+	#[pallet::storage]
+	#[pallet::getter(fn upgrade_proposals)]
+	pub type UpgradeProposals<T: Config> =
+		StorageMap<_, Twox64Concat, EvmAddress, UpgradeProposal<T::BlockNumber>, OptionQuery>;
+
+	/// The contracts sponsored by an account, and the limits of a sponsored call.
+	///
+	/// Sponsorships: double_map AccountId, EvmAddress => Option<SponsorshipLimits<Balance>>
+	#[pallet::storage]
+	#[pallet::getter(fn sponsorships)]
+	pub type Sponsorships<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		EvmAddress,
+		SponsorshipLimits<BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	/// The spend limit of a sponsor budget, and the amount spent in the current period.
+	///
+	/// SponsorSpendings: map AccountId => Option<SponsorSpending<Balance, BlockNumber>>
+	#[pallet::storage]
+	#[pallet::getter(fn sponsor_spendings)]
+	pub type SponsorSpendings<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, SponsorSpending<BalanceOf<T>, T::BlockNumber>, OptionQuery>;
+
+	/// The verifications submitted by the maintainers of contracts.
+	///
+	/// ContractVerifications: map EvmAddress => Option<ContractVerification>
+	#[pallet::storage]
+	#[pallet::getter(fn contract_verifications)]
+	pub type ContractVerifications<T: Config> =
+		StorageMap<_, Twox64Concat, EvmAddress, ContractVerification, OptionQuery>;
+
+	/// The storage deposits reserved for the verifications, and the accounts they are reserved
+	/// from.
+	///
+	/// VerificationDeposits: map EvmAddress => Option<(AccountId, Balance)>
+	#[pallet::storage]
+	#[pallet::getter(fn verification_deposits)]
+	pub type VerificationDeposits<T: Config> =
+		StorageMap<_, Twox64Concat, EvmAddress, (T::AccountId, BalanceOf<T>), OptionQuery>;
+
 	/// The storages for EVM contracts.
 	///
 	/// AccountStorages: double_map EvmAddress, H256 => H256
@@ -487,6 +635,74 @@ pub mod module {
 		ContractSetCode { contract: EvmAddress },
 		/// Selfdestructed contract code.
 		ContractSelfdestructed { contract: EvmAddress },
+		/// Reclaimed an inactive contract, its storage deposit is refunded to the maintainer.
+		ContractReclaimed {
+			contract: EvmAddress,
+			maintainer: EvmAddress,
+		},
+		/// Proposed a code upgrade.
+		UpgradeProposed {
+			contract: EvmAddress,
+			code_hash: H256,
+			enact_after: T::BlockNumber,
+		},
+		/// Cancelled a code upgrade.
+		UpgradeCancelled { contract: EvmAddress, code_hash: H256 },
+		/// Enacted a code upgrade.
+		UpgradeEnacted { contract: EvmAddress, code_hash: H256 },
+		/// Deposited into the sponsor budget.
+		SponsorBudgetDeposited {
+			sponsor: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Withdrawn from the sponsor budget.
+		SponsorBudgetWithdrawn {
+			sponsor: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Sponsored calls to a contract.
+		ContractSponsored {
+			sponsor: T::AccountId,
+			contract: EvmAddress,
+			limits: SponsorshipLimits<BalanceOf<T>>,
+		},
+		/// Stopped sponsoring calls to a contract.
+		ContractUnsponsored {
+			sponsor: T::AccountId,
+			contract: EvmAddress,
+		},
+		/// Submitted the source of a contract for verification.
+		VerificationSubmitted {
+			contract: EvmAddress,
+			code_hash: H256,
+			source_hash: H256,
+		},
+		/// Recorded the result of a contract verification.
+		VerificationUpdated {
+			contract: EvmAddress,
+			status: VerificationStatus,
+		},
+		/// A batch of calls has been executed.
+		BatchCallExecuted {
+			from: EvmAddress,
+			succeeded: u32,
+			failed: u32,
+		},
+		/// An atomic batch of calls has been reverted because the call at `index` failed.
+		BatchCallReverted { from: EvmAddress, index: u32 },
+		/// A call has been sponsored, the storage deposit is paid from the sponsor budget.
+		CallSponsored {
+			sponsor: T::AccountId,
+			who: T::AccountId,
+			contract: EvmAddress,
+			used_storage: i32,
+		},
+		/// Set the spend limit of the sponsor budget.
+		SponsorSpendLimitSet {
+			sponsor: T::AccountId,
+			max_spend_per_period: BalanceOf<T>,
+			period: T::BlockNumber,
+		},
 	}
 
 	#[pallet::error]
@@ -521,14 +737,56 @@ pub mod module {
 		ChargeStorageFailed,
 		/// Invalid decimals
 		InvalidDecimals,
+		/// The scheduled call reverted or failed
+		ScheduledCallFailed,
+		/// The reclamation of inactive contracts is disabled
+		ContractReclamationDisabled,
+		/// The contract was accessed within the inactivity period
+		ContractStillActive,
+		/// A code upgrade is already proposed for the contract
+		UpgradeAlreadyProposed,
+		/// No code upgrade is proposed for the contract
+		UpgradeNotProposed,
+		/// The upgrade delay has not passed yet
+		UpgradeTimelocked,
+		/// The contract is not sponsored by the sponsor
+		NotSponsored,
+		/// The call exceeds the limits of the sponsorship
+		SponsorLimitExceeded,
+		/// The sponsor budget is not enough
+		SponsorBudgetInsufficient,
+		/// The compiler version or settings exceed the max size
+		VerificationInfoTooLarge,
+		/// No verification is submitted for the code of the contract
+		VerificationNotSubmitted,
+		/// The gas limit of the scheduled call exceeds the weight of an idle block
+		ScheduledGasLimitTooHigh,
+		/// Sponsored calls can not pay a tip
+		SponsoredTipNotAllowed,
+		/// The spend period of the sponsor budget must not be zero
+		InvalidSponsorSpendPeriod,
+		/// The sponsor budget exceeds its spend limit of the period
+		SponsorSpendLimitExceeded,
+		/// The batch call has no calls
+		EmptyBatchCall,
+		/// The batch call has more calls than `MaxBatchCalls`
+		TooManyBatchCalls,
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::v1::migrate::<T>()
+		}
+
 		fn integrity_test() {
 			assert!(convert_decimals_from_evm(T::StorageDepositPerByte::get()).is_some());
 		}
@@ -581,59 +839,177 @@ pub mod module {
 			let who = ensure_signed(origin)?;
 			let source = T::AddressMapping::get_or_create_evm_address(&who);
 
-			match T::Runner::call(
+			let (post_info, _) =
+				Self::execute_call(source, target, input, value, gas_limit, storage_limit, access_list);
+			Ok(post_info)
+		}
+
+		/// Submit the source of a contract for verification, replacing the previous submission.
+		/// The source is recompiled off-chain by the `evm_verifyContract` RPC of a node, and the
+		/// result is recorded by `VerifierOrigin`. A storage deposit for the verification is
+		/// reserved from the caller, and the deposit of the previous submission is released.
+		///
+		/// - `contract`: The contract, the caller must be the contract's maintainer
+		/// - `compiler_version`: The compiler version, e.g. `0.8.2+commit.661d1103`
+		/// - `settings`: The compiler settings, in the `settings` format of the solc standard JSON
+		///   input
+		/// - `source_hash`: The hash of the source, or of the metadata which references the source
+		#[pallet::weight(<T as Config>::WeightInfo::submit_verification(settings.len() as u32))]
+		#[transactional]
+		pub fn submit_verification(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			compiler_version: Vec<u8>,
+			settings: Vec<u8>,
+			source_hash: H256,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let caller = T::AddressMapping::get_evm_address(&who).ok_or(Error::<T>::AddressNotMapped)?;
+			let contract_info = Self::ensure_maintainer(&caller, &contract)?;
+			ensure!(
+				compiler_version.len() as u32 <= MaxCompilerVersionSize::get()
+					&& settings.len() as u32 <= MaxCompilerSettingsSize::get(),
+				Error::<T>::VerificationInfoTooLarge
+			);
+
+			let verification = ContractVerification {
+				code_hash: contract_info.code_hash,
+				compiler_version,
+				settings,
+				source_hash,
+				status: VerificationStatus::Pending,
+			};
+			let deposit =
+				Self::get_storage_deposit_per_byte().saturating_mul((verification.encoded_size() as u32).into());
+			if let Some((depositor, amount)) = VerificationDeposits::<T>::take(contract) {
+				T::Currency::unreserve_named(&RESERVE_ID_VERIFICATION_DEPOSIT, &depositor, amount);
+			}
+			T::Currency::reserve_named(&RESERVE_ID_VERIFICATION_DEPOSIT, &who, deposit)?;
+			VerificationDeposits::<T>::insert(contract, (who, deposit));
+			ContractVerifications::<T>::insert(contract, verification);
+
+			Pallet::<T>::deposit_event(Event::<T>::VerificationSubmitted {
+				contract,
+				code_hash: contract_info.code_hash,
+				source_hash,
+			});
+
+			Ok(().into())
+		}
+
+		/// Record the result of a contract verification.
+		///
+		/// - `contract`: The contract
+		/// - `code_hash`: The code hash the source was compiled against, must match the
+		///   submission
+		/// - `verified`: Whether the recompiled source matches the code
+		#[pallet::weight(<T as Config>::WeightInfo::set_verification_status())]
+		#[transactional]
+		pub fn set_verification_status(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			code_hash: H256,
+			verified: bool,
+		) -> DispatchResultWithPostInfo {
+			T::VerifierOrigin::ensure_origin(origin)?;
+
+			let status = if verified {
+				VerificationStatus::Verified
+			} else {
+				VerificationStatus::Failed
+			};
+			ContractVerifications::<T>::try_mutate(contract, |maybe_verification| -> DispatchResult {
+				let verification = maybe_verification
+					.as_mut()
+					.filter(|verification| verification.code_hash == code_hash)
+					.ok_or(Error::<T>::VerificationNotSubmitted)?;
+				verification.status = status;
+				Ok(())
+			})?;
+
+			Pallet::<T>::deposit_event(Event::<T>::VerificationUpdated { contract, status });
+
+			Ok(().into())
+		}
+
+		/// Issue a batch of EVM call operations with a single transaction. The storage limits of
+		/// the calls are added up into a storage limit shared by the whole batch, and its storage
+		/// deposit is reserved once.
+		///
+		/// - `calls`: the calls to execute in order, at least one and at most `MaxBatchCalls`
+		/// - `atomic`: if true, the batch stops at the first failed call and reverts all the calls,
+		///   otherwise the remaining calls are still executed
+		#[pallet::weight(batch_call_weight::<T>(calls))]
+		#[transactional]
+		pub fn batch_call(
+			origin: OriginFor<T>,
+			calls: Vec<BatchCallItem<BalanceOf<T>>>,
+			atomic: bool,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!calls.is_empty(), Error::<T>::EmptyBatchCall);
+			ensure!(
+				calls.len() <= MaxBatchCalls::get() as usize,
+				Error::<T>::TooManyBatchCalls
+			);
+			let source = T::AddressMapping::get_or_create_evm_address(&who);
+
+			let targets = calls
+				.iter()
+				.map(|call| (call.target, call.gas_limit))
+				.collect::<Vec<_>>();
+			let results = T::Runner::batch_call(
 				source,
 				source,
-				target,
-				input,
-				value,
-				gas_limit,
-				storage_limit,
-				access_list.into_iter().map(|v| (v.address, v.storage_keys)).collect(),
+				calls
+					.into_iter()
+					.map(|call| (call.target, call.input, call.value, call.gas_limit, call.storage_limit))
+					.collect(),
+				atomic,
 				T::config(),
-			) {
-				Err(e) => {
-					Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed {
-						from: source,
-						contract: target,
-						exit_reason: ExitReason::Error(ExitError::Other(Into::<&str>::into(e).into())),
-						output: vec![],
-						logs: vec![],
-						used_gas: gas_limit,
-						used_storage: Default::default(),
-					});
-
-					Ok(().into())
-				}
-				Ok(info) => {
-					let used_gas: u64 = info.used_gas.unique_saturated_into();
+			)?;
 
-					if info.exit_reason.is_succeed() {
-						Pallet::<T>::deposit_event(Event::<T>::Executed {
-							from: source,
-							contract: target,
-							logs: info.logs,
-							used_gas,
-							used_storage: info.used_storage,
-						});
-					} else {
-						Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed {
-							from: source,
-							contract: target,
-							exit_reason: info.exit_reason.clone(),
-							output: info.value.clone(),
-							logs: info.logs,
-							used_gas,
-							used_storage: Default::default(),
-						});
-					}
+			let executed = results.len() as u32;
+			let is_failed =
+				|result: &Result<CallInfo, DispatchError>| !matches!(result, Ok(info) if info.exit_reason.is_succeed());
+			// an atomic batch is reverted by its last executed call
+			let reverted = atomic && results.last().map_or(false, is_failed);
+
+			let mut failed: u32 = 0;
+			let mut actual_weight = batch_call_base_weight::<T>();
+			for (index, ((target, gas_limit), result)) in targets.into_iter().zip(results).enumerate() {
+				let used_gas = match &result {
+					Ok(info) => info.used_gas.unique_saturated_into(),
+					Err(_) => gas_limit,
+				};
+				actual_weight = actual_weight.saturating_add(call_weight::<T>(used_gas));
 
-					Ok(PostDispatchInfo {
-						actual_weight: Some(call_weight::<T>(used_gas)),
-						pays_fee: Pays::Yes,
-					})
+				if is_failed(&result) {
+					failed = failed.saturating_add(1);
+				}
+				// the reverted calls of an atomic batch only report the failed call
+				if !reverted || index as u32 == executed - 1 {
+					Self::deposit_call_event(source, target, gas_limit, result);
 				}
 			}
+
+			if reverted {
+				Pallet::<T>::deposit_event(Event::<T>::BatchCallReverted {
+					from: source,
+					index: executed - 1,
+				});
+			} else {
+				Pallet::<T>::deposit_event(Event::<T>::BatchCallExecuted {
+					from: source,
+					succeeded: executed - failed,
+					failed,
+				});
+			}
+
+			Ok(PostDispatchInfo {
+				actual_weight: Some(actual_weight),
+				pays_fee: Pays::Yes,
+			})
 		}
 
 		/// Issue an EVM call operation on a scheduled contract call, and
@@ -660,19 +1036,7 @@ pub mod module {
 		) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
 
-			let _from_account = T::AddressMapping::get_account_id(&from);
-			let _payed: NegativeImbalanceOf<T>;
-			#[cfg(not(feature = "with-ethereum-compatibility"))]
-			{
-				// unreserve the transaction fee for gas_limit
-				let weight = T::GasToWeight::convert(gas_limit);
-				let (_, imbalance) = T::ChargeTransactionPayment::unreserve_and_charge_fee(&_from_account, weight)
-					.map_err(|_| Error::<T>::ChargeFeeFailed)?;
-				_payed = imbalance;
-			}
-
-			match T::Runner::call(
-				from,
+			let (used_gas, _) = Pallet::<T>::do_scheduled_call(
 				from,
 				target,
 				input,
@@ -680,66 +1044,12 @@ pub mod module {
 				gas_limit,
 				storage_limit,
 				access_list.into_iter().map(|v| (v.address, v.storage_keys)).collect(),
-				T::config(),
-			) {
-				Err(e) => {
-					Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed {
-						from,
-						contract: target,
-						exit_reason: ExitReason::Error(ExitError::Other(Into::<&str>::into(e).into())),
-						output: vec![],
-						logs: vec![],
-						used_gas: gas_limit,
-						used_storage: Default::default(),
-					});
-
-					Ok(().into())
-				}
-				Ok(info) => {
-					let used_gas: u64 = info.used_gas.unique_saturated_into();
-
-					if info.exit_reason.is_succeed() {
-						Pallet::<T>::deposit_event(Event::<T>::Executed {
-							from,
-							contract: target,
-							logs: info.logs,
-							used_gas,
-							used_storage: info.used_storage,
-						});
-					} else {
-						Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed {
-							from,
-							contract: target,
-							exit_reason: info.exit_reason.clone(),
-							output: info.value.clone(),
-							logs: info.logs,
-							used_gas,
-							used_storage: Default::default(),
-						});
-					}
-
-					#[cfg(not(feature = "with-ethereum-compatibility"))]
-					{
-						use sp_runtime::traits::Zero;
-						let refund_gas = gas_limit.saturating_sub(used_gas);
-						if !refund_gas.is_zero() {
-							// ignore the result to continue. if it fails, just the user will not
-							// be refunded, there will not increase user balance.
-							let res = T::ChargeTransactionPayment::refund_fee(
-								&_from_account,
-								T::GasToWeight::convert(refund_gas),
-								_payed,
-							);
-							debug_assert!(res.is_ok());
-						}
-					}
+			)?;
 
-					Ok(PostDispatchInfo {
-						actual_weight: Some(T::GasToWeight::convert(used_gas)),
-						pays_fee: Pays::Yes,
-					})
-				}
-			}
+			Ok(PostDispatchInfo {
+				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				pays_fee: Pays::Yes,
+			})
 		}
 
 		/// Issue an EVM create operation. This is similar to a contract
@@ -1166,53 +1476,474 @@ pub mod module {
 
 			Ok(().into())
 		}
-	}
-}
 
-impl<T: Config> Pallet<T> {
-	/// Get StorageDepositPerByte of actual decimals
-	pub fn get_storage_deposit_per_byte() -> BalanceOf<T> {
-		// StorageDepositPerByte decimals is 18, KAR/ACA decimals is 12, convert to 12 here.
-		convert_decimals_from_evm(T::StorageDepositPerByte::get()).expect("checked in integrity_test; qed")
-	}
+		/// Reclaim an unpublished contract that has not been called for
+		/// `ContractInactivityPeriod` blocks. Its storage deposit and balance are refunded to the
+		/// maintainer.
+		///
+		/// - `contract`: The contract to reclaim, must not be marked as published
+		#[pallet::weight(<T as Config>::WeightInfo::reclaim_contract())]
+		#[transactional]
+		pub fn reclaim_contract(origin: OriginFor<T>, contract: EvmAddress) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let maintainer = Self::do_reclaim_contract(&contract)?;
 
-	/// Check whether an account is empty.
-	pub fn is_account_empty(address: &H160) -> bool {
-		let account_id = T::AddressMapping::get_account_id(address);
-		let balance = T::Currency::total_balance(&account_id);
+			Pallet::<T>::deposit_event(Event::<T>::ContractReclaimed { contract, maintainer });
 
-		if !balance.is_zero() {
-			return false;
+			Ok(().into())
 		}
 
-		Self::accounts(address).map_or(true, |account_info| {
-			account_info.contract_info.is_none() && account_info.nonce.is_zero()
-		})
-	}
+		/// Propose to replace the code of a contract after `UpgradeDelay` blocks. The storage
+		/// deposit of the proposed code is reserved from the maintainer until the proposal is
+		/// enacted or cancelled.
+		///
+		/// - `contract`: The contract to upgrade, the caller must be the contract's maintainer
+		/// - `code`: The new code of the contract
+		#[pallet::weight(<T as Config>::WeightInfo::propose_upgrade(code.len() as u32))]
+		#[transactional]
+		pub fn propose_upgrade(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			code: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let caller = T::AddressMapping::get_evm_address(&who).ok_or(Error::<T>::AddressNotMapped)?;
+			let proposal = Self::do_propose_upgrade(&caller, contract, code)?;
 
-	/// Remove an account if its empty.
-	/// Keep the non-zero nonce exists.
-	pub fn remove_account_if_empty(address: &H160) {
-		if Self::is_account_empty(address) {
-			let res = Self::remove_account(address);
-			debug_assert!(res.is_ok());
+			Pallet::<T>::deposit_event(Event::<T>::UpgradeProposed {
+				contract,
+				code_hash: proposal.code_hash,
+				enact_after: proposal.enact_after,
+			});
+
+			Ok(().into())
 		}
-	}
 
-	#[transactional]
-	pub fn remove_contract(caller: &EvmAddress, contract: &EvmAddress) -> DispatchResult {
-		let contract_account = T::AddressMapping::get_account_id(contract);
+		/// Cancel the proposed code upgrade of a contract.
+		///
+		/// - `contract`: The contract, the caller must be the contract's maintainer
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_upgrade())]
+		#[transactional]
+		pub fn cancel_upgrade(origin: OriginFor<T>, contract: EvmAddress) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let caller = T::AddressMapping::get_evm_address(&who).ok_or(Error::<T>::AddressNotMapped)?;
+			Self::ensure_maintainer(&caller, &contract)?;
+			let proposal = UpgradeProposals::<T>::take(contract).ok_or(Error::<T>::UpgradeNotProposed)?;
+			Self::release_upgrade_proposal(&proposal)?;
 
-		Accounts::<T>::try_mutate_exists(contract, |account_info| -> DispatchResult {
-			// We will keep the nonce until the storages are cleared.
-			// Only remove the `contract_info`
-			let account_info = account_info.as_mut().ok_or(Error::<T>::ContractNotFound)?;
-			let contract_info = account_info.contract_info.take().ok_or(Error::<T>::ContractNotFound)?;
+			Pallet::<T>::deposit_event(Event::<T>::UpgradeCancelled {
+				contract,
+				code_hash: proposal.code_hash,
+			});
 
-			CodeInfos::<T>::mutate_exists(&contract_info.code_hash, |maybe_code_info| {
-				if let Some(code_info) = maybe_code_info.as_mut() {
-					code_info.ref_count = code_info.ref_count.saturating_sub(1);
-					if code_info.ref_count == 0 {
+			Ok(().into())
+		}
+
+		/// Replace the code of a contract with its proposed code, once the upgrade delay has
+		/// passed. The storage deposit of the code size change is charged from the maintainer.
+		///
+		/// - `contract`: The contract, the caller must be the contract's maintainer
+		#[pallet::weight(<T as Config>::WeightInfo::enact_upgrade())]
+		#[transactional]
+		pub fn enact_upgrade(origin: OriginFor<T>, contract: EvmAddress) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let caller = T::AddressMapping::get_evm_address(&who).ok_or(Error::<T>::AddressNotMapped)?;
+			let code_hash = Self::do_enact_upgrade(&caller, contract)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::UpgradeEnacted { contract, code_hash });
+
+			Ok(().into())
+		}
+
+		/// Reserve `amount` into the sponsor budget of the caller. The budget pays the fees and
+		/// storage deposits of the calls sponsored by the caller.
+		///
+		/// - `amount`: The amount to deposit
+		#[pallet::weight(<T as Config>::WeightInfo::deposit_sponsor_budget())]
+		#[transactional]
+		pub fn deposit_sponsor_budget(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let sponsor = ensure_signed(origin)?;
+			T::Currency::reserve_named(&RESERVE_ID_SPONSOR_BUDGET, &sponsor, amount)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::SponsorBudgetDeposited { sponsor, amount });
+
+			Ok(().into())
+		}
+
+		/// Unreserve `amount` from the sponsor budget of the caller.
+		///
+		/// - `amount`: The amount to withdraw
+		#[pallet::weight(<T as Config>::WeightInfo::withdraw_sponsor_budget())]
+		#[transactional]
+		pub fn withdraw_sponsor_budget(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(
+				T::Currency::reserved_balance_named(&RESERVE_ID_SPONSOR_BUDGET, &sponsor) >= amount,
+				Error::<T>::SponsorBudgetInsufficient
+			);
+			let err_amount = T::Currency::unreserve_named(&RESERVE_ID_SPONSOR_BUDGET, &sponsor, amount);
+			debug_assert!(err_amount.is_zero());
+
+			Pallet::<T>::deposit_event(Event::<T>::SponsorBudgetWithdrawn { sponsor, amount });
+
+			Ok(().into())
+		}
+
+		/// Sponsor the calls to a contract with the budget of the caller. Overwrites the limits if
+		/// the contract is already sponsored.
+		///
+		/// - `contract`: The contract to sponsor
+		/// - `max_fee_per_call`: The maximum transaction fee paid for a single call
+		/// - `max_storage_limit`: The maximum storage limit of a single call
+		#[pallet::weight(<T as Config>::WeightInfo::sponsor_contract())]
+		#[transactional]
+		pub fn sponsor_contract(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			#[pallet::compact] max_fee_per_call: BalanceOf<T>,
+			#[pallet::compact] max_storage_limit: u32,
+		) -> DispatchResultWithPostInfo {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(
+				Accounts::<T>::get(contract).map_or(false, |account| account.contract_info.is_some()),
+				Error::<T>::ContractNotFound
+			);
+
+			let limits = SponsorshipLimits {
+				max_fee_per_call,
+				max_storage_limit,
+			};
+			Sponsorships::<T>::insert(&sponsor, contract, limits);
+
+			Pallet::<T>::deposit_event(Event::<T>::ContractSponsored {
+				sponsor,
+				contract,
+				limits,
+			});
+
+			Ok(().into())
+		}
+
+		/// Stop sponsoring the calls to a contract.
+		///
+		/// - `contract`: The sponsored contract
+		#[pallet::weight(<T as Config>::WeightInfo::unsponsor_contract())]
+		#[transactional]
+		pub fn unsponsor_contract(origin: OriginFor<T>, contract: EvmAddress) -> DispatchResultWithPostInfo {
+			let sponsor = ensure_signed(origin)?;
+			Sponsorships::<T>::take(&sponsor, contract).ok_or(Error::<T>::NotSponsored)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::ContractUnsponsored { sponsor, contract });
+
+			Ok(().into())
+		}
+
+		/// Issue an EVM call operation paid by a sponsor. The transaction fee and the storage
+		/// deposit are paid from the sponsor budget, within the limits the sponsor set for the
+		/// contract and the spend limit of the sponsor. Unused storage deposit is returned to the
+		/// budget.
+		///
+		/// - `sponsor`: the account which sponsors the call
+		/// - `target`: the contract address to call
+		/// - `input`: the data supplied for the call
+		/// - `value`: the amount sent for payable calls, paid by the caller
+		/// - `gas_limit`: the maximum gas the call can use
+		/// - `storage_limit`: the total bytes the contract's storage can increase by
+		#[pallet::weight(call_weight::<T>(*gas_limit).saturating_add(<T as Config>::WeightInfo::sponsored_call()))]
+		#[transactional]
+		pub fn sponsored_call(
+			origin: OriginFor<T>,
+			sponsor: T::AccountId,
+			target: EvmAddress,
+			input: Vec<u8>,
+			#[pallet::compact] value: BalanceOf<T>,
+			#[pallet::compact] gas_limit: u64,
+			#[pallet::compact] storage_limit: u32,
+			access_list: Vec<AccessListItem>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let limits = Sponsorships::<T>::get(&sponsor, target).ok_or(Error::<T>::NotSponsored)?;
+			ensure!(
+				storage_limit <= limits.max_storage_limit,
+				Error::<T>::SponsorLimitExceeded
+			);
+
+			let deposit = Self::get_storage_deposit_per_byte().saturating_mul(storage_limit.into());
+			Self::release_sponsor_budget(&sponsor, deposit)?;
+
+			// the storage deposit is reserved from the sponsor
+			let source = T::AddressMapping::get_or_create_evm_address(&who);
+			let payer = T::AddressMapping::get_or_create_evm_address(&sponsor);
+			let (mut post_info, used_storage) = runner::stack::with_storage_payer(payer, || {
+				Self::execute_call(source, target, input, value, gas_limit, storage_limit, access_list)
+			});
+
+			// return the unused storage deposit to the sponsor budget
+			let used = cmp::max(used_storage, 0) as u32;
+			let unused_deposit =
+				Self::get_storage_deposit_per_byte().saturating_mul(storage_limit.saturating_sub(used).into());
+			Self::restore_sponsor_budget(&sponsor, unused_deposit)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::CallSponsored {
+				sponsor,
+				who,
+				contract: target,
+				used_storage,
+			});
+
+			post_info.actual_weight = post_info
+				.actual_weight
+				.map(|weight| weight.saturating_add(<T as Config>::WeightInfo::sponsored_call()));
+			Ok(post_info)
+		}
+
+		/// Set the maximum amount the sponsor budget pays for sponsored calls in a period.
+		/// Sponsored calls are refused until a spend limit is set.
+		///
+		/// - `max_spend_per_period`: the maximum amount paid from the budget in a period
+		/// - `period`: the length of a period in blocks
+		#[pallet::weight(<T as Config>::WeightInfo::set_sponsor_spend_limit())]
+		#[transactional]
+		pub fn set_sponsor_spend_limit(
+			origin: OriginFor<T>,
+			#[pallet::compact] max_spend_per_period: BalanceOf<T>,
+			period: T::BlockNumber,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(!period.is_zero(), Error::<T>::InvalidSponsorSpendPeriod);
+
+			SponsorSpendings::<T>::mutate(&sponsor, |maybe_spending| {
+				let now = frame_system::Pallet::<T>::block_number();
+				let spending = maybe_spending.get_or_insert(SponsorSpending {
+					max_spend_per_period,
+					period,
+					period_start: now,
+					spent: Zero::zero(),
+				});
+				spending.max_spend_per_period = max_spend_per_period;
+				spending.period = period;
+			});
+
+			Pallet::<T>::deposit_event(Event::<T>::SponsorSpendLimitSet {
+				sponsor,
+				max_spend_per_period,
+				period,
+			});
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Get StorageDepositPerByte of actual decimals
+	pub fn get_storage_deposit_per_byte() -> BalanceOf<T> {
+		// StorageDepositPerByte decimals is 18, KAR/ACA decimals is 12, convert to 12 here.
+		convert_decimals_from_evm(T::StorageDepositPerByte::get()).expect("checked in integrity_test; qed")
+	}
+
+	/// Check whether an account is empty.
+	pub fn is_account_empty(address: &H160) -> bool {
+		let account_id = T::AddressMapping::get_account_id(address);
+		let balance = T::Currency::total_balance(&account_id);
+
+		if !balance.is_zero() {
+			return false;
+		}
+
+		Self::accounts(address).map_or(true, |account_info| {
+			account_info.contract_info.is_none() && account_info.nonce.is_zero()
+		})
+	}
+
+	/// Remove an account if its empty.
+	/// Keep the non-zero nonce exists.
+	pub fn remove_account_if_empty(address: &H160) {
+		if Self::is_account_empty(address) {
+			let res = Self::remove_account(address);
+			debug_assert!(res.is_ok());
+		}
+	}
+
+	/// Reserve the fee for `gas_limit` from `from` and queue the call to the idle scheduler, to be
+	/// executed by `EvmTask::Schedule` when a block leaves enough idle weight.
+	#[transactional]
+	pub fn schedule_idle_call(
+		from: EvmAddress,
+		target: EvmAddress,
+		input: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+	) -> DispatchResult {
+		ensure!(
+			T::GasToWeight::convert(gas_limit) <= Self::max_scheduled_call_weight(),
+			Error::<T>::ScheduledGasLimitTooHigh
+		);
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		{
+			// reserve the transaction fee for gas_limit, charged when the call is executed
+			let from_account = T::AddressMapping::get_account_id(&from);
+			T::ChargeTransactionPayment::reserve_fee(&from_account, T::GasToWeight::convert(gas_limit))?;
+		}
+
+		T::IdleScheduler::schedule(
+			EvmTask::Schedule {
+				from,
+				target,
+				input,
+				value,
+				gas_limit,
+				storage_limit,
+			}
+			.into(),
+		)
+	}
+
+	/// The max weight of a scheduled call. A block without normal extrinsics leaves at least the
+	/// max weight of a normal extrinsic to the idle hooks.
+	fn max_scheduled_call_weight() -> Weight {
+		let weights = <T as frame_system::Config>::BlockWeights::get();
+		weights
+			.get(DispatchClass::Normal)
+			.max_extrinsic
+			.unwrap_or(weights.max_block)
+	}
+
+	/// Release the fee reserved for a scheduled call that can never be executed.
+	fn release_scheduled_call_fee(_from: EvmAddress, _gas_limit: u64) {
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		{
+			// the reserved fee is charged and refunded in full
+			let from_account = T::AddressMapping::get_account_id(&_from);
+			let weight = T::GasToWeight::convert(_gas_limit);
+			if let Ok((_, payed)) = T::ChargeTransactionPayment::unreserve_and_charge_fee(&from_account, weight) {
+				let res = T::ChargeTransactionPayment::refund_fee(&from_account, weight, payed);
+				debug_assert!(res.is_ok());
+			}
+		}
+	}
+
+	/// Execute a scheduled call, charging the fee reserved for `gas_limit` and refunding the
+	/// unused gas.
+	///
+	/// Returns the used gas and the result of the call, or an error without executing anything
+	/// if the fee cannot be charged.
+	#[transactional]
+	fn do_scheduled_call(
+		from: EvmAddress,
+		target: EvmAddress,
+		input: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<(H160, Vec<H256>)>,
+	) -> Result<(u64, DispatchResult), DispatchError> {
+		let _from_account = T::AddressMapping::get_account_id(&from);
+		let _payed: NegativeImbalanceOf<T>;
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		{
+			// unreserve the transaction fee for gas_limit
+			let weight = T::GasToWeight::convert(gas_limit);
+			let (_, imbalance) = T::ChargeTransactionPayment::unreserve_and_charge_fee(&_from_account, weight)
+				.map_err(|_| Error::<T>::ChargeFeeFailed)?;
+			_payed = imbalance;
+		}
+
+		match T::Runner::call(
+			from,
+			from,
+			target,
+			input,
+			value,
+			gas_limit,
+			storage_limit,
+			access_list,
+			T::config(),
+		) {
+			Err(e) => {
+				Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed {
+					from,
+					contract: target,
+					exit_reason: ExitReason::Error(ExitError::Other(Into::<&str>::into(e).into())),
+					output: vec![],
+					logs: vec![],
+					used_gas: gas_limit,
+					used_storage: Default::default(),
+				});
+
+				Ok((gas_limit, Err(e)))
+			}
+			Ok(info) => {
+				let used_gas: u64 = info.used_gas.unique_saturated_into();
+				let succeed = info.exit_reason.is_succeed();
+
+				if succeed {
+					Pallet::<T>::deposit_event(Event::<T>::Executed {
+						from,
+						contract: target,
+						logs: info.logs,
+						used_gas,
+						used_storage: info.used_storage,
+					});
+				} else {
+					Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed {
+						from,
+						contract: target,
+						exit_reason: info.exit_reason.clone(),
+						output: info.value.clone(),
+						logs: info.logs,
+						used_gas,
+						used_storage: Default::default(),
+					});
+				}
+
+				#[cfg(not(feature = "with-ethereum-compatibility"))]
+				{
+					use sp_runtime::traits::Zero;
+					let refund_gas = gas_limit.saturating_sub(used_gas);
+					if !refund_gas.is_zero() {
+						// ignore the result to continue. if it fails, just the user will not
+						// be refunded, there will not increase user balance.
+						let res = T::ChargeTransactionPayment::refund_fee(
+							&_from_account,
+							T::GasToWeight::convert(refund_gas),
+							_payed,
+						);
+						debug_assert!(res.is_ok());
+					}
+				}
+
+				let result = if succeed {
+					Ok(())
+				} else {
+					Err(Error::<T>::ScheduledCallFailed.into())
+				};
+				Ok((used_gas, result))
+			}
+		}
+	}
+
+	#[transactional]
+	pub fn remove_contract(caller: &EvmAddress, contract: &EvmAddress) -> DispatchResult {
+		let contract_account = T::AddressMapping::get_account_id(contract);
+
+		Accounts::<T>::try_mutate_exists(contract, |account_info| -> DispatchResult {
+			// We will keep the nonce until the storages are cleared.
+			// Only remove the `contract_info`
+			let account_info = account_info.as_mut().ok_or(Error::<T>::ContractNotFound)?;
+			let contract_info = account_info.contract_info.take().ok_or(Error::<T>::ContractNotFound)?;
+
+			CodeInfos::<T>::mutate_exists(&contract_info.code_hash, |maybe_code_info| {
+				if let Some(code_info) = maybe_code_info.as_mut() {
+					code_info.ref_count = code_info.ref_count.saturating_sub(1);
+					if code_info.ref_count == 0 {
 						Codes::<T>::remove(&contract_info.code_hash);
 						*maybe_code_info = None;
 					}
@@ -1223,6 +1954,14 @@ impl<T: Config> Pallet<T> {
 			});
 
 			ContractStorageSizes::<T>::take(contract);
+			ContractLastAccessed::<T>::remove(contract);
+			ContractVerifications::<T>::remove(contract);
+			if let Some((depositor, amount)) = VerificationDeposits::<T>::take(contract) {
+				T::Currency::unreserve_named(&RESERVE_ID_VERIFICATION_DEPOSIT, &depositor, amount);
+			}
+			if let Some(proposal) = UpgradeProposals::<T>::take(contract) {
+				Self::release_upgrade_proposal(&proposal)?;
+			}
 
 			T::IdleScheduler::schedule(
 				EvmTask::Remove {
@@ -1332,6 +2071,10 @@ impl<T: Config> Pallet<T> {
 			}
 		});
 
+		if !contract_info.published {
+			ContractLastAccessed::<T>::insert(address, frame_system::Pallet::<T>::block_number());
+		}
+
 		frame_system::Pallet::<T>::inc_providers(&T::AddressMapping::get_account_id(&address));
 	}
 
@@ -1465,6 +2208,7 @@ impl<T: Config> Pallet<T> {
 				}
 				ensure!(!contract_info.published, Error::<T>::ContractAlreadyPublished);
 				contract_info.published = true;
+				ContractLastAccessed::<T>::remove(contract);
 				Ok(())
 			} else {
 				Err(Error::<T>::ContractNotFound.into())
@@ -1516,32 +2260,10 @@ impl<T: Config> Pallet<T> {
 			Self::charge_storage(&source, &contract, storage_size_chainged)?;
 			Self::update_contract_storage_size(&contract, storage_size_chainged);
 
-			// try remove old codes
-			CodeInfos::<T>::mutate_exists(&contract_info.code_hash, |maybe_code_info| -> DispatchResult {
-				let code_info = maybe_code_info.as_mut().ok_or(Error::<T>::ContractNotFound)?;
-				code_info.ref_count = code_info.ref_count.saturating_sub(1);
-				if code_info.ref_count == 0 {
-					Codes::<T>::remove(&contract_info.code_hash);
-					*maybe_code_info = None;
-				}
-				Ok(())
-			})?;
-
-			CodeInfos::<T>::mutate_exists(&code_hash, |maybe_code_info| {
-				if let Some(code_info) = maybe_code_info.as_mut() {
-					code_info.ref_count = code_info.ref_count.saturating_add(1);
-				} else {
-					let new = CodeInfo {
-						code_size,
-						ref_count: 1,
-					};
-					*maybe_code_info = Some(new);
-
-					Codes::<T>::insert(&code_hash, bounded_code);
-				}
-			});
+			Self::dec_code_ref(&contract_info.code_hash);
 			// update code_hash
-			contract_info.code_hash = code_hash;
+			contract_info.code_hash = Self::inc_code_ref(bounded_code);
+			Self::touch_contract(&contract);
 
 			Ok(())
 		})
@@ -1561,6 +2283,305 @@ impl<T: Config> Pallet<T> {
 		Self::remove_contract(caller, contract)
 	}
 
+	/// Reclaim an unpublished contract that has been inactive for `ContractInactivityPeriod`.
+	///
+	/// Returns the maintainer the storage deposit is refunded to.
+	fn do_reclaim_contract(contract: &EvmAddress) -> Result<EvmAddress, DispatchError> {
+		let inactivity_period = T::ContractInactivityPeriod::get();
+		ensure!(!inactivity_period.is_zero(), Error::<T>::ContractReclamationDisabled);
+
+		let contract_info = Self::accounts(contract)
+			.and_then(|account_info| account_info.contract_info)
+			.ok_or(Error::<T>::ContractNotFound)?;
+		ensure!(!contract_info.published, Error::<T>::ContractAlreadyPublished);
+
+		let last_accessed = Self::contract_last_accessed(contract).unwrap_or_else(Self::contract_access_tracked_since);
+		ensure!(
+			frame_system::Pallet::<T>::block_number() >= last_accessed.saturating_add(inactivity_period),
+			Error::<T>::ContractStillActive
+		);
+
+		Self::remove_contract(&contract_info.maintainer, contract)?;
+		Ok(contract_info.maintainer)
+	}
+
+	/// Ensure `caller` is the maintainer of `contract`.
+	fn ensure_maintainer(caller: &EvmAddress, contract: &EvmAddress) -> Result<ContractInfo, DispatchError> {
+		let contract_info = Self::accounts(contract)
+			.and_then(|account_info| account_info.contract_info)
+			.ok_or(Error::<T>::ContractNotFound)?;
+		ensure!(contract_info.maintainer == *caller, Error::<T>::NoPermission);
+		Ok(contract_info)
+	}
+
+	/// Get the verification of a contract, if it was submitted for the current code of the
+	/// contract.
+	pub fn current_verification(contract: &EvmAddress) -> Option<ContractVerification> {
+		let code_hash = Self::accounts(contract)?.contract_info?.code_hash;
+		Self::contract_verifications(contract).filter(|verification| verification.code_hash == code_hash)
+	}
+
+	/// Save `code` if not saved yet, and add a reference to it.
+	fn inc_code_ref(code: BoundedVec<u8, MaxCodeSize>) -> H256 {
+		let code_hash = code_hash(code.as_slice());
+		CodeInfos::<T>::mutate_exists(&code_hash, |maybe_code_info| {
+			if let Some(code_info) = maybe_code_info.as_mut() {
+				code_info.ref_count = code_info.ref_count.saturating_add(1);
+			} else {
+				*maybe_code_info = Some(CodeInfo {
+					code_size: code.len() as u32,
+					ref_count: 1,
+				});
+				Codes::<T>::insert(&code_hash, code);
+			}
+		});
+		code_hash
+	}
+
+	/// Remove a reference to a code, and remove the code if it is not referenced anymore.
+	fn dec_code_ref(code_hash: &H256) {
+		CodeInfos::<T>::mutate_exists(code_hash, |maybe_code_info| {
+			if let Some(code_info) = maybe_code_info.as_mut() {
+				code_info.ref_count = code_info.ref_count.saturating_sub(1);
+				if code_info.ref_count == 0 {
+					Codes::<T>::remove(code_hash);
+					*maybe_code_info = None;
+				}
+			} else {
+				// code info removed while still having reference to it?
+				debug_assert!(false);
+			}
+		});
+	}
+
+	/// Propose the code upgrade of a contract. The proposal keeps a reference to the new code,
+	/// and the storage deposit of it reserved from the proposer, until it is enacted or
+	/// cancelled.
+	fn do_propose_upgrade(
+		caller: &EvmAddress,
+		contract: EvmAddress,
+		code: Vec<u8>,
+	) -> Result<UpgradeProposal<T::BlockNumber>, DispatchError> {
+		Self::ensure_maintainer(caller, &contract)?;
+		ensure!(
+			!UpgradeProposals::<T>::contains_key(contract),
+			Error::<T>::UpgradeAlreadyProposed
+		);
+
+		let bounded_code: BoundedVec<u8, MaxCodeSize> =
+			code.try_into().map_err(|_| Error::<T>::ContractExceedsMaxCodeSize)?;
+		let deposit = bounded_code.len() as u32;
+		Self::reserve_storage(caller, deposit)?;
+
+		let proposal = UpgradeProposal {
+			code_hash: Self::inc_code_ref(bounded_code),
+			enact_after: frame_system::Pallet::<T>::block_number().saturating_add(T::UpgradeDelay::get()),
+			proposer: *caller,
+			deposit,
+		};
+		UpgradeProposals::<T>::insert(contract, proposal);
+
+		Ok(proposal)
+	}
+
+	/// Enact the proposed code upgrade of a contract, the reference of the proposal to the new
+	/// code is moved to the contract. The deposit of the proposal is unreserved, and the storage
+	/// deposit of the code size change is charged from the caller instead.
+	fn do_enact_upgrade(caller: &EvmAddress, contract: EvmAddress) -> Result<H256, DispatchError> {
+		let proposal = Self::upgrade_proposals(contract).ok_or(Error::<T>::UpgradeNotProposed)?;
+		ensure!(
+			frame_system::Pallet::<T>::block_number() >= proposal.enact_after,
+			Error::<T>::UpgradeTimelocked
+		);
+		Self::unreserve_storage(&proposal.proposer, 0, 0, proposal.deposit)?;
+
+		Accounts::<T>::mutate(contract, |maybe_account_info| -> DispatchResult {
+			let contract_info = maybe_account_info
+				.as_mut()
+				.and_then(|account_info| account_info.contract_info.as_mut())
+				.ok_or(Error::<T>::ContractNotFound)?;
+			ensure!(contract_info.maintainer == *caller, Error::<T>::NoPermission);
+
+			let old_code_info = Self::code_infos(&contract_info.code_hash).ok_or(Error::<T>::ContractNotFound)?;
+			let new_code_info = Self::code_infos(&proposal.code_hash).ok_or(Error::<T>::ContractNotFound)?;
+
+			let storage_size_changed = new_code_info.code_size as i32 - old_code_info.code_size as i32;
+			if storage_size_changed.is_positive() {
+				Self::reserve_storage(caller, storage_size_changed as u32)?;
+			}
+			Self::charge_storage(caller, &contract, storage_size_changed)?;
+			if storage_size_changed.is_negative() {
+				Self::unreserve_storage(caller, 0, 0, storage_size_changed.unsigned_abs())?;
+			}
+			Self::update_contract_storage_size(&contract, storage_size_changed);
+
+			Self::dec_code_ref(&contract_info.code_hash);
+			contract_info.code_hash = proposal.code_hash;
+			Ok(())
+		})?;
+
+		UpgradeProposals::<T>::remove(contract);
+		Self::touch_contract(&contract);
+
+		Ok(proposal.code_hash)
+	}
+
+	/// Release the code reference and the storage deposit of a dropped upgrade proposal.
+	fn release_upgrade_proposal(proposal: &UpgradeProposal<T::BlockNumber>) -> DispatchResult {
+		Self::unreserve_storage(&proposal.proposer, 0, 0, proposal.deposit)?;
+		Self::dec_code_ref(&proposal.code_hash);
+		Ok(())
+	}
+
+	/// Execute an EVM call and deposit the execution event. Returns the post dispatch info and
+	/// the used storage.
+	fn execute_call(
+		source: EvmAddress,
+		target: EvmAddress,
+		input: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<AccessListItem>,
+	) -> (PostDispatchInfo, i32) {
+		let result = T::Runner::call(
+			source,
+			source,
+			target,
+			input,
+			value,
+			gas_limit,
+			storage_limit,
+			access_list.into_iter().map(|v| (v.address, v.storage_keys)).collect(),
+			T::config(),
+		);
+		let (used_gas, used_storage) = Self::deposit_call_event(source, target, gas_limit, result);
+
+		(
+			PostDispatchInfo {
+				actual_weight: used_gas.map(call_weight::<T>),
+				pays_fee: Pays::Yes,
+			},
+			used_storage,
+		)
+	}
+
+	/// Deposit the execution event of an EVM call. Returns the used gas if the call was
+	/// executed, and the used storage if the call succeeded.
+	fn deposit_call_event(
+		source: EvmAddress,
+		target: EvmAddress,
+		gas_limit: u64,
+		result: Result<CallInfo, DispatchError>,
+	) -> (Option<u64>, i32) {
+		match result {
+			Err(e) => {
+				Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed {
+					from: source,
+					contract: target,
+					exit_reason: ExitReason::Error(ExitError::Other(Into::<&str>::into(e).into())),
+					output: vec![],
+					logs: vec![],
+					used_gas: gas_limit,
+					used_storage: Default::default(),
+				});
+
+				(None, Default::default())
+			}
+			Ok(info) => {
+				let used_gas: u64 = info.used_gas.unique_saturated_into();
+				let mut used_storage = Default::default();
+
+				if info.exit_reason.is_succeed() {
+					used_storage = info.used_storage;
+					Pallet::<T>::deposit_event(Event::<T>::Executed {
+						from: source,
+						contract: target,
+						logs: info.logs,
+						used_gas,
+						used_storage,
+					});
+				} else {
+					Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed {
+						from: source,
+						contract: target,
+						exit_reason: info.exit_reason,
+						output: info.value,
+						logs: info.logs,
+						used_gas,
+						used_storage: Default::default(),
+					});
+				}
+
+				(Some(used_gas), used_storage)
+			}
+		}
+	}
+
+	/// Move `amount` from the sponsor budget of `sponsor` to its free balance, within the spend
+	/// limit of the current period.
+	fn release_sponsor_budget(sponsor: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		ensure!(
+			T::Currency::reserved_balance_named(&RESERVE_ID_SPONSOR_BUDGET, sponsor) >= amount,
+			Error::<T>::SponsorBudgetInsufficient
+		);
+		SponsorSpendings::<T>::try_mutate(sponsor, |maybe_spending| -> DispatchResult {
+			let spending = maybe_spending.as_mut().ok_or(Error::<T>::SponsorSpendLimitExceeded)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			if now >= spending.period_start.saturating_add(spending.period) {
+				spending.period_start = now;
+				spending.spent = Zero::zero();
+			}
+			let spent = spending.spent.saturating_add(amount);
+			ensure!(
+				spent <= spending.max_spend_per_period,
+				Error::<T>::SponsorSpendLimitExceeded
+			);
+			spending.spent = spent;
+			Ok(())
+		})?;
+
+		let err_amount = T::Currency::unreserve_named(&RESERVE_ID_SPONSOR_BUDGET, sponsor, amount);
+		debug_assert!(err_amount.is_zero());
+		Ok(())
+	}
+
+	/// Return the unused part `amount` of a released payment to the sponsor budget of
+	/// `sponsor`.
+	fn restore_sponsor_budget(sponsor: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		T::Currency::reserve_named(&RESERVE_ID_SPONSOR_BUDGET, sponsor, amount)?;
+		SponsorSpendings::<T>::mutate(sponsor, |maybe_spending| {
+			if let Some(spending) = maybe_spending {
+				spending.spent = spending.spent.saturating_sub(amount);
+			}
+		});
+		Ok(())
+	}
+
+	/// Record an access to an unpublished contract, postponing its reclamation.
+	pub fn touch_contract(contract: &EvmAddress) {
+		let now = frame_system::Pallet::<T>::block_number();
+		ContractLastAccessed::<T>::mutate_exists(contract, |maybe_last_accessed| {
+			if let Some(last_accessed) = maybe_last_accessed {
+				*last_accessed = now;
+			} else if matches!(
+				Self::accounts(contract).and_then(|account_info| account_info.contract_info),
+				Some(ContractInfo { published: false, .. })
+			) {
+				// created before the accesses were tracked
+				*maybe_last_accessed = Some(now);
+			}
+		});
+	}
+
 	fn ensure_root_or_signed(o: T::Origin) -> Result<Either<(), T::AccountId>, BadOrigin> {
 		EnsureOneOf::<EnsureRoot<T::AccountId>, EnsureSigned<T::AccountId>>::try_origin(o).map_or(Err(BadOrigin), Ok)
 	}
@@ -1769,6 +2790,10 @@ impl<T: Config> EVMTrait<T::AccountId> for Pallet<T> {
 	fn set_origin(origin: T::AccountId) {
 		ExtrinsicOrigin::<T>::set(Some(origin));
 	}
+
+	fn gas_to_weight(gas: u64) -> Weight {
+		T::GasToWeight::convert(gas)
+	}
 }
 
 impl<T: Config> EVMManager<T::AccountId, BalanceOf<T>> for Pallet<T> {
@@ -1818,6 +2843,33 @@ impl<T: Config> EVMManager<T::AccountId, BalanceOf<T>> for Pallet<T> {
 	}
 }
 
+impl<T: Config, C: IsSubType<Call<T>>> TransactionFeeSponsor<T::AccountId, C, BalanceOf<T>> for Pallet<T> {
+	/// Release the fee of `sponsored_call` from the sponsor budget, within the limits of the
+	/// sponsorship and the spend limit of the sponsor. Sponsored calls can not pay a tip.
+	fn sponsor_fee(
+		_who: &T::AccountId,
+		call: &C,
+		fee: BalanceOf<T>,
+		tip: BalanceOf<T>,
+	) -> Result<Option<T::AccountId>, DispatchError> {
+		if let Some(Call::sponsored_call { sponsor, target, .. }) = call.is_sub_type() {
+			let limits = Sponsorships::<T>::get(sponsor, target).ok_or(Error::<T>::NotSponsored)?;
+			ensure!(tip.is_zero(), Error::<T>::SponsoredTipNotAllowed);
+			ensure!(fee <= limits.max_fee_per_call, Error::<T>::SponsorLimitExceeded);
+
+			Self::release_sponsor_budget(sponsor, fee)?;
+			Ok(Some(sponsor.clone()))
+		} else {
+			Ok(None)
+		}
+	}
+
+	fn refund_fee(sponsor: &T::AccountId, refund: BalanceOf<T>) {
+		let res = Self::restore_sponsor_budget(sponsor, refund);
+		debug_assert!(res.is_ok());
+	}
+}
+
 pub struct CallKillAccount<T>(PhantomData<T>);
 impl<T: Config> OnKilledAccount<T::AccountId> for CallKillAccount<T> {
 	fn on_killed_account(who: &T::AccountId) {
@@ -1906,7 +2958,8 @@ impl<T: Config + Send + Sync> SignedExtension for SetEvmOrigin<T> {
 
 #[derive(Clone, RuntimeDebug, PartialEq, Encode, Decode, TypeInfo)]
 pub enum EvmTask<T: Config> {
-	// TODO: update
+	/// A contract call executed when the chain is idle. The fee for `gas_limit` is charged from
+	/// `from` when the call is dispatched, and the unused gas refunded.
 	Schedule {
 		from: EvmAddress,
 		target: EvmAddress,
@@ -1925,13 +2978,65 @@ pub enum EvmTask<T: Config> {
 impl<T: Config> DispatchableTask for EvmTask<T> {
 	fn dispatch(self, weight: Weight) -> TaskResult {
 		match self {
-			// TODO: update
-			EvmTask::Schedule { .. } => {
-				// check weight and call `scheduled_call`
+			EvmTask::Schedule {
+				from,
+				target,
+				input,
+				value,
+				gas_limit,
+				storage_limit,
+			} => {
+				let gas_weight = T::GasToWeight::convert(gas_limit);
+				if gas_weight > Pallet::<T>::max_scheduled_call_weight() {
+					// no block leaves enough idle weight, drop the call
+					Pallet::<T>::release_scheduled_call_fee(from, gas_limit);
+					Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed {
+						from,
+						contract: target,
+						exit_reason: ExitReason::Error(ExitError::OutOfGas),
+						output: vec![],
+						logs: vec![],
+						used_gas: 0,
+						used_storage: Default::default(),
+					});
+					return TaskResult {
+						result: Err(Error::<T>::ScheduledGasLimitTooHigh.into()),
+						used_weight: 0,
+						finished: true,
+					};
+				}
+
+				// wait for a block with enough idle weight for the whole gas limit
+				if gas_weight > weight {
+					return TaskResult {
+						result: Ok(()),
+						used_weight: 0,
+						finished: false,
+					};
+				}
+
+				let (used_weight, result) = match Pallet::<T>::do_scheduled_call(
+					from,
+					target,
+					input,
+					value,
+					gas_limit,
+					storage_limit,
+					vec![],
+				) {
+					Ok((used_gas, result)) => (T::GasToWeight::convert(used_gas), result),
+					Err(e) => (0, Err(e)),
+				};
+				log::debug!(
+					target: "evm",
+					"EvmTask::Schedule: [from: {:?}, target: {:?}, gas_limit: {:?}, result: {:?}]",
+					from, target, gas_limit, result
+				);
+
 				TaskResult {
-					result: Ok(()),
-					used_weight: 0,
-					finished: false,
+					result,
+					used_weight,
+					finished: true,
 				}
 			}
 			EvmTask::Remove {
@@ -0,0 +1,50 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use frame_support::{
+	log,
+	traits::{Get, GetStorageVersion, StorageVersion},
+	weights::Weight,
+};
+
+pub mod v1 {
+	use super::*;
+	use crate::*;
+
+	/// Track the accesses of the existing unpublished contracts from the current block, so they
+	/// only become reclaimable after a whole inactivity period. Their accesses are recorded
+	/// lazily, the contracts are not iterated.
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_storage_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_storage_version >= 1 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		ContractAccessTrackedSince::<T>::put(now);
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		log::info!(
+			target: "evm",
+			"Migrated to storage version 1, tracking the contract accesses since block {:?}",
+			now,
+		);
+
+		T::DbWeight::get().reads_writes(1, 2)
+	}
+}
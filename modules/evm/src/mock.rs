@@ -180,6 +180,8 @@ ord_parameter_types! {
 pub const NEW_CONTRACT_EXTRA_BYTES: u32 = 100;
 pub const DEVELOPER_DEPOSIT: u128 = 1000;
 pub const PUBLICATION_FEE: u128 = 200;
+pub const CONTRACT_INACTIVITY_PERIOD: u64 = 100;
+pub const UPGRADE_DELAY: u64 = 10;
 impl Config for Runtime {
 	type AddressMapping = MockAddressMapping;
 	type Currency = Balances;
@@ -199,8 +201,11 @@ impl Config for Runtime {
 	type NetworkContractSource = NetworkContractSource;
 	type DeveloperDeposit = ConstU128<DEVELOPER_DEPOSIT>;
 	type PublicationFee = ConstU128<PUBLICATION_FEE>;
+	type ContractInactivityPeriod = ConstU64<CONTRACT_INACTIVITY_PERIOD>;
+	type UpgradeDelay = ConstU64<UPGRADE_DELAY>;
 	type TreasuryAccount = TreasuryAccount;
 	type FreePublicationOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
+	type VerifierOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
 
 	type Runner = crate::runner::stack::Runner<Self>;
 	type FindAuthor = AuthorGiven;
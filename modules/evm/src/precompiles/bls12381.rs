@@ -0,0 +1,849 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::Precompile;
+use crate::runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult};
+use bls12_381::{
+	hash_to_curve::MapToCurve, multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, Gt,
+	Scalar,
+};
+use module_evm_utility::evm::{Context, ExitError, ExitSucceed};
+use sp_std::vec::Vec;
+
+// The field types are not exported by `bls12_381`, name them through the curve maps.
+type Fp = <G1Projective as MapToCurve>::Field;
+type Fp2 = <G2Projective as MapToCurve>::Field;
+
+// https://eips.ethereum.org/EIPS/eip-2537
+const FP_LENGTH: usize = 48;
+const PADDED_FP_LENGTH: usize = 64;
+const G1_LENGTH: usize = 2 * PADDED_FP_LENGTH;
+const G2_LENGTH: usize = 4 * PADDED_FP_LENGTH;
+const SCALAR_LENGTH: usize = 32;
+
+/// The MSM discounts, in thousandths, by the number of pairs.
+const G1_MSM_DISCOUNTS: [u16; 128] = [
+	1000, 949, 848, 797, 764, 750, 738, 728, 719, 712, 705, 698, 692, 687, 682, 677, 673, 669, 665, 661, 658, 654, 651,
+	648, 645, 642, 640, 637, 635, 632, 630, 627, 625, 623, 621, 619, 617, 615, 613, 611, 609, 608, 606, 604, 603, 601,
+	599, 598, 596, 595, 593, 592, 591, 589, 588, 586, 585, 584, 582, 581, 580, 579, 577, 576, 575, 574, 573, 572, 570,
+	569, 568, 567, 566, 565, 564, 563, 562, 561, 560, 559, 558, 557, 556, 555, 554, 553, 552, 551, 550, 549, 548, 547,
+	547, 546, 545, 544, 543, 542, 541, 540, 540, 539, 538, 537, 536, 536, 535, 534, 533, 532, 532, 531, 530, 529, 528,
+	528, 527, 526, 525, 525, 524, 523, 522, 522, 521, 520, 520, 519,
+];
+const G2_MSM_DISCOUNTS: [u16; 128] = [
+	1000, 1000, 923, 884, 855, 832, 812, 796, 782, 770, 759, 749, 740, 732, 724, 717, 711, 704, 699, 693, 688, 683,
+	679, 674, 670, 666, 663, 659, 655, 652, 649, 646, 643, 640, 637, 634, 632, 629, 627, 624, 622, 620, 618, 615, 613,
+	611, 609, 607, 606, 604, 602, 600, 598, 597, 595, 593, 592, 590, 589, 587, 586, 584, 583, 582, 580, 579, 578, 576,
+	575, 574, 573, 571, 570, 569, 568, 567, 566, 565, 563, 562, 561, 560, 559, 558, 557, 556, 555, 554, 553, 552, 552,
+	551, 550, 549, 548, 547, 546, 545, 545, 544, 543, 542, 541, 541, 540, 539, 538, 537, 537, 536, 535, 535, 534, 533,
+	532, 532, 531, 530, 530, 529, 528, 528, 527, 526, 526, 525, 524, 524,
+];
+
+fn error(reason: &'static str) -> PrecompileFailure {
+	PrecompileFailure::Error {
+		exit_status: ExitError::Other(reason.into()),
+	}
+}
+
+fn ensure_gas(target_gas: Option<u64>, cost: u64) -> Result<(), PrecompileFailure> {
+	if let Some(gas_left) = target_gas {
+		if gas_left < cost {
+			return Err(PrecompileFailure::Error {
+				exit_status: ExitError::OutOfGas,
+			});
+		}
+	}
+	Ok(())
+}
+
+/// The gas cost of a MSM of `k` pairs.
+fn msm_cost(k: usize, multiplier: u64, discounts: &[u16; 128]) -> u64 {
+	let discount = discounts.get(k.saturating_sub(1)).unwrap_or(&discounts[127]);
+	(k as u64).saturating_mul(multiplier).saturating_mul(*discount as u64) / 1000
+}
+
+/// Read a big-endian field element, padded to 64 bytes.
+fn read_fp_bytes(input: &[u8]) -> Result<[u8; FP_LENGTH], PrecompileFailure> {
+	let (padding, fp) = input.split_at(PADDED_FP_LENGTH - FP_LENGTH);
+	if padding.iter().any(|b| *b != 0) {
+		return Err(error("Invalid field element padding"));
+	}
+
+	let mut buf = [0u8; FP_LENGTH];
+	buf.copy_from_slice(fp);
+	// ensure the field element is canonical, so no encoding flag is set
+	if bool::from(Fp::from_bytes(&buf).is_none()) {
+		return Err(error("Invalid field element"));
+	}
+	Ok(buf)
+}
+
+fn read_fp(input: &[u8]) -> Result<Fp, PrecompileFailure> {
+	Option::from(Fp::from_bytes(&read_fp_bytes(input)?)).ok_or_else(|| error("Invalid field element"))
+}
+
+/// Read a G1 point, the point at infinity is encoded as zeros.
+fn read_g1(input: &[u8], subgroup_check: bool) -> Result<G1Affine, PrecompileFailure> {
+	let x = read_fp_bytes(&input[..PADDED_FP_LENGTH])?;
+	let y = read_fp_bytes(&input[PADDED_FP_LENGTH..G1_LENGTH])?;
+	if input.iter().all(|b| *b == 0) {
+		return Ok(G1Affine::identity());
+	}
+
+	let mut buf = [0u8; 2 * FP_LENGTH];
+	buf[..FP_LENGTH].copy_from_slice(&x);
+	buf[FP_LENGTH..].copy_from_slice(&y);
+	let point: G1Affine =
+		Option::from(G1Affine::from_uncompressed_unchecked(&buf)).ok_or_else(|| error("Invalid point"))?;
+	if !bool::from(point.is_on_curve()) {
+		return Err(error("Invalid point - not on curve"));
+	}
+	if subgroup_check && !bool::from(point.is_torsion_free()) {
+		return Err(error("Invalid point - not in subgroup"));
+	}
+	Ok(point)
+}
+
+/// Read a G2 point, the point at infinity is encoded as zeros.
+fn read_g2(input: &[u8], subgroup_check: bool) -> Result<G2Affine, PrecompileFailure> {
+	let x_c0 = read_fp_bytes(&input[..PADDED_FP_LENGTH])?;
+	let x_c1 = read_fp_bytes(&input[PADDED_FP_LENGTH..2 * PADDED_FP_LENGTH])?;
+	let y_c0 = read_fp_bytes(&input[2 * PADDED_FP_LENGTH..3 * PADDED_FP_LENGTH])?;
+	let y_c1 = read_fp_bytes(&input[3 * PADDED_FP_LENGTH..G2_LENGTH])?;
+	if input.iter().all(|b| *b == 0) {
+		return Ok(G2Affine::identity());
+	}
+
+	// the uncompressed encoding puts c1 first
+	let mut buf = [0u8; 4 * FP_LENGTH];
+	buf[..FP_LENGTH].copy_from_slice(&x_c1);
+	buf[FP_LENGTH..2 * FP_LENGTH].copy_from_slice(&x_c0);
+	buf[2 * FP_LENGTH..3 * FP_LENGTH].copy_from_slice(&y_c1);
+	buf[3 * FP_LENGTH..].copy_from_slice(&y_c0);
+	let point: G2Affine =
+		Option::from(G2Affine::from_uncompressed_unchecked(&buf)).ok_or_else(|| error("Invalid point"))?;
+	if !bool::from(point.is_on_curve()) {
+		return Err(error("Invalid point - not on curve"));
+	}
+	if subgroup_check && !bool::from(point.is_torsion_free()) {
+		return Err(error("Invalid point - not in subgroup"));
+	}
+	Ok(point)
+}
+
+/// Read a big-endian scalar, it is not required to be less than the group order.
+fn read_scalar(input: &[u8]) -> Scalar {
+	let mut buf = [0u8; 2 * SCALAR_LENGTH];
+	for (i, b) in input.iter().rev().enumerate() {
+		buf[i] = *b;
+	}
+	Scalar::from_bytes_wide(&buf)
+}
+
+fn encode_g1(point: G1Affine) -> Vec<u8> {
+	let mut output = vec![0u8; G1_LENGTH];
+	if !bool::from(point.is_identity()) {
+		let bytes = point.to_uncompressed();
+		output[PADDED_FP_LENGTH - FP_LENGTH..PADDED_FP_LENGTH].copy_from_slice(&bytes[..FP_LENGTH]);
+		output[G1_LENGTH - FP_LENGTH..].copy_from_slice(&bytes[FP_LENGTH..]);
+	}
+	output
+}
+
+fn encode_g2(point: G2Affine) -> Vec<u8> {
+	let mut output = vec![0u8; G2_LENGTH];
+	if !bool::from(point.is_identity()) {
+		let bytes = point.to_uncompressed();
+		// c1 comes first in the uncompressed encoding
+		for (i, j) in [(0, 1), (1, 0), (2, 3), (3, 2)] {
+			let start = i * PADDED_FP_LENGTH + PADDED_FP_LENGTH - FP_LENGTH;
+			output[start..start + FP_LENGTH].copy_from_slice(&bytes[j * FP_LENGTH..(j + 1) * FP_LENGTH]);
+		}
+	}
+	output
+}
+
+/// The Bls12381G1Add builtin
+pub struct Bls12381G1Add;
+
+impl Bls12381G1Add {
+	const GAS_COST: u64 = 375;
+}
+
+impl Precompile for Bls12381G1Add {
+	fn execute(input: &[u8], target_gas: Option<u64>, _context: &Context, _is_static: bool) -> PrecompileResult {
+		ensure_gas(target_gas, Bls12381G1Add::GAS_COST)?;
+
+		if input.len() != 2 * G1_LENGTH {
+			return Err(error("Invalid input length, must be 256"));
+		}
+
+		let p1 = read_g1(&input[..G1_LENGTH], false)?;
+		let p2 = read_g1(&input[G1_LENGTH..], false)?;
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: Bls12381G1Add::GAS_COST,
+			output: encode_g1((G1Projective::from(p1) + p2).into()),
+			logs: Default::default(),
+		})
+	}
+}
+
+/// The Bls12381G1Msm builtin
+pub struct Bls12381G1Msm;
+
+impl Bls12381G1Msm {
+	const MULTIPLIER: u64 = 12_000;
+}
+
+impl Precompile for Bls12381G1Msm {
+	fn execute(input: &[u8], target_gas: Option<u64>, _context: &Context, _is_static: bool) -> PrecompileResult {
+		const PAIR_LENGTH: usize = G1_LENGTH + SCALAR_LENGTH;
+
+		if input.is_empty() || input.len() % PAIR_LENGTH != 0 {
+			return Err(error("Invalid input length, must be multiple of 160"));
+		}
+
+		let k = input.len() / PAIR_LENGTH;
+		let gas_cost = msm_cost(k, Bls12381G1Msm::MULTIPLIER, &G1_MSM_DISCOUNTS);
+		ensure_gas(target_gas, gas_cost)?;
+
+		let mut sum = G1Projective::identity();
+		for pair in input.chunks_exact(PAIR_LENGTH) {
+			let point = read_g1(&pair[..G1_LENGTH], true)?;
+			sum += point * read_scalar(&pair[G1_LENGTH..]);
+		}
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gas_cost,
+			output: encode_g1(sum.into()),
+			logs: Default::default(),
+		})
+	}
+}
+
+/// The Bls12381G2Add builtin
+pub struct Bls12381G2Add;
+
+impl Bls12381G2Add {
+	const GAS_COST: u64 = 600;
+}
+
+impl Precompile for Bls12381G2Add {
+	fn execute(input: &[u8], target_gas: Option<u64>, _context: &Context, _is_static: bool) -> PrecompileResult {
+		ensure_gas(target_gas, Bls12381G2Add::GAS_COST)?;
+
+		if input.len() != 2 * G2_LENGTH {
+			return Err(error("Invalid input length, must be 512"));
+		}
+
+		let p1 = read_g2(&input[..G2_LENGTH], false)?;
+		let p2 = read_g2(&input[G2_LENGTH..], false)?;
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: Bls12381G2Add::GAS_COST,
+			output: encode_g2((G2Projective::from(p1) + p2).into()),
+			logs: Default::default(),
+		})
+	}
+}
+
+/// The Bls12381G2Msm builtin
+pub struct Bls12381G2Msm;
+
+impl Bls12381G2Msm {
+	const MULTIPLIER: u64 = 22_500;
+}
+
+impl Precompile for Bls12381G2Msm {
+	fn execute(input: &[u8], target_gas: Option<u64>, _context: &Context, _is_static: bool) -> PrecompileResult {
+		const PAIR_LENGTH: usize = G2_LENGTH + SCALAR_LENGTH;
+
+		if input.is_empty() || input.len() % PAIR_LENGTH != 0 {
+			return Err(error("Invalid input length, must be multiple of 288"));
+		}
+
+		let k = input.len() / PAIR_LENGTH;
+		let gas_cost = msm_cost(k, Bls12381G2Msm::MULTIPLIER, &G2_MSM_DISCOUNTS);
+		ensure_gas(target_gas, gas_cost)?;
+
+		let mut sum = G2Projective::identity();
+		for pair in input.chunks_exact(PAIR_LENGTH) {
+			let point = read_g2(&pair[..G2_LENGTH], true)?;
+			sum += point * read_scalar(&pair[G2_LENGTH..]);
+		}
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gas_cost,
+			output: encode_g2(sum.into()),
+			logs: Default::default(),
+		})
+	}
+}
+
+/// The Bls12381Pairing builtin
+pub struct Bls12381Pairing;
+
+impl Bls12381Pairing {
+	const BASE_GAS_COST: u64 = 37_700;
+	const GAS_COST_PER_PAIRING: u64 = 32_600;
+}
+
+impl Precompile for Bls12381Pairing {
+	fn execute(input: &[u8], target_gas: Option<u64>, _context: &Context, _is_static: bool) -> PrecompileResult {
+		const PAIR_LENGTH: usize = G1_LENGTH + G2_LENGTH;
+
+		if input.is_empty() || input.len() % PAIR_LENGTH != 0 {
+			return Err(error("Invalid input length, must be multiple of 384"));
+		}
+
+		let k = input.len() / PAIR_LENGTH;
+		let gas_cost = Bls12381Pairing::GAS_COST_PER_PAIRING
+			.saturating_mul(k as u64)
+			.saturating_add(Bls12381Pairing::BASE_GAS_COST);
+		ensure_gas(target_gas, gas_cost)?;
+
+		let mut pairs = Vec::with_capacity(k);
+		for pair in input.chunks_exact(PAIR_LENGTH) {
+			let a = read_g1(&pair[..G1_LENGTH], true)?;
+			let b = read_g2(&pair[G1_LENGTH..], true)?;
+			pairs.push((a, G2Prepared::from(b)));
+		}
+		let terms = pairs.iter().map(|(a, b)| (a, b)).collect::<Vec<_>>();
+
+		let mut buf = [0u8; 32];
+		if multi_miller_loop(&terms).final_exponentiation() == Gt::identity() {
+			buf[31] = 1;
+		}
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gas_cost,
+			output: buf.to_vec(),
+			logs: Default::default(),
+		})
+	}
+}
+
+/// The Bls12381MapG1 builtin
+pub struct Bls12381MapG1;
+
+impl Bls12381MapG1 {
+	const GAS_COST: u64 = 5_500;
+}
+
+impl Precompile for Bls12381MapG1 {
+	fn execute(input: &[u8], target_gas: Option<u64>, _context: &Context, _is_static: bool) -> PrecompileResult {
+		ensure_gas(target_gas, Bls12381MapG1::GAS_COST)?;
+
+		if input.len() != PADDED_FP_LENGTH {
+			return Err(error("Invalid input length, must be 64"));
+		}
+
+		let fp = read_fp(input)?;
+		let point = <G1Projective as MapToCurve>::map_to_curve(&fp).clear_h();
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: Bls12381MapG1::GAS_COST,
+			output: encode_g1(point.into()),
+			logs: Default::default(),
+		})
+	}
+}
+
+/// The Bls12381MapG2 builtin
+pub struct Bls12381MapG2;
+
+impl Bls12381MapG2 {
+	const GAS_COST: u64 = 23_800;
+}
+
+impl Precompile for Bls12381MapG2 {
+	fn execute(input: &[u8], target_gas: Option<u64>, _context: &Context, _is_static: bool) -> PrecompileResult {
+		ensure_gas(target_gas, Bls12381MapG2::GAS_COST)?;
+
+		if input.len() != 2 * PADDED_FP_LENGTH {
+			return Err(error("Invalid input length, must be 128"));
+		}
+
+		let fp2 = Fp2 {
+			c0: read_fp(&input[..PADDED_FP_LENGTH])?,
+			c1: read_fp(&input[PADDED_FP_LENGTH..])?,
+		};
+		let point = <G2Projective as MapToCurve>::map_to_curve(&fp2).clear_h();
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: Bls12381MapG2::GAS_COST,
+			output: encode_g2(point.into()),
+			logs: Default::default(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use hex_literal::hex;
+	use sp_core::U256;
+
+	fn get_context() -> Context {
+		Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: U256::zero(),
+		}
+	}
+
+	#[test]
+	fn bls12_381_g1_add() {
+		// G + G = 2G
+		{
+			let input = hex! {"
+			0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb
+			0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1
+			0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb
+			0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1
+		"};
+
+			let expected = hex! {"
+			000000000000000000000000000000000572cbea904d67468808c8eb50a9450c9721db309128012543902d0ac358a62ae28f75bb8f1c7c42c39a8c5529bf0f4e
+			00000000000000000000000000000000166a9d8cabc673a322fda673779d8e3822ba3ecb8670e461f73bb9021d5fd76a4c56d9d4cd16bd1bba86881979749d28
+		"};
+
+			let resp = Bls12381G1Add::execute(&input[..], None, &get_context(), false).unwrap();
+			assert_eq!(resp.output, expected);
+			assert_eq!(resp.cost, 375);
+		}
+
+		// G + (-G) = 0
+		{
+			let input = hex! {"
+			0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb
+			0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1
+			0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb
+			00000000000000000000000000000000114d1d6855d545a8aa7d76c8cf2e21f267816aef1db507c96655b9d5caac42364e6f38ba0ecb751bad54dcd6b939c2ca
+		"};
+
+			assert_eq!(
+				Bls12381G1Add::execute(&input[..], None, &get_context(), false)
+					.unwrap()
+					.output,
+				[0u8; 128]
+			);
+		}
+
+		// G + 0 = G, no subgroup check
+		{
+			let input = hex! {"
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004
+			000000000000000000000000000000000a989badd40d6212b33cffc3f3763e9bc760f988c9926b26da9dd85e928483446346b8ed00e1de5d5ea93e354abe706c
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000
+		"};
+
+			let expected = hex! {"
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004
+			000000000000000000000000000000000a989badd40d6212b33cffc3f3763e9bc760f988c9926b26da9dd85e928483446346b8ed00e1de5d5ea93e354abe706c
+		"};
+
+			assert_eq!(
+				Bls12381G1Add::execute(&input[..], None, &get_context(), false)
+					.unwrap()
+					.output,
+				expected
+			);
+		}
+
+		// should fail - point not on curve
+		{
+			let mut input = [0u8; 256];
+			input[63] = 1;
+			input[127] = 1;
+
+			assert_eq!(
+				Bls12381G1Add::execute(&input[..], None, &get_context(), false),
+				Err(error("Invalid point - not on curve"))
+			);
+		}
+
+		// should fail - invalid padding
+		{
+			let mut input = [0u8; 256];
+			input[0] = 1;
+
+			assert_eq!(
+				Bls12381G1Add::execute(&input[..], None, &get_context(), false),
+				Err(error("Invalid field element padding"))
+			);
+		}
+
+		// should fail - invalid input length
+		assert_eq!(
+			Bls12381G1Add::execute(&[0u8; 255], None, &get_context(), false),
+			Err(error("Invalid input length, must be 256"))
+		);
+	}
+
+	#[test]
+	fn bls12_381_g1_msm() {
+		// 2 * G + 1 * G = 3G
+		{
+			let input = hex! {"
+			0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb
+			0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1
+			0000000000000000000000000000000000000000000000000000000000000002
+			0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb
+			0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1
+			0000000000000000000000000000000000000000000000000000000000000001
+		"};
+
+			let expected = hex! {"
+			0000000000000000000000000000000009ece308f9d1f0131765212deca99697b112d61f9be9a5f1f3780a51335b3ff981747a0b2ca2179b96d2c0c9024e5224
+			00000000000000000000000000000000032b80d3a6f5b09f8a84623389c5f80ca69a0cddabc3097f9d9c27310fd43be6e745256c634af45ca3473b0590ae30d1
+		"};
+
+			let resp = Bls12381G1Msm::execute(&input[..], None, &get_context(), false).unwrap();
+			assert_eq!(resp.output, expected);
+			assert_eq!(resp.cost, 2 * 12_000 * 949 / 1000);
+		}
+
+		// the scalar is not required to be less than the group order, r * G = 0
+		{
+			let input = hex! {"
+			0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb
+			0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1
+			73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001
+		"};
+
+			let resp = Bls12381G1Msm::execute(&input[..], None, &get_context(), false).unwrap();
+			assert_eq!(resp.output, [0u8; 128]);
+			assert_eq!(resp.cost, 12_000);
+		}
+
+		// should fail - point not in subgroup
+		{
+			let input = hex! {"
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004
+			000000000000000000000000000000000a989badd40d6212b33cffc3f3763e9bc760f988c9926b26da9dd85e928483446346b8ed00e1de5d5ea93e354abe706c
+			0000000000000000000000000000000000000000000000000000000000000001
+		"};
+
+			assert_eq!(
+				Bls12381G1Msm::execute(&input[..], None, &get_context(), false),
+				Err(error("Invalid point - not in subgroup"))
+			);
+		}
+
+		// should fail - empty input
+		assert_eq!(
+			Bls12381G1Msm::execute(&[], None, &get_context(), false),
+			Err(error("Invalid input length, must be multiple of 160"))
+		);
+
+		// should fail - out of gas
+		assert_eq!(
+			Bls12381G1Msm::execute(&[0u8; 160], Some(11_999), &get_context(), false),
+			Err(PrecompileFailure::Error {
+				exit_status: ExitError::OutOfGas
+			})
+		);
+	}
+
+	#[test]
+	fn bls12_381_g2_add() {
+		// G + G = 2G
+		{
+			let input = hex! {"
+			00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8
+			0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e
+			000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801
+			000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be
+			00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8
+			0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e
+			000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801
+			000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be
+		"};
+
+			let expected = hex! {"
+			000000000000000000000000000000001638533957d540a9d2370f17cc7ed5863bc0b995b8825e0ee1ea1e1e4d00dbae81f14b0bf3611b78c952aacab827a053
+			000000000000000000000000000000000a4edef9c1ed7f729f520e47730a124fd70662a904ba1074728114d1031e1572c6c886f6b57ec72a6178288c47c33577
+			000000000000000000000000000000000468fb440d82b0630aeb8dca2b5256789a66da69bf91009cbfe6bd221e47aa8ae88dece9764bf3bd999d95d71e4c9899
+			000000000000000000000000000000000f6d4552fa65dd2638b361543f887136a43253d9c66c411697003f7a13c308f5422e1aa0a59c8967acdefd8b6e36ccf3
+		"};
+
+			let resp = Bls12381G2Add::execute(&input[..], None, &get_context(), false).unwrap();
+			assert_eq!(resp.output, expected);
+			assert_eq!(resp.cost, 600);
+		}
+
+		// G + (-G) = 0
+		{
+			let input = hex! {"
+			00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8
+			0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e
+			000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801
+			000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be
+			00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8
+			0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e
+			000000000000000000000000000000000d1b3cc2c7027888be51d9ef691d77bcb679afda66c73f17f9ee3837a55024f78c71363275a75d75d86bab79f74782aa
+			0000000000000000000000000000000013fa4d4a0ad8b1ce186ed5061789213d993923066dddaf1040bc3ff59f825c78df74f2d75467e25e0f55f8a00fa030ed
+		"};
+
+			assert_eq!(
+				Bls12381G2Add::execute(&input[..], None, &get_context(), false)
+					.unwrap()
+					.output,
+				[0u8; 256]
+			);
+		}
+
+		// G + 0 = G, no subgroup check
+		{
+			let input = hex! {"
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000
+			00000000000000000000000000000000013a59858b6809fca4d9a3b6539246a70051a3c88899964a42bc9a69cf9acdd9dd387cfa9086b894185b9a46a402be73
+			0000000000000000000000000000000002d27e0ec3356299a346a09ad7dc4ef68a483c3aed53f9139d2f929a3eecebf72082e5e58c6da24ee32e03040c406d4f
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000
+		"};
+
+			let expected = hex! {"
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000
+			00000000000000000000000000000000013a59858b6809fca4d9a3b6539246a70051a3c88899964a42bc9a69cf9acdd9dd387cfa9086b894185b9a46a402be73
+			0000000000000000000000000000000002d27e0ec3356299a346a09ad7dc4ef68a483c3aed53f9139d2f929a3eecebf72082e5e58c6da24ee32e03040c406d4f
+		"};
+
+			assert_eq!(
+				Bls12381G2Add::execute(&input[..], None, &get_context(), false)
+					.unwrap()
+					.output,
+				expected
+			);
+		}
+
+		// should fail - point not on curve
+		{
+			let mut input = [0u8; 512];
+			input[63] = 1;
+			input[191] = 1;
+
+			assert_eq!(
+				Bls12381G2Add::execute(&input[..], None, &get_context(), false),
+				Err(error("Invalid point - not on curve"))
+			);
+		}
+
+		// should fail - invalid input length
+		assert_eq!(
+			Bls12381G2Add::execute(&[0u8; 511], None, &get_context(), false),
+			Err(error("Invalid input length, must be 512"))
+		);
+	}
+
+	#[test]
+	fn bls12_381_g2_msm() {
+		// 2 * G + 1 * G = 3G
+		{
+			let input = hex! {"
+			00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8
+			0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e
+			000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801
+			000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be
+			0000000000000000000000000000000000000000000000000000000000000002
+			00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8
+			0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e
+			000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801
+			000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be
+			0000000000000000000000000000000000000000000000000000000000000001
+		"};
+
+			let expected = hex! {"
+			00000000000000000000000000000000122915c824a0857e2ee414a3dccb23ae691ae54329781315a0c75df1c04d6d7a50a030fc866f09d516020ef82324afae
+			0000000000000000000000000000000009380275bbc8e5dcea7dc4dd7e0550ff2ac480905396eda55062650f8d251c96eb480673937cc6d9d6a44aaa56ca66dc
+			000000000000000000000000000000000b21da7955969e61010c7a1abc1a6f0136961d1e3b20b1a7326ac738fef5c721479dfd948b52fdf2455e44813ecfd892
+			0000000000000000000000000000000008f239ba329b3967fe48d718a36cfe5f62a7e42e0bf1c1ed714150a166bfbd6bcf6b3b58b975b9edea56d53f23a0e849
+		"};
+
+			let resp = Bls12381G2Msm::execute(&input[..], None, &get_context(), false).unwrap();
+			assert_eq!(resp.output, expected);
+			assert_eq!(resp.cost, 2 * 22_500);
+		}
+
+		// should fail - point not in subgroup
+		{
+			let input = hex! {"
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000
+			00000000000000000000000000000000013a59858b6809fca4d9a3b6539246a70051a3c88899964a42bc9a69cf9acdd9dd387cfa9086b894185b9a46a402be73
+			0000000000000000000000000000000002d27e0ec3356299a346a09ad7dc4ef68a483c3aed53f9139d2f929a3eecebf72082e5e58c6da24ee32e03040c406d4f
+			0000000000000000000000000000000000000000000000000000000000000001
+		"};
+
+			assert_eq!(
+				Bls12381G2Msm::execute(&input[..], None, &get_context(), false),
+				Err(error("Invalid point - not in subgroup"))
+			);
+		}
+
+		// should fail - empty input
+		assert_eq!(
+			Bls12381G2Msm::execute(&[], None, &get_context(), false),
+			Err(error("Invalid input length, must be multiple of 288"))
+		);
+	}
+
+	#[test]
+	fn bls12_381_pairing() {
+		// e(G1, G2) * e(-G1, G2) = 1
+		{
+			let input = hex! {"
+			0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb
+			0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1
+			00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8
+			0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e
+			000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801
+			000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be
+			0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb
+			00000000000000000000000000000000114d1d6855d545a8aa7d76c8cf2e21f267816aef1db507c96655b9d5caac42364e6f38ba0ecb751bad54dcd6b939c2ca
+			00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8
+			0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e
+			000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801
+			000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be
+		"};
+
+			let expected = hex! {"
+				0000000000000000000000000000000000000000000000000000000000000001
+			"};
+
+			let resp = Bls12381Pairing::execute(&input[..], None, &get_context(), false).unwrap();
+			assert_eq!(resp.output, expected);
+			assert_eq!(resp.cost, 37_700 + 2 * 32_600);
+		}
+
+		// e(G1, G2) != 1
+		{
+			let input = hex! {"
+			0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb
+			0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1
+			00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8
+			0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e
+			000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801
+			000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be
+		"};
+
+			assert_eq!(
+				Bls12381Pairing::execute(&input[..], None, &get_context(), false)
+					.unwrap()
+					.output,
+				[0u8; 32]
+			);
+		}
+
+		// e(0, G2) = 1
+		{
+			let input = hex! {"
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000
+			00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8
+			0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e
+			000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801
+			000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be
+		"};
+
+			let expected = hex! {"
+				0000000000000000000000000000000000000000000000000000000000000001
+			"};
+
+			assert_eq!(
+				Bls12381Pairing::execute(&input[..], None, &get_context(), false)
+					.unwrap()
+					.output,
+				expected
+			);
+		}
+
+		// should fail - point not in subgroup
+		{
+			let input = hex! {"
+			00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004
+			000000000000000000000000000000000a989badd40d6212b33cffc3f3763e9bc760f988c9926b26da9dd85e928483446346b8ed00e1de5d5ea93e354abe706c
+			00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8
+			0000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e
+			000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801
+			000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be
+		"};
+
+			assert_eq!(
+				Bls12381Pairing::execute(&input[..], None, &get_context(), false),
+				Err(error("Invalid point - not in subgroup"))
+			);
+		}
+
+		// should fail - empty input
+		assert_eq!(
+			Bls12381Pairing::execute(&[], None, &get_context(), false),
+			Err(error("Invalid input length, must be multiple of 384"))
+		);
+	}
+
+	#[test]
+	fn bls12_381_map_to_curve() {
+		// the mapped points are in the subgroups
+		let resp = Bls12381MapG1::execute(&[0u8; 64], None, &get_context(), false).unwrap();
+		assert_eq!(resp.cost, 5_500);
+		assert!(read_g1(&resp.output, true).is_ok());
+
+		let mut input = [0u8; 64];
+		input[16..].copy_from_slice(&[0x11u8; 48]);
+		let resp = Bls12381MapG1::execute(&input, None, &get_context(), false).unwrap();
+		assert!(read_g1(&resp.output, true).is_ok());
+		assert_ne!(resp.output, [0u8; 128]);
+
+		let mut input = [0u8; 128];
+		input[16..64].copy_from_slice(&[0x11u8; 48]);
+		input[80..].copy_from_slice(&[0x12u8; 48]);
+		let resp = Bls12381MapG2::execute(&input, None, &get_context(), false).unwrap();
+		assert_eq!(resp.cost, 23_800);
+		assert!(read_g2(&resp.output, true).is_ok());
+		assert_ne!(resp.output, [0u8; 256]);
+
+		// should fail - field element not less than the modulus
+		let mut input = [0u8; 64];
+		input[16..].copy_from_slice(&[0xffu8; 48]);
+		assert_eq!(
+			Bls12381MapG1::execute(&input, None, &get_context(), false),
+			Err(error("Invalid field element"))
+		);
+
+		// should fail - invalid input length
+		assert_eq!(
+			Bls12381MapG2::execute(&[0u8; 64], None, &get_context(), false),
+			Err(error("Invalid input length, must be 128"))
+		);
+	}
+}
@@ -23,21 +23,27 @@ use module_evm_utility::evm::{Context, ExitError, ExitSucceed};
 use sp_std::vec::Vec;
 
 mod blake2;
+mod bls12381;
 mod bn128;
 mod ecrecover;
 mod ecrecover_publickey;
 mod identity;
 mod modexp;
+mod p256verify;
 mod ripemd;
 mod sha256;
 mod sha3fips;
 
 pub use blake2::Blake2F;
+pub use bls12381::{
+	Bls12381G1Add, Bls12381G1Msm, Bls12381G2Add, Bls12381G2Msm, Bls12381MapG1, Bls12381MapG2, Bls12381Pairing,
+};
 pub use bn128::{Bn128Add, Bn128Mul, Bn128Pairing};
 pub use ecrecover::ECRecover;
 pub use ecrecover_publickey::ECRecoverPublicKey;
 pub use identity::Identity;
 pub use modexp::{IstanbulModexp, Modexp};
+pub use p256verify::P256Verify;
 pub use ripemd::Ripemd160;
 pub use sha256::Sha256;
 pub use sha3fips::{Sha3FIPS256, Sha3FIPS512};
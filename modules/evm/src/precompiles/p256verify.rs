@@ -0,0 +1,187 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::Precompile;
+use crate::runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult};
+use ecdsa::hazmat::VerifyPrimitive;
+use module_evm_utility::evm::{Context, ExitError, ExitSucceed};
+use p256::{
+	ecdsa::Signature,
+	elliptic_curve::{ops::Reduce, sec1::FromEncodedPoint},
+	EncodedPoint, FieldBytes, PublicKey, Scalar, U256,
+};
+use sp_std::vec::Vec;
+
+/// The P256Verify builtin, verifies a secp256r1 signature of a message hash.
+///
+/// https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md
+pub struct P256Verify;
+
+impl P256Verify {
+	const GAS_COST: u64 = 3_450;
+	const INPUT_LENGTH: usize = 160;
+
+	/// The input is `hash | r | s | x | y`.
+	fn verify(input: &[u8]) -> bool {
+		if input.len() != P256Verify::INPUT_LENGTH {
+			return false;
+		}
+
+		let signature = match Signature::from_scalars(
+			*FieldBytes::from_slice(&input[32..64]),
+			*FieldBytes::from_slice(&input[64..96]),
+		) {
+			Ok(signature) => signature,
+			Err(_) => return false,
+		};
+		let public_key = EncodedPoint::from_affine_coordinates(
+			FieldBytes::from_slice(&input[96..128]),
+			FieldBytes::from_slice(&input[128..160]),
+			false,
+		);
+
+		// the hash is taken as a field element, reduced by the curve order
+		let hash = <Scalar as Reduce<U256>>::from_be_bytes_reduced(*FieldBytes::from_slice(&input[..32]));
+
+		Option::<PublicKey>::from(PublicKey::from_encoded_point(&public_key)).map_or(false, |public_key| {
+			public_key.as_affine().verify_prehashed(hash, &signature).is_ok()
+		})
+	}
+}
+
+impl Precompile for P256Verify {
+	fn execute(input: &[u8], target_gas: Option<u64>, _context: &Context, _is_static: bool) -> PrecompileResult {
+		if let Some(gas_left) = target_gas {
+			if gas_left < P256Verify::GAS_COST {
+				return Err(PrecompileFailure::Error {
+					exit_status: ExitError::OutOfGas,
+				});
+			}
+		}
+
+		// returns nothing if the signature is invalid
+		let output = if P256Verify::verify(input) {
+			let mut buf = [0u8; 32];
+			buf[31] = 1;
+			buf.to_vec()
+		} else {
+			Vec::new()
+		};
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: P256Verify::GAS_COST,
+			output,
+			logs: Default::default(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use hex_literal::hex;
+	use sp_core::U256;
+
+	fn get_context() -> Context {
+		Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: U256::zero(),
+		}
+	}
+
+	#[test]
+	fn p256_verify() {
+		let expected = hex! {"
+			0000000000000000000000000000000000000000000000000000000000000001
+		"};
+
+		// https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md#test-cases
+		let input = hex! {"
+			4cee90eb86eaa050036147a12d49004b6b9c72bd725d39d4785011fe190f0b4d
+			a73bd4903f0ce3b639bbbf6e8e80d16931ff4bcf5993d58468e8fb19086e8cac
+			36dbcd03009df8c59286b162af3bd7fcc0450c9aa81be5d10d312af6c66b1d60
+			4aebd3099c618202fcfe16ae7770b0c49ab5eadf74b754204a3bb6060e44eff3
+			7618b065f9832de4ca6ca971a7a1adc826d0f7c00181a5fb2ddf79ae00b4e10e
+		"};
+		let resp = P256Verify::execute(&input[..], None, &get_context(), false).unwrap();
+		assert_eq!(resp.output, expected);
+		assert_eq!(resp.cost, 3_450);
+
+		// RFC 6979 A.2.5, SHA-256 of "sample"
+		let input = hex! {"
+			af2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a62add1bf
+			efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716
+			f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8
+			60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6
+			7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299
+		"};
+		assert_eq!(
+			P256Verify::execute(&input[..], None, &get_context(), false)
+				.unwrap()
+				.output,
+			expected
+		);
+
+		// invalid signature
+		let mut invalid = input;
+		invalid[0] ^= 1;
+		assert_eq!(
+			P256Verify::execute(&invalid[..], None, &get_context(), false)
+				.unwrap()
+				.output,
+			Vec::<u8>::new()
+		);
+
+		// invalid public key
+		let mut invalid = input;
+		invalid[159] ^= 1;
+		assert_eq!(
+			P256Verify::execute(&invalid[..], None, &get_context(), false)
+				.unwrap()
+				.output,
+			Vec::<u8>::new()
+		);
+
+		// zero r
+		let mut invalid = input;
+		invalid[32..64].copy_from_slice(&[0u8; 32]);
+		assert_eq!(
+			P256Verify::execute(&invalid[..], None, &get_context(), false)
+				.unwrap()
+				.output,
+			Vec::<u8>::new()
+		);
+
+		// invalid input length
+		assert_eq!(
+			P256Verify::execute(&input[..159], None, &get_context(), false)
+				.unwrap()
+				.output,
+			Vec::<u8>::new()
+		);
+
+		assert_eq!(
+			P256Verify::execute(&input[..], Some(3_449), &get_context(), false),
+			Err(PrecompileFailure::Error {
+				exit_status: ExitError::OutOfGas
+			})
+		);
+	}
+}
@@ -0,0 +1,178 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Access list generation.
+//!
+//! The runner reports the accounts and storage slots accessed by an execution, and the storage
+//! it used for each contract, to the collector set by [`collect`].
+
+use crate::{
+	runner::{
+		stack::Runner,
+		state::{Accessed, PrecompileSet},
+		Runner as RunnerT,
+	},
+	BalanceOf, Config, ContractStorageSizes, Pallet,
+};
+use frame_support::{storage::with_transaction, traits::Get};
+use primitives::evm::{AccessListInfo, AccessListItem, StorageDepositInfo};
+use sp_core::{H160, H256};
+use sp_runtime::{
+	traits::{UniqueSaturatedInto, Zero},
+	DispatchError, TransactionOutcome,
+};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+/// The maximum number of executions to wait for the generated access list to settle.
+pub const MAX_ACCESS_LIST_ITERATIONS: u32 = 8;
+
+environmental::environmental!(collector: Collected);
+
+/// The state accessed by an EVM execution.
+#[derive(Default)]
+pub struct Collected {
+	pub accessed: Accessed,
+	/// The change of the storage used by each contract, in bytes.
+	pub storages: BTreeMap<H160, i32>,
+}
+
+/// Run `f` and return the state accessed by the outermost EVM execution it makes.
+pub fn collect<R, F: FnOnce() -> R>(f: F) -> (R, Collected) {
+	let mut collected = Collected::default();
+	let result = collector::using(&mut collected, f);
+	(result, collected)
+}
+
+/// Record the state accessed by an execution. The outermost execution finishes last, so it
+/// replaces the executions nested in precompiles.
+pub(crate) fn record(accessed: &Option<Accessed>, storage_logs: &[(H160, i32)]) {
+	collector::with(|collected| {
+		let mut storages = BTreeMap::<H160, i32>::new();
+		for (address, storage) in storage_logs {
+			let entry = storages.entry(*address).or_default();
+			*entry = entry.saturating_add(*storage);
+		}
+		*collected = Collected {
+			accessed: accessed.clone().unwrap_or_default(),
+			storages,
+		};
+	});
+}
+
+/// Build the access list of `accessed`. Like geth, the accounts in `excluded` and the
+/// precompiles are left out unless some of their storage slots were accessed.
+fn to_access_list<T: Config>(accessed: Accessed, excluded: &[H160]) -> Vec<AccessListItem> {
+	let precompiles = T::PrecompilesValue::get();
+	let mut items = BTreeMap::<H160, Vec<H256>>::new();
+	for address in accessed.accessed_addresses {
+		if !excluded.contains(&address) && !precompiles.is_precompile(address) {
+			items.entry(address).or_default();
+		}
+	}
+	for (address, key) in accessed.accessed_storage {
+		items.entry(address).or_default().push(key);
+	}
+	items
+		.into_iter()
+		.map(|(address, storage_keys)| AccessListItem { address, storage_keys })
+		.collect()
+}
+
+/// Estimate a call, or a create if `target` is `None`, and generate its access list and storage
+/// deposits. The execution is repeated with the generated access list until the list settles, and
+/// its changes are always discarded.
+pub fn create_access_list<T: Config>(
+	source: H160,
+	target: Option<H160>,
+	input: Vec<u8>,
+	value: BalanceOf<T>,
+	gas_limit: u64,
+	storage_limit: u32,
+	mut access_list: Vec<AccessListItem>,
+) -> Result<AccessListInfo, DispatchError> {
+	let mut config = T::config().clone();
+	config.estimate = true;
+
+	let mut iterations = 0;
+	loop {
+		let list = access_list
+			.iter()
+			.map(|item| (item.address, item.storage_keys.clone()))
+			.collect();
+		let info = with_transaction(|| {
+			let (result, collected) = collect(|| match target {
+				Some(target) => Runner::<T>::call(
+					source,
+					source,
+					target,
+					input.clone(),
+					value,
+					gas_limit,
+					storage_limit,
+					list,
+					&config,
+				)
+				.map(|info| (info.exit_reason, info.value, target, info.used_gas, info.used_storage)),
+				None => Runner::<T>::create(source, input.clone(), value, gas_limit, storage_limit, list, &config).map(
+					|info| {
+						(
+							info.exit_reason,
+							Vec::new(),
+							info.value,
+							info.used_gas,
+							info.used_storage,
+						)
+					},
+				),
+			});
+
+			// the storage sizes must be read before the changes are discarded
+			let result = result.map(|(exit_reason, output, callee, used_gas, used_storage)| {
+				let storage_deposits = collected
+					.storages
+					.iter()
+					.filter(|(_, storage)| !storage.is_zero())
+					.map(|(address, storage)| StorageDepositInfo {
+						address: *address,
+						storage: *storage,
+						total_storage: ContractStorageSizes::<T>::get(address),
+						deposit: Pallet::<T>::get_storage_deposit_per_byte()
+							.saturating_mul((storage.abs() as u32).into())
+							.unique_saturated_into(),
+					})
+					.collect();
+
+				AccessListInfo {
+					exit_reason,
+					output,
+					used_gas,
+					used_storage,
+					access_list: to_access_list::<T>(collected.accessed, &[source, callee]),
+					storage_deposits,
+				}
+			});
+			TransactionOutcome::Rollback(result)
+		})?;
+
+		iterations += 1;
+		if info.access_list == access_list || iterations >= MAX_ACCESS_LIST_ITERATIONS {
+			return Ok(info);
+		}
+		access_list = info.access_list.clone();
+	}
+}
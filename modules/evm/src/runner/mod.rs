@@ -16,9 +16,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+pub mod access_list;
 pub mod stack;
 pub mod state;
 pub mod storage_meter;
+#[cfg(feature = "tracing")]
+pub mod tracing;
 
 use crate::{BalanceOf, CallInfo, Config, CreateInfo};
 use frame_support::dispatch::DispatchError;
@@ -40,6 +43,14 @@ pub trait Runner<T: Config> {
 		config: &evm::Config,
 	) -> Result<CallInfo, DispatchError>;
 
+	fn batch_call(
+		source: H160,
+		origin: H160,
+		calls: Vec<(H160, Vec<u8>, BalanceOf<T>, u64, u32)>,
+		atomic: bool,
+		config: &evm::Config,
+	) -> Result<Vec<Result<CallInfo, DispatchError>>, DispatchError>;
+
 	fn create(
 		source: H160,
 		init: Vec<u8>,
@@ -21,7 +21,9 @@
 
 use crate::{
 	runner::{
+		access_list,
 		state::{Accessed, StackExecutor, StackState as StackStateT, StackSubstateMetadata},
+		storage_meter::StorageMeter,
 		Runner as RunnerT,
 	},
 	AccountInfo, AccountStorages, Accounts, BalanceOf, CallInfo, Config, CreateInfo, Error, ExecutionInfo, One, Pallet,
@@ -30,6 +32,7 @@ use crate::{
 use frame_support::{
 	dispatch::DispatchError,
 	ensure, log,
+	storage::with_transaction,
 	traits::{Currency, ExistenceRequirement, Get},
 	transactional,
 };
@@ -44,7 +47,10 @@ pub use primitives::{
 };
 use sha3::{Digest, Keccak256};
 use sp_core::{H160, H256, U256};
-use sp_runtime::traits::{UniqueSaturatedInto, Zero};
+use sp_runtime::{
+	traits::{UniqueSaturatedInto, Zero},
+	TransactionOutcome,
+};
 use sp_std::{
 	boxed::Box,
 	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
@@ -53,6 +59,20 @@ use sp_std::{
 	vec::Vec,
 };
 
+environmental::environmental!(storage_payer: Option<H160>);
+
+/// Execute `f` with the storage deposit of the outermost EVM execution paid by `payer`
+/// instead of its origin.
+pub fn with_storage_payer<R, F: FnOnce() -> R>(payer: H160, f: F) -> R {
+	storage_payer::using(&mut Some(payer), f)
+}
+
+/// Take the payer of the storage deposit set by `with_storage_payer`, or default to
+/// `origin`. The payer is taken so nested executions pay their own storage deposit.
+fn take_storage_payer(origin: H160) -> H160 {
+	storage_payer::with(|payer| payer.take()).flatten().unwrap_or(origin)
+}
+
 #[derive(Default)]
 pub struct Runner<T: Config> {
 	_marker: PhantomData<T>,
@@ -70,6 +90,135 @@ impl<T: Config> Runner<T> {
 		precompiles: &'precompiles T::PrecompilesType,
 		f: F,
 	) -> Result<ExecutionInfo<R>, sp_runtime::DispatchError>
+	where
+		F: FnOnce(
+			&mut StackExecutor<'config, 'precompiles, SubstrateStackState<'_, 'config, T>, T::PrecompilesType>,
+		) -> (ExitReason, R),
+	{
+		let payer = take_storage_payer(origin);
+		Self::do_execute(
+			source,
+			origin,
+			payer,
+			value,
+			gas_limit,
+			storage_limit,
+			config,
+			precompiles,
+			true,
+			f,
+		)
+	}
+
+	fn do_batch_call(
+		source: H160,
+		origin: H160,
+		calls: Vec<(H160, Vec<u8>, BalanceOf<T>, u64, u32)>,
+		atomic: bool,
+		config: &evm::Config,
+	) -> Result<Vec<Result<CallInfo, DispatchError>>, DispatchError> {
+		let payer = take_storage_payer(origin);
+		let storage_limit = calls.iter().fold(0u32, |limit, (_, _, _, _, storage_limit)| {
+			limit.saturating_add(*storage_limit)
+		});
+		if !config.estimate {
+			Pallet::<T>::reserve_storage(&payer, storage_limit).map_err(|e| {
+				log::debug!(
+					target: "evm",
+					"ReserveStorageFailed {:?} [source: {:?}, storage_limit: {:?}]",
+					e,
+					payer,
+					storage_limit
+				);
+				Error::<T>::ReserveStorageFailed
+			})?;
+		}
+
+		let precompiles = T::PrecompilesValue::get();
+		let mut storage_meter = StorageMeter::new(storage_limit);
+		let mut results = Vec::with_capacity(calls.len());
+		for (target, input, value, gas_limit, _) in calls {
+			// every call can use the storage left by the previous calls
+			let available_storage = storage_meter.available_storage();
+			let result = with_transaction(|| {
+				if !Pallet::<T>::can_call_contract(&target, &source) {
+					return TransactionOutcome::Rollback(Err(Error::<T>::NoPermission.into()));
+				}
+
+				let value = U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(value));
+				match Self::do_execute(
+					source,
+					origin,
+					payer,
+					value,
+					gas_limit,
+					available_storage,
+					config,
+					&precompiles,
+					false,
+					|executor| executor.transact_call(source, target, value, input, gas_limit, vec![]),
+				) {
+					Ok(info) => TransactionOutcome::Commit(Ok(info)),
+					Err(e) => TransactionOutcome::Rollback(Err(e)),
+				}
+			});
+
+			let succeed = match &result {
+				Ok(info) => {
+					if info.used_storage.is_positive() {
+						storage_meter.charge(info.used_storage as u32);
+					} else {
+						storage_meter.refund(info.used_storage.unsigned_abs());
+					}
+					info.exit_reason.is_succeed()
+				}
+				Err(_) => false,
+			};
+			results.push(result);
+
+			if atomic && !succeed {
+				break;
+			}
+		}
+
+		if !config.estimate {
+			Pallet::<T>::unreserve_storage(
+				&payer,
+				storage_limit,
+				storage_meter.total_used(),
+				storage_meter.total_refunded(),
+			)
+			.map_err(|e| {
+				log::debug!(
+					target: "evm",
+					"UnreserveStorageFailed {:?} [source: {:?}, storage_limit: {:?}, used_storage: {:?}, refunded_storage: {:?}]",
+					e,
+					payer,
+					storage_limit,
+					storage_meter.total_used(),
+					storage_meter.total_refunded()
+				);
+				Error::<T>::UnreserveStorageFailed
+			})?;
+		}
+
+		Ok(results)
+	}
+
+	/// Execute an EVM operation. The storage deposit of `storage_limit` is reserved from
+	/// `payer` only if `reserve_storage` is true, otherwise it must be reserved by the caller.
+	fn do_execute<'config, 'precompiles, F, R>(
+		source: H160,
+		origin: H160,
+		payer: H160,
+		value: U256,
+		gas_limit: u64,
+		storage_limit: u32,
+		config: &'config evm::Config,
+		precompiles: &'precompiles T::PrecompilesType,
+		reserve_storage: bool,
+		f: F,
+	) -> Result<ExecutionInfo<R>, sp_runtime::DispatchError>
 	where
 		F: FnOnce(
 			&mut StackExecutor<'config, 'precompiles, SubstrateStackState<'_, 'config, T>, T::PrecompilesType>,
@@ -91,13 +240,13 @@ impl<T: Config> Runner<T> {
 			Error::<T>::InvalidDecimals
 		);
 
-		if !config.estimate {
-			Pallet::<T>::reserve_storage(&origin, storage_limit).map_err(|e| {
+		if reserve_storage && !config.estimate {
+			Pallet::<T>::reserve_storage(&payer, storage_limit).map_err(|e| {
 				log::debug!(
 					target: "evm",
 					"ReserveStorageFailed {:?} [source: {:?}, storage_limit: {:?}]",
 					e,
-					origin,
+					payer,
 					storage_limit
 				);
 				Error::<T>::ReserveStorageFailed
@@ -120,6 +269,8 @@ impl<T: Config> Runner<T> {
 
 		let state = executor.into_state();
 
+		access_list::record(state.metadata().accessed(), &state.substate.storage_logs);
+
 		// charge storage
 		let actual_storage = state
 			.metadata()
@@ -136,12 +287,12 @@ impl<T: Config> Runner<T> {
 		let mut sum_storage: i32 = 0;
 		for (target, storage) in &state.substate.storage_logs {
 			if !config.estimate {
-				Pallet::<T>::charge_storage(&origin, target, *storage).map_err(|e| {
+				Pallet::<T>::charge_storage(&payer, target, *storage).map_err(|e| {
 					log::debug!(
 						target: "evm",
 						"ChargeStorageFailed {:?} [source: {:?}, target: {:?}, storage: {:?}]",
 						e,
-						origin,
+						payer,
 						target,
 						storage
 					);
@@ -150,6 +301,20 @@ impl<T: Config> Runner<T> {
 			}
 			sum_storage += storage;
 		}
+
+		if !config.estimate {
+			// postpone the reclamation of the contracts entered or updated by the execution
+			let mut touched = state
+				.metadata()
+				.accessed()
+				.as_ref()
+				.map(|accessed| accessed.accessed_addresses.clone())
+				.unwrap_or_default();
+			touched.extend(state.substate.storage_logs.iter().map(|(target, _)| *target));
+			for contract in touched.iter() {
+				Pallet::<T>::touch_contract(contract);
+			}
+		}
 		if actual_storage != sum_storage {
 			log::debug!(
 				target: "evm",
@@ -159,13 +324,13 @@ impl<T: Config> Runner<T> {
 			return Err(Error::<T>::ChargeStorageFailed.into());
 		}
 
-		if !config.estimate {
-			Pallet::<T>::unreserve_storage(&origin, storage_limit, used_storage, refunded_storage).map_err(|e| {
+		if reserve_storage && !config.estimate {
+			Pallet::<T>::unreserve_storage(&payer, storage_limit, used_storage, refunded_storage).map_err(|e| {
 				log::debug!(
 					target: "evm",
 					"UnreserveStorageFailed {:?} [source: {:?}, storage_limit: {:?}, used_storage: {:?}, refunded_storage: {:?}]",
 					e,
-					origin,
+					payer,
 					storage_limit,
 					used_storage,
 					refunded_storage
@@ -230,7 +395,7 @@ impl<T: Config> RunnerT<T> for Runner<T> {
 
 		let precompiles = T::PrecompilesValue::get();
 		let value = U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(value));
-		Self::execute(
+		let info = Self::execute(
 			source,
 			origin,
 			value,
@@ -239,7 +404,41 @@ impl<T: Config> RunnerT<T> for Runner<T> {
 			config,
 			&precompiles,
 			|executor| executor.transact_call(source, target, value, input, gas_limit, access_list),
-		)
+		)?;
+
+		if !config.estimate {
+			Pallet::<T>::touch_contract(&target);
+		}
+
+		Ok(info)
+	}
+
+	/// Execute the calls one by one with a storage meter shared by the whole batch, the storage
+	/// deposit of the summed storage limits is reserved once. If `atomic` is true, the batch
+	/// stops at the first failed call and all the calls are reverted.
+	fn batch_call(
+		source: H160,
+		origin: H160,
+		calls: Vec<(H160, Vec<u8>, BalanceOf<T>, u64, u32)>,
+		atomic: bool,
+		config: &evm::Config,
+	) -> Result<Vec<Result<CallInfo, DispatchError>>, DispatchError> {
+		with_transaction(|| {
+			let results = match Self::do_batch_call(source, origin, calls, atomic, config) {
+				Ok(results) => results,
+				Err(e) => return TransactionOutcome::Rollback(Err(e)),
+			};
+
+			let failed = results.last().map_or(
+				false,
+				|result| !matches!(result, Ok(info) if info.exit_reason.is_succeed()),
+			);
+			if atomic && failed {
+				TransactionOutcome::Rollback(Ok(results))
+			} else {
+				TransactionOutcome::Commit(Ok(results))
+			}
+		})
 	}
 
 	/// Require transactional here. Always need to send events.
@@ -52,33 +52,7 @@ macro_rules! event {
 }
 
 #[cfg(feature = "tracing")]
-mod tracing {
-	pub struct Tracer;
-	impl module_evm_utility::evm::tracing::EventListener for Tracer {
-		fn event(&mut self, event: module_evm_utility::evm::tracing::Event) {
-			frame_support::log::debug!(
-				target: "evm", "evm tracing: {:?}", event
-			);
-		}
-	}
-	impl module_evm_utility::evm_runtime::tracing::EventListener for Tracer {
-		fn event(&mut self, event: module_evm_utility::evm_runtime::tracing::Event) {
-			frame_support::log::debug!(
-				target: "evm", "evm_runtime tracing: {:?}", event
-			);
-		}
-	}
-	impl module_evm_utility::evm_gasometer::tracing::EventListener for Tracer {
-		fn event(&mut self, event: module_evm_utility::evm_gasometer::tracing::Event) {
-			frame_support::log::debug!(
-				target: "evm", "evm_gasometer tracing: {:?}", event
-			);
-		}
-	}
-}
-
-#[cfg(feature = "tracing")]
-use tracing::*;
+use crate::runner::tracing::{self, CallType, Listener};
 
 macro_rules! emit_exit {
 	($reason:expr) => {{
@@ -508,6 +482,9 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 		}
 		self.initialize_with_access_list(access_list);
 
+		#[cfg(feature = "tracing")]
+		self.trace_enter_create(CreateScheme::Legacy { caller }, caller, &init_code, value, gas_limit);
+
 		match self.create_inner(
 			caller,
 			CreateScheme::Legacy { caller },
@@ -516,7 +493,11 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 			Some(gas_limit),
 			false,
 		) {
-			Capture::Exit((s, _, _)) => emit_exit!(s),
+			Capture::Exit((s, _address, _return_value)) => {
+				#[cfg(feature = "tracing")]
+				self.trace_exit_create(&s, _address, &_return_value, self.used_gas());
+				emit_exit!(s)
+			}
 			Capture::Trap(_) => unreachable!(),
 		}
 	}
@@ -550,6 +531,19 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 		}
 		self.initialize_with_access_list(access_list);
 
+		#[cfg(feature = "tracing")]
+		self.trace_enter_create(
+			CreateScheme::Create2 {
+				caller,
+				code_hash,
+				salt,
+			},
+			caller,
+			&init_code,
+			value,
+			gas_limit,
+		);
+
 		match self.create_inner(
 			caller,
 			CreateScheme::Create2 {
@@ -562,7 +556,11 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 			Some(gas_limit),
 			false,
 		) {
-			Capture::Exit((s, _, _)) => emit_exit!(s),
+			Capture::Exit((s, _address, _return_value)) => {
+				#[cfg(feature = "tracing")]
+				self.trace_exit_create(&s, _address, &_return_value, self.used_gas());
+				emit_exit!(s)
+			}
 			Capture::Trap(_) => unreachable!(),
 		}
 	}
@@ -590,6 +588,9 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 		}
 		self.initialize_with_access_list(access_list);
 
+		#[cfg(feature = "tracing")]
+		self.trace_enter_create(CreateScheme::Fixed(address), caller, &init_code, value, gas_limit);
+
 		match self.create_inner(
 			caller,
 			CreateScheme::Fixed(address),
@@ -598,7 +599,11 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 			Some(gas_limit),
 			false,
 		) {
-			Capture::Exit((s, _, _)) => emit_exit!(s),
+			Capture::Exit((s, _address, _return_value)) => {
+				#[cfg(feature = "tracing")]
+				self.trace_exit_create(&s, _address, &_return_value, self.used_gas());
+				emit_exit!(s)
+			}
 			Capture::Trap(_) => unreachable!(),
 		}
 	}
@@ -649,6 +654,9 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 			apparent_value: value,
 		};
 
+		#[cfg(feature = "tracing")]
+		tracing::enter(CallType::Call, caller, address, &data, value, gas_limit);
+
 		match self.call_inner(
 			address,
 			Some(Transfer {
@@ -663,7 +671,11 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 			false,
 			context,
 		) {
-			Capture::Exit((s, v)) => emit_exit!(s, v),
+			Capture::Exit((s, v)) => {
+				#[cfg(feature = "tracing")]
+				tracing::exit(&s, &v, self.used_gas());
+				emit_exit!(s, v)
+			}
 			Capture::Trap(_) => unreachable!(),
 		}
 	}
@@ -860,7 +872,10 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 
 		let mut runtime = Runtime::new(Rc::new(init_code), Rc::new(Vec::new()), context, self.config);
 
+		#[cfg(not(feature = "tracing"))]
 		let reason = self.execute(&mut runtime);
+		#[cfg(feature = "tracing")]
+		let reason = module_evm_utility::evm_runtime::tracing::using(&mut Listener, || self.execute(&mut runtime));
 		log::debug!(target: "evm", "Create execution using address {}: {:?}", address, reason);
 
 		match reason {
@@ -1046,9 +1061,9 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 		#[cfg(not(feature = "tracing"))]
 		let reason = self.execute(&mut runtime);
 		#[cfg(feature = "tracing")]
-		//let reason = module_evm_utility::evm::tracing::using(&mut Tracer, || self.execute(&mut runtime));
-		let reason = module_evm_utility::evm_runtime::tracing::using(&mut Tracer, || self.execute(&mut runtime));
-		//let reason = module_evm_utility::evm_gasometer::tracing::using(&mut Tracer, || self.execute(&mut
+		//let reason = module_evm_utility::evm::tracing::using(&mut Listener, || self.execute(&mut runtime));
+		let reason = module_evm_utility::evm_runtime::tracing::using(&mut Listener, || self.execute(&mut runtime));
+		//let reason = module_evm_utility::evm_gasometer::tracing::using(&mut Listener, || self.execute(&mut
 		// runtime));
 
 		log::debug!(target: "evm", "Call execution using address {}: {:?}", code_address, reason);
@@ -1225,9 +1240,20 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> Handler
 		init_code: Vec<u8>,
 		target_gas: Option<u64>,
 	) -> Capture<(ExitReason, Option<H160>, Vec<u8>), Self::CreateInterrupt> {
+		let gas = self.state.metadata().gasometer.gas();
+		self.trace_enter_create(
+			scheme,
+			caller,
+			&init_code,
+			value,
+			target_gas.map_or(gas, |target_gas| min(target_gas, gas)),
+		);
+
 		let capture = self.create_inner(caller, scheme, value, init_code, target_gas, true);
 
-		if let Capture::Exit((ref reason, _, ref return_value)) = capture {
+		if let Capture::Exit((ref reason, address, ref return_value)) = capture {
+			let gas_used = gas.saturating_sub(self.state.metadata().gasometer.gas());
+			self.trace_exit_create(reason, address, return_value, gas_used);
 			emit_exit!(reason, return_value);
 		}
 
@@ -1266,6 +1292,32 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> Handler
 		is_static: bool,
 		context: Context,
 	) -> Capture<(ExitReason, Vec<u8>), Self::CallInterrupt> {
+		let gas = self.state.metadata().gasometer.gas();
+		if tracing::is_tracing() {
+			let call_type = if is_static {
+				CallType::StaticCall
+			} else if context.address == code_address {
+				CallType::Call
+			} else if transfer.is_some() {
+				CallType::CallCode
+			} else {
+				CallType::DelegateCall
+			};
+			// the caller of CALLCODE and DELEGATECALL keeps its own context
+			let from = match call_type {
+				CallType::CallCode | CallType::DelegateCall => context.address,
+				_ => context.caller,
+			};
+			tracing::enter(
+				call_type,
+				from,
+				code_address,
+				&input,
+				transfer.as_ref().map_or_else(U256::zero, |transfer| transfer.value),
+				target_gas.map_or(gas, |target_gas| min(target_gas, gas)),
+			);
+		}
+
 		let capture = self.call_inner(
 			code_address,
 			transfer,
@@ -1278,6 +1330,11 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> Handler
 		);
 
 		if let Capture::Exit((ref reason, ref return_value)) = capture {
+			tracing::exit(
+				reason,
+				return_value,
+				gas.saturating_sub(self.state.metadata().gasometer.gas()),
+			);
 			emit_exit!(reason, return_value);
 		}
 
@@ -1286,6 +1343,21 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> Handler
 
 	#[inline]
 	fn pre_validate(&mut self, context: &Context, opcode: Opcode, stack: &Stack) -> Result<(), ExitError> {
+		#[cfg(feature = "tracing")]
+		let gas = self.state.metadata().gasometer.gas();
+
+		let result = self.record_opcode_cost(context, opcode, stack);
+
+		#[cfg(feature = "tracing")]
+		tracing::record_cost(gas, self.state.metadata().gasometer.gas());
+
+		result
+	}
+}
+
+impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecutor<'config, 'precompiles, S, P> {
+	#[inline]
+	fn record_opcode_cost(&mut self, context: &Context, opcode: Opcode, stack: &Stack) -> Result<(), ExitError> {
 		// log::trace!(target: "evm", "Running opcode: {:?}, Pre gas-left: {:?}", opcode, gasometer.gas());
 
 		if let Some(cost) = gasometer::static_opcode_cost(opcode) {
@@ -1307,4 +1379,26 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> Handler
 
 		Ok(())
 	}
+	#[cfg(feature = "tracing")]
+	fn trace_enter_create(&self, scheme: CreateScheme, caller: H160, init_code: &[u8], value: U256, gas: u64) {
+		if tracing::is_tracing() {
+			let call_type = match scheme {
+				CreateScheme::Create2 { .. } => CallType::Create2,
+				_ => CallType::Create,
+			};
+			let address = self.create_address(scheme).unwrap_or_default();
+			tracing::enter(call_type, caller, address, init_code, value, gas);
+		}
+	}
+
+	/// The output of a traced create is the deployed code.
+	#[cfg(feature = "tracing")]
+	fn trace_exit_create(&self, reason: &ExitReason, address: Option<H160>, return_value: &[u8], gas_used: u64) {
+		if tracing::is_tracing() {
+			match address {
+				Some(address) if reason.is_succeed() => tracing::exit(reason, &self.state.code(address), gas_used),
+				_ => tracing::exit(reason, return_value, gas_used),
+			}
+		}
+	}
 }
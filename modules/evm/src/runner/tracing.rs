@@ -0,0 +1,240 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! EVM execution tracer.
+//!
+//! The executor reports the call frames, the executed opcodes and the storage accesses to the
+//! tracer set by [`trace`]. Nothing is recorded when no tracer is set, so the hooks are cheap for
+//! the regular block execution of a native runtime built with the `tracing` feature.
+
+use frame_support::log;
+use module_evm_utility::{
+	evm::{ExitError, ExitFatal, ExitReason, Memory, Opcode, Stack},
+	evm_gasometer, evm_runtime,
+};
+pub use primitives::evm::tracing::{CallTrace, CallType, OpcodeConfig, StructLog, StructLogTrace, Trace, TracerConfig};
+use sp_core::{H160, H256, U256};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+environmental::environmental!(tracer: Tracer);
+
+/// Collects the trace of an EVM execution.
+pub struct Tracer {
+	config: TracerConfig,
+	/// The open call frames, the innermost last.
+	frames: Vec<CallTrace>,
+	root: Option<CallTrace>,
+	struct_logs: Vec<StructLog>,
+	/// The storage slots accessed so far by each contract.
+	storages: BTreeMap<H160, BTreeMap<H256, H256>>,
+}
+
+impl Tracer {
+	pub fn new(config: TracerConfig) -> Self {
+		Self {
+			config,
+			frames: Vec::new(),
+			root: None,
+			struct_logs: Vec::new(),
+			storages: BTreeMap::new(),
+		}
+	}
+
+	/// The trace of the first top-level call, `None` if no EVM execution was traced.
+	pub fn finalize(self) -> Option<Trace> {
+		let root = self.root?;
+		Some(match self.config {
+			TracerConfig::CallTracer => Trace::Calls(root),
+			TracerConfig::OpcodeTracer(_) => Trace::Opcodes(StructLogTrace {
+				gas: root.gas_used,
+				failed: root.error.is_some(),
+				return_value: root.output,
+				struct_logs: self.struct_logs,
+			}),
+		})
+	}
+
+	fn enter(&mut self, call_type: CallType, from: H160, to: H160, input: &[u8], value: U256, gas: u64) {
+		// only the first top-level call is traced
+		if self.frames.is_empty() && self.root.is_some() {
+			return;
+		}
+		self.frames.push(CallTrace {
+			call_type,
+			from,
+			to,
+			input: input.to_vec(),
+			value,
+			gas,
+			gas_used: 0,
+			output: Vec::new(),
+			error: None,
+			calls: Vec::new(),
+		});
+	}
+
+	fn exit(&mut self, reason: &ExitReason, output: &[u8], gas_used: u64) {
+		if let Some(mut call) = self.frames.pop() {
+			call.gas_used = gas_used;
+			call.output = output.to_vec();
+			call.error = exit_error(reason);
+			match self.frames.last_mut() {
+				Some(parent) => parent.calls.push(call),
+				None => self.root = Some(call),
+			}
+		}
+	}
+
+	fn step(&mut self, opcode: Opcode, pc: usize, stack: &Stack, memory: &Memory) {
+		let config = match self.config {
+			TracerConfig::OpcodeTracer(config) if !self.frames.is_empty() => config,
+			_ => return,
+		};
+		self.struct_logs.push(StructLog {
+			pc: pc as u64,
+			op: opcode.0,
+			gas: 0,
+			gas_cost: 0,
+			depth: self.frames.len() as u32,
+			stack: (!config.disable_stack).then(|| stack.data().iter().map(|v| U256::from(v.as_bytes())).collect()),
+			memory: config.enable_memory.then(|| {
+				memory
+					.data()
+					.chunks(32)
+					.map(|chunk| {
+						let mut word = H256::zero();
+						word[..chunk.len()].copy_from_slice(chunk);
+						word
+					})
+					.collect()
+			}),
+			storage: None,
+		});
+	}
+
+	fn record_cost(&mut self, gas: u64, gas_left: u64) {
+		if let Some(log) = self.struct_logs.last_mut() {
+			log.gas = gas;
+			log.gas_cost = gas.saturating_sub(gas_left);
+		}
+	}
+
+	fn storage_access(&mut self, address: H160, index: H256, value: H256) {
+		if !matches!(self.config, TracerConfig::OpcodeTracer(config) if !config.disable_storage)
+			|| self.frames.is_empty()
+		{
+			return;
+		}
+		let storage = self.storages.entry(address).or_default();
+		storage.insert(index, value);
+		if let Some(log) = self.struct_logs.last_mut() {
+			log.storage = Some(storage.clone());
+		}
+	}
+}
+
+/// The geth error message of a failed execution.
+fn exit_error(reason: &ExitReason) -> Option<Vec<u8>> {
+	let message: &[u8] = match reason {
+		ExitReason::Succeed(_) => return None,
+		ExitReason::Revert(_) => b"execution reverted",
+		ExitReason::Error(ExitError::OutOfGas) => b"out of gas",
+		ExitReason::Error(ExitError::OutOfFund) => b"insufficient balance for transfer",
+		ExitReason::Error(ExitError::CallTooDeep) => b"max call depth exceeded",
+		ExitReason::Error(ExitError::CreateCollision) => b"contract address collision",
+		ExitReason::Error(ExitError::CreateContractLimit) => b"max code size exceeded",
+		ExitReason::Error(ExitError::InvalidCode) => b"invalid code: must not begin with 0xef",
+		ExitReason::Error(ExitError::InvalidJump) => b"invalid jump destination",
+		ExitReason::Error(ExitError::StackUnderflow) => b"stack underflow",
+		ExitReason::Error(ExitError::StackOverflow) => b"stack limit reached",
+		ExitReason::Error(ExitError::DesignatedInvalid) => b"invalid opcode",
+		ExitReason::Error(ExitError::OutOfOffset) => b"return data out of bounds",
+		ExitReason::Error(ExitError::Other(message)) | ExitReason::Fatal(ExitFatal::Other(message)) => {
+			message.as_bytes()
+		}
+		ExitReason::Error(_) => b"execution error",
+		ExitReason::Fatal(_) => b"execution fatal",
+	};
+	Some(message.to_vec())
+}
+
+/// Run `f` and return the trace of the first EVM execution it makes.
+pub fn trace<R, F: FnOnce() -> R>(config: TracerConfig, f: F) -> (R, Option<Trace>) {
+	let mut tracer = Tracer::new(config);
+	let result = tracer::using(&mut tracer, f);
+	(result, tracer.finalize())
+}
+
+/// Whether the current execution is traced.
+pub(crate) fn is_tracing() -> bool {
+	tracer::with(|_| ()).is_some()
+}
+
+/// Open a call frame.
+pub(crate) fn enter(call_type: CallType, from: H160, to: H160, input: &[u8], value: U256, gas: u64) {
+	tracer::with(|tracer| tracer.enter(call_type, from, to, input, value, gas));
+}
+
+/// Close the innermost call frame.
+pub(crate) fn exit(reason: &ExitReason, output: &[u8], gas_used: u64) {
+	tracer::with(|tracer| tracer.exit(reason, output, gas_used));
+}
+
+/// Record the gas available to and charged by the last opcode.
+pub(crate) fn record_cost(gas: u64, gas_left: u64) {
+	tracer::with(|tracer| tracer.record_cost(gas, gas_left));
+}
+
+/// Forwards the events of the EVM to the current tracer.
+pub struct Listener;
+
+impl module_evm_utility::evm::tracing::EventListener for Listener {
+	fn event(&mut self, event: module_evm_utility::evm::tracing::Event) {
+		log::debug!(
+			target: "evm", "evm tracing: {:?}", event
+		);
+	}
+}
+
+impl evm_runtime::tracing::EventListener for Listener {
+	fn event(&mut self, event: evm_runtime::tracing::Event) {
+		log::debug!(
+			target: "evm", "evm_runtime tracing: {:?}", event
+		);
+		tracer::with(|tracer| match event {
+			evm_runtime::tracing::Event::Step {
+				opcode,
+				position: Ok(pc),
+				stack,
+				memory,
+				..
+			} => tracer.step(opcode, *pc, stack, memory),
+			evm_runtime::tracing::Event::SLoad { address, index, value }
+			| evm_runtime::tracing::Event::SStore { address, index, value } => tracer.storage_access(address, index, value),
+			_ => {}
+		});
+	}
+}
+
+impl evm_gasometer::tracing::EventListener for Listener {
+	fn event(&mut self, event: evm_gasometer::tracing::Event) {
+		log::debug!(
+			target: "evm", "evm_gasometer tracing: {:?}", event
+		);
+	}
+}
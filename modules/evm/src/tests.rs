@@ -1322,6 +1322,783 @@ fn should_set_code() {
 	});
 }
 
+#[test]
+fn should_propose_and_enact_upgrade() {
+	// the `should_set_code` contract
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+	let new_code = from_hex("0x6080604052600080fd").unwrap();
+	let new_code_hash = code_hash(&new_code);
+
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
+		let bob_account_id = <Runtime as Config>::AddressMapping::get_account_id(&bob());
+
+		System::set_block_number(1);
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			21_000_000,
+			21_000_000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+		let alice_balance = INITIAL_BALANCE - 284 * EVM::get_storage_deposit_per_byte();
+		assert_eq!(balance(alice()), alice_balance);
+		assert_eq!(reserved_balance(contract_address), 2840);
+
+		let code_hash = H256::from_str("164981e02df203a0fb32a0af7c2cd1cc7f9df7bb49a4d2b0219307bb68a4b603").unwrap();
+
+		assert_noop!(
+			EVM::propose_upgrade(
+				Origin::signed(bob_account_id.clone()),
+				contract_address,
+				new_code.clone()
+			),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			EVM::propose_upgrade(
+				Origin::signed(alice_account_id.clone()),
+				contract_address,
+				[8u8; (MaxCodeSize::get() + 1) as usize].to_vec(),
+			),
+			Error::<Runtime>::ContractExceedsMaxCodeSize
+		);
+
+		assert_ok!(EVM::propose_upgrade(
+			Origin::signed(alice_account_id.clone()),
+			contract_address,
+			new_code.clone()
+		));
+		System::assert_last_event(Event::EVM(crate::Event::UpgradeProposed {
+			contract: contract_address,
+			code_hash: new_code_hash,
+			enact_after: 1 + UPGRADE_DELAY,
+		}));
+		assert_eq!(
+			EVM::upgrade_proposals(contract_address),
+			Some(UpgradeProposal {
+				code_hash: new_code_hash,
+				enact_after: 1 + UPGRADE_DELAY,
+				proposer: alice(),
+				deposit: 9,
+			})
+		);
+		// the storage deposit of the proposed code is reserved from the maintainer
+		assert_eq!(
+			balance(alice()),
+			alice_balance - 9 * EVM::get_storage_deposit_per_byte()
+		);
+		assert_eq!(
+			CodeInfos::<Runtime>::get(&new_code_hash),
+			Some(CodeInfo {
+				code_size: 9,
+				ref_count: 1,
+			})
+		);
+
+		assert_noop!(
+			EVM::propose_upgrade(Origin::signed(alice_account_id.clone()), contract_address, new_code),
+			Error::<Runtime>::UpgradeAlreadyProposed
+		);
+
+		System::set_block_number(UPGRADE_DELAY);
+		assert_noop!(
+			EVM::enact_upgrade(Origin::signed(alice_account_id.clone()), contract_address),
+			Error::<Runtime>::UpgradeTimelocked
+		);
+
+		System::set_block_number(1 + UPGRADE_DELAY);
+		assert_noop!(
+			EVM::enact_upgrade(Origin::signed(bob_account_id), contract_address),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(EVM::enact_upgrade(
+			Origin::signed(alice_account_id.clone()),
+			contract_address
+		));
+		System::assert_last_event(Event::EVM(crate::Event::UpgradeEnacted {
+			contract: contract_address,
+			code_hash: new_code_hash,
+		}));
+
+		assert_eq!(EVM::code_hash_at_address(&contract_address), new_code_hash);
+		assert_eq!(EVM::upgrade_proposals(contract_address), None);
+		assert_eq!(CodeInfos::<Runtime>::get(&code_hash), None);
+		assert_eq!(Codes::<Runtime>::contains_key(&code_hash), false);
+		assert_eq!(
+			CodeInfos::<Runtime>::get(&new_code_hash),
+			Some(CodeInfo {
+				code_size: 9,
+				ref_count: 1,
+			})
+		);
+
+		// the storage deposit of the removed code is refunded to the maintainer
+		assert_eq!(ContractStorageSizes::<Runtime>::get(&contract_address), 284 - 175);
+		assert_eq!(reserved_balance(contract_address), 2840 - 1750);
+		assert_eq!(balance(alice()), alice_balance + 1750);
+
+		assert_noop!(
+			EVM::enact_upgrade(Origin::signed(alice_account_id), contract_address),
+			Error::<Runtime>::UpgradeNotProposed
+		);
+	});
+}
+
+#[test]
+fn should_cancel_upgrade() {
+	// pragma solidity 0.8.2;
+	//
+	// contract Empty { }
+	let empty = from_hex(
+		"0x6080604052348015600f57600080fd5b50603f80601d6000396000f3fe6080604052600080fdfea2646970667358221220e2641e5566296523edeafd776846b0e535aac278dfcf496804a865948b29646064736f6c63430008020033"
+	).unwrap();
+	let new_code = from_hex("0x6080604052600080fd").unwrap();
+	let new_code_hash = code_hash(&new_code);
+
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
+		let bob_account_id = <Runtime as Config>::AddressMapping::get_account_id(&bob());
+
+		System::set_block_number(1);
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			empty,
+			0,
+			1000000,
+			100000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+		let code_hash = EVM::code_hash_at_address(&contract_address);
+		let alice_balance = balance(alice());
+
+		assert_noop!(
+			EVM::cancel_upgrade(Origin::signed(alice_account_id.clone()), contract_address),
+			Error::<Runtime>::UpgradeNotProposed
+		);
+
+		assert_ok!(EVM::propose_upgrade(
+			Origin::signed(alice_account_id.clone()),
+			contract_address,
+			new_code
+		));
+		assert!(Codes::<Runtime>::contains_key(&new_code_hash));
+		assert_eq!(
+			balance(alice()),
+			alice_balance - 9 * EVM::get_storage_deposit_per_byte()
+		);
+
+		assert_noop!(
+			EVM::cancel_upgrade(Origin::signed(bob_account_id), contract_address),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(EVM::cancel_upgrade(
+			Origin::signed(alice_account_id.clone()),
+			contract_address
+		));
+		System::assert_last_event(Event::EVM(crate::Event::UpgradeCancelled {
+			contract: contract_address,
+			code_hash: new_code_hash,
+		}));
+
+		// the unreferenced code is removed and its deposit unreserved, the contract is unchanged
+		assert_eq!(EVM::upgrade_proposals(contract_address), None);
+		assert_eq!(balance(alice()), alice_balance);
+		assert_eq!(CodeInfos::<Runtime>::get(&new_code_hash), None);
+		assert!(!Codes::<Runtime>::contains_key(&new_code_hash));
+		assert_eq!(EVM::code_hash_at_address(&contract_address), code_hash);
+
+		System::set_block_number(1 + UPGRADE_DELAY);
+		assert_noop!(
+			EVM::enact_upgrade(Origin::signed(alice_account_id), contract_address),
+			Error::<Runtime>::UpgradeNotProposed
+		);
+	});
+}
+
+#[test]
+fn should_sponsor_call() {
+	// pragma solidity 0.8.2;
+	//
+	// contract Storage {
+	//     uint256 public number;
+	//
+	//     function store(uint256 num) public {
+	//         number = num;
+	//     }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5061012c806100206000396000f3fe6080604052348015600f57600080fd5b506004361060325760003560e01c80636057361d1460375780638381f58a14604f575b600080fd5b604d600480360381019060499190608c565b6069565b005b60556073565b6040516060919060bf565b60405180910390f35b8060008190555050565b60005481565b60008135905060868160e2565b92915050565b600060208284031215609d57600080fd5b600060a9848285016079565b91505092915050565b60b98160d8565b82525050565b600060208201905060d2600083018460b2565b92915050565b6000819050919050565b60e98160d8565b811460f357600080fd5b5056fea2646970667358221220b161a9e6cc3d4aac8bc0fd65e420da7555db59fefe6a1d4e8e7eea98e99b293b64736f6c63430008020033"
+	).unwrap();
+	// Storage.store(1)
+	let store = from_hex("0x6057361d0000000000000000000000000000000000000000000000000000000000000001").unwrap();
+
+	new_test_ext().execute_with(|| {
+		let bob_account_id = <Runtime as Config>::AddressMapping::get_account_id(&bob());
+		let charlie_account_id = <Runtime as Config>::AddressMapping::get_account_id(&charlie());
+		let budget = |who: &AccountId32| Balances::reserved_balance_named(&RESERVE_ID_SPONSOR_BUDGET, who);
+
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			1000000,
+			1000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		publish_free(contract_address);
+		let contract_reserved = reserved_balance(contract_address);
+
+		assert_noop!(
+			EVM::sponsor_contract(Origin::signed(bob_account_id.clone()), charlie(), 1000, 100),
+			Error::<Runtime>::ContractNotFound
+		);
+		assert_noop!(
+			EVM::sponsored_call(
+				Origin::signed(charlie_account_id.clone()),
+				bob_account_id.clone(),
+				contract_address,
+				store.clone(),
+				0,
+				1000000,
+				100,
+				vec![],
+			),
+			Error::<Runtime>::NotSponsored
+		);
+
+		assert_ok!(EVM::sponsor_contract(
+			Origin::signed(bob_account_id.clone()),
+			contract_address,
+			1000,
+			100
+		));
+		let limits = SponsorshipLimits {
+			max_fee_per_call: 1000,
+			max_storage_limit: 100,
+		};
+		System::assert_last_event(Event::EVM(crate::Event::ContractSponsored {
+			sponsor: bob_account_id.clone(),
+			contract: contract_address,
+			limits,
+		}));
+		assert_eq!(EVM::sponsorships(&bob_account_id, contract_address), Some(limits));
+
+		assert_noop!(
+			EVM::sponsored_call(
+				Origin::signed(charlie_account_id.clone()),
+				bob_account_id.clone(),
+				contract_address,
+				store.clone(),
+				0,
+				1000000,
+				101,
+				vec![],
+			),
+			Error::<Runtime>::SponsorLimitExceeded
+		);
+		assert_noop!(
+			EVM::sponsored_call(
+				Origin::signed(charlie_account_id.clone()),
+				bob_account_id.clone(),
+				contract_address,
+				store.clone(),
+				0,
+				1000000,
+				100,
+				vec![],
+			),
+			Error::<Runtime>::SponsorBudgetInsufficient
+		);
+
+		assert_ok!(EVM::deposit_sponsor_budget(
+			Origin::signed(bob_account_id.clone()),
+			10_000
+		));
+		System::assert_last_event(Event::EVM(crate::Event::SponsorBudgetDeposited {
+			sponsor: bob_account_id.clone(),
+			amount: 10_000,
+		}));
+		assert_eq!(budget(&bob_account_id), 10_000);
+
+		// sponsored calls are refused until the spend limit is set
+		assert_noop!(
+			EVM::sponsored_call(
+				Origin::signed(charlie_account_id.clone()),
+				bob_account_id.clone(),
+				contract_address,
+				store.clone(),
+				0,
+				1000000,
+				100,
+				vec![],
+			),
+			Error::<Runtime>::SponsorSpendLimitExceeded
+		);
+		assert_noop!(
+			EVM::set_sponsor_spend_limit(Origin::signed(bob_account_id.clone()), 1000, 0),
+			Error::<Runtime>::InvalidSponsorSpendPeriod
+		);
+		assert_ok!(EVM::set_sponsor_spend_limit(
+			Origin::signed(bob_account_id.clone()),
+			1000,
+			10
+		));
+		System::assert_last_event(Event::EVM(crate::Event::SponsorSpendLimitSet {
+			sponsor: bob_account_id.clone(),
+			max_spend_per_period: 1000,
+			period: 10,
+		}));
+
+		// the fee of other calls is not sponsored
+		let remark = mock::Call::System(frame_system::Call::remark { remark: vec![] });
+		assert_eq!(
+			<EVM as TransactionFeeSponsor<_, _, _>>::sponsor_fee(&charlie_account_id, &remark, 100, 0),
+			Ok(None)
+		);
+
+		let call = mock::Call::EVM(crate::Call::sponsored_call {
+			sponsor: bob_account_id.clone(),
+			target: contract_address,
+			input: store.clone(),
+			value: 0,
+			gas_limit: 1000000,
+			storage_limit: 100,
+			access_list: vec![],
+		});
+		assert_noop!(
+			<EVM as TransactionFeeSponsor<_, _, _>>::sponsor_fee(&charlie_account_id, &call, 1001, 0),
+			Error::<Runtime>::SponsorLimitExceeded
+		);
+		assert_noop!(
+			<EVM as TransactionFeeSponsor<_, _, _>>::sponsor_fee(&charlie_account_id, &call, 100, 1),
+			Error::<Runtime>::SponsoredTipNotAllowed
+		);
+
+		// the fee is released to the sponsor which pays it, nothing goes to charlie
+		let bob_balance = balance(bob());
+		assert_eq!(
+			<EVM as TransactionFeeSponsor<_, _, _>>::sponsor_fee(&charlie_account_id, &call, 100, 0),
+			Ok(Some(bob_account_id.clone()))
+		);
+		assert_eq!(balance(charlie()), 0);
+		assert_eq!(balance(bob()), bob_balance + 100);
+		assert_eq!(budget(&bob_account_id), 10_000 - 100);
+
+		// the refund goes back to the sponsor budget
+		<EVM as TransactionFeeSponsor<_, mock::Call, _>>::refund_fee(&bob_account_id, 40);
+		assert_eq!(budget(&bob_account_id), 10_000 - 60);
+		assert_eq!(EVM::sponsor_spendings(&bob_account_id).map(|s| s.spent), Some(60));
+
+		// the storage deposit exceeds the spend limit of the period
+		assert_noop!(
+			EVM::sponsored_call(
+				Origin::signed(charlie_account_id.clone()),
+				bob_account_id.clone(),
+				contract_address,
+				store.clone(),
+				0,
+				1000000,
+				100,
+				vec![],
+			),
+			Error::<Runtime>::SponsorSpendLimitExceeded
+		);
+
+		// a new period
+		System::set_block_number(11);
+		assert_ok!(EVM::sponsored_call(
+			Origin::signed(charlie_account_id.clone()),
+			bob_account_id.clone(),
+			contract_address,
+			store,
+			0,
+			1000000,
+			100,
+			vec![],
+		));
+		System::assert_last_event(Event::EVM(crate::Event::CallSponsored {
+			sponsor: bob_account_id.clone(),
+			who: charlie_account_id.clone(),
+			contract: contract_address,
+			used_storage: 64,
+		}));
+		assert_eq!(
+			EVM::account_storages(contract_address, H256::zero()),
+			H256::from_low_u64_be(1)
+		);
+
+		// the storage deposit is paid by bob, the unused deposit is returned to the budget
+		assert_eq!(balance(charlie()), 0);
+		assert_eq!(reserved_balance(charlie()), 0);
+		assert_eq!(reserved_balance(contract_address), contract_reserved + 640);
+		assert_eq!(budget(&bob_account_id), 10_000 - 60 - 640);
+		assert_eq!(reserved_balance(bob()), budget(&bob_account_id));
+		assert_eq!(
+			EVM::sponsor_spendings(&bob_account_id),
+			Some(SponsorSpending {
+				max_spend_per_period: 1000,
+				period: 10,
+				period_start: 11,
+				spent: 640,
+			})
+		);
+
+		assert_noop!(
+			EVM::withdraw_sponsor_budget(Origin::signed(bob_account_id.clone()), 10_000),
+			Error::<Runtime>::SponsorBudgetInsufficient
+		);
+		let bob_balance = balance(bob());
+		assert_ok!(EVM::withdraw_sponsor_budget(
+			Origin::signed(bob_account_id.clone()),
+			10_000 - 60 - 640
+		));
+		System::assert_last_event(Event::EVM(crate::Event::SponsorBudgetWithdrawn {
+			sponsor: bob_account_id.clone(),
+			amount: 10_000 - 60 - 640,
+		}));
+		assert_eq!(budget(&bob_account_id), 0);
+		assert_eq!(balance(bob()), bob_balance + 10_000 - 60 - 640);
+
+		assert_ok!(EVM::unsponsor_contract(
+			Origin::signed(bob_account_id.clone()),
+			contract_address
+		));
+		System::assert_last_event(Event::EVM(crate::Event::ContractUnsponsored {
+			sponsor: bob_account_id.clone(),
+			contract: contract_address,
+		}));
+		assert_eq!(EVM::sponsorships(&bob_account_id, contract_address), None);
+		assert_noop!(
+			EVM::unsponsor_contract(Origin::signed(bob_account_id), contract_address),
+			Error::<Runtime>::NotSponsored
+		);
+	});
+}
+
+#[test]
+fn should_batch_call() {
+	// pragma solidity 0.8.2;
+	//
+	// contract Storage {
+	//     uint256 public number;
+	//
+	//     function store(uint256 num) public {
+	//         number = num;
+	//     }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5061012c806100206000396000f3fe6080604052348015600f57600080fd5b506004361060325760003560e01c80636057361d1460375780638381f58a14604f575b600080fd5b604d600480360381019060499190608c565b6069565b005b60556073565b6040516060919060bf565b60405180910390f35b8060008190555050565b60005481565b60008135905060868160e2565b92915050565b600060208284031215609d57600080fd5b600060a9848285016079565b91505092915050565b60b98160d8565b82525050565b600060208201905060d2600083018460b2565b92915050565b6000819050919050565b60e98160d8565b811460f357600080fd5b5056fea2646970667358221220b161a9e6cc3d4aac8bc0fd65e420da7555db59fefe6a1d4e8e7eea98e99b293b64736f6c63430008020033"
+	).unwrap();
+	// Storage.store(1)
+	let store_1 = from_hex("0x6057361d0000000000000000000000000000000000000000000000000000000000000001").unwrap();
+	// Storage.store(2)
+	let store_2 = from_hex("0x6057361d0000000000000000000000000000000000000000000000000000000000000002").unwrap();
+	// unknown selector, reverts
+	let invalid = from_hex("0x12345678").unwrap();
+
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
+
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract.clone(),
+			0,
+			1000000,
+			1000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+		let item = |input: Vec<u8>, storage_limit: u32| BatchCallItem {
+			target: contract_address,
+			input,
+			value: 0,
+			gas_limit: 1000000,
+			storage_limit,
+		};
+
+		let alice_balance = balance(alice());
+		let alice_reserved = reserved_balance(alice());
+		let contract_reserved = reserved_balance(contract_address);
+
+		assert_noop!(
+			EVM::batch_call(Origin::signed(alice_account_id.clone()), vec![], false),
+			Error::<Runtime>::EmptyBatchCall
+		);
+		assert_noop!(
+			EVM::batch_call(
+				Origin::signed(alice_account_id.clone()),
+				vec![item(store_1.clone(), 0); MaxBatchCalls::get() as usize + 1],
+				false,
+			),
+			Error::<Runtime>::TooManyBatchCalls
+		);
+
+		// the storage limit of the second call is used by the first call
+		assert_ok!(EVM::batch_call(
+			Origin::signed(alice_account_id.clone()),
+			vec![item(store_1.clone(), 0), item(invalid.clone(), 64)],
+			false,
+		));
+		System::assert_last_event(Event::EVM(crate::Event::BatchCallExecuted {
+			from: alice(),
+			succeeded: 1,
+			failed: 1,
+		}));
+		assert_eq!(
+			EVM::account_storages(contract_address, H256::zero()),
+			H256::from_low_u64_be(1)
+		);
+		assert_eq!(balance(alice()), alice_balance - 640);
+		assert_eq!(reserved_balance(alice()), alice_reserved);
+		assert_eq!(reserved_balance(contract_address), contract_reserved + 640);
+
+		// all the calls are reverted if one of them failed
+		assert_ok!(EVM::batch_call(
+			Origin::signed(alice_account_id.clone()),
+			vec![item(store_2.clone(), 0), item(invalid, 0), item(store_2, 0)],
+			true,
+		));
+		System::assert_last_event(Event::EVM(crate::Event::BatchCallReverted {
+			from: alice(),
+			index: 1,
+		}));
+		assert_eq!(
+			EVM::account_storages(contract_address, H256::zero()),
+			H256::from_low_u64_be(1)
+		);
+		assert_eq!(balance(alice()), alice_balance - 640);
+		assert_eq!(reserved_balance(alice()), alice_reserved);
+
+		// the shared storage limit is not enough for a new storage slot
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			1000000,
+			1000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let new_contract_address = result.value;
+		let alice_balance = balance(alice());
+		assert_ok!(EVM::batch_call(
+			Origin::signed(alice_account_id),
+			vec![BatchCallItem {
+				target: new_contract_address,
+				input: store_1,
+				value: 0,
+				gas_limit: 1000000,
+				storage_limit: 32,
+			}],
+			false,
+		));
+		System::assert_has_event(Event::EVM(crate::Event::ExecutedFailed {
+			from: alice(),
+			contract: new_contract_address,
+			exit_reason: ExitReason::Error(ExitError::Other(
+				Into::<&str>::into(Error::<Runtime>::OutOfStorage).into(),
+			)),
+			output: vec![],
+			logs: vec![],
+			used_gas: 1000000,
+			used_storage: 0,
+		}));
+		System::assert_last_event(Event::EVM(crate::Event::BatchCallExecuted {
+			from: alice(),
+			succeeded: 0,
+			failed: 1,
+		}));
+		assert_eq!(EVM::account_storages(new_contract_address, H256::zero()), H256::zero());
+		assert_eq!(balance(alice()), alice_balance);
+		assert_eq!(reserved_balance(alice()), alice_reserved);
+	});
+}
+
+#[test]
+fn should_submit_and_verify_contract() {
+	// pragma solidity 0.8.2;
+	//
+	// contract Empty { }
+	let empty = from_hex(
+		"0x6080604052348015600f57600080fd5b50603f80601d6000396000f3fe6080604052600080fdfea2646970667358221220e2641e5566296523edeafd776846b0e535aac278dfcf496804a865948b29646064736f6c63430008020033"
+	).unwrap();
+	let compiler_version = b"0.8.2+commit.661d1103".to_vec();
+	let settings = br#"{"optimizer":{"enabled":false,"runs":200},"evmVersion":"istanbul"}"#.to_vec();
+	let source_hash = H256::repeat_byte(1);
+
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
+		let bob_account_id = <Runtime as Config>::AddressMapping::get_account_id(&bob());
+
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			empty,
+			0,
+			1000000,
+			100000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+		let code_hash = EVM::code_hash_at_address(&contract_address);
+
+		assert_noop!(
+			EVM::submit_verification(
+				Origin::signed(bob_account_id),
+				contract_address,
+				compiler_version.clone(),
+				settings.clone(),
+				source_hash
+			),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			EVM::submit_verification(
+				Origin::signed(alice_account_id.clone()),
+				contract_address,
+				vec![0; MaxCompilerVersionSize::get() as usize + 1],
+				settings.clone(),
+				source_hash
+			),
+			Error::<Runtime>::VerificationInfoTooLarge
+		);
+		assert_noop!(
+			EVM::set_verification_status(Origin::signed(CouncilAccount::get()), contract_address, code_hash, true),
+			Error::<Runtime>::VerificationNotSubmitted
+		);
+
+		assert_ok!(EVM::submit_verification(
+			Origin::signed(alice_account_id.clone()),
+			contract_address,
+			compiler_version.clone(),
+			settings.clone(),
+			source_hash
+		));
+		System::assert_last_event(Event::EVM(crate::Event::VerificationSubmitted {
+			contract: contract_address,
+			code_hash,
+			source_hash,
+		}));
+		let mut verification = ContractVerification {
+			code_hash,
+			compiler_version,
+			settings,
+			source_hash,
+			status: VerificationStatus::Pending,
+		};
+		assert_eq!(EVM::current_verification(&contract_address), Some(verification.clone()));
+		let deposit = EVM::get_storage_deposit_per_byte() * verification.encoded_size() as u128;
+		assert_eq!(
+			EVM::verification_deposits(&contract_address),
+			Some((alice_account_id.clone(), deposit))
+		);
+		assert_eq!(
+			Balances::reserved_balance_named(&RESERVE_ID_VERIFICATION_DEPOSIT, &alice_account_id),
+			deposit
+		);
+
+		// a new submission replaces the deposit
+		assert_ok!(EVM::submit_verification(
+			Origin::signed(alice_account_id.clone()),
+			contract_address,
+			verification.compiler_version.clone(),
+			vec![],
+			source_hash
+		));
+		let smaller_deposit = EVM::get_storage_deposit_per_byte()
+			* ContractVerification {
+				settings: vec![],
+				..verification.clone()
+			}
+			.encoded_size() as u128;
+		assert_eq!(
+			Balances::reserved_balance_named(&RESERVE_ID_VERIFICATION_DEPOSIT, &alice_account_id),
+			smaller_deposit
+		);
+		assert_ok!(EVM::submit_verification(
+			Origin::signed(alice_account_id.clone()),
+			contract_address,
+			verification.compiler_version.clone(),
+			verification.settings.clone(),
+			source_hash
+		));
+		assert_eq!(
+			Balances::reserved_balance_named(&RESERVE_ID_VERIFICATION_DEPOSIT, &alice_account_id),
+			deposit
+		);
+
+		assert_noop!(
+			EVM::set_verification_status(
+				Origin::signed(alice_account_id.clone()),
+				contract_address,
+				code_hash,
+				true
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			EVM::set_verification_status(
+				Origin::signed(CouncilAccount::get()),
+				contract_address,
+				H256::zero(),
+				true
+			),
+			Error::<Runtime>::VerificationNotSubmitted
+		);
+		assert_ok!(EVM::set_verification_status(
+			Origin::signed(CouncilAccount::get()),
+			contract_address,
+			code_hash,
+			true
+		));
+		System::assert_last_event(Event::EVM(crate::Event::VerificationUpdated {
+			contract: contract_address,
+			status: VerificationStatus::Verified,
+		}));
+		verification.status = VerificationStatus::Verified;
+		assert_eq!(EVM::current_verification(&contract_address), Some(verification.clone()));
+
+		// the verification doesn't apply to the new code
+		assert_ok!(EVM::set_code(
+			Origin::signed(alice_account_id.clone()),
+			contract_address,
+			from_hex("0x6080604052600080fd").unwrap()
+		));
+		assert_eq!(EVM::current_verification(&contract_address), None);
+		assert_eq!(EVM::contract_verifications(&contract_address), Some(verification));
+
+		// the verification is removed with the contract
+		assert_ok!(EVM::selfdestruct(
+			Origin::signed(alice_account_id.clone()),
+			contract_address
+		));
+		assert_eq!(EVM::contract_verifications(&contract_address), None);
+		assert_eq!(EVM::verification_deposits(&contract_address), None);
+		assert_eq!(
+			Balances::reserved_balance_named(&RESERVE_ID_VERIFICATION_DEPOSIT, &alice_account_id),
+			0
+		);
+	});
+}
+
 #[test]
 fn should_selfdestruct() {
 	// pragma solidity ^0.5.0;
@@ -1385,59 +2162,224 @@ fn should_selfdestruct() {
 		assert!(Codes::<Runtime>::contains_key(&code_hash));
 
 		assert_noop!(
-			EVM::selfdestruct(Origin::signed(bob_account_id), contract_address),
-			Error::<Runtime>::NoPermission
+			EVM::selfdestruct(Origin::signed(bob_account_id), contract_address),
+			Error::<Runtime>::NoPermission
+		);
+		let contract_account_id = <Runtime as Config>::AddressMapping::get_account_id(&contract_address);
+		assert_eq!(System::providers(&contract_account_id), 2);
+		assert_ok!(EVM::selfdestruct(Origin::signed(alice_account_id), contract_address));
+
+		assert_eq!(System::providers(&contract_account_id), 1);
+		assert!(System::account_exists(&contract_account_id));
+		assert!(Accounts::<Runtime>::contains_key(&contract_address));
+		assert!(!ContractStorageSizes::<Runtime>::contains_key(&contract_address));
+		assert_eq!(AccountStorages::<Runtime>::iter_prefix(&contract_address).count(), 1);
+		assert!(!CodeInfos::<Runtime>::contains_key(&code_hash));
+		assert!(!Codes::<Runtime>::contains_key(&code_hash));
+
+		assert_eq!(balance(alice()), alice_balance);
+		assert_eq!(balance(contract_address), 1000);
+		assert_eq!(
+			reserved_balance(contract_address),
+			287 * EVM::get_storage_deposit_per_byte()
+		);
+
+		// can't publish at the same address
+		assert_noop!(
+			EVM::create_predeploy_contract(
+				Origin::signed(NetworkContractAccount::get()),
+				contract_address,
+				vec![],
+				0,
+				1000000,
+				1000000,
+				vec![],
+			),
+			DispatchErrorWithPostInfo {
+				post_info: PostDispatchInfo {
+					actual_weight: None,
+					pays_fee: Pays::Yes,
+				},
+				error: Error::<Runtime>::ContractAlreadyExisted.into()
+			}
+		);
+
+		IdleScheduler::on_idle(0, 1_000_000_000_000);
+
+		// refund storage deposit
+		assert_eq!(balance(alice()), alice_balance + 3870);
+		assert_eq!(balance(contract_address), 0);
+		assert_eq!(reserved_balance(contract_address), 0);
+
+		assert_eq!(System::providers(&contract_account_id), 0);
+		assert!(!System::account_exists(&contract_account_id));
+		assert!(Accounts::<Runtime>::contains_key(&contract_address));
+		assert_eq!(AccountStorages::<Runtime>::iter_prefix(&contract_address).count(), 0);
+	});
+}
+
+#[test]
+fn should_reclaim_inactive_contract() {
+	// the `should_selfdestruct` contract, constructed with 123
+	let contract = from_hex(
+		"0x60806040526040516100c73803806100c783398181016040526020811015602557600080fd5b81019080805190602001909291905050508060008190555050607b8061004c6000396000f3fe608060405260043610601c5760003560e01c806320965255146021575b600080fd5b6027603d565b6040518082815260200191505060405180910390f35b6000805490509056fea265627a7a72315820b832564a9db725638dcef03d07bfbdd2dc818020ea359630317e2126e95c314964736f6c63430005110032000000000000000000000000000000000000000000000000000000000000007b"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let bob_account_id = <Runtime as Config>::AddressMapping::get_account_id(&bob());
+		let amount = 1000u128;
+
+		System::set_block_number(1);
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			convert_decimals_to_evm(amount),
+			1000000,
+			100000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+		let alice_balance = INITIAL_BALANCE - 287 * EVM::get_storage_deposit_per_byte() - amount;
+		assert_eq!(balance(alice()), alice_balance);
+		assert_eq!(EVM::contract_last_accessed(contract_address), Some(1));
+
+		// call method `getValue()`
+		System::set_block_number(50);
+		assert_ok!(<Runtime as Config>::Runner::call(
+			alice(),
+			alice(),
+			contract_address,
+			from_hex("0x20965255").unwrap(),
+			0,
+			1000000,
+			0,
+			vec![],
+			<Runtime as Config>::config(),
+		));
+		assert_eq!(EVM::contract_last_accessed(contract_address), Some(50));
+
+		System::set_block_number(50 + CONTRACT_INACTIVITY_PERIOD - 1);
+		assert_noop!(
+			EVM::reclaim_contract(Origin::signed(bob_account_id.clone()), contract_address),
+			Error::<Runtime>::ContractStillActive
+		);
+
+		System::set_block_number(50 + CONTRACT_INACTIVITY_PERIOD);
+		assert_ok!(EVM::reclaim_contract(Origin::signed(bob_account_id), contract_address));
+		System::assert_last_event(Event::EVM(crate::Event::ContractReclaimed {
+			contract: contract_address,
+			maintainer: alice(),
+		}));
+		assert!(!ContractLastAccessed::<Runtime>::contains_key(&contract_address));
+		assert!(!ContractStorageSizes::<Runtime>::contains_key(&contract_address));
+
+		IdleScheduler::on_idle(0, 1_000_000_000_000);
+
+		// the storage deposit and the balance are refunded to the maintainer
+		assert_eq!(balance(alice()), alice_balance + 3870);
+		assert_eq!(balance(bob()), INITIAL_BALANCE);
+		assert_eq!(balance(contract_address), 0);
+		assert_eq!(reserved_balance(contract_address), 0);
+		assert_eq!(AccountStorages::<Runtime>::iter_prefix(&contract_address).count(), 0);
+	});
+}
+
+#[test]
+fn should_not_reclaim_published_contract() {
+	// pragma solidity 0.8.2;
+	//
+	// contract Empty { }
+	let empty = from_hex(
+		"0x6080604052348015600f57600080fd5b50603f80601d6000396000f3fe6080604052600080fdfea2646970667358221220e2641e5566296523edeafd776846b0e535aac278dfcf496804a865948b29646064736f6c63430008020033"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let bob_account_id = <Runtime as Config>::AddressMapping::get_account_id(&bob());
+
+		System::set_block_number(1);
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			empty,
+			0,
+			1000000,
+			100000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+
+		assert_ok!(EVM::publish_free(
+			Origin::signed(CouncilAccount::get()),
+			contract_address
+		));
+		assert_eq!(EVM::contract_last_accessed(contract_address), None);
+
+		System::set_block_number(1 + CONTRACT_INACTIVITY_PERIOD);
+		assert_noop!(
+			EVM::reclaim_contract(Origin::signed(bob_account_id), contract_address),
+			Error::<Runtime>::ContractAlreadyPublished
+		);
+	});
+}
+
+#[test]
+fn migration_should_track_unpublished_contracts() {
+	// pragma solidity 0.8.2;
+	//
+	// contract Empty { }
+	let empty = from_hex(
+		"0x6080604052348015600f57600080fd5b50603f80601d6000396000f3fe6080604052600080fdfea2646970667358221220e2641e5566296523edeafd776846b0e535aac278dfcf496804a865948b29646064736f6c63430008020033"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			empty,
+			0,
+			1000000,
+			100000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+
+		ContractLastAccessed::<Runtime>::remove(&contract_address);
+		StorageVersion::new(0).put::<EVM>();
+
+		System::set_block_number(10);
+		crate::migrations::v1::migrate::<Runtime>();
+		assert_eq!(EVM::contract_access_tracked_since(), 10);
+		assert_eq!(EVM::contract_last_accessed(contract_address), None);
+		assert_eq!(EVM::on_chain_storage_version(), 1);
+
+		// only migrates once
+		System::set_block_number(20);
+		crate::migrations::v1::migrate::<Runtime>();
+		assert_eq!(EVM::contract_access_tracked_since(), 10);
+
+		// the contract is treated as accessed when the tracking started
+		let bob_account_id = <Runtime as Config>::AddressMapping::get_account_id(&bob());
+		System::set_block_number(10 + CONTRACT_INACTIVITY_PERIOD - 1);
+		assert_noop!(
+			EVM::reclaim_contract(Origin::signed(bob_account_id.clone()), contract_address),
+			Error::<Runtime>::ContractStillActive
 		);
-		let contract_account_id = <Runtime as Config>::AddressMapping::get_account_id(&contract_address);
-		assert_eq!(System::providers(&contract_account_id), 2);
-		assert_ok!(EVM::selfdestruct(Origin::signed(alice_account_id), contract_address));
-
-		assert_eq!(System::providers(&contract_account_id), 1);
-		assert!(System::account_exists(&contract_account_id));
-		assert!(Accounts::<Runtime>::contains_key(&contract_address));
-		assert!(!ContractStorageSizes::<Runtime>::contains_key(&contract_address));
-		assert_eq!(AccountStorages::<Runtime>::iter_prefix(&contract_address).count(), 1);
-		assert!(!CodeInfos::<Runtime>::contains_key(&code_hash));
-		assert!(!Codes::<Runtime>::contains_key(&code_hash));
 
-		assert_eq!(balance(alice()), alice_balance);
-		assert_eq!(balance(contract_address), 1000);
+		// and its accesses are tracked lazily
+		EVM::touch_contract(&contract_address);
 		assert_eq!(
-			reserved_balance(contract_address),
-			287 * EVM::get_storage_deposit_per_byte()
+			EVM::contract_last_accessed(contract_address),
+			Some(10 + CONTRACT_INACTIVITY_PERIOD - 1)
 		);
-
-		// can't publish at the same address
+		System::set_block_number(10 + CONTRACT_INACTIVITY_PERIOD);
 		assert_noop!(
-			EVM::create_predeploy_contract(
-				Origin::signed(NetworkContractAccount::get()),
-				contract_address,
-				vec![],
-				0,
-				1000000,
-				1000000,
-				vec![],
-			),
-			DispatchErrorWithPostInfo {
-				post_info: PostDispatchInfo {
-					actual_weight: None,
-					pays_fee: Pays::Yes,
-				},
-				error: Error::<Runtime>::ContractAlreadyExisted.into()
-			}
+			EVM::reclaim_contract(Origin::signed(bob_account_id), contract_address),
+			Error::<Runtime>::ContractStillActive
 		);
-
-		IdleScheduler::on_idle(0, 1_000_000_000_000);
-
-		// refund storage deposit
-		assert_eq!(balance(alice()), alice_balance + 3870);
-		assert_eq!(balance(contract_address), 0);
-		assert_eq!(reserved_balance(contract_address), 0);
-
-		assert_eq!(System::providers(&contract_account_id), 0);
-		assert!(!System::account_exists(&contract_account_id));
-		assert!(Accounts::<Runtime>::contains_key(&contract_address));
-		assert_eq!(AccountStorages::<Runtime>::iter_prefix(&contract_address).count(), 0);
 	});
 }
 
@@ -2018,3 +2960,364 @@ fn remove_account_works() {
 		assert_eq!(Accounts::<Runtime>::contains_key(&address), false);
 	});
 }
+
+#[test]
+fn scheduled_task_should_call_contract() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+	// multiply(2, 3)
+	let multiply = from_hex(
+		"0x165c4a1600000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			1000000,
+			1000000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		publish_free(contract_address);
+
+		let task = EvmTask::<Runtime>::Schedule {
+			from: alice(),
+			target: contract_address,
+			input: multiply,
+			value: 0,
+			gas_limit: 100_000,
+			storage_limit: 0,
+		};
+
+		// not enough weight for the gas limit, wait for another block
+		assert_eq!(
+			task.clone().dispatch(99_999),
+			TaskResult {
+				result: Ok(()),
+				used_weight: 0,
+				finished: false,
+			}
+		);
+
+		let result = task.dispatch(100_000);
+		assert_eq!(result.result, Ok(()));
+		assert!(result.finished);
+		assert!(result.used_weight > 0 && result.used_weight < 100_000);
+
+		// unknown selector reverts
+		let task = EvmTask::<Runtime>::Schedule {
+			from: alice(),
+			target: contract_address,
+			input: vec![0x12, 0x34, 0x56, 0x78],
+			value: 0,
+			gas_limit: 100_000,
+			storage_limit: 0,
+		};
+		assert_ok!(IdleScheduler::schedule_task(Origin::root(), task.into()));
+		assert_eq!(module_idle_scheduler::Tasks::<Runtime>::iter().count(), 1);
+
+		IdleScheduler::on_idle(0, 1_000_000_000_000);
+
+		assert_eq!(module_idle_scheduler::Tasks::<Runtime>::iter().count(), 0);
+		System::assert_has_event(Event::IdleScheduler(module_idle_scheduler::Event::TaskDispatched {
+			task_id: 0,
+			result: Err(Error::<Runtime>::ScheduledCallFailed.into()),
+		}));
+	});
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn tracer_should_trace_call() {
+	use crate::runner::tracing::{trace, CallType, OpcodeConfig, Trace, TracerConfig};
+
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+	// multiply(2, 3)
+	let multiply = from_hex(
+		"0x165c4a1600000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			1000000,
+			1000000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		publish_free(contract_address);
+
+		let call = || {
+			<Runtime as Config>::Runner::call(
+				alice(),
+				alice(),
+				contract_address,
+				multiply.clone(),
+				0,
+				100_000,
+				0,
+				vec![],
+				<Runtime as Config>::config(),
+			)
+			.unwrap()
+		};
+
+		let (info, call_trace) = trace(TracerConfig::CallTracer, call);
+		match call_trace {
+			Some(Trace::Calls(call_trace)) => {
+				assert_eq!(call_trace.call_type, CallType::Call);
+				assert_eq!(call_trace.from, alice());
+				assert_eq!(call_trace.to, contract_address);
+				assert_eq!(call_trace.input, multiply);
+				assert_eq!(call_trace.output, info.value);
+				assert_eq!(call_trace.gas, 100_000);
+				assert_eq!(U256::from(call_trace.gas_used), info.used_gas);
+				assert_eq!(call_trace.error, None);
+				assert!(call_trace.calls.is_empty());
+			}
+			other => panic!("unexpected trace: {:?}", other),
+		}
+
+		let (info, opcode_trace) = trace(TracerConfig::OpcodeTracer(OpcodeConfig::default()), call);
+		match opcode_trace {
+			Some(Trace::Opcodes(opcode_trace)) => {
+				assert!(!opcode_trace.failed);
+				assert_eq!(opcode_trace.return_value, info.value);
+				let logs = opcode_trace.struct_logs;
+				// PUSH1 0x80 PUSH1 0x40 MSTORE
+				assert_eq!(logs[0].op, 0x60);
+				assert_eq!(logs[0].pc, 0);
+				assert_eq!(logs[0].depth, 1);
+				assert_eq!(logs[0].gas_cost, 3);
+				assert_eq!(logs[0].stack, Some(vec![]));
+				assert_eq!(logs[0].memory, None);
+				assert_eq!(logs[1].gas, logs[0].gas - logs[0].gas_cost);
+				assert_eq!(logs[2].op, 0x52);
+				assert_eq!(logs[2].pc, 4);
+				assert_eq!(logs[2].stack, Some(vec![U256::from(0x80), U256::from(0x40)]));
+				// RETURN
+				assert_eq!(logs.last().map(|log| log.op), Some(0xf3));
+			}
+			other => panic!("unexpected trace: {:?}", other),
+		}
+
+		// not traced
+		assert_eq!(call().value, info.value);
+	});
+}
+
+#[test]
+fn create_access_list_works() {
+	use crate::runner::access_list::create_access_list;
+	use primitives::evm::{AccessListItem, StorageDepositInfo};
+
+	// the `should_update_storage` contract
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b50602a6000803373ffffffffffffffffffffffffffffffffffffffff1673ffffffffffffffffffffffffffffffffffffffff16815260200190815260200160002081905550610154806100646000396000f3fe608060405234801561001057600080fd5b50600436106100365760003560e01c806354fe9fd71461003b57806360fe47b114610093575b600080fd5b61007d6004803603602081101561005157600080fd5b81019080803573ffffffffffffffffffffffffffffffffffffffff1690602001909291905050506100c1565b6040518082815260200191505060405180910390f35b6100bf600480360360208110156100a957600080fd5b81019080803590602001909291905050506100d9565b005b60006020528060005260406000206000915090505481565b806000803373ffffffffffffffffffffffffffffffffffffffff1673ffffffffffffffffffffffffffffffffffffffff168152602001908152602001600020819055505056fea265627a7a723158207ab6991e97c9c12f57d81df0c7f955435418354adeb26116b581d7f2f035ca8f64736f6c63430005110032"
+	).unwrap();
+	// set(123)
+	let set = from_hex("0x60fe47b1000000000000000000000000000000000000000000000000000000000000007b").unwrap();
+
+	new_test_ext().execute_with(|| {
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			500000,
+			100000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+		let contract_storage = ContractStorageSizes::<Runtime>::get(&contract_address);
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		publish_free(contract_address);
+
+		// values[bob]
+		let slot = H256::from(sp_io::hashing::keccak_256(
+			&[H256::from(bob()).as_bytes(), H256::zero().as_bytes()].concat(),
+		));
+
+		let info = create_access_list::<Runtime>(
+			bob(),
+			Some(contract_address),
+			set.clone(),
+			0,
+			1000000,
+			STORAGE_SIZE,
+			vec![],
+		)
+		.unwrap();
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		assert_eq!(info.used_storage, STORAGE_SIZE as i32);
+		// the caller is left out, the callee is kept for its storage slot
+		assert_eq!(
+			info.access_list,
+			vec![AccessListItem {
+				address: contract_address,
+				storage_keys: vec![slot],
+			}]
+		);
+		assert_eq!(
+			info.storage_deposits,
+			vec![StorageDepositInfo {
+				address: contract_address,
+				storage: STORAGE_SIZE as i32,
+				total_storage: contract_storage + STORAGE_SIZE,
+				deposit: EVM::get_storage_deposit_per_byte() * STORAGE_SIZE as u128,
+			}]
+		);
+
+		// the estimations are discarded
+		assert_eq!(
+			ContractStorageSizes::<Runtime>::get(&contract_address),
+			contract_storage
+		);
+
+		// the used gas is the one of the call with the access list applied
+		let mut config = <Runtime as Config>::config().clone();
+		config.estimate = true;
+		let result = <Runtime as Config>::Runner::call(
+			bob(),
+			bob(),
+			contract_address,
+			set,
+			0,
+			1000000,
+			STORAGE_SIZE,
+			vec![(contract_address, vec![slot])],
+			&config,
+		)
+		.unwrap();
+		assert_eq!(result.used_gas, info.used_gas);
+	});
+}
+
+#[test]
+fn schedule_idle_call_works() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+	// multiply(2, 3)
+	let multiply = from_hex(
+		"0x165c4a1600000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			1000000,
+			1000000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		publish_free(contract_address);
+
+		// more than the max weight of a normal extrinsic
+		assert_noop!(
+			EVM::schedule_idle_call(alice(), contract_address, multiply.clone(), 0, 1_000_000_000_000, 0),
+			Error::<Runtime>::ScheduledGasLimitTooHigh
+		);
+
+		assert_ok!(EVM::schedule_idle_call(
+			alice(),
+			contract_address,
+			multiply,
+			0,
+			100_000,
+			0
+		));
+		assert_eq!(module_idle_scheduler::Tasks::<Runtime>::iter().count(), 1);
+
+		// not enough idle weight, wait for another block
+		IdleScheduler::on_idle(0, 50_000);
+		assert_eq!(module_idle_scheduler::Tasks::<Runtime>::iter().count(), 1);
+
+		IdleScheduler::on_idle(0, 1_000_000_000_000);
+		assert_eq!(module_idle_scheduler::Tasks::<Runtime>::iter().count(), 0);
+		System::assert_has_event(Event::IdleScheduler(module_idle_scheduler::Event::TaskDispatched {
+			task_id: 0,
+			result: Ok(()),
+		}));
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			Event::EVM(crate::Event::Executed { from, contract, .. }) if from == alice() && contract == contract_address
+		)));
+	});
+}
+
+#[test]
+fn scheduled_task_exceeding_idle_weight_is_dropped() {
+	new_test_ext().execute_with(|| {
+		let task = EvmTask::<Runtime>::Schedule {
+			from: alice(),
+			target: contract_a(),
+			input: vec![],
+			value: 0,
+			gas_limit: 1_000_000_000_000,
+			storage_limit: 0,
+		};
+		assert_ok!(IdleScheduler::schedule_task(Origin::root(), task.into()));
+
+		IdleScheduler::on_idle(0, 1_000_000_000_000);
+
+		assert_eq!(module_idle_scheduler::Tasks::<Runtime>::iter().count(), 0);
+		System::assert_has_event(Event::IdleScheduler(module_idle_scheduler::Event::TaskDispatched {
+			task_id: 0,
+			result: Err(Error::<Runtime>::ScheduledGasLimitTooHigh.into()),
+		}));
+		System::assert_has_event(Event::EVM(crate::Event::ExecutedFailed {
+			from: alice(),
+			contract: contract_a(),
+			exit_reason: ExitReason::Error(ExitError::OutOfGas),
+			output: vec![],
+			logs: vec![],
+			used_gas: 0,
+			used_storage: 0,
+		}));
+	});
+}
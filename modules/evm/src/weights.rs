@@ -21,6 +21,12 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2022-01-30, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+//!
+//! The weights of `reclaim_contract`, `propose_upgrade`, `cancel_upgrade`, `enact_upgrade`,
+//! `deposit_sponsor_budget`, `withdraw_sponsor_budget`, `sponsor_contract`, `unsponsor_contract`,
+//! `sponsored_call`, `set_sponsor_spend_limit`, `submit_verification` and `set_verification_status`
+//! are NOT benchmark results. Their storage accesses are counted from each call and the execution
+//! times are rough upper bounds, to be replaced once these calls are benchmarked.
 
 // Executed Command:
 // target/release/acala
@@ -58,6 +64,18 @@ pub trait WeightInfo {
 	fn disable_contract_development() -> Weight;
 	fn set_code(c: u32, ) -> Weight;
 	fn selfdestruct() -> Weight;
+	fn reclaim_contract() -> Weight;
+	fn propose_upgrade(c: u32, ) -> Weight;
+	fn cancel_upgrade() -> Weight;
+	fn enact_upgrade() -> Weight;
+	fn deposit_sponsor_budget() -> Weight;
+	fn withdraw_sponsor_budget() -> Weight;
+	fn sponsor_contract() -> Weight;
+	fn unsponsor_contract() -> Weight;
+	fn sponsored_call() -> Weight;
+	fn set_sponsor_spend_limit() -> Weight;
+	fn submit_verification(c: u32, ) -> Weight;
+	fn set_verification_status() -> Weight;
 }
 
 /// Weights for module_evm using the Acala node and recommended hardware.
@@ -188,6 +206,114 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(7 as Weight))
 			.saturating_add(T::DbWeight::get().writes(7 as Weight))
 	}
+	// Storage: EVM Accounts (r:1 w:1)
+	// Storage: EVM ContractLastAccessed (r:1 w:1)
+	// Storage: EVM CodeInfos (r:1 w:1)
+	// Storage: EVM ContractStorageSizes (r:1 w:1)
+	// Storage: IdleScheduler NextTaskId (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: IdleScheduler Tasks (r:0 w:1)
+	// Storage: EVM Codes (r:0 w:1)
+	fn reclaim_contract() -> Weight {
+		(122_641_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+	// Storage: EVM Accounts (r:1 w:0)
+	// Storage: EVM UpgradeProposals (r:1 w:1)
+	// Storage: EVM CodeInfos (r:1 w:1)
+	// Storage: EVM Codes (r:0 w:1)
+	fn propose_upgrade(c: u32, ) -> Weight {
+		(68_472_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((5_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+	// Storage: EVM Accounts (r:1 w:0)
+	// Storage: EVM UpgradeProposals (r:1 w:1)
+	// Storage: EVM CodeInfos (r:1 w:1)
+	// Storage: EVM Codes (r:0 w:1)
+	fn cancel_upgrade() -> Weight {
+		(56_120_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+	// Storage: EVM UpgradeProposals (r:1 w:1)
+	// Storage: EVM Accounts (r:1 w:1)
+	// Storage: EVM CodeInfos (r:2 w:1)
+	// Storage: Balances Reserves (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: EVM ContractStorageSizes (r:1 w:1)
+	// Storage: EVM ContractLastAccessed (r:1 w:1)
+	// Storage: EVM Codes (r:0 w:1)
+	fn enact_upgrade() -> Weight {
+		(172_335_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn deposit_sponsor_budget() -> Weight {
+		(38_914_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn withdraw_sponsor_budget() -> Weight {
+		(37_206_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: EVM Accounts (r:1 w:0)
+	// Storage: EVM Sponsorships (r:0 w:1)
+	fn sponsor_contract() -> Weight {
+		(21_588_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: EVM Sponsorships (r:1 w:1)
+	fn unsponsor_contract() -> Weight {
+		(19_732_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: EVM Sponsorships (r:1 w:0)
+	// Storage: EVM SponsorSpendings (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: EvmAccounts EvmAddresses (r:1 w:1)
+	fn sponsored_call() -> Weight {
+		(62_417_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: EVM SponsorSpendings (r:1 w:1)
+	fn set_sponsor_spend_limit() -> Weight {
+		(18_204_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+	// Storage: EVM Accounts (r:1 w:0)
+	// Storage: EVM ContractVerifications (r:0 w:1)
+	fn submit_verification(c: u32, ) -> Weight {
+		(41_873_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((1_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: EVM ContractVerifications (r:1 w:1)
+	fn set_verification_status() -> Weight {
+		(24_516_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -254,4 +380,68 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
 	}
+	fn reclaim_contract() -> Weight {
+		(122_641_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn propose_upgrade(c: u32, ) -> Weight {
+		(68_472_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((5_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn cancel_upgrade() -> Weight {
+		(56_120_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn enact_upgrade() -> Weight {
+		(172_335_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+	}
+	fn deposit_sponsor_budget() -> Weight {
+		(38_914_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn withdraw_sponsor_budget() -> Weight {
+		(37_206_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn sponsor_contract() -> Weight {
+		(21_588_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn unsponsor_contract() -> Weight {
+		(19_732_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn sponsored_call() -> Weight {
+		(62_417_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn set_sponsor_spend_limit() -> Weight {
+		(18_204_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn submit_verification(c: u32, ) -> Weight {
+		(41_873_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((1_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn set_verification_status() -> Weight {
+		(24_516_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
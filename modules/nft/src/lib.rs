@@ -47,6 +47,7 @@ use sp_runtime::{
 	DispatchResult, RuntimeDebug,
 };
 use sp_std::prelude::*;
+use support::PositionNFT;
 
 pub mod benchmarking;
 mod mock;
@@ -151,6 +152,10 @@ pub mod module {
 		AttributesTooLarge,
 		/// The given token ID is not correct
 		IncorrectTokenId,
+		/// Position NFTs can not be minted of a burnable class
+		BurnablePositionClass,
+		/// Position NFTs can not be minted of a class with mutable properties
+		MutablePositionClass,
 	}
 
 	#[pallet::event]
@@ -566,3 +571,66 @@ impl<T: Config> Transfer<T::AccountId> for Pallet<T> {
 		Self::do_transfer(&owner, destination, (*class, *instance))
 	}
 }
+
+impl<T: Config> PositionNFT<T::AccountId> for Pallet<T> {
+	type ClassId = ClassIdOf<T>;
+	type TokenId = TokenIdOf<T>;
+
+	/// The class must be mintable, and neither burnable nor have mutable properties, so the
+	/// tokens can only be burned with their positions.
+	fn ensure_position_class(class_id: Self::ClassId) -> DispatchResult {
+		let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+		let properties = class_info.data.properties.0;
+		ensure!(properties.contains(ClassProperty::Mintable), Error::<T>::NonMintable);
+		ensure!(
+			!properties.contains(ClassProperty::Burnable),
+			Error::<T>::BurnablePositionClass
+		);
+		ensure!(
+			!properties.contains(ClassProperty::ClassPropertiesMutable),
+			Error::<T>::MutablePositionClass
+		);
+		Ok(())
+	}
+
+	/// Mint a token to `to`, the deposit is paid by `to`.
+	fn mint(class_id: Self::ClassId, to: &T::AccountId) -> Result<Self::TokenId, DispatchError> {
+		Self::ensure_position_class(class_id)?;
+		let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+
+		let deposit = T::CreateTokenDeposit::get();
+		<T as module::Config>::Currency::reserve_named(&RESERVE_ID, to, deposit)?;
+		let data = TokenData {
+			deposit,
+			attributes: Default::default(),
+		};
+		let token_id = orml_nft::Pallet::<T>::mint(to, class_id, Default::default(), data)?;
+
+		Self::deposit_event(Event::MintedToken {
+			from: class_info.owner,
+			to: to.clone(),
+			class_id,
+			quantity: 1,
+		});
+		Ok(token_id)
+	}
+
+	/// Burn the token, the deposit is returned to the token owner.
+	fn burn(class_id: Self::ClassId, token_id: Self::TokenId) -> DispatchResult {
+		let token_info = orml_nft::Pallet::<T>::tokens(class_id, token_id).ok_or(Error::<T>::TokenIdNotFound)?;
+
+		orml_nft::Pallet::<T>::burn(&token_info.owner, (class_id, token_id))?;
+		<T as module::Config>::Currency::unreserve_named(&RESERVE_ID, &token_info.owner, token_info.data.deposit);
+
+		Self::deposit_event(Event::BurnedToken {
+			owner: token_info.owner,
+			class_id,
+			token_id,
+		});
+		Ok(())
+	}
+
+	fn owner(class_id: Self::ClassId, token_id: Self::TokenId) -> Option<T::AccountId> {
+		<Self as Inspect<T::AccountId>>::owner(&class_id, &token_id)
+	}
+}
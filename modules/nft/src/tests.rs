@@ -712,3 +712,111 @@ fn update_class_properties_should_work() {
 		);
 	});
 }
+
+#[test]
+fn position_nft_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Mintable),
+			Default::default(),
+		));
+		let class_free_balance = free_balance(&class_id_account());
+		let bob_free_balance = free_balance(&BOB);
+
+		assert_noop!(
+			<NFTModule as PositionNFT<AccountId>>::mint(CLASS_ID_NOT_EXIST, &BOB),
+			Error::<Runtime>::ClassIdNotFound
+		);
+
+		// the deposit is paid by the token owner
+		assert_eq!(
+			<NFTModule as PositionNFT<AccountId>>::mint(CLASS_ID, &BOB),
+			Ok(TOKEN_ID)
+		);
+		assert_eq!(
+			<NFTModule as PositionNFT<AccountId>>::owner(CLASS_ID, TOKEN_ID),
+			Some(BOB)
+		);
+		assert_eq!(reserved_balance(&BOB), CREATE_TOKEN_DEPOSIT);
+		assert_eq!(free_balance(&BOB), bob_free_balance - CREATE_TOKEN_DEPOSIT);
+		assert_eq!(free_balance(&class_id_account()), class_free_balance);
+
+		// the holder can't burn the token of its position
+		assert_noop!(
+			NFTModule::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NonBurnable
+		);
+
+		assert_ok!(NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(
+			<NFTModule as PositionNFT<AccountId>>::owner(CLASS_ID, TOKEN_ID),
+			Some(ALICE)
+		);
+		assert_eq!(reserved_balance(&BOB), 0);
+		let alice_free_balance = free_balance(&ALICE);
+
+		// the deposit is returned to the token owner
+		assert_ok!(<NFTModule as PositionNFT<AccountId>>::burn(CLASS_ID, TOKEN_ID));
+		System::assert_last_event(Event::NFTModule(crate::Event::BurnedToken {
+			owner: ALICE,
+			class_id: CLASS_ID,
+			token_id: TOKEN_ID,
+		}));
+		assert_eq!(<NFTModule as PositionNFT<AccountId>>::owner(CLASS_ID, TOKEN_ID), None);
+		assert_eq!(free_balance(&ALICE), alice_free_balance + CREATE_TOKEN_DEPOSIT);
+		assert_eq!(free_balance(&class_id_account()), class_free_balance);
+		assert_noop!(
+			<NFTModule as PositionNFT<AccountId>>::burn(CLASS_ID, TOKEN_ID),
+			Error::<Runtime>::TokenIdNotFound
+		);
+
+		// position NFTs can't be minted of a burnable class
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			Default::default(),
+		));
+		assert_noop!(
+			<NFTModule as PositionNFT<AccountId>>::mint(CLASS_ID + 1, &BOB),
+			Error::<Runtime>::BurnablePositionClass
+		);
+
+		// nor of a class whose properties can be made burnable later
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::ClassPropertiesMutable | ClassProperty::Mintable),
+			Default::default(),
+		));
+		assert_noop!(
+			<NFTModule as PositionNFT<AccountId>>::ensure_position_class(CLASS_ID + 2),
+			Error::<Runtime>::MutablePositionClass
+		);
+		assert_noop!(
+			<NFTModule as PositionNFT<AccountId>>::mint(CLASS_ID + 2, &BOB),
+			Error::<Runtime>::MutablePositionClass
+		);
+
+		// the class owner can't make the tokens of existing positions burnable
+		assert_ok!(<NFTModule as PositionNFT<AccountId>>::ensure_position_class(CLASS_ID));
+		assert_eq!(
+			<NFTModule as PositionNFT<AccountId>>::mint(CLASS_ID, &BOB),
+			Ok(TOKEN_ID + 1)
+		);
+		assert_noop!(
+			NFTModule::update_class_properties(
+				Origin::signed(ALICE),
+				CLASS_ID,
+				Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable)
+			),
+			Error::<Runtime>::Immutable
+		);
+		assert_noop!(
+			NFTModule::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID + 1)),
+			Error::<Runtime>::NonBurnable
+		);
+	});
+}
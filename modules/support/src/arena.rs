@@ -0,0 +1,80 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::H160;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// A contract registered in the arena.
+#[derive(RuntimeDebug, Encode, Decode, Clone, Copy, Default, PartialEq, Eq, TypeInfo)]
+pub struct ArenaContractInfo {
+	pub points: u128,
+	/// The number of instances of the contract currently playing.
+	pub instances: u32,
+	pub wins: u32,
+	pub rating: u32,
+}
+
+/// The game round in progress in the arena.
+#[derive(RuntimeDebug, Encode, Decode, Clone, Copy, Default, PartialEq, Eq, TypeInfo)]
+pub struct ArenaRoundInfo {
+	pub started_at: u64,
+	/// 0 for play, 1 for commit and 2 for reveal.
+	pub phase: u8,
+	/// The number of instances playing the round.
+	pub instances: u32,
+	/// The number of pairings processed in the play or commit phase.
+	pub progress: u32,
+}
+
+pub trait ArenaManager {
+	fn contract_info(contract: H160) -> Option<ArenaContractInfo>;
+
+	/// The `count` best rated contracts, with their ratings.
+	fn leaderboard(count: u32) -> Vec<(H160, u32)>;
+
+	/// The number of rated contracts.
+	fn rated_contracts() -> u32;
+
+	fn current_round() -> Option<ArenaRoundInfo>;
+
+	fn queue_length() -> u32;
+}
+
+impl ArenaManager for () {
+	fn contract_info(_contract: H160) -> Option<ArenaContractInfo> {
+		None
+	}
+
+	fn leaderboard(_count: u32) -> Vec<(H160, u32)> {
+		Default::default()
+	}
+
+	fn rated_contracts() -> u32 {
+		Default::default()
+	}
+
+	fn current_round() -> Option<ArenaRoundInfo> {
+		None
+	}
+
+	fn queue_length() -> u32 {
+		Default::default()
+	}
+}
@@ -17,10 +17,14 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use codec::{Decode, Encode};
+use frame_support::{
+	pallet_prelude::{MaxEncodedLen, Member},
+	Parameter,
+};
 use nutsfinance_stable_asset::{PoolTokenIndex, StableAssetPoolId};
 use scale_info::TypeInfo;
 use sp_core::H160;
-use sp_runtime::{DispatchError, RuntimeDebug};
+use sp_runtime::{DispatchError, DispatchResult, RuntimeDebug};
 use sp_std::{cmp::PartialEq, prelude::*};
 
 #[derive(RuntimeDebug, Encode, Decode, Clone, Copy, PartialEq, TypeInfo)]
@@ -146,3 +150,43 @@ where
 		Ok(Default::default())
 	}
 }
+
+/// NFTs issued to represent the ownership of DEX liquidity positions.
+pub trait PositionNFT<AccountId> {
+	type ClassId: Parameter + Member + Copy + MaxEncodedLen;
+	type TokenId: Parameter + Member + Copy + MaxEncodedLen;
+
+	/// Ensure position tokens can be minted of `class_id`, and that the class can never allow
+	/// burning them other than through `burn`.
+	fn ensure_position_class(class_id: Self::ClassId) -> DispatchResult;
+
+	/// Mint a token of `class_id` to `to`.
+	fn mint(class_id: Self::ClassId, to: &AccountId) -> sp_std::result::Result<Self::TokenId, DispatchError>;
+
+	/// Burn the token.
+	fn burn(class_id: Self::ClassId, token_id: Self::TokenId) -> DispatchResult;
+
+	/// Get the owner of the token.
+	fn owner(class_id: Self::ClassId, token_id: Self::TokenId) -> Option<AccountId>;
+}
+
+impl<AccountId> PositionNFT<AccountId> for () {
+	type ClassId = u32;
+	type TokenId = u64;
+
+	fn ensure_position_class(_class_id: Self::ClassId) -> DispatchResult {
+		Err(DispatchError::Other("position NFT is not supported"))
+	}
+
+	fn mint(_class_id: Self::ClassId, _to: &AccountId) -> sp_std::result::Result<Self::TokenId, DispatchError> {
+		Err(DispatchError::Other("position NFT is not supported"))
+	}
+
+	fn burn(_class_id: Self::ClassId, _token_id: Self::TokenId) -> DispatchResult {
+		Err(DispatchError::Other("position NFT is not supported"))
+	}
+
+	fn owner(_class_id: Self::ClassId, _token_id: Self::TokenId) -> Option<AccountId> {
+		None
+	}
+}
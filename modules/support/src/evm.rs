@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use codec::{Decode, Encode};
+use frame_support::weights::Weight;
 use primitives::{
 	evm::{CallInfo, EvmAddress},
 	CurrencyId,
@@ -53,6 +54,8 @@ pub trait EVM<AccountId> {
 	fn get_origin() -> Option<AccountId>;
 	/// Provide a method to set origin for `on_initialize`
 	fn set_origin(origin: AccountId);
+	/// Convert gas to the weight of its execution.
+	fn gas_to_weight(gas: u64) -> Weight;
 }
 
 #[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug)]
@@ -29,11 +29,13 @@ use sp_std::prelude::*;
 
 use xcm::latest::prelude::*;
 
+pub mod arena;
 pub mod dex;
 pub mod evm;
 pub mod honzon;
 pub mod mocks;
 
+pub use crate::arena::*;
 pub use crate::dex::*;
 pub use crate::evm::*;
 pub use crate::honzon::*;
@@ -140,6 +142,34 @@ impl<AccountId, Balance: Default + Copy, NegativeImbalance: Imbalance<Balance>>
 	}
 }
 
+/// Used by the transaction payment module to let a third party pay the fee of a call.
+pub trait TransactionFeeSponsor<AccountId, Call, Balance> {
+	/// Release `fee` of `call` signed by `who` from the budget of its sponsor, the fee is then
+	/// withdrawn from the sponsor. Returns the sponsor, or `None` if `who` pays the fee.
+	fn sponsor_fee(
+		who: &AccountId,
+		call: &Call,
+		fee: Balance,
+		tip: Balance,
+	) -> Result<Option<AccountId>, DispatchError>;
+
+	/// Return the refunded part of a fee paid by `sponsor` to its budget.
+	fn refund_fee(sponsor: &AccountId, refund: Balance);
+}
+
+impl<AccountId, Call, Balance> TransactionFeeSponsor<AccountId, Call, Balance> for () {
+	fn sponsor_fee(
+		_who: &AccountId,
+		_call: &Call,
+		_fee: Balance,
+		_tip: Balance,
+	) -> Result<Option<AccountId>, DispatchError> {
+		Ok(None)
+	}
+
+	fn refund_fee(_sponsor: &AccountId, _refund: Balance) {}
+}
+
 /// Used to interface with the Compound's Cash module
 pub trait CompoundCashTrait<Balance, Moment> {
 	fn set_future_yield(next_cash_yield: Balance, yield_index: u128, timestamp_effective: Moment) -> DispatchResult;
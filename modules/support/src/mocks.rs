@@ -17,15 +17,16 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 #![allow(clippy::type_complexity)]
-use crate::{AddressMapping, CurrencyId, Erc20InfoMapping};
+use crate::{AddressMapping, CurrencyId, Erc20InfoMapping, ExecutionMode, InvokeContext, EVM};
 use codec::Encode;
+use frame_support::weights::Weight;
 use nutsfinance_stable_asset::traits::StableAsset;
 use nutsfinance_stable_asset::{
 	PoolTokenIndex, RedeemProportionResult, StableAssetPoolId, StableAssetPoolInfo, SwapResult,
 };
 use primitives::{
 	currency::TokenInfo,
-	evm::{EvmAddress, H160_POSITION_TOKEN},
+	evm::{CallInfo, EvmAddress, H160_POSITION_TOKEN},
 };
 use sp_core::{crypto::AccountId32, H160};
 use sp_io::hashing::blake2_256;
@@ -98,6 +99,33 @@ impl Erc20InfoMapping for MockErc20InfoMapping {
 	}
 }
 
+pub struct MockEVM;
+
+impl<AccountId> EVM<AccountId> for MockEVM {
+	type Balance = u128;
+
+	fn execute(
+		_context: InvokeContext,
+		_input: Vec<u8>,
+		_value: Self::Balance,
+		_gas_limit: u64,
+		_storage_limit: u32,
+		_mode: ExecutionMode,
+	) -> Result<CallInfo, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm"))
+	}
+
+	fn get_origin() -> Option<AccountId> {
+		None
+	}
+
+	fn set_origin(_origin: AccountId) {}
+
+	fn gas_to_weight(gas: u64) -> Weight {
+		gas
+	}
+}
+
 pub struct MockStableAsset<CurrencyId, Balance, AccountId, BlockNumber> {
 	_value1: CurrencyId,
 	_value2: Balance,
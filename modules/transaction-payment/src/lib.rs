@@ -59,7 +59,7 @@ use sp_runtime::{
 	FixedPointNumber, FixedPointOperand, FixedU128, Percent, Perquintill,
 };
 use sp_std::prelude::*;
-use support::{DEXManager, PriceProvider, Ratio, SwapLimit, TransactionPayment};
+use support::{DEXManager, PriceProvider, Ratio, SwapLimit, TransactionFeeSponsor, TransactionPayment};
 use xcm::opaque::latest::{prelude::XcmError, AssetId, Fungibility::Fungible, MultiAsset, MultiLocation};
 use xcm_builder::TakeRevenue;
 use xcm_executor::{traits::WeightTrader, Assets};
@@ -348,6 +348,9 @@ pub mod module {
 		#[pallet::constant]
 		type DefaultFeeTokens: Get<Vec<CurrencyId>>;
 
+		/// Third party which may pay the fee of a call on behalf of the signer.
+		type FeeSponsor: TransactionFeeSponsor<Self::AccountId, CallOf<Self>, PalletBalanceOf<Self>>;
+
 		/// The origin which change swap balance threshold or enable charge fee pool.
 		type UpdateOrigin: EnsureOrigin<Self::Origin>;
 	}
@@ -1182,6 +1185,7 @@ where
 			PalletBalanceOf<T>,
 			Option<NegativeImbalanceOf<T>>,
 			Option<PalletBalanceOf<T>>,
+			Option<T::AccountId>,
 		),
 		TransactionValidityError,
 	> {
@@ -1190,7 +1194,7 @@ where
 
 		// Only mess with balances if fee is not zero.
 		if fee.is_zero() {
-			return Ok((fee, None, None));
+			return Ok((fee, None, None, None));
 		}
 
 		let reason = if tip.is_zero() {
@@ -1199,12 +1203,22 @@ where
 			WithdrawReasons::TRANSACTION_PAYMENT | WithdrawReasons::TIP
 		};
 
+		// withdraw the fee of a sponsored call from the sponsor, there is no surplus.
+		if let Some(sponsor) =
+			T::FeeSponsor::sponsor_fee(who, call, fee, tip).map_err(|_| InvalidTransaction::Payment)?
+		{
+			return match <T as Config>::Currency::withdraw(&sponsor, fee, reason, ExistenceRequirement::KeepAlive) {
+				Ok(imbalance) => Ok((fee, Some(imbalance), None, Some(sponsor))),
+				Err(_) => Err(InvalidTransaction::Payment.into()),
+			};
+		}
+
 		let fee_surplus =
 			Pallet::<T>::ensure_can_charge_fee_with_call(who, fee, call).map_err(|_| InvalidTransaction::Payment)?;
 
 		// withdraw native currency as fee, also consider surplus when swap from dex or pool.
 		match <T as Config>::Currency::withdraw(who, fee + fee_surplus, reason, ExistenceRequirement::KeepAlive) {
-			Ok(imbalance) => Ok((fee + fee_surplus, Some(imbalance), Some(fee_surplus))),
+			Ok(imbalance) => Ok((fee + fee_surplus, Some(imbalance), Some(fee_surplus), None)),
 			Err(_) => Err(InvalidTransaction::Payment.into()),
 		}
 	}
@@ -1303,6 +1317,7 @@ where
 		Option<NegativeImbalanceOf<T>>,
 		PalletBalanceOf<T>,         // fee includes surplus
 		Option<PalletBalanceOf<T>>, // surplus
+		Option<Self::AccountId>,    // sponsor which paid the fee
 	);
 
 	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
@@ -1316,7 +1331,7 @@ where
 		info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> TransactionValidity {
-		let (final_fee, _, _) = self.withdraw_fee(who, call, info, len)?;
+		let (final_fee, _, _, _) = self.withdraw_fee(who, call, info, len)?;
 		let tip = self.0;
 		Ok(ValidTransaction {
 			priority: Self::get_priority(info, len, tip, final_fee),
@@ -1331,8 +1346,8 @@ where
 		info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> Result<Self::Pre, TransactionValidityError> {
-		let (fee, imbalance, surplus) = self.withdraw_fee(who, call, info, len)?;
-		Ok((self.0, who.clone(), imbalance, fee, surplus))
+		let (fee, imbalance, surplus, sponsor) = self.withdraw_fee(who, call, info, len)?;
+		Ok((self.0, who.clone(), imbalance, fee, surplus, sponsor))
 	}
 
 	fn post_dispatch(
@@ -1342,7 +1357,7 @@ where
 		len: usize,
 		_result: &DispatchResult,
 	) -> Result<(), TransactionValidityError> {
-		if let Some((tip, who, Some(payed), fee, surplus)) = pre {
+		if let Some((tip, who, Some(payed), fee, surplus, sponsor)) = pre {
 			let actual_fee = Pallet::<T>::compute_actual_fee(len as u32, info, post_info, tip);
 			let refund_fee = fee.saturating_sub(actual_fee);
 			let mut refund = refund_fee;
@@ -1363,8 +1378,13 @@ where
 				let actual_surplus = percent.mul_ceil(actual_fee);
 				refund = refund.saturating_sub(actual_surplus);
 			}
-			let actual_payment = match <T as Config>::Currency::deposit_into_existing(&who, refund) {
+			// the refund of a sponsored fee goes back to the sponsor budget
+			let payer = sponsor.as_ref().unwrap_or(&who);
+			let actual_payment = match <T as Config>::Currency::deposit_into_existing(payer, refund) {
 				Ok(refund_imbalance) => {
+					if let Some(sponsor) = &sponsor {
+						T::FeeSponsor::refund_fee(sponsor, refund);
+					}
 					// The refund cannot be larger than the up front payed max weight.
 					// `PostDispatchInfo::calc_unspent` guards against such a case.
 					match payed.offset(refund_imbalance) {
@@ -39,7 +39,7 @@ use sp_runtime::{
 	Perbill,
 };
 use sp_std::cell::RefCell;
-use support::mocks::MockStableAsset;
+use support::mocks::{MockEVM, MockStableAsset};
 use support::{mocks::MockAddressMapping, Price};
 
 pub type AccountId = AccountId32;
@@ -181,6 +181,12 @@ impl module_dex::Config for Runtime {
 	type ListingOrigin = frame_system::EnsureSignedBy<Zero, AccountId>;
 	type ExtendedProvisioningBlocks = ConstU64<0>;
 	type OnLiquidityPoolUpdated = ();
+	type PositionNFT = ();
+	type MaxInitializedTicks = ConstU32<16>;
+	type MaxLimitOrdersPerAccount = ConstU32<8>;
+	type LimitOrderKeeperFee = ();
+	type MaxRouteSplits = ConstU32<4>;
+	type EVM = MockEVM;
 }
 
 parameter_types! {
@@ -270,6 +276,7 @@ impl Config for Runtime {
 	type CustomFeeSurplus = CustomFeeSurplus;
 	type AlternativeFeeSurplus = AlternativeFeeSurplus;
 	type DefaultFeeTokens = DefaultFeeTokens;
+	type FeeSponsor = ();
 }
 
 thread_local! {
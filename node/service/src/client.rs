@@ -42,6 +42,8 @@ pub trait RuntimeApiCollection:
 	+ orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, TimeStampedPrice>
 	+ orml_tokens_rpc::TokensRuntimeApi<Block, CurrencyId, Balance>
 	+ module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance>
+	+ module_evm_rpc_runtime_api::EVMTraceApi<Block, Balance>
+	+ module_arena_rpc_runtime_api::ArenaApi<Block, BlockNumber>
 	+ sp_api::Metadata<Block>
 	+ sp_offchain::OffchainWorkerApi<Block>
 	+ sp_session::SessionKeys<Block>
@@ -61,6 +63,8 @@ where
 		+ orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, TimeStampedPrice>
 		+ orml_tokens_rpc::TokensRuntimeApi<Block, CurrencyId, Balance>
 		+ module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance>
+		+ module_evm_rpc_runtime_api::EVMTraceApi<Block, Balance>
+		+ module_arena_rpc_runtime_api::ArenaApi<Block, BlockNumber>
 		+ sp_api::Metadata<Block>
 		+ sp_offchain::OffchainWorkerApi<Block>
 		+ sp_session::SessionKeys<Block>
@@ -31,9 +31,11 @@ use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_core::{H160, H256, U256};
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{RuntimeDebug, SaturatedConversion};
 use sp_std::vec::Vec;
 
+pub mod tracing;
+
 /// Evm Address.
 pub type EvmAddress = sp_core::H160;
 
@@ -68,6 +70,59 @@ pub struct ExecutionInfo<T> {
 pub type CallInfo = ExecutionInfo<Vec<u8>>;
 pub type CreateInfo = ExecutionInfo<H160>;
 
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct StorageDepositInfo {
+	/// The contract
+	pub address: EvmAddress,
+	/// The change of the storage used by the contract, in bytes
+	pub storage: i32,
+	/// The storage used by the contract after the execution, in bytes
+	pub total_storage: u32,
+	/// The deposit reserved for the contract, or refunded to the caller if `storage` is negative
+	pub deposit: Balance,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum VerificationStatus {
+	/// Waiting for a verifier to recompile the source
+	Pending,
+	/// The recompiled source matches the code of the contract
+	Verified,
+	/// The recompiled source doesn't match the code of the contract
+	Failed,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ContractVerification {
+	/// The code hash of the contract when the verification was submitted
+	pub code_hash: H256,
+	/// The compiler version, e.g. `0.8.2+commit.661d1103`
+	pub compiler_version: Vec<u8>,
+	/// The compiler settings, in the `settings` format of the solc standard JSON input
+	pub settings: Vec<u8>,
+	/// The hash of the source, or of the metadata which references the source
+	pub source_hash: H256,
+	pub status: VerificationStatus,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AccessListInfo {
+	pub exit_reason: ExitReason,
+	/// The output of a call, empty for a create
+	pub output: Vec<u8>,
+	/// The gas used with `access_list` applied
+	pub used_gas: U256,
+	pub used_storage: i32,
+	/// The accounts and storage slots accessed, without the caller, the callee and the precompiles
+	pub access_list: Vec<AccessListItem>,
+	/// The storage deposit changes, one per contract whose storage changed
+	pub storage_deposits: Vec<StorageDepositInfo>,
+}
+
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct EstimateResourcesRequest {
@@ -146,6 +201,26 @@ pub fn is_system_contract(address: EvmAddress) -> bool {
 	address.as_bytes().starts_with(&SYSTEM_CONTRACT_ADDRESS_PREFIX)
 }
 
+/// Decode the revert reason of an `Error(string)` revert output.
+pub fn decode_revert_message(data: &[u8]) -> Option<&str> {
+	// A minimum size of error function selector (4) + offset (32) + string length
+	// (32) should contain a utf-8 encoded revert reason.
+	let msg_start: usize = 68;
+	if data.len() > msg_start {
+		let message_len = U256::from(&data[36..msg_start]).saturated_into::<usize>();
+		let msg_end = msg_start.checked_add(message_len)?;
+
+		if data.len() < msg_end {
+			return None;
+		}
+		let body: &[u8] = &data[msg_start..msg_end];
+		if let Ok(reason) = sp_std::str::from_utf8(body) {
+			return Some(reason);
+		}
+	}
+	None
+}
+
 pub const H160_POSITION_CURRENCY_ID_TYPE: usize = 9;
 pub const H160_POSITION_TOKEN: usize = 19;
 pub const H160_POSITION_TOKEN_NFT: Range<usize> = 16..20;
@@ -0,0 +1,335 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! EVM execution traces.
+//!
+//! The traces are collected by the `tracing` feature of module-evm and serialize to the output of
+//! the geth `callTracer` and of the default struct logger.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Serialize, Serializer};
+use sp_core::{H160, H256, U256};
+use sp_runtime::RuntimeDebug;
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+/// The tracer used to trace an EVM execution.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum TracerConfig {
+	/// Collect the tree of call frames.
+	CallTracer,
+	/// Collect the executed opcodes.
+	OpcodeTracer(OpcodeConfig),
+}
+
+/// Options of the opcode tracer.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+pub struct OpcodeConfig {
+	/// Do not record the stack of each step.
+	pub disable_stack: bool,
+	/// Do not record the storage slots accessed by the contract.
+	pub disable_storage: bool,
+	/// Record the memory of each step.
+	pub enable_memory: bool,
+}
+
+/// The kind of a call frame.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize), serde(rename_all = "UPPERCASE"))]
+pub enum CallType {
+	Call,
+	CallCode,
+	DelegateCall,
+	StaticCall,
+	Create,
+	Create2,
+}
+
+/// A call frame of the `callTracer`.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize), serde(rename_all = "camelCase"))]
+pub struct CallTrace {
+	#[cfg_attr(feature = "std", serde(rename = "type"))]
+	pub call_type: CallType,
+	pub from: H160,
+	pub to: H160,
+	#[cfg_attr(feature = "std", serde(serialize_with = "sp_core::bytes::serialize"))]
+	pub input: Vec<u8>,
+	pub value: U256,
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_quantity"))]
+	pub gas: u64,
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_quantity"))]
+	pub gas_used: u64,
+	#[cfg_attr(feature = "std", serde(serialize_with = "sp_core::bytes::serialize"))]
+	pub output: Vec<u8>,
+	#[cfg_attr(
+		feature = "std",
+		serde(skip_serializing_if = "Option::is_none", serialize_with = "serialize_error")
+	)]
+	pub error: Option<Vec<u8>>,
+	#[cfg_attr(feature = "std", serde(skip_serializing_if = "Vec::is_empty"))]
+	pub calls: Vec<CallTrace>,
+}
+
+/// A step of the struct logger.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize), serde(rename_all = "camelCase"))]
+pub struct StructLog {
+	pub pc: u64,
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_opcode"))]
+	pub op: u8,
+	pub gas: u64,
+	pub gas_cost: u64,
+	pub depth: u32,
+	#[cfg_attr(feature = "std", serde(skip_serializing_if = "Option::is_none"))]
+	pub stack: Option<Vec<U256>>,
+	#[cfg_attr(
+		feature = "std",
+		serde(skip_serializing_if = "Option::is_none", serialize_with = "serialize_memory")
+	)]
+	pub memory: Option<Vec<H256>>,
+	#[cfg_attr(
+		feature = "std",
+		serde(skip_serializing_if = "Option::is_none", serialize_with = "serialize_storage")
+	)]
+	pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+/// The output of the struct logger.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize), serde(rename_all = "camelCase"))]
+pub struct StructLogTrace {
+	pub gas: u64,
+	pub failed: bool,
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_hex"))]
+	pub return_value: Vec<u8>,
+	pub struct_logs: Vec<StructLog>,
+}
+
+/// The trace of an EVM execution.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize), serde(untagged))]
+pub enum Trace {
+	Calls(CallTrace),
+	Opcodes(StructLogTrace),
+}
+
+/// The mnemonic of `opcode`, `None` if the opcode is not defined.
+pub fn opcode_name(opcode: u8) -> Option<&'static str> {
+	let name = match opcode {
+		0x00 => "STOP",
+		0x01 => "ADD",
+		0x02 => "MUL",
+		0x03 => "SUB",
+		0x04 => "DIV",
+		0x05 => "SDIV",
+		0x06 => "MOD",
+		0x07 => "SMOD",
+		0x08 => "ADDMOD",
+		0x09 => "MULMOD",
+		0x0a => "EXP",
+		0x0b => "SIGNEXTEND",
+		0x10 => "LT",
+		0x11 => "GT",
+		0x12 => "SLT",
+		0x13 => "SGT",
+		0x14 => "EQ",
+		0x15 => "ISZERO",
+		0x16 => "AND",
+		0x17 => "OR",
+		0x18 => "XOR",
+		0x19 => "NOT",
+		0x1a => "BYTE",
+		0x1b => "SHL",
+		0x1c => "SHR",
+		0x1d => "SAR",
+		0x20 => "SHA3",
+		0x30 => "ADDRESS",
+		0x31 => "BALANCE",
+		0x32 => "ORIGIN",
+		0x33 => "CALLER",
+		0x34 => "CALLVALUE",
+		0x35 => "CALLDATALOAD",
+		0x36 => "CALLDATASIZE",
+		0x37 => "CALLDATACOPY",
+		0x38 => "CODESIZE",
+		0x39 => "CODECOPY",
+		0x3a => "GASPRICE",
+		0x3b => "EXTCODESIZE",
+		0x3c => "EXTCODECOPY",
+		0x3d => "RETURNDATASIZE",
+		0x3e => "RETURNDATACOPY",
+		0x3f => "EXTCODEHASH",
+		0x40 => "BLOCKHASH",
+		0x41 => "COINBASE",
+		0x42 => "TIMESTAMP",
+		0x43 => "NUMBER",
+		0x44 => "DIFFICULTY",
+		0x45 => "GASLIMIT",
+		0x46 => "CHAINID",
+		0x47 => "SELFBALANCE",
+		0x48 => "BASEFEE",
+		0x50 => "POP",
+		0x51 => "MLOAD",
+		0x52 => "MSTORE",
+		0x53 => "MSTORE8",
+		0x54 => "SLOAD",
+		0x55 => "SSTORE",
+		0x56 => "JUMP",
+		0x57 => "JUMPI",
+		0x58 => "PC",
+		0x59 => "MSIZE",
+		0x5a => "GAS",
+		0x5b => "JUMPDEST",
+		0x60 => "PUSH1",
+		0x61 => "PUSH2",
+		0x62 => "PUSH3",
+		0x63 => "PUSH4",
+		0x64 => "PUSH5",
+		0x65 => "PUSH6",
+		0x66 => "PUSH7",
+		0x67 => "PUSH8",
+		0x68 => "PUSH9",
+		0x69 => "PUSH10",
+		0x6a => "PUSH11",
+		0x6b => "PUSH12",
+		0x6c => "PUSH13",
+		0x6d => "PUSH14",
+		0x6e => "PUSH15",
+		0x6f => "PUSH16",
+		0x70 => "PUSH17",
+		0x71 => "PUSH18",
+		0x72 => "PUSH19",
+		0x73 => "PUSH20",
+		0x74 => "PUSH21",
+		0x75 => "PUSH22",
+		0x76 => "PUSH23",
+		0x77 => "PUSH24",
+		0x78 => "PUSH25",
+		0x79 => "PUSH26",
+		0x7a => "PUSH27",
+		0x7b => "PUSH28",
+		0x7c => "PUSH29",
+		0x7d => "PUSH30",
+		0x7e => "PUSH31",
+		0x7f => "PUSH32",
+		0x80 => "DUP1",
+		0x81 => "DUP2",
+		0x82 => "DUP3",
+		0x83 => "DUP4",
+		0x84 => "DUP5",
+		0x85 => "DUP6",
+		0x86 => "DUP7",
+		0x87 => "DUP8",
+		0x88 => "DUP9",
+		0x89 => "DUP10",
+		0x8a => "DUP11",
+		0x8b => "DUP12",
+		0x8c => "DUP13",
+		0x8d => "DUP14",
+		0x8e => "DUP15",
+		0x8f => "DUP16",
+		0x90 => "SWAP1",
+		0x91 => "SWAP2",
+		0x92 => "SWAP3",
+		0x93 => "SWAP4",
+		0x94 => "SWAP5",
+		0x95 => "SWAP6",
+		0x96 => "SWAP7",
+		0x97 => "SWAP8",
+		0x98 => "SWAP9",
+		0x99 => "SWAP10",
+		0x9a => "SWAP11",
+		0x9b => "SWAP12",
+		0x9c => "SWAP13",
+		0x9d => "SWAP14",
+		0x9e => "SWAP15",
+		0x9f => "SWAP16",
+		0xa0 => "LOG0",
+		0xa1 => "LOG1",
+		0xa2 => "LOG2",
+		0xa3 => "LOG3",
+		0xa4 => "LOG4",
+		0xf0 => "CREATE",
+		0xf1 => "CALL",
+		0xf2 => "CALLCODE",
+		0xf3 => "RETURN",
+		0xf4 => "DELEGATECALL",
+		0xf5 => "CREATE2",
+		0xfa => "STATICCALL",
+		0xfd => "REVERT",
+		0xfe => "INVALID",
+		0xff => "SELFDESTRUCT",
+		_ => return None,
+	};
+	Some(name)
+}
+
+#[cfg(feature = "std")]
+fn serialize_quantity<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+	U256::from(*value).serialize(serializer)
+}
+
+#[cfg(feature = "std")]
+fn serialize_hex<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_str(&sp_core::hexdisplay::HexDisplay::from(&bytes).to_string())
+}
+
+#[cfg(feature = "std")]
+fn serialize_error<S: Serializer>(error: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error> {
+	match error {
+		Some(error) => serializer.serialize_str(&String::from_utf8_lossy(error)),
+		None => serializer.serialize_none(),
+	}
+}
+
+#[cfg(feature = "std")]
+fn serialize_opcode<S: Serializer>(opcode: &u8, serializer: S) -> Result<S::Ok, S::Error> {
+	match opcode_name(*opcode) {
+		Some(name) => serializer.serialize_str(name),
+		None => serializer.serialize_str(&format!("opcode 0x{:02x} not defined", opcode)),
+	}
+}
+
+#[cfg(feature = "std")]
+fn serialize_memory<S: Serializer>(memory: &Option<Vec<H256>>, serializer: S) -> Result<S::Ok, S::Error> {
+	use serde::ser::SerializeSeq;
+
+	let memory = memory.as_deref().unwrap_or_default();
+	let mut seq = serializer.serialize_seq(Some(memory.len()))?;
+	for word in memory {
+		seq.serialize_element(&sp_core::hexdisplay::HexDisplay::from(&word.as_bytes()).to_string())?;
+	}
+	seq.end()
+}
+
+#[cfg(feature = "std")]
+fn serialize_storage<S: Serializer>(storage: &Option<BTreeMap<H256, H256>>, serializer: S) -> Result<S::Ok, S::Error> {
+	use serde::ser::SerializeMap;
+	use sp_core::hexdisplay::HexDisplay;
+
+	let mut map = serializer.serialize_map(storage.as_ref().map(|s| s.len()))?;
+	for (key, value) in storage.iter().flatten() {
+		map.serialize_entry(
+			&HexDisplay::from(&key.as_bytes()).to_string(),
+			&HexDisplay::from(&value.as_bytes()).to_string(),
+		)?;
+	}
+	map.end()
+}
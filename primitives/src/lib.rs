@@ -176,6 +176,9 @@ pub enum ReserveIdentifier {
 	Nft,
 	TransactionPayment,
 	TransactionPaymentDeposit,
+	Arena,
+	EvmSponsorBudget,
+	EvmVerificationDeposit,
 
 	// always the last, indicate number of variants
 	Count,
@@ -35,12 +35,14 @@ pub enum AcalaMultiSignature {
 	Sr25519(sr25519::Signature),
 	/// An ECDSA/SECP256k1 signature.
 	Ecdsa(ecdsa::Signature),
-	// An Ethereum compatible SECP256k1 signature.
+	// An Ethereum compatible SECP256k1 signature. The legacy transaction can't carry an access list.
 	Ethereum([u8; 65]),
 	// An Ethereum SECP256k1 signature using Eip1559 for message encoding.
 	Eip1559([u8; 65]),
 	// An Ethereum SECP256k1 signature using Eip712 for message encoding.
 	AcalaEip712([u8; 65]),
+	// An Ethereum SECP256k1 signature using Eip2930 for message encoding.
+	Eip2930([u8; 65]),
 }
 
 impl From<ed25519::Signature> for AcalaMultiSignature {
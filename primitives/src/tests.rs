@@ -17,7 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use super::*;
-use crate::evm::{is_system_contract, EvmAddress, SYSTEM_CONTRACT_ADDRESS_PREFIX};
+use crate::evm::{decode_revert_message, is_system_contract, EvmAddress, SYSTEM_CONTRACT_ADDRESS_PREFIX};
 use frame_support::assert_ok;
 use sp_core::H160;
 use std::str::FromStr;
@@ -179,3 +179,123 @@ fn is_system_contract_works() {
 
 	assert!(!is_system_contract(bytes.into()));
 }
+
+#[test]
+fn decode_revert_message_should_work() {
+	use sp_core::bytes::from_hex;
+	assert_eq!(decode_revert_message(&[]), None);
+
+	let data = from_hex("0x8c379a00000000000000000000000000000000000000000000000000000000000000020").unwrap();
+	assert_eq!(decode_revert_message(&data), None);
+
+	let data = from_hex("0x8c379a00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000d6572726f72206d65737361676").unwrap();
+	assert_eq!(decode_revert_message(&data), None);
+
+	let data = from_hex("0x8c379a00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000d6572726f72206d65737361676500000000000000000000000000000000000000").unwrap();
+	assert_eq!(decode_revert_message(&data), Some("error message"));
+
+	// ensures we protect against msg_start + message_len overflow
+	let data = from_hex("0x9850188c1837189a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000018d618571827182618f718220618d6185718371836161876").unwrap();
+	assert_eq!(decode_revert_message(&data), None);
+	// ensures we protect against msg_start + message_len overflow
+	let data = from_hex("0x9860189818501818188c181818371818189a00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000181818d6181818571818182718181826181818f71818182206181818d61818185718181837181818361618181876").unwrap();
+	assert_eq!(decode_revert_message(&data), None);
+	// ensures we protect against msg_start + message_len overflow
+	let data = from_hex("0x98640818c3187918a0000000000000000000000000000000000000000000000000000000000000001820000000000000000000000000000000000000000000000000000000000000000d186518721872186f18721820186d18651873187318611867186500000000000000000000000000000000000000").unwrap();
+	assert_eq!(decode_revert_message(&data), None);
+}
+
+#[test]
+fn trace_serialize_works() {
+	use crate::evm::tracing::{CallTrace, CallType, StructLog, StructLogTrace, Trace};
+	use serde_json::json;
+	use sp_core::{H256, U256};
+
+	let call = CallTrace {
+		call_type: CallType::DelegateCall,
+		from: H160::from_low_u64_be(1),
+		to: H160::from_low_u64_be(2),
+		input: vec![0x12, 0x34],
+		value: U256::zero(),
+		gas: 100_000,
+		gas_used: 21_000,
+		output: vec![],
+		error: Some(b"execution reverted".to_vec()),
+		calls: vec![],
+	};
+	assert_eq!(
+		serde_json::to_value(Trace::Calls(call)).unwrap(),
+		json!({
+			"type": "DELEGATECALL",
+			"from": "0x0000000000000000000000000000000000000001",
+			"to": "0x0000000000000000000000000000000000000002",
+			"input": "0x1234",
+			"value": "0x0",
+			"gas": "0x186a0",
+			"gasUsed": "0x5208",
+			"output": "0x",
+			"error": "execution reverted",
+		})
+	);
+
+	let log = StructLog {
+		pc: 2,
+		op: 0x55,
+		gas: 100,
+		gas_cost: 20_000,
+		depth: 1,
+		stack: Some(vec![U256::from(1), U256::from(0x80)]),
+		memory: Some(vec![H256::from_low_u64_be(0x80)]),
+		storage: Some(
+			vec![(H256::from_low_u64_be(0), H256::from_low_u64_be(1))]
+				.into_iter()
+				.collect(),
+		),
+	};
+	assert_eq!(
+		serde_json::to_value(Trace::Opcodes(StructLogTrace {
+			gas: 21_000,
+			failed: false,
+			return_value: vec![0xab],
+			struct_logs: vec![
+				StructLog {
+					op: 0x0c,
+					..log.clone()
+				},
+				log
+			],
+		}))
+		.unwrap(),
+		json!({
+			"gas": 21000,
+			"failed": false,
+			"returnValue": "ab",
+			"structLogs": [
+				{
+					"pc": 2,
+					"op": "opcode 0x0c not defined",
+					"gas": 100,
+					"gasCost": 20000,
+					"depth": 1,
+					"stack": ["0x1", "0x80"],
+					"memory": ["0000000000000000000000000000000000000000000000000000000000000080"],
+					"storage": {
+						"0000000000000000000000000000000000000000000000000000000000000000": "0000000000000000000000000000000000000000000000000000000000000001",
+					},
+				},
+				{
+					"pc": 2,
+					"op": "SSTORE",
+					"gas": 100,
+					"gasCost": 20000,
+					"depth": 1,
+					"stack": ["0x1", "0x80"],
+					"memory": ["0000000000000000000000000000000000000000000000000000000000000080"],
+					"storage": {
+						"0000000000000000000000000000000000000000000000000000000000000000": "0000000000000000000000000000000000000000000000000000000000000001",
+					},
+				},
+			],
+		})
+	);
+}
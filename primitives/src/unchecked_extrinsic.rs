@@ -23,7 +23,9 @@ use frame_support::{
 	traits::{ExtrinsicCall, Get},
 	weights::{DispatchInfo, GetDispatchInfo},
 };
-use module_evm_utility::ethereum::{EIP1559TransactionMessage, LegacyTransactionMessage, TransactionAction};
+use module_evm_utility::ethereum::{
+	EIP1559TransactionMessage, EIP2930TransactionMessage, LegacyTransactionMessage, TransactionAction,
+};
 use module_evm_utility_macro::keccak256;
 use scale_info::TypeInfo;
 use sp_core::{H160, H256};
@@ -115,23 +117,22 @@ where
 					target: "evm", "Ethereum eth_msg: {:?}", eth_msg
 				);
 
-				if !eth_msg.tip.is_zero() {
-					// Not yet supported, require zero tip
-					return Err(InvalidTransaction::BadProof.into());
-				}
-
 				if !eth_msg.access_list.len().is_zero() {
-					// Not yet supported, require empty
+					// The legacy transaction doesn't sign the access list, so it can't carry one
+					// and is rejected. Transactions with an access list use `Eip2930` instead.
 					return Err(InvalidTransaction::BadProof.into());
 				}
 
 				let (tx_gas_price, tx_gas_limit) =
 					recover_sign_data(&eth_msg, TxFeePerGas::get(), StorageDepositPerByte::get())
 						.ok_or(InvalidTransaction::BadProof)?;
+				let tip_gas_price = recover_tip_gas_price(&eth_msg)
+					.and_then(|tip_gas_price| tip_gas_price.checked_add(tx_gas_price))
+					.ok_or(InvalidTransaction::BadProof)?;
 
 				let msg = LegacyTransactionMessage {
 					nonce: eth_msg.nonce.into(),
-					gas_price: tx_gas_price.into(),
+					gas_price: tip_gas_price.into(),
 					gas_limit: tx_gas_limit.into(),
 					action: eth_msg.action,
 					value: eth_msg.value.into(),
@@ -203,6 +204,50 @@ where
 					function,
 				})
 			}
+			Some((addr, AcalaMultiSignature::Eip2930(sig), extra)) => {
+				let function = self.0.function;
+				let (eth_msg, eth_extra) = ConvertTx::convert((function.clone(), extra))?;
+				log::trace!(
+					target: "evm", "Eip2930 eth_msg: {:?}", eth_msg
+				);
+
+				let (tx_gas_price, tx_gas_limit) =
+					recover_sign_data(&eth_msg, TxFeePerGas::get(), StorageDepositPerByte::get())
+						.ok_or(InvalidTransaction::BadProof)?;
+				let tip_gas_price = recover_tip_gas_price(&eth_msg)
+					.and_then(|tip_gas_price| tip_gas_price.checked_add(tx_gas_price))
+					.ok_or(InvalidTransaction::BadProof)?;
+
+				let msg = EIP2930TransactionMessage {
+					chain_id: eth_msg.chain_id,
+					nonce: eth_msg.nonce.into(),
+					gas_price: tip_gas_price.into(),
+					gas_limit: tx_gas_limit.into(),
+					action: eth_msg.action,
+					value: eth_msg.value.into(),
+					input: eth_msg.input,
+					access_list: eth_msg.access_list,
+				};
+				log::trace!(
+					target: "evm", "tx msg: {:?}", msg
+				);
+
+				let msg_hash = msg.hash();
+
+				let signer = recover_signer(&sig, msg_hash.as_fixed_bytes()).ok_or(InvalidTransaction::BadProof)?;
+
+				let account_id = lookup.lookup(Address::Address20(signer.into()))?;
+				let expected_account_id = lookup.lookup(addr)?;
+
+				if account_id != expected_account_id {
+					return Err(InvalidTransaction::BadProof.into());
+				}
+
+				Ok(CheckedExtrinsic {
+					signed: Some((account_id, eth_extra)),
+					function,
+				})
+			}
 			Some((addr, AcalaMultiSignature::AcalaEip712(sig), extra)) => {
 				let function = self.0.function;
 
@@ -353,13 +398,117 @@ fn recover_sign_data(
 	Some((tx_gas_price, tx_gas_limit))
 }
 
+/// The step of the tip gas price of legacy and Eip2930 transactions, above the largest
+/// `block_period << 16` of `(u32::MAX / 30) << 16` and the storage entry limit.
+const TIP_STEP: u128 = 1 << 44;
+
+fn recover_tip_gas_price(eth_msg: &EthereumTransactionMessage) -> Option<u128> {
+	// The gas price of legacy and Eip2930 transactions can't carry a priority fee of any amount, the
+	// tip is paid in steps above the block period and storage entry limit of the gas price, so they
+	// can still be decoded from it.
+	// tip_gas_price = tip_number * TIP_STEP
+	// tip = tip_gas_price * gas_limit
+	if eth_msg.tip.is_zero() {
+		return Some(0);
+	}
+
+	let tip_gas_price = eth_msg.tip.checked_div(eth_msg.gas_limit.into())?;
+	// the tip must be paid exactly
+	if tip_gas_price % TIP_STEP != 0 || tip_gas_price.checked_mul(eth_msg.gas_limit.into())? != eth_msg.tip {
+		return None;
+	}
+
+	Some(tip_gas_price)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use frame_support::traits::ConstU128;
 	use hex_literal::hex;
 	use module_evm_utility::ethereum::AccessListItem;
 	use sp_core::U256;
-	use std::{ops::Add, str::FromStr};
+	use sp_runtime::traits::{DispatchInfoOf, LookupError};
+	use std::{cell::RefCell, ops::Add, str::FromStr};
+
+	thread_local! {
+		static ETH_MSG: RefCell<Option<EthereumTransactionMessage>> = RefCell::new(None);
+	}
+
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	struct TestExtra;
+	impl SignedExtension for TestExtra {
+		const IDENTIFIER: &'static str = "TestExtra";
+		type AccountId = AccountId32;
+		type Call = ();
+		type AdditionalSigned = ();
+		type Pre = ();
+
+		fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+			Ok(())
+		}
+
+		fn pre_dispatch(
+			self,
+			_who: &Self::AccountId,
+			_call: &Self::Call,
+			_info: &DispatchInfoOf<Self::Call>,
+			_len: usize,
+		) -> Result<Self::Pre, TransactionValidityError> {
+			Ok(())
+		}
+	}
+
+	struct TestConvertTx;
+	impl Convert<((), TestExtra), Result<(EthereumTransactionMessage, TestExtra), InvalidTransaction>> for TestConvertTx {
+		fn convert((_, extra): ((), TestExtra)) -> Result<(EthereumTransactionMessage, TestExtra), InvalidTransaction> {
+			ETH_MSG
+				.with(|v| v.borrow().clone())
+				.map(|eth_msg| (eth_msg, extra))
+				.ok_or(InvalidTransaction::Call)
+		}
+	}
+
+	fn evm_account(address: H160) -> AccountId32 {
+		let mut data = [0u8; 32];
+		data[0..4].copy_from_slice(b"evm:");
+		data[4..24].copy_from_slice(address.as_bytes());
+		data.into()
+	}
+
+	struct TestLookup;
+	impl traits::Lookup for TestLookup {
+		type Source = Address;
+		type Target = AccountId32;
+
+		fn lookup(&self, source: Address) -> Result<AccountId32, LookupError> {
+			match source {
+				Address::Address20(address) => Ok(evm_account(address.into())),
+				Address::Id(account_id) => Ok(account_id),
+				_ => Err(LookupError),
+			}
+		}
+	}
+
+	// storage_deposit_per_byte = 100_000_000_000_000, tx_fee_per_gas = 200 gwei & !0xffff
+	type TestXt = AcalaUncheckedExtrinsic<
+		(),
+		TestExtra,
+		TestConvertTx,
+		ConstU128<100_000_000_000_000>,
+		ConstU128<199_999_946_752>,
+	>;
+
+	fn check_eth_msg(
+		eth_msg: EthereumTransactionMessage,
+		signer: H160,
+		signature: AcalaMultiSignature,
+	) -> Result<Option<AccountId32>, TransactionValidityError> {
+		ETH_MSG.with(|v| *v.borrow_mut() = Some(eth_msg));
+		let xt = TestXt::new((), Some((Address::Address20(signer.into()), signature, TestExtra))).unwrap();
+		xt.check(&TestLookup)
+			.map(|checked| checked.signed.map(|(account_id, _)| account_id))
+	}
 
 	#[test]
 	fn verify_eip712_should_works() {
@@ -577,6 +726,82 @@ mod tests {
 		assert_ne!(recover_signer(&sign, new_msg.hash().as_fixed_bytes()), sender);
 	}
 
+	#[test]
+	fn verify_eth_2930_should_works() {
+		let msg = EIP2930TransactionMessage {
+			chain_id: 595,
+			nonce: U256::from(1),
+			gas_price: U256::from("0x640000006a"),
+			gas_limit: U256::from(21000),
+			action: TransactionAction::Call(H160::from_str("0x1111111111222222222233333333334444444444").unwrap()),
+			value: U256::from(123123),
+			input: vec![],
+			access_list: vec![AccessListItem {
+				address: hex!("bb9bc244d798123fde783fcc1c72d3bb8c189413").into(),
+				storage_keys: vec![
+					H256::from_str("0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef").unwrap(),
+					H256::from_str("0x0000000000111111111122222222223333333333444444444455555555556666").unwrap(),
+				],
+			}],
+		};
+
+		// keccak256(0x01 || rlp([chain_id, nonce, gas_price, gas_limit, to, value, data, access_list]))
+		assert_eq!(
+			msg.hash(),
+			H256::from_str("0x5ee4d7783bb44cbcee6364dc198f5d28263ca8da3fd6b61c723d707199c65923").unwrap()
+		);
+
+		let sign = hex!("31014280acc9ecafe051677183c550da56c1864c0cf5d70128e865e253ecb0ee3ce2ee6b0c2713e6723ffbe9d1717a9c671dae42f1c548e80eceb60d90e8413a1c");
+		let sender = Some(H160::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap());
+
+		assert_eq!(recover_signer(&sign, msg.hash().as_fixed_bytes()), sender);
+
+		let mut new_msg = msg.clone();
+		new_msg.chain_id = new_msg.chain_id.add(1u64);
+		assert_ne!(recover_signer(&sign, new_msg.hash().as_fixed_bytes()), sender);
+
+		let mut new_msg = msg.clone();
+		new_msg.nonce = new_msg.nonce.add(U256::one());
+		assert_ne!(recover_signer(&sign, new_msg.hash().as_fixed_bytes()), sender);
+
+		let mut new_msg = msg.clone();
+		new_msg.gas_price = new_msg.gas_price.add(U256::one());
+		assert_ne!(recover_signer(&sign, new_msg.hash().as_fixed_bytes()), sender);
+
+		let mut new_msg = msg.clone();
+		new_msg.gas_limit = new_msg.gas_limit.add(U256::one());
+		assert_ne!(recover_signer(&sign, new_msg.hash().as_fixed_bytes()), sender);
+
+		let mut new_msg = msg.clone();
+		new_msg.action = TransactionAction::Create;
+		assert_ne!(recover_signer(&sign, new_msg.hash().as_fixed_bytes()), sender);
+
+		let mut new_msg = msg.clone();
+		new_msg.value = new_msg.value.add(U256::one());
+		assert_ne!(recover_signer(&sign, new_msg.hash().as_fixed_bytes()), sender);
+
+		let mut new_msg = msg.clone();
+		new_msg.input = vec![0x00];
+		assert_ne!(recover_signer(&sign, new_msg.hash().as_fixed_bytes()), sender);
+
+		// tampered access list
+		let mut new_msg = msg.clone();
+		new_msg.access_list = vec![];
+		assert_ne!(recover_signer(&sign, new_msg.hash().as_fixed_bytes()), sender);
+
+		let mut new_msg = msg.clone();
+		new_msg.access_list[0].address = hex!("0000000000000000000000000000000000000000").into();
+		assert_ne!(recover_signer(&sign, new_msg.hash().as_fixed_bytes()), sender);
+
+		let mut new_msg = msg.clone();
+		new_msg.access_list[0].storage_keys.pop();
+		assert_ne!(recover_signer(&sign, new_msg.hash().as_fixed_bytes()), sender);
+
+		let mut new_msg = msg;
+		new_msg.access_list[0].storage_keys[1] = H256::zero();
+		assert_ne!(recover_signer(&sign, new_msg.hash().as_fixed_bytes()), sender);
+	}
+
 	#[test]
 	fn recover_sign_data_should_works() {
 		let mut msg = EthereumTransactionMessage {
@@ -630,4 +855,120 @@ mod tests {
 
 		assert_eq!(recover_sign_data(&msg, u128::MAX, u128::MAX), None);
 	}
+
+	#[test]
+	fn recover_tip_gas_price_should_works() {
+		let mut msg = EthereumTransactionMessage {
+			chain_id: 595,
+			genesis: Default::default(),
+			nonce: 1,
+			tip: 0,
+			gas_limit: 2100000,
+			storage_limit: 64000,
+			action: TransactionAction::Call(H160::from_str("0x1111111111222222222233333333334444444444").unwrap()),
+			value: 0,
+			input: vec![],
+			access_list: vec![],
+			valid_until: 30,
+		};
+
+		// the tip doesn't collide with the largest block period and storage entry limit
+		msg.valid_until = u32::MAX;
+		msg.storage_limit = 0xffff * 64 + 63;
+		let ts_fee_per_gas = 200u128.saturating_mul(10u128.saturating_pow(9)) & !0xffff;
+		let (tx_gas_price, _) = recover_sign_data(&msg, ts_fee_per_gas, 100_000_000_000_000).unwrap();
+		assert!(tx_gas_price - ts_fee_per_gas < TIP_STEP);
+
+		assert_eq!(recover_tip_gas_price(&msg), Some(0));
+
+		msg.tip = TIP_STEP * 2100000;
+		assert_eq!(recover_tip_gas_price(&msg), Some(TIP_STEP));
+
+		msg.tip = 3 * TIP_STEP * 2100000;
+		assert_eq!(recover_tip_gas_price(&msg), Some(3 * TIP_STEP));
+
+		// the tip must be a multiple of TIP_STEP * gas_limit
+		msg.tip = TIP_STEP * 2100000 + 1;
+		assert_eq!(recover_tip_gas_price(&msg), None);
+
+		msg.tip = (TIP_STEP + 1) * 2100000;
+		assert_eq!(recover_tip_gas_price(&msg), None);
+
+		msg.tip = TIP_STEP;
+		assert_eq!(recover_tip_gas_price(&msg), None);
+
+		msg.gas_limit = 0;
+		assert_eq!(recover_tip_gas_price(&msg), None);
+	}
+
+	#[test]
+	fn check_tip_should_works() {
+		let sender = H160::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+		// gas_price = tx_gas_price + 2 * TIP_STEP = 200000013288 + 2 * (1 << 44)
+		let msg = EthereumTransactionMessage {
+			chain_id: 595,
+			genesis: Default::default(),
+			nonce: 1,
+			tip: 2 * TIP_STEP * 2100000,
+			gas_limit: 2100000,
+			storage_limit: 64000,
+			action: TransactionAction::Call(H160::from_str("0x1111111111222222222233333333334444444444").unwrap()),
+			value: 0,
+			input: vec![],
+			access_list: vec![],
+			valid_until: 30,
+		};
+		let access_list = vec![AccessListItem {
+			address: hex!("bb9bc244d798123fde783fcc1c72d3bb8c189413").into(),
+			storage_keys: vec![
+				H256::from_str("0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef").unwrap(),
+				H256::from_str("0x0000000000111111111122222222223333333333444444444455555555556666").unwrap(),
+			],
+		}];
+
+		// legacy
+		let sign = AcalaMultiSignature::Ethereum(hex!("3ba17640e8af8dee5240333c1ce9cf6fa3415734260a92dcceeae7a4890f3ec90c9cd34a269e0cacf2715b9816a050a1883f79cdbb2951aea114804dbde8968b1c"));
+		assert_eq!(
+			check_eth_msg(msg.clone(), sender, sign.clone()),
+			Ok(Some(evm_account(sender)))
+		);
+
+		let mut new_msg = msg.clone();
+		new_msg.tip = TIP_STEP * 2100000;
+		assert_eq!(
+			check_eth_msg(new_msg, sender, sign.clone()),
+			Err(InvalidTransaction::BadProof.into())
+		);
+
+		// the legacy transaction doesn't sign an access list
+		let mut new_msg = msg.clone();
+		new_msg.access_list = access_list.clone();
+		assert_eq!(
+			check_eth_msg(new_msg, sender, sign),
+			Err(InvalidTransaction::BadProof.into())
+		);
+
+		// Eip2930
+		let mut msg = msg;
+		msg.access_list = access_list;
+		let sign = AcalaMultiSignature::Eip2930(hex!("045e1aee35d54db7232575a0b2c1602888fbc172d07223802f86dd95107770a104779280fae796372403d3a3e07785d97c72f5b303575ec646f47f432e5c63111c"));
+		assert_eq!(
+			check_eth_msg(msg.clone(), sender, sign.clone()),
+			Ok(Some(evm_account(sender)))
+		);
+
+		let mut new_msg = msg.clone();
+		new_msg.tip = 3 * TIP_STEP * 2100000;
+		assert_eq!(
+			check_eth_msg(new_msg, sender, sign.clone()),
+			Err(InvalidTransaction::BadProof.into())
+		);
+
+		let mut new_msg = msg;
+		new_msg.access_list[0].storage_keys.pop();
+		assert_eq!(
+			check_eth_msg(new_msg, sender, sign),
+			Err(InvalidTransaction::BadProof.into())
+		);
+	}
 }
@@ -86,7 +86,10 @@ pub use constants::{fee::*, time::*};
 use module_support::mocks::MockStableAsset;
 pub use primitives::{
 	define_combined_task,
-	evm::{AccessListItem, EstimateResourcesRequest},
+	evm::{
+		tracing::{Trace, TracerConfig},
+		AccessListInfo, AccessListItem, ContractVerification, EstimateResourcesRequest,
+	},
 	task::TaskResult,
 	AccountId, AccountIndex, Address, Amount, AuctionId, AuthoritysOriginId, Balance, BlockNumber, CurrencyId,
 	DataProviderId, EraIndex, Hash, Lease, Moment, Nonce, ReserveIdentifier, Share, Signature, TokenSymbol,
@@ -1067,6 +1070,7 @@ impl module_emergency_shutdown::Config for Runtime {
 
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (3, 1000);	// 0.3%
+	pub LimitOrderKeeperFee: Ratio = Ratio::saturating_from_rational(1, 1000);	// 0.1%
 	pub const ExtendedProvisioningBlocks: BlockNumber = 2 * DAYS;
 	pub const TradingPathLimit: u32 = 4;
 }
@@ -1084,6 +1088,12 @@ impl module_dex::Config for Runtime {
 	type ExtendedProvisioningBlocks = ExtendedProvisioningBlocks;
 	type StableAsset = MockStableAsset<CurrencyId, Balance, AccountId, BlockNumber>;
 	type OnLiquidityPoolUpdated = ();
+	type PositionNFT = NFT;
+	type MaxInitializedTicks = ConstU32<512>;
+	type MaxLimitOrdersPerAccount = ConstU32<32>;
+	type LimitOrderKeeperFee = LimitOrderKeeperFee;
+	type MaxRouteSplits = ConstU32<4>;
+	type EVM = EVM;
 }
 
 impl module_dex_oracle::Config for Runtime {
@@ -1168,6 +1178,7 @@ impl module_transaction_payment::Config for Runtime {
 	type CustomFeeSurplus = CustomFeeSurplus;
 	type AlternativeFeeSurplus = AlternativeFeeSurplus;
 	type DefaultFeeTokens = DefaultFeeTokens;
+	type FeeSponsor = EVM;
 }
 
 impl module_evm_accounts::Config for Runtime {
@@ -1328,6 +1339,8 @@ parameter_types! {
 	pub NetworkContractSource: H160 = H160::from_low_u64_be(0);
 	pub DeveloperDeposit: Balance = 100 * dollar(ACA);
 	pub PublicationFee: Balance = 10000 * dollar(ACA);
+	pub const ContractInactivityPeriod: BlockNumber = 365 * DAYS;
+	pub const UpgradeDelay: BlockNumber = 7 * DAYS;
 	pub PrecompilesValue: AllPrecompiles<Runtime> = AllPrecompiles::<_>::acala();
 }
 
@@ -1368,8 +1381,11 @@ impl module_evm::Config for Runtime {
 	type NetworkContractSource = NetworkContractSource;
 	type DeveloperDeposit = DeveloperDeposit;
 	type PublicationFee = PublicationFee;
+	type ContractInactivityPeriod = ContractInactivityPeriod;
+	type UpgradeDelay = UpgradeDelay;
 	type TreasuryAccount = AcalaTreasuryAccount;
 	type FreePublicationOrigin = EnsureRootOrHalfGeneralCouncil;
+	type VerifierOrigin = EnsureRootOrHalfGeneralCouncil;
 	type Runner = module_evm::runner::stack::Runner<Self>;
 	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
 	type Task = ScheduledTasks;
@@ -1934,6 +1950,151 @@ impl_runtime_apis! {
 
 			request.ok_or(sp_runtime::DispatchError::Other("Invalid parameter extrinsic, not evm Call"))
 		}
+
+		fn create_access_list(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+		) -> Result<AccessListInfo, sp_runtime::DispatchError> {
+			module_evm::runner::access_list::create_access_list::<Runtime>(
+				from,
+				to,
+				data,
+				value,
+				gas_limit,
+				storage_limit,
+				access_list.unwrap_or_default(),
+			)
+		}
+
+		fn contract_verification(address: H160) -> Option<ContractVerification> {
+			EVM::current_verification(&address)
+		}
+	}
+
+	impl module_evm_rpc_runtime_api::EVMTraceApi<Block, Balance> for Runtime {
+		fn trace_extrinsic(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			transaction_index: u32,
+			tracer: TracerConfig,
+		) -> Result<Trace, sp_runtime::DispatchError> {
+			#[cfg(feature = "tracing")]
+			{
+				let transaction_index = transaction_index as usize;
+				let transaction = extrinsics
+					.get(transaction_index)
+					.cloned()
+					.ok_or(sp_runtime::DispatchError::Other("Invalid parameter transaction_index"))?;
+
+				for extrinsic in extrinsics.into_iter().take(transaction_index) {
+					let _ = Executive::apply_extrinsic(extrinsic);
+				}
+
+				let (_, trace) = module_evm::runner::tracing::trace(tracer, || Executive::apply_extrinsic(transaction));
+				trace.ok_or(sp_runtime::DispatchError::Other("Invalid parameter transaction_index, not evm transaction"))
+			}
+			#[cfg(not(feature = "tracing"))]
+			{
+				let _ = (extrinsics, transaction_index, tracer);
+				Err(sp_runtime::DispatchError::Other("Runtime is not built with the tracing feature"))
+			}
+		}
+
+		fn trace_call(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			tracer: TracerConfig,
+		) -> Result<Trace, sp_runtime::DispatchError> {
+			#[cfg(feature = "tracing")]
+			{
+				let access_list = access_list.unwrap_or_default().into_iter().map(|v| (v.address, v.storage_keys)).collect();
+				let config = <Runtime as module_evm::Config>::config();
+
+				let (result, trace) = module_evm::runner::tracing::trace(tracer, || match to {
+					Some(to) => module_evm::runner::stack::Runner::<Runtime>::call(
+						from,
+						from,
+						to,
+						data,
+						value,
+						gas_limit,
+						storage_limit,
+						access_list,
+						config,
+					)
+					.map(|_| ()),
+					None => module_evm::runner::stack::Runner::<Runtime>::create(
+						from,
+						data,
+						value,
+						gas_limit,
+						storage_limit,
+						access_list,
+						config,
+					)
+					.map(|_| ()),
+				});
+				result?;
+
+				trace.ok_or(sp_runtime::DispatchError::Other("Execution not traced"))
+			}
+			#[cfg(not(feature = "tracing"))]
+			{
+				let _ = (from, to, data, value, gas_limit, storage_limit, access_list, tracer);
+				Err(sp_runtime::DispatchError::Other("Runtime is not built with the tracing feature"))
+			}
+		}
+	}
+
+	impl module_dex_rpc_runtime_api::DexApi<Block, AccountId, BlockNumber> for Runtime {
+		fn open_limit_orders(
+			who: AccountId,
+		) -> Vec<(module_dex::LimitOrderId, module_dex::LimitOrder<AccountId, BlockNumber>)> {
+			Dex::open_limit_orders(&who)
+		}
+
+		fn get_best_routes(
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			supply_amount: Balance,
+			max_splits: u32,
+		) -> Vec<module_dex::RouteQuote> {
+			Dex::get_best_routes(supply_currency_id, target_currency_id, supply_amount, max_splits)
+		}
+	}
+
+	// The arena is only deployed on Mandala.
+	impl module_arena_rpc_runtime_api::ArenaApi<Block, BlockNumber> for Runtime {
+		fn leaderboard(_season: Option<module_arena_rpc_runtime_api::SeasonIndex>) -> Vec<(H160, u32)> {
+			vec![]
+		}
+
+		fn match_history(
+			_season: module_arena_rpc_runtime_api::SeasonIndex,
+			_contract: H160,
+		) -> Vec<module_arena_rpc_runtime_api::MatchRecord<BlockNumber>> {
+			vec![]
+		}
+
+		fn contract_rating(_contract: H160) -> u32 {
+			0
+		}
+
+		fn dry_run_match(
+			_a: module_arena_rpc_runtime_api::Contender,
+			_b: module_arena_rpc_runtime_api::Contender,
+		) -> Result<module_arena_rpc_runtime_api::MatchReport, sp_runtime::DispatchError> {
+			Err(sp_runtime::DispatchError::Other("Arena is not available"))
+		}
 	}
 
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
@@ -21,6 +21,12 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2022-03-30, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("acala-dev"), DB CACHE: 1024
+//!
+//! The weights of `set_trading_pair_fee`, `set_protocol_fee`, `claim_protocol_fee`,
+//! `create_concentrated_pool`, `set_position_nft_class`, `open_position`, `decrease_position`,
+//! `collect_position_fees`, `place_limit_order`, `cancel_limit_order`, `fill_limit_order` and
+//! `swap_with_routes` are NOT benchmark results. Their storage accesses are counted from each call
+//! and the execution times are rough upper bounds, to be replaced once these calls are benchmarked.
 
 // Executed Command:
 // target/production/acala
@@ -155,6 +161,9 @@ impl<T: frame_system::Config> module_dex::WeightInfo for WeightInfo<T> {
 	}
 	// Storage: Dex TradingPairStatuses (r:1 w:0)
 	// Storage: Dex LiquidityPool (r:1 w:1)
+	// Storage: Dex ConcentratedPools (r:1 w:0)
+	// Storage: Dex TradingPairFees (r:1 w:0)
+	// Storage: Dex ProtocolFee (r:1 w:0)
 	// Storage: System Account (r:1 w:1)
 	// Storage: Tokens Accounts (r:2 w:2)
 	fn swap_with_exact_supply(u: u32, ) -> Weight {
@@ -162,12 +171,15 @@ impl<T: frame_system::Config> module_dex::WeightInfo for WeightInfo<T> {
 			// Standard Error: 134_000
 			.saturating_add((9_839_000 as Weight).saturating_mul(u as Weight))
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(u as Weight)))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
 	}
 	// Storage: Dex TradingPairStatuses (r:1 w:0)
 	// Storage: Dex LiquidityPool (r:1 w:1)
+	// Storage: Dex ConcentratedPools (r:1 w:0)
+	// Storage: Dex TradingPairFees (r:1 w:0)
+	// Storage: Dex ProtocolFee (r:1 w:0)
 	// Storage: System Account (r:1 w:1)
 	// Storage: Tokens Accounts (r:2 w:2)
 	fn swap_with_exact_target(u: u32, ) -> Weight {
@@ -175,7 +187,7 @@ impl<T: frame_system::Config> module_dex::WeightInfo for WeightInfo<T> {
 			// Standard Error: 159_000
 			.saturating_add((9_690_000 as Weight).saturating_mul(u as Weight))
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(u as Weight)))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
 	}
@@ -198,4 +210,130 @@ impl<T: frame_system::Config> module_dex::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Storage: Dex TradingPairFees (r:0 w:1)
+	fn set_trading_pair_fee() -> Weight {
+		(11_359_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dex ProtocolFee (r:0 w:1)
+	fn set_protocol_fee() -> Weight {
+		(10_134_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dex ProtocolFee (r:1 w:0)
+	// Storage: Dex AccruedProtocolFees (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn claim_protocol_fee() -> Weight {
+		(26_696_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Dex TradingPairStatuses (r:1 w:0)
+	// Storage: Dex ConcentratedPools (r:1 w:1)
+	// Storage: Dex LiquidityPool (r:1 w:0)
+	// Storage: Tokens TotalIssuance (r:1 w:0)
+	fn create_concentrated_pool() -> Weight {
+		(17_866_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: OrmlNFT Classes (r:1 w:0)
+	// Storage: Dex PositionNFTClass (r:0 w:1)
+	fn set_position_nft_class() -> Weight {
+		(10_174_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dex TradingPairStatuses (r:1 w:0)
+	// Storage: Dex ConcentratedPools (r:1 w:1)
+	// Storage: Dex PositionNFTClass (r:1 w:0)
+	// Storage: OrmlNFT Classes (r:1 w:1)
+	// Storage: OrmlNFT NextTokenId (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: Dex NextPositionId (r:1 w:1)
+	// Storage: Dex InitializedTicks (r:1 w:1)
+	// Storage: Dex ConcentratedTicks (r:2 w:2)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: OrmlNFT Tokens (r:0 w:1)
+	// Storage: OrmlNFT TokensByOwner (r:0 w:1)
+	// Storage: Dex Positions (r:0 w:1)
+	fn open_position() -> Weight {
+		(153_402_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(16 as Weight))
+			.saturating_add(T::DbWeight::get().writes(17 as Weight))
+	}
+	// Storage: Dex Positions (r:1 w:1)
+	// Storage: OrmlNFT Tokens (r:1 w:1)
+	// Storage: Dex ConcentratedPools (r:1 w:1)
+	// Storage: Dex InitializedTicks (r:1 w:1)
+	// Storage: Dex ConcentratedTicks (r:2 w:2)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: OrmlNFT Classes (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: OrmlNFT TokensByOwner (r:0 w:1)
+	fn decrease_position() -> Weight {
+		(146_388_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(15 as Weight))
+	}
+	// Storage: Dex Positions (r:1 w:1)
+	// Storage: OrmlNFT Tokens (r:1 w:0)
+	// Storage: Dex ConcentratedPools (r:1 w:1)
+	// Storage: Dex InitializedTicks (r:1 w:1)
+	// Storage: Dex ConcentratedTicks (r:2 w:2)
+	// Storage: Tokens Accounts (r:4 w:4)
+	fn collect_position_fees() -> Weight {
+		(70_085_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: Dex TradingPairStatuses (r:1 w:0)
+	// Storage: Dex NextLimitOrderId (r:1 w:1)
+	// Storage: Dex AccountLimitOrders (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: Dex LimitOrders (r:0 w:1)
+	fn place_limit_order() -> Weight {
+		(27_943_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Dex LimitOrders (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: Dex AccountLimitOrders (r:1 w:1)
+	fn cancel_limit_order() -> Weight {
+		(25_790_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Dex LimitOrders (r:1 w:1)
+	// Storage: Dex TradingPairStatuses (r:1 w:0)
+	// Storage: Dex LiquidityPool (r:1 w:1)
+	// Storage: Dex ConcentratedPools (r:1 w:0)
+	// Storage: Dex TradingPairFees (r:1 w:0)
+	// Storage: Dex ProtocolFee (r:1 w:0)
+	// Storage: Tokens Accounts (r:3 w:3)
+	// Storage: Dex AccountLimitOrders (r:1 w:1)
+	fn fill_limit_order() -> Weight {
+		(80_236_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Dex TradingPairStatuses (r:1 w:0)
+	// Storage: Dex LiquidityPool (r:1 w:1)
+	// Storage: Dex ConcentratedPools (r:1 w:0)
+	// Storage: Dex TradingPairFees (r:1 w:0)
+	// Storage: Dex ProtocolFee (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn swap_with_routes(u: u32, ) -> Weight {
+		(46_958_000 as Weight)
+			// Standard Error: 148_000
+			.saturating_add((13_655_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(u as Weight)))
+	}
 }
@@ -56,8 +56,8 @@ pub use check_nonce::CheckNonce;
 use module_evm::GenesisAccount;
 use orml_traits::GetByKey;
 pub use precompile::{
-	AllPrecompiles, DEXPrecompile, EVMPrecompile, MultiCurrencyPrecompile, NFTPrecompile, OraclePrecompile,
-	SchedulePrecompile,
+	AllPrecompiles, ArenaPrecompile, DEXPrecompile, EVMPrecompile, MultiCurrencyPrecompile, NFTPrecompile,
+	OraclePrecompile, SchedulePrecompile,
 };
 pub use primitives::{
 	currency::{TokenInfo, ACA, AUSD, BNC, DOT, KAR, KBTC, KINT, KSM, KUSD, LCDOT, LDOT, LKSM, PHA, RENBTC, VSKSM},
@@ -209,8 +209,11 @@ impl module_evm::Config for TestRuntime {
 	type NetworkContractSource = NetworkContractSource;
 	type DeveloperDeposit = ConstU128<1000>;
 	type PublicationFee = ConstU128<200>;
+	type ContractInactivityPeriod = ConstU64<100>;
+	type UpgradeDelay = ConstU64<10>;
 	type TreasuryAccount = TreasuryAccount;
 	type FreePublicationOrigin = frame_system::EnsureSignedBy<CouncilAccount, AccountId32>;
+	type VerifierOrigin = frame_system::EnsureSignedBy<CouncilAccount, AccountId32>;
 
 	type Runner = module_evm::runner::stack::Runner<Self>;
 	type FindAuthor = AuthorGiven;
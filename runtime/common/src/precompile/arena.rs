@@ -0,0 +1,358 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use super::{
+	input::{Input, InputT, Output},
+	target_gas_limit,
+};
+use crate::WeightToGas;
+use ethabi::Token;
+use frame_support::{log, traits::Get};
+use module_evm::{
+	precompiles::Precompile,
+	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
+	Context, ExitError, ExitSucceed,
+};
+use module_support::ArenaManager;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use sp_runtime::{traits::Convert, RuntimeDebug};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// The `Arena` impl precompile.
+///
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Get contract info. Rest `input` bytes: `contract`.
+/// - Get leaderboard. Rest `input` bytes: `count`.
+/// - Get current round.
+/// - Get queue length.
+pub struct ArenaPrecompile<R, A>(PhantomData<(R, A)>);
+
+#[module_evm_utility_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	GetContractInfo = "getContractInfo(address)",
+	GetLeaderboard = "getLeaderboard(uint256)",
+	CurrentRound = "currentRound()",
+	QueueLength = "queueLength()",
+}
+
+impl<Runtime, Arena> Precompile for ArenaPrecompile<Runtime, Arena>
+where
+	Runtime: module_evm::Config,
+	Arena: ArenaManager,
+{
+	fn execute(input: &[u8], target_gas: Option<u64>, _context: &Context, _is_static: bool) -> PrecompileResult {
+		let input = Input::<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>::new(
+			input,
+			target_gas_limit(target_gas),
+		);
+
+		let gas_cost = Pricer::<Runtime, Arena>::cost(&input)?;
+
+		if let Some(gas_limit) = target_gas {
+			if gas_limit < gas_cost {
+				return Err(PrecompileFailure::Error {
+					exit_status: ExitError::OutOfGas,
+				});
+			}
+		}
+
+		let action = input.action()?;
+
+		let output = match action {
+			Action::GetContractInfo => {
+				let contract = input.evm_address_at(1)?;
+				let info = Arena::contract_info(contract);
+
+				log::debug!(target: "evm", "arena: getContractInfo contract: {:?}, info: {:?}", contract, info);
+
+				// returns (bool registered, uint256 points, uint256 instances, uint256 wins, uint256 rating)
+				let info = info.map_or_else(
+					|| {
+						vec![
+							Token::Bool(false),
+							Token::Uint(0.into()),
+							Token::Uint(0.into()),
+							Token::Uint(0.into()),
+							Token::Uint(0.into()),
+						]
+					},
+					|info| {
+						vec![
+							Token::Bool(true),
+							Token::Uint(info.points.into()),
+							Token::Uint(info.instances.into()),
+							Token::Uint(info.wins.into()),
+							Token::Uint(info.rating.into()),
+						]
+					},
+				);
+				ethabi::encode(&info)
+			}
+			Action::GetLeaderboard => {
+				let count = input.u32_at(1)?;
+				let leaderboard = Arena::leaderboard(count);
+
+				log::debug!(target: "evm", "arena: getLeaderboard count: {:?}, leaderboard: {:?}", count, leaderboard);
+
+				// returns (address[] contracts, uint256[] ratings)
+				let (contracts, ratings): (Vec<_>, Vec<_>) = leaderboard
+					.into_iter()
+					.map(|(contract, rating)| (Token::Address(contract), Token::Uint(rating.into())))
+					.unzip();
+				ethabi::encode(&[Token::Array(contracts), Token::Array(ratings)])
+			}
+			Action::CurrentRound => {
+				let round = Arena::current_round();
+
+				log::debug!(target: "evm", "arena: currentRound round: {:?}", round);
+
+				// returns (bool inProgress, uint256 startedAt, uint8 phase, uint256 instances, uint256 progress)
+				let round = round.map_or_else(
+					|| {
+						vec![
+							Token::Bool(false),
+							Token::Uint(0.into()),
+							Token::Uint(0.into()),
+							Token::Uint(0.into()),
+							Token::Uint(0.into()),
+						]
+					},
+					|round| {
+						vec![
+							Token::Bool(true),
+							Token::Uint(round.started_at.into()),
+							Token::Uint(round.phase.into()),
+							Token::Uint(round.instances.into()),
+							Token::Uint(round.progress.into()),
+						]
+					},
+				);
+				ethabi::encode(&round)
+			}
+			Action::QueueLength => {
+				let length = Arena::queue_length();
+
+				log::debug!(target: "evm", "arena: queueLength length: {:?}", length);
+
+				Output::default().encode_u32(length)
+			}
+		};
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gas_cost,
+			output,
+			logs: Default::default(),
+		})
+	}
+}
+
+struct Pricer<R, A>(PhantomData<(R, A)>);
+
+impl<Runtime, Arena> Pricer<Runtime, Arena>
+where
+	Runtime: module_evm::Config,
+	Arena: ArenaManager,
+{
+	const BASE_COST: u64 = 200;
+
+	fn cost(
+		input: &Input<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>,
+	) -> Result<u64, PrecompileFailure> {
+		let action = input.action()?;
+
+		let cost = match action {
+			Action::GetContractInfo => {
+				// Arena::ContractInfos (r: 1)
+				// Arena::Ratings (r: 1)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(2);
+				WeightToGas::convert(weight)
+			}
+			Action::GetLeaderboard => {
+				// Arena::CounterForRatings (r: 1)
+				// Arena::Ratings (r: rated_contracts)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get()
+					.reads(u64::from(Arena::rated_contracts()).saturating_add(1));
+				WeightToGas::convert(weight)
+			}
+			Action::CurrentRound => {
+				// Arena::CurrentRound (r: 1)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+				WeightToGas::convert(weight)
+			}
+			Action::QueueLength => {
+				// Arena::ContenderQueue (r: 1)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+				WeightToGas::convert(weight)
+			}
+		};
+		Ok(Self::BASE_COST.saturating_add(cost))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::precompile::mock::{alice_evm_addr, new_test_ext, Test};
+	use hex_literal::hex;
+	use module_support::{ArenaContractInfo, ArenaRoundInfo};
+	use sp_core::H160;
+
+	pub struct MockArena;
+
+	impl ArenaManager for MockArena {
+		fn contract_info(contract: H160) -> Option<ArenaContractInfo> {
+			(contract == H160::from_low_u64_be(1)).then(|| ArenaContractInfo {
+				points: 100,
+				instances: 2,
+				wins: 3,
+				rating: 1516,
+			})
+		}
+
+		fn leaderboard(count: u32) -> Vec<(H160, u32)> {
+			vec![(H160::from_low_u64_be(1), 1516), (H160::from_low_u64_be(2), 1484)]
+				.into_iter()
+				.take(count as usize)
+				.collect()
+		}
+
+		fn rated_contracts() -> u32 {
+			2
+		}
+
+		fn current_round() -> Option<ArenaRoundInfo> {
+			Some(ArenaRoundInfo {
+				started_at: 10,
+				phase: 2,
+				instances: 4,
+				progress: 16,
+			})
+		}
+
+		fn queue_length() -> u32 {
+			5
+		}
+	}
+
+	type ArenaPrecompile = crate::precompile::ArenaPrecompile<Test, MockArena>;
+	type NoArenaPrecompile = crate::precompile::ArenaPrecompile<Test, ()>;
+
+	fn context() -> Context {
+		Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		}
+	}
+
+	#[test]
+	fn get_contract_info_works() {
+		new_test_ext().execute_with(|| {
+			// getContractInfo(address) -> 0xcd481e51
+			// contract
+			let input = hex! {"
+				cd481e51
+				000000000000000000000000 0000000000000000000000000000000000000001
+			"};
+
+			let expected_output = hex! {"
+				00000000000000000000000000000000 00000000000000000000000000000001
+				00000000000000000000000000000000 00000000000000000000000000000064
+				00000000000000000000000000000000 00000000000000000000000000000002
+				00000000000000000000000000000000 00000000000000000000000000000003
+				00000000000000000000000000000000 000000000000000000000000000005ec
+			"};
+
+			let resp = ArenaPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, expected_output.to_vec());
+
+			// not registered
+			let resp = NoArenaPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, [0u8; 5 * 32].to_vec());
+		});
+	}
+
+	#[test]
+	fn get_leaderboard_works() {
+		new_test_ext().execute_with(|| {
+			// getLeaderboard(uint256) -> 0xa34b46fe
+			// count
+			let input = hex! {"
+				a34b46fe
+				00000000000000000000000000000000 00000000000000000000000000000001
+			"};
+
+			let expected_output = hex! {"
+				00000000000000000000000000000000 00000000000000000000000000000040
+				00000000000000000000000000000000 00000000000000000000000000000080
+				00000000000000000000000000000000 00000000000000000000000000000001
+				000000000000000000000000 0000000000000000000000000000000000000001
+				00000000000000000000000000000000 00000000000000000000000000000001
+				00000000000000000000000000000000 000000000000000000000000000005ec
+			"};
+
+			let resp = ArenaPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, expected_output.to_vec());
+		});
+	}
+
+	#[test]
+	fn current_round_works() {
+		new_test_ext().execute_with(|| {
+			// currentRound() -> 0x8a19c8bc
+			let input = hex! {"8a19c8bc"};
+
+			let expected_output = hex! {"
+				00000000000000000000000000000000 00000000000000000000000000000001
+				00000000000000000000000000000000 0000000000000000000000000000000a
+				00000000000000000000000000000000 00000000000000000000000000000002
+				00000000000000000000000000000000 00000000000000000000000000000004
+				00000000000000000000000000000000 00000000000000000000000000000010
+			"};
+
+			let resp = ArenaPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, expected_output.to_vec());
+		});
+	}
+
+	#[test]
+	fn queue_length_works() {
+		new_test_ext().execute_with(|| {
+			// queueLength() -> 0xab91c7b0
+			let input = hex! {"ab91c7b0"};
+
+			let expected_output = hex! {"
+				00000000000000000000000000000000 00000000000000000000000000000005
+			"};
+
+			let resp = ArenaPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, expected_output.to_vec());
+		});
+	}
+}
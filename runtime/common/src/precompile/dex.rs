@@ -28,9 +28,9 @@ use module_evm::{
 	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
 	Context, ExitError, ExitRevert, ExitSucceed,
 };
-use module_support::{DEXManager, SwapLimit};
+use module_support::{AddressMapping, DEXManager, InvokeContext, SwapLimit, EVM};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
-use primitives::{Balance, CurrencyId};
+use primitives::{Balance, CurrencyId, TradingPair};
 use sp_runtime::{traits::Convert, RuntimeDebug};
 use sp_std::{marker::PhantomData, prelude::*};
 
@@ -43,8 +43,14 @@ use sp_std::{marker::PhantomData, prelude::*};
 /// - Get liquidity. Rest `input` bytes: `currency_id_a`, `currency_id_b`.
 /// - Swap with exact supply. Rest `input` bytes: `who`, `currency_id_a`, `currency_id_b`,
 ///   `supply_amount`, `min_target_amount`.
+/// - Flash swap. Rest `input` bytes: `who`, `borrow_currency_id`, `other_currency_id`,
+///   `borrow_amount`, `repay_currency_id`, `data`. `who` is the borrower contract called back
+///   with `onFlashSwap(address,address,uint256,uint256,bytes)`.
 pub struct DEXPrecompile<R>(PhantomData<R>);
 
+/// The storage limit of the callback of a flash swap.
+const FLASH_SWAP_STORAGE_LIMIT: u32 = 64 * 20;
+
 #[module_evm_utility_macro::generate_function_selector]
 #[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
 #[repr(u32)]
@@ -57,6 +63,8 @@ pub enum Action {
 	SwapWithExactTarget = "swapWithExactTarget(address,address[],uint256,uint256)",
 	AddLiquidity = "addLiquidity(address,address,address,uint256,uint256,uint256)",
 	RemoveLiquidity = "removeLiquidity(address,address,address,uint256,uint256,uint256)",
+	GetExchangeFee = "getExchangeFee(address,address)",
+	FlashSwap = "flashSwap(address,address,address,uint256,address,bytes)",
 }
 
 impl<Runtime> Precompile for DEXPrecompile<Runtime>
@@ -64,7 +72,7 @@ where
 	Runtime: module_evm::Config + module_dex::Config + module_prices::Config,
 	module_dex::Pallet<Runtime>: DEXManager<Runtime::AccountId, CurrencyId, Balance>,
 {
-	fn execute(input: &[u8], target_gas: Option<u64>, _context: &Context, _is_static: bool) -> PrecompileResult {
+	fn execute(input: &[u8], target_gas: Option<u64>, context: &Context, _is_static: bool) -> PrecompileResult {
 		let input = Input::<
 			Action,
 			Runtime::AccountId,
@@ -131,6 +139,31 @@ where
 					logs: Default::default(),
 				})
 			}
+			Action::GetExchangeFee => {
+				let currency_id_a = input.currency_id_at(1)?;
+				let currency_id_b = input.currency_id_at(2)?;
+				log::debug!(
+					target: "evm",
+					"dex: get_exchange_fee currency_id_a: {:?}, currency_id_b: {:?}",
+					currency_id_a, currency_id_b
+				);
+
+				let trading_pair = TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or_else(|| {
+					PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: "Dex get_exchange_fee failed".into(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					}
+				})?;
+				let (fee_numerator, fee_denominator) = module_dex::Pallet::<Runtime>::get_exchange_fee(trading_pair);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::default().encode_u128_tuple(fee_numerator.into(), fee_denominator.into()),
+					logs: Default::default(),
+				})
+			}
 			Action::GetSwapTargetAmount => {
 				// solidity abi enocde array will add an offset at input[1]
 				let supply_amount = input.balance_at(2)?;
@@ -327,6 +360,64 @@ where
 					logs: Default::default(),
 				})
 			}
+			Action::FlashSwap => {
+				let who = input.account_id_at(1)?;
+				let borrower = input.evm_address_at(1)?;
+				let borrow_currency_id = input.currency_id_at(2)?;
+				let other_currency_id = input.currency_id_at(3)?;
+				let borrow_amount = input.balance_at(4)?;
+				let repay_currency_id = input.currency_id_at(5)?;
+				// solidity abi enocde bytes will add an offset at input[6]
+				let data_len = input.u32_at(7)?;
+				let data = input.bytes_at(8, data_len as usize)?;
+
+				log::debug!(
+					target: "evm",
+					"dex: flash_swap who: {:?}, borrow_currency_id: {:?}, other_currency_id: {:?}, borrow_amount: {:?}, repay_currency_id: {:?}, data_len: {:?}",
+					who, borrow_currency_id, other_currency_id, borrow_amount, repay_currency_id, data_len,
+				);
+
+				let origin = <Runtime as module_dex::Config>::EVM::get_origin()
+					.map(|origin| {
+						Runtime::AddressMapping::get_evm_address(&origin)
+							.unwrap_or_else(|| Runtime::AddressMapping::get_default_evm_address(&origin))
+					})
+					.unwrap_or(borrower);
+				// the borrower is called back by the caller of the precompile, with the gas left
+				// after the cost of the flash swap
+				let invoke_context = InvokeContext {
+					contract: borrower,
+					sender: context.caller,
+					origin,
+				};
+				let gas_limit = target_gas_limit(target_gas)
+					.unwrap_or_default()
+					.saturating_sub(gas_cost);
+
+				let (repay_amount, used_gas) = module_dex::Pallet::<Runtime>::do_flash_swap(
+					&who,
+					invoke_context,
+					borrow_currency_id,
+					other_currency_id,
+					repay_currency_id,
+					borrow_amount,
+					data,
+					gas_limit,
+					FLASH_SWAP_STORAGE_LIMIT,
+				)
+				.map_err(|e| PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: Into::<&str>::into(e).as_bytes().to_vec(),
+					cost: target_gas_limit(target_gas).unwrap_or_default(),
+				})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost.saturating_add(used_gas),
+					output: Output::default().encode_u128(repay_amount),
+					logs: Default::default(),
+				})
+			}
 		}
 	}
 }
@@ -379,6 +470,20 @@ where
 					.saturating_add(read_currency_b)
 					.saturating_add(WeightToGas::convert(weight))
 			}
+			Action::GetExchangeFee => {
+				let currency_id_a = input.currency_id_at(1)?;
+				let currency_id_b = input.currency_id_at(2)?;
+				let read_currency_a = InputPricer::<Runtime>::read_currency(currency_id_a);
+				let read_currency_b = InputPricer::<Runtime>::read_currency(currency_id_b);
+
+				// DEX::TradingPairFees (r: 1)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+
+				Self::BASE_COST
+					.saturating_add(read_currency_a)
+					.saturating_add(read_currency_b)
+					.saturating_add(WeightToGas::convert(weight))
+			}
 			Action::GetSwapTargetAmount => {
 				let path_len = input.u32_at(3)?;
 
@@ -390,8 +495,9 @@ where
 
 				// DEX::TradingPairStatuses (r: 1 * (path_len - 1))
 				// DEX::LiquidityPool (r: 1 * (path_len - 1))
+				// DEX::TradingPairFees (r: 1 * (path_len - 1))
 				let weight = <Runtime as frame_system::Config>::DbWeight::get()
-					.reads(path_len.saturating_sub(1).saturating_mul(2).into());
+					.reads(path_len.saturating_sub(1).saturating_mul(3).into());
 
 				Self::BASE_COST
 					.saturating_add(read_currency)
@@ -408,8 +514,9 @@ where
 
 				// DEX::TradingPairStatuses (r: 1 * (path_len - 1))
 				// DEX::LiquidityPool (r: 1 * (path_len - 1))
+				// DEX::TradingPairFees (r: 1 * (path_len - 1))
 				let weight = <Runtime as frame_system::Config>::DbWeight::get()
-					.reads(path_len.saturating_sub(1).saturating_mul(2).into());
+					.reads(path_len.saturating_sub(1).saturating_mul(3).into());
 
 				Self::BASE_COST
 					.saturating_add(read_currency)
@@ -477,6 +584,30 @@ where
 
 				let weight = <Runtime as module_dex::Config>::WeightInfo::remove_liquidity();
 
+				Self::BASE_COST
+					.saturating_add(read_account)
+					.saturating_add(read_currency_a)
+					.saturating_add(read_currency_b)
+					.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::FlashSwap => {
+				let read_account = InputPricer::<Runtime>::read_accounts(1);
+				let currency_id_a = input.currency_id_at(2)?;
+				let currency_id_b = input.currency_id_at(3)?;
+
+				let read_currency_a = InputPricer::<Runtime>::read_currency(currency_id_a);
+				let read_currency_b = InputPricer::<Runtime>::read_currency(currency_id_b);
+
+				// DEX::TradingPairStatuses (r: 1)
+				// DEX::ConcentratedPools (r: 1)
+				// DEX::FlashSwapLocks (r: 2, w: 2)
+				// DEX::LiquidityPool (r: 2, w: 1)
+				// DEX::TradingPairFees (r: 1)
+				// DEX::ProtocolFee (r: 1)
+				// DEX::AccruedProtocolFees (r: 1, w: 1)
+				// Tokens::Accounts (r: 4, w: 4)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads_writes(13, 8);
+
 				Self::BASE_COST
 					.saturating_add(read_account)
 					.saturating_add(read_currency_a)
@@ -651,6 +782,92 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn get_exchange_fee_works() {
+		new_test_ext().execute_with(|| {
+			// enable RENBTC/AUSD
+			assert_ok!(DexModule::enable_trading_pair(Origin::signed(ALICE), RENBTC, AUSD,));
+
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				RENBTC,
+				AUSD,
+				1_000,
+				1_000_000,
+				0,
+				true
+			));
+
+			let context = Context {
+				address: Default::default(),
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+
+			// getExchangeFee(address,address) -> 0x02cd5bef
+			// RENBTC
+			// AUSD
+			let input = hex! {"
+				02cd5bef
+				000000000000000000000000 0000000000000000000100000000000000000014
+				000000000000000000000000 0000000000000000000100000000000000000001
+			"};
+
+			// 1
+			// 100
+			let expected_output = hex! {"
+				00000000000000000000000000000000 00000000000000000000000000000001
+				00000000000000000000000000000000 00000000000000000000000000000064
+			"};
+
+			let resp = DEXPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, expected_output.to_vec());
+
+			assert_ok!(DexModule::set_trading_pair_fee(
+				Origin::signed(ALICE),
+				RENBTC,
+				AUSD,
+				Some((5, 1000))
+			));
+
+			// 5
+			// 1000
+			let expected_output = hex! {"
+				00000000000000000000000000000000 00000000000000000000000000000005
+				00000000000000000000000000000000 000000000000000000000000000003e8
+			"};
+
+			let resp = DEXPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, expected_output.to_vec());
+
+			// getSwapTargetAmount(address[],uint256) -> 0x4d60beb1
+			// offset
+			// supply_amount
+			// path_len
+			// RENBTC
+			// AUSD
+			let input = hex! {"
+				4d60beb1
+				00000000000000000000000000000000 00000000000000000000000000000000
+				00000000000000000000000000000000 00000000000000000000000000000001
+				00000000000000000000000000000000000000000000000000000000 00000002
+				000000000000000000000000 0000000000000000000100000000000000000014
+				000000000000000000000000 0000000000000000000100000000000000000001
+			"};
+
+			// 994
+			let expected_output = hex! {"
+				00000000000000000000000000000000 000000000000000000000000000003e2
+			"};
+
+			let resp = DEXPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, expected_output.to_vec());
+		});
+	}
+
 	#[test]
 	fn get_swap_supply_amount_works() {
 		new_test_ext().execute_with(|| {
@@ -802,4 +1019,78 @@ mod tests {
 			assert_eq!(resp.output, expected_output.to_vec());
 		});
 	}
+
+	#[test]
+	fn flash_swap_works() {
+		new_test_ext().execute_with(|| {
+			// enable RENBTC/AUSD
+			assert_ok!(DexModule::enable_trading_pair(Origin::signed(ALICE), RENBTC, AUSD,));
+
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				RENBTC,
+				AUSD,
+				1_000,
+				1_000_000,
+				0,
+				true
+			));
+
+			let context = Context {
+				address: Default::default(),
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+
+			// flashSwap(address,address,address,uint256,address,bytes) -> 0x9f234f46
+			// who
+			// RENBTC
+			// AUSD
+			// borrow_amount
+			// RENBTC
+			// offset
+			// data_len
+			let input = hex! {"
+				9f234f46
+				000000000000000000000000 1000000000000000000000000000000000000001
+				000000000000000000000000 0000000000000000000100000000000000000014
+				000000000000000000000000 0000000000000000000100000000000000000001
+				00000000000000000000000000000000 00000000000000000000000000000064
+				000000000000000000000000 0000000000000000000100000000000000000014
+				00000000000000000000000000000000 000000000000000000000000000000c0
+				00000000000000000000000000000000 00000000000000000000000000000000
+			"};
+
+			// 102
+			let expected_output = hex! {"
+				00000000000000000000000000000000 00000000000000000000000000000066
+			"};
+
+			let resp = DEXPrecompile::execute(&input, Some(1_000_000), &context, false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, expected_output.to_vec());
+			assert_eq!(DexModule::get_liquidity_pool(RENBTC, AUSD), (1_002, 1_000_000));
+
+			// borrow more than the liquidity pool
+			let input = hex! {"
+				9f234f46
+				000000000000000000000000 1000000000000000000000000000000000000001
+				000000000000000000000000 0000000000000000000100000000000000000014
+				000000000000000000000000 0000000000000000000100000000000000000001
+				00000000000000000000000000000000 000000000000000000000000000003ea
+				000000000000000000000000 0000000000000000000100000000000000000014
+				00000000000000000000000000000000 000000000000000000000000000000c0
+				00000000000000000000000000000000 00000000000000000000000000000000
+			"};
+
+			assert_noop!(
+				DEXPrecompile::execute(&input, Some(1_000_000), &context, false),
+				PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "InsufficientLiquidity".into(),
+					cost: target_gas_limit(Some(1_000_000)).unwrap(),
+				}
+			);
+		});
+	}
 }
@@ -18,6 +18,7 @@
 
 //! The precompiles for EVM, includes standard Ethereum precompiles, and more:
 //! - MultiCurrency at address `H160::from_low_u64_be(1024)`.
+//! - Arena at address `H160::from_low_u64_be(1030)`, readable by any contract.
 
 #![allow(clippy::upper_case_acronyms)]
 
@@ -29,16 +30,18 @@ use frame_support::log;
 use hex_literal::hex;
 use module_evm::{
 	precompiles::{
-		Blake2F, Bn128Add, Bn128Mul, Bn128Pairing, ECRecover, ECRecoverPublicKey, Identity, IstanbulModexp, Modexp,
-		Precompile, Ripemd160, Sha256, Sha3FIPS256, Sha3FIPS512,
+		Blake2F, Bls12381G1Add, Bls12381G1Msm, Bls12381G2Add, Bls12381G2Msm, Bls12381MapG1, Bls12381MapG2,
+		Bls12381Pairing, Bn128Add, Bn128Mul, Bn128Pairing, ECRecover, ECRecoverPublicKey, Identity, IstanbulModexp,
+		Modexp, P256Verify, Precompile, Ripemd160, Sha256, Sha3FIPS256, Sha3FIPS512,
 	},
 	runner::state::{PrecompileFailure, PrecompileResult, PrecompileSet},
 	Context, ExitRevert,
 };
-use module_support::PrecompileCallerFilter as PrecompileCallerFilterT;
+use module_support::{ArenaManager, PrecompileCallerFilter as PrecompileCallerFilterT};
 use sp_core::H160;
 use sp_std::{collections::btree_set::BTreeSet, marker::PhantomData};
 
+pub mod arena;
 pub mod dex;
 pub mod evm;
 pub mod input;
@@ -48,6 +51,7 @@ pub mod oracle;
 pub mod schedule;
 
 use crate::SystemContractsFilter;
+pub use arena::ArenaPrecompile;
 pub use dex::DEXPrecompile;
 pub use evm::EVMPrecompile;
 pub use multicurrency::MultiCurrencyPrecompile;
@@ -64,10 +68,18 @@ pub const BN_ADD: H160 = H160(hex!("0000000000000000000000000000000000000006"));
 pub const BN_MUL: H160 = H160(hex!("0000000000000000000000000000000000000007"));
 pub const BN_PAIRING: H160 = H160(hex!("0000000000000000000000000000000000000008"));
 pub const BLAKE2F: H160 = H160(hex!("0000000000000000000000000000000000000009"));
+pub const BLS12_G1ADD: H160 = H160(hex!("000000000000000000000000000000000000000b"));
+pub const BLS12_G1MSM: H160 = H160(hex!("000000000000000000000000000000000000000c"));
+pub const BLS12_G2ADD: H160 = H160(hex!("000000000000000000000000000000000000000d"));
+pub const BLS12_G2MSM: H160 = H160(hex!("000000000000000000000000000000000000000e"));
+pub const BLS12_PAIRING: H160 = H160(hex!("000000000000000000000000000000000000000f"));
+pub const BLS12_MAP_FP_TO_G1: H160 = H160(hex!("0000000000000000000000000000000000000010"));
+pub const BLS12_MAP_FP2_TO_G2: H160 = H160(hex!("0000000000000000000000000000000000000011"));
 
 pub const ECRECOVER_PUBLICKEY: H160 = H160(hex!("0000000000000000000000000000000000000080"));
 pub const SHA3_256: H160 = H160(hex!("0000000000000000000000000000000000000081"));
 pub const SHA3_512: H160 = H160(hex!("0000000000000000000000000000000000000082"));
+pub const P256_VERIFY: H160 = H160(hex!("0000000000000000000000000000000000000100"));
 
 pub const MULTI_CURRENCY: H160 = H160(hex!("0000000000000000000000000000000000000400"));
 pub const NFT: H160 = H160(hex!("0000000000000000000000000000000000000401"));
@@ -75,18 +87,21 @@ pub const EVM: H160 = H160(hex!("0000000000000000000000000000000000000402"));
 pub const ORACLE: H160 = H160(hex!("0000000000000000000000000000000000000403"));
 pub const SCHEDULER: H160 = H160(hex!("0000000000000000000000000000000000000404"));
 pub const DEX: H160 = H160(hex!("0000000000000000000000000000000000000405"));
+pub const ARENA: H160 = H160(hex!("0000000000000000000000000000000000000406"));
 
 pub fn target_gas_limit(target_gas: Option<u64>) -> Option<u64> {
 	// srtool support rust 1.57.0
 	target_gas.map(|x| x.checked_div(10).expect("divisor is non-zero; qed").saturating_mul(9)) // 90%
 }
 
-pub struct AllPrecompiles<R> {
+/// All the precompiles of a runtime. `A` is the arena exposed by the `ArenaPrecompile`, only
+/// used by runtimes that activate it.
+pub struct AllPrecompiles<R, A = ()> {
 	active: BTreeSet<H160>,
-	_marker: PhantomData<R>,
+	_marker: PhantomData<(R, A)>,
 }
 
-impl<R> AllPrecompiles<R>
+impl<R, A> AllPrecompiles<R, A>
 where
 	R: module_evm::Config,
 {
@@ -102,10 +117,18 @@ where
 				BN_MUL,
 				BN_PAIRING,
 				BLAKE2F,
+				BLS12_G1ADD,
+				BLS12_G1MSM,
+				BLS12_G2ADD,
+				BLS12_G2MSM,
+				BLS12_PAIRING,
+				BLS12_MAP_FP_TO_G1,
+				BLS12_MAP_FP2_TO_G2,
 				// Non-standard precompile starts with 128
 				ECRECOVER_PUBLICKEY,
 				SHA3_256,
 				SHA3_512,
+				P256_VERIFY,
 				// Acala precompile
 				MULTI_CURRENCY,
 				// NFT,
@@ -130,10 +153,18 @@ where
 				BN_MUL,
 				BN_PAIRING,
 				BLAKE2F,
+				BLS12_G1ADD,
+				BLS12_G1MSM,
+				BLS12_G2ADD,
+				BLS12_G2MSM,
+				BLS12_PAIRING,
+				BLS12_MAP_FP_TO_G1,
+				BLS12_MAP_FP2_TO_G2,
 				// Non-standard precompile starts with 128
 				ECRECOVER_PUBLICKEY,
 				SHA3_256,
 				SHA3_512,
+				P256_VERIFY,
 				// Acala precompile
 				MULTI_CURRENCY,
 				// NFT,
@@ -158,10 +189,18 @@ where
 				BN_MUL,
 				BN_PAIRING,
 				BLAKE2F,
+				BLS12_G1ADD,
+				BLS12_G1MSM,
+				BLS12_G2ADD,
+				BLS12_G2MSM,
+				BLS12_PAIRING,
+				BLS12_MAP_FP_TO_G1,
+				BLS12_MAP_FP2_TO_G2,
 				// Non-standard precompile starts with 128
 				ECRECOVER_PUBLICKEY,
 				SHA3_256,
 				SHA3_512,
+				P256_VERIFY,
 				// Acala precompile
 				MULTI_CURRENCY,
 				NFT,
@@ -169,21 +208,24 @@ where
 				ORACLE,
 				SCHEDULER,
 				DEX,
+				ARENA,
 			]),
 			_marker: Default::default(),
 		}
 	}
 }
 
-impl<R> PrecompileSet for AllPrecompiles<R>
+impl<R, A> PrecompileSet for AllPrecompiles<R, A>
 where
 	R: module_evm::Config,
+	A: ArenaManager,
 	MultiCurrencyPrecompile<R>: Precompile,
 	NFTPrecompile<R>: Precompile,
 	EVMPrecompile<R>: Precompile,
 	OraclePrecompile<R>: Precompile,
 	DEXPrecompile<R>: Precompile,
 	SchedulePrecompile<R>: Precompile,
+	ArenaPrecompile<R, A>: Precompile,
 {
 	fn execute(
 		&self,
@@ -221,6 +263,20 @@ where
 			Some(Bn128Pairing::execute(input, target_gas, context, is_static))
 		} else if address == BLAKE2F {
 			Some(Blake2F::execute(input, target_gas, context, is_static))
+		} else if address == BLS12_G1ADD {
+			Some(Bls12381G1Add::execute(input, target_gas, context, is_static))
+		} else if address == BLS12_G1MSM {
+			Some(Bls12381G1Msm::execute(input, target_gas, context, is_static))
+		} else if address == BLS12_G2ADD {
+			Some(Bls12381G2Add::execute(input, target_gas, context, is_static))
+		} else if address == BLS12_G2MSM {
+			Some(Bls12381G2Msm::execute(input, target_gas, context, is_static))
+		} else if address == BLS12_PAIRING {
+			Some(Bls12381Pairing::execute(input, target_gas, context, is_static))
+		} else if address == BLS12_MAP_FP_TO_G1 {
+			Some(Bls12381MapG1::execute(input, target_gas, context, is_static))
+		} else if address == BLS12_MAP_FP2_TO_G2 {
+			Some(Bls12381MapG2::execute(input, target_gas, context, is_static))
 		}
 		// Non-standard precompile starts with 128
 		else if address == ECRECOVER_PUBLICKEY {
@@ -229,6 +285,12 @@ where
 			Some(Sha3FIPS256::execute(input, target_gas, context, is_static))
 		} else if address == SHA3_512 {
 			Some(Sha3FIPS512::execute(input, target_gas, context, is_static))
+		} else if address == P256_VERIFY {
+			Some(P256Verify::execute(input, target_gas, context, is_static))
+		}
+		// Arena precompile, read only and open to any contract
+		else if address == ARENA {
+			Some(ArenaPrecompile::<R, A>::execute(input, target_gas, context, is_static))
 		}
 		// Acala precompile
 		else {
@@ -66,6 +66,8 @@ pub struct TaskInfo {
 /// Actions:
 /// - Schedule. Rest `input` bytes: `from`, `target`, `value`, `gas_limit`, `storage_limit`,
 ///   `min_delay`, `input_len`, `input_data`.
+/// - ScheduleIdle. Rest `input` bytes: `from`, `target`, `value`, `gas_limit`, `storage_limit`,
+///   `input_len`, `input_data`. Executed when a block leaves enough idle weight.
 pub struct SchedulePrecompile<Runtime>(PhantomData<Runtime>);
 
 #[module_evm_utility_macro::generate_function_selector]
@@ -75,6 +77,7 @@ pub enum Action {
 	Schedule = "scheduleCall(address,address,uint256,uint256,uint256,bytes)",
 	Cancel = "cancelCall(address,bytes)",
 	Reschedule = "rescheduleCall(address,uint256,bytes)",
+	ScheduleIdle = "scheduleIdleCall(address,address,uint256,uint256,uint256,bytes)",
 }
 
 type PalletBalanceOf<T> =
@@ -325,6 +328,50 @@ where
 					cost: target_gas_limit(target_gas).unwrap_or_default(),
 				})?;
 
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: vec![],
+					logs: Default::default(),
+				})
+			}
+			Action::ScheduleIdle => {
+				let from = input.evm_address_at(1)?;
+				let target = input.evm_address_at(2)?;
+				let value = input.balance_at(3)?;
+				let gas_limit = input.u64_at(4)?;
+				let storage_limit = input.u32_at(5)?;
+				// solidity abi enocde bytes will add an length at input[6]
+				let input_len = input.u32_at(7)?;
+				let input_data = input.bytes_at(8, input_len as usize)?;
+
+				log::debug!(
+					target: "evm",
+					"schedule idle call: from: {:?}, target: {:?}, value: {:?}, gas_limit: {:?}, storage_limit: {:?}, input_len: {:?}, input_data: {:?}",
+					from,
+					target,
+					value,
+					gas_limit,
+					storage_limit,
+					input_len,
+					input_data,
+				);
+
+				// the fee for gas_limit is reserved by module_evm
+				module_evm::Pallet::<Runtime>::schedule_idle_call(
+					from,
+					target,
+					input_data,
+					value,
+					gas_limit,
+					storage_limit,
+				)
+				.map_err(|e| PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: Into::<&str>::into(e).as_bytes().to_vec(),
+					cost: target_gas_limit(target_gas).unwrap_or_default(),
+				})?;
+
 				Ok(PrecompileOutput {
 					exit_status: ExitSucceed::Returned,
 					cost: 0,
@@ -358,8 +405,10 @@ mod tests {
 	use super::*;
 
 	use crate::precompile::mock::{
-		alice_evm_addr, bob_evm_addr, new_test_ext, run_to_block, Balances, Event as TestEvent, System, Test,
+		alice_evm_addr, bob_evm_addr, new_test_ext, run_to_block, Balances, Event as TestEvent, IdleScheduler, System,
+		Test,
 	};
+	use frame_support::traits::Hooks;
 	use hex_literal::hex;
 	use sp_core::H160;
 
@@ -591,6 +640,72 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn schedule_idle_call_should_work() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: Default::default(),
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+
+			// scheduleIdleCall(address,address,uint256,uint256,uint256,bytes) -> 0xbb16b501
+			// from
+			// target
+			// value
+			// gas_limit
+			// storage_limit
+			// offset
+			// input_len
+			// transfer bytes4(keccak256(signature)) 0xa9059cbb
+			// to address
+			// amount
+			let input = hex! {"
+				bb16b501
+				000000000000000000000000 1000000000000000000000000000000000000001
+				000000000000000000000000 0000000000000000000100000000000000000000
+				00000000000000000000000000000000 00000000000000000000000000000000
+				000000000000000000000000000000000000000000000000 00000000000493e0
+				00000000000000000000000000000000000000000000000000000000 00000064
+				00000000000000000000000000000000000000000000000000000000 00000000
+				00000000000000000000000000000000000000000000000000000000 00000044
+				a9059cbb
+				000000000000000000000000 1000000000000000000000000000000000000002
+				00000000000000000000000000000000 000000000000000000000000000003e8
+			"};
+
+			let resp = SchedulePrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(module_idle_scheduler::Tasks::<Test>::iter().count(), 1);
+
+			let from_account = <Test as module_evm::Config>::AddressMapping::get_account_id(&alice_evm_addr());
+			let to_account = <Test as module_evm::Config>::AddressMapping::get_account_id(&bob_evm_addr());
+			#[cfg(not(feature = "with-ethereum-compatibility"))]
+			{
+				assert_eq!(Balances::free_balance(from_account.clone()), 999999700000);
+				assert_eq!(Balances::reserved_balance(from_account.clone()), 300000);
+			}
+
+			IdleScheduler::on_idle(System::block_number(), 1_000_000_000);
+			assert_eq!(module_idle_scheduler::Tasks::<Test>::iter().count(), 0);
+			let event = TestEvent::IdleScheduler(module_idle_scheduler::Event::<Test>::TaskDispatched {
+				task_id: 0,
+				result: Ok(()),
+			});
+			assert!(System::events().iter().any(|record| record.event == event));
+
+			// the unused gas is refunded as for `scheduleCall`
+			#[cfg(not(feature = "with-ethereum-compatibility"))]
+			{
+				assert_eq!(Balances::free_balance(from_account.clone()), 999999930074);
+				assert_eq!(Balances::reserved_balance(from_account), 0);
+			}
+			#[cfg(feature = "with-ethereum-compatibility")]
+			assert_eq!(Balances::free_balance(from_account), 999999999000);
+			assert_eq!(Balances::free_balance(to_account), 1000000001000);
+		});
+	}
+
 	#[test]
 	fn task_id_max_and_min() {
 		let task_id = TaskInfo {
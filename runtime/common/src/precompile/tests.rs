@@ -78,3 +78,22 @@ fn precompile_filter_does_not_work_on_non_system_contracts() {
 		.execute(non_system.into(), &[0u8; 1], None, &non_system_caller_context, false)
 		.is_none());
 }
+
+#[test]
+fn precompile_filter_does_not_work_on_arena_precompile() {
+	let mut non_system = [0u8; 20];
+	non_system[0] = 1;
+
+	let non_system_caller_context = Context {
+		address: ARENA,
+		caller: non_system.into(),
+		apparent_value: 0.into(),
+	};
+	// queueLength()
+	let input = hex!("ab91c7b0");
+	let resp = PrecompilesValue::get()
+		.execute(ARENA, &input, None, &non_system_caller_context, true)
+		.unwrap()
+		.unwrap();
+	assert_eq!(resp.output, [0u8; 32].to_vec());
+}
@@ -21,6 +21,12 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2022-03-30, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("karura-dev"), DB CACHE: 1024
+//!
+//! The weights of `reclaim_contract`, `propose_upgrade`, `cancel_upgrade`, `enact_upgrade`,
+//! `deposit_sponsor_budget`, `withdraw_sponsor_budget`, `sponsor_contract`, `unsponsor_contract`,
+//! `sponsored_call`, `set_sponsor_spend_limit`, `submit_verification` and `set_verification_status`
+//! are NOT benchmark results. Their storage accesses are counted from each call and the execution
+//! times are rough upper bounds, to be replaced once these calls are benchmarked.
 
 // Executed Command:
 // target/production/acala
@@ -171,4 +177,112 @@ impl<T: frame_system::Config> module_evm::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(7 as Weight))
 			.saturating_add(T::DbWeight::get().writes(7 as Weight))
 	}
+	// Storage: EVM Accounts (r:1 w:1)
+	// Storage: EVM ContractLastAccessed (r:1 w:1)
+	// Storage: EVM CodeInfos (r:1 w:1)
+	// Storage: EVM ContractStorageSizes (r:1 w:1)
+	// Storage: IdleScheduler NextTaskId (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: IdleScheduler Tasks (r:0 w:1)
+	// Storage: EVM Codes (r:0 w:1)
+	fn reclaim_contract() -> Weight {
+		(105_318_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+	// Storage: EVM Accounts (r:1 w:0)
+	// Storage: EVM UpgradeProposals (r:1 w:1)
+	// Storage: EVM CodeInfos (r:1 w:1)
+	// Storage: EVM Codes (r:0 w:1)
+	fn propose_upgrade(c: u32, ) -> Weight {
+		(58_310_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((5_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+	// Storage: EVM Accounts (r:1 w:0)
+	// Storage: EVM UpgradeProposals (r:1 w:1)
+	// Storage: EVM CodeInfos (r:1 w:1)
+	// Storage: EVM Codes (r:0 w:1)
+	fn cancel_upgrade() -> Weight {
+		(47_962_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+	// Storage: EVM UpgradeProposals (r:1 w:1)
+	// Storage: EVM Accounts (r:1 w:1)
+	// Storage: EVM CodeInfos (r:2 w:1)
+	// Storage: Balances Reserves (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: EVM ContractStorageSizes (r:1 w:1)
+	// Storage: EVM ContractLastAccessed (r:1 w:1)
+	// Storage: EVM Codes (r:0 w:1)
+	fn enact_upgrade() -> Weight {
+		(148_275_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn deposit_sponsor_budget() -> Weight {
+		(36_968_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn withdraw_sponsor_budget() -> Weight {
+		(35_345_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: EVM Accounts (r:1 w:0)
+	// Storage: EVM Sponsorships (r:0 w:1)
+	fn sponsor_contract() -> Weight {
+		(20_508_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: EVM Sponsorships (r:1 w:1)
+	fn unsponsor_contract() -> Weight {
+		(18_745_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: EVM Sponsorships (r:1 w:0)
+	// Storage: EVM SponsorSpendings (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: EvmAccounts EvmAddresses (r:1 w:1)
+	fn sponsored_call() -> Weight {
+		(59_296_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: EVM SponsorSpendings (r:1 w:1)
+	fn set_sponsor_spend_limit() -> Weight {
+		(17_291_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+	// Storage: EVM Accounts (r:1 w:0)
+	// Storage: EVM ContractVerifications (r:0 w:1)
+	fn submit_verification(c: u32, ) -> Weight {
+		(39_779_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((1_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: EVM ContractVerifications (r:1 w:1)
+	fn set_verification_status() -> Weight {
+		(23_290_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
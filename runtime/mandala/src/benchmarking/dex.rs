@@ -18,18 +18,21 @@
 
 use super::utils::dollar;
 use crate::{
-	AccountId, Balance, Currencies, CurrencyId, Dex, Event, ExtendedProvisioningBlocks, GetLiquidCurrencyId,
-	GetNativeCurrencyId, GetStableCurrencyId, GetStakingCurrencyId, Runtime, System,
+	AccountId, Balance, Currencies, CurrencyId, Dex, Event, ExchangeRate, ExtendedProvisioningBlocks, GetLiquidCurrencyId,
+	GetNativeCurrencyId, GetStableCurrencyId, GetStakingCurrencyId, Ratio, Runtime, System, NFT,
 };
 use frame_benchmarking::{account, whitelisted_caller};
 use frame_support::traits::Get;
 use frame_system::RawOrigin;
-use module_dex::TradingPairStatus;
+use module_dex::{RouteHop, TradingPairStatus};
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::{MultiCurrency, MultiCurrencyExtended};
-use primitives::TradingPair;
+use primitives::{
+	nft::{ClassProperty, Properties},
+	TradingPair,
+};
 use runtime_common::{BNC, RENBTC, VSKSM};
-use sp_runtime::traits::UniqueSaturatedInto;
+use sp_runtime::{traits::UniqueSaturatedInto, FixedPointNumber};
 use sp_std::prelude::*;
 
 const SEED: u32 = 0;
@@ -80,6 +83,56 @@ fn inject_liquidity(
 	Ok(())
 }
 
+fn create_concentrated_pool(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> Result<TradingPair, &'static str> {
+	let trading_pair = TradingPair::from_currency_ids(currency_id_a, currency_id_b).unwrap();
+	let _ = Dex::enable_trading_pair(RawOrigin::Root.into(), currency_id_a, currency_id_b);
+	Dex::create_concentrated_pool(RawOrigin::Root.into(), currency_id_a, currency_id_b, 60, 0)?;
+
+	// create the position NFT class, the deposits of position NFTs are paid by the makers
+	let creator: AccountId = account("creator", 0, SEED);
+	let class_id = orml_nft::Pallet::<Runtime>::next_class_id();
+	<Currencies as MultiCurrencyExtended<_>>::update_balance(
+		NATIVE,
+		&creator,
+		(1_000 * dollar(NATIVE)).unique_saturated_into(),
+	)?;
+	NFT::create_class(
+		RawOrigin::Signed(creator).into(),
+		vec![1],
+		Properties(ClassProperty::Transferable | ClassProperty::Mintable),
+		Default::default(),
+	)?;
+	Dex::set_position_nft_class(RawOrigin::Root.into(), class_id)?;
+
+	Ok(trading_pair)
+}
+
+fn open_position(maker: AccountId, trading_pair: TradingPair) -> Result<(), &'static str> {
+	let (currency_id_0, currency_id_1) = (trading_pair.first(), trading_pair.second());
+	<Currencies as MultiCurrencyExtended<_>>::update_balance(
+		currency_id_0,
+		&maker,
+		(10_000 * dollar(currency_id_0)).unique_saturated_into(),
+	)?;
+	<Currencies as MultiCurrencyExtended<_>>::update_balance(
+		currency_id_1,
+		&maker,
+		(10_000 * dollar(currency_id_1)).unique_saturated_into(),
+	)?;
+	Dex::open_position(
+		RawOrigin::Signed(maker).into(),
+		currency_id_0,
+		currency_id_1,
+		-600,
+		600,
+		1_000 * dollar(currency_id_0),
+		1_000 * dollar(currency_id_1),
+		0,
+	)?;
+
+	Ok(())
+}
+
 runtime_benchmarks! {
 	{ Runtime, module_dex }
 
@@ -402,6 +455,135 @@ runtime_benchmarks! {
 
 		System::set_block_number(ExtendedProvisioningBlocks::get() + 1);
 	}: _(RawOrigin::Signed(whitelisted_caller()), trading_pair.first(), trading_pair.second())
+
+	set_trading_pair_fee {
+		let trading_pair = TradingPair::from_currency_ids(STABLECOIN, NATIVE).unwrap();
+	}: _(RawOrigin::Root, trading_pair.first(), trading_pair.second(), Some((5, 1000)))
+	verify {
+		assert_last_event(module_dex::Event::TradingPairFeeUpdated{trading_pair, fee: Some((5, 1000))}.into());
+	}
+
+	set_protocol_fee {
+		let receiver: AccountId = account("receiver", 0, SEED);
+		let share = Ratio::saturating_from_rational(1, 6);
+	}: _(RawOrigin::Root, receiver.clone(), share)
+	verify {
+		assert_last_event(module_dex::Event::ProtocolFeeUpdated{receiver, share}.into());
+	}
+
+	claim_protocol_fee {
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = whitelisted_caller();
+		let receiver: AccountId = account("receiver", 0, SEED);
+		let path = vec![STABLECOIN, NATIVE];
+		inject_liquidity(maker, STABLECOIN, NATIVE, 10_000 * dollar(STABLECOIN), 10_000 * dollar(NATIVE), false)?;
+		Dex::set_protocol_fee(RawOrigin::Root.into(), receiver, Ratio::saturating_from_rational(1, 6))?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(STABLECOIN, &taker, (10_000 * dollar(STABLECOIN)).unique_saturated_into())?;
+		Dex::swap_with_exact_supply(RawOrigin::Signed(taker.clone()).into(), path, 1_000 * dollar(STABLECOIN), 0)?;
+	}: _(RawOrigin::Signed(taker), STABLECOIN)
+	verify {
+		assert_eq!(Dex::accrued_protocol_fees(STABLECOIN), 0);
+	}
+
+	create_concentrated_pool {
+		let trading_pair = TradingPair::from_currency_ids(STABLECOIN, NATIVE).unwrap();
+		let _ = Dex::enable_trading_pair(RawOrigin::Root.into(), STABLECOIN, NATIVE);
+	}: _(RawOrigin::Root, trading_pair.first(), trading_pair.second(), 60, 0)
+	verify {
+		assert_last_event(module_dex::Event::ConcentratedPoolCreated{trading_pair, tick_spacing: 60, tick: 0}.into());
+	}
+
+	set_position_nft_class {
+	}: _(RawOrigin::Root, 0)
+	verify {
+		assert_last_event(module_dex::Event::PositionNFTClassUpdated{class_id: 0}.into());
+	}
+
+	open_position {
+		let maker: AccountId = whitelisted_caller();
+		let trading_pair = create_concentrated_pool(STABLECOIN, NATIVE)?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.first(), &maker, (10_000 * dollar(trading_pair.first())).unique_saturated_into())?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.second(), &maker, (10_000 * dollar(trading_pair.second())).unique_saturated_into())?;
+	}: _(RawOrigin::Signed(maker), trading_pair.first(), trading_pair.second(), -600, 600, 1_000 * dollar(trading_pair.first()), 1_000 * dollar(trading_pair.second()), 0)
+	verify {
+		assert!(Dex::positions(0).is_some());
+	}
+
+	decrease_position {
+		let maker: AccountId = whitelisted_caller();
+		let trading_pair = create_concentrated_pool(STABLECOIN, NATIVE)?;
+		open_position(maker.clone(), trading_pair)?;
+		let liquidity = Dex::positions(0).unwrap().liquidity;
+	}: _(RawOrigin::Signed(maker), 0, liquidity, 0, 0)
+	verify {
+		assert!(Dex::positions(0).is_none());
+	}
+
+	collect_position_fees {
+		let maker: AccountId = whitelisted_caller();
+		let taker: AccountId = account("taker", 0, SEED);
+		let trading_pair = create_concentrated_pool(STABLECOIN, NATIVE)?;
+		open_position(maker.clone(), trading_pair)?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(STABLECOIN, &taker, (1_000 * dollar(STABLECOIN)).unique_saturated_into())?;
+		Dex::swap_with_exact_supply(RawOrigin::Signed(taker).into(), vec![STABLECOIN, NATIVE], 100 * dollar(STABLECOIN), 0)?;
+	}: _(RawOrigin::Signed(maker), 0)
+	verify {
+		assert_eq!(Dex::positions(0).unwrap().tokens_owed, (0, 0));
+	}
+
+	place_limit_order {
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = whitelisted_caller();
+		inject_liquidity(maker, STABLECOIN, NATIVE, 10_000 * dollar(STABLECOIN), 10_000 * dollar(NATIVE), false)?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(STABLECOIN, &taker, (1_000 * dollar(STABLECOIN)).unique_saturated_into())?;
+	}: _(RawOrigin::Signed(taker), STABLECOIN, NATIVE, 100 * dollar(STABLECOIN), ExchangeRate::saturating_from_rational(9, 10), 100)
+	verify {
+		assert!(Dex::limit_orders(0).is_some());
+	}
+
+	cancel_limit_order {
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = whitelisted_caller();
+		inject_liquidity(maker, STABLECOIN, NATIVE, 10_000 * dollar(STABLECOIN), 10_000 * dollar(NATIVE), false)?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(STABLECOIN, &taker, (1_000 * dollar(STABLECOIN)).unique_saturated_into())?;
+		Dex::place_limit_order(RawOrigin::Signed(taker.clone()).into(), STABLECOIN, NATIVE, 100 * dollar(STABLECOIN), ExchangeRate::saturating_from_rational(2, 1), 100)?;
+	}: _(RawOrigin::Signed(taker), 0)
+	verify {
+		assert!(Dex::limit_orders(0).is_none());
+	}
+
+	fill_limit_order {
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = account("taker", 0, SEED);
+		let keeper: AccountId = whitelisted_caller();
+		inject_liquidity(maker, STABLECOIN, NATIVE, 10_000 * dollar(STABLECOIN), 10_000 * dollar(NATIVE), false)?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(STABLECOIN, &taker, (1_000 * dollar(STABLECOIN)).unique_saturated_into())?;
+		Dex::place_limit_order(RawOrigin::Signed(taker).into(), STABLECOIN, NATIVE, 100 * dollar(STABLECOIN), ExchangeRate::saturating_from_rational(9, 10), 100)?;
+	}: _(RawOrigin::Signed(keeper), 0)
+	verify {
+		assert!(Dex::limit_orders(0).is_none());
+	}
+
+	swap_with_routes {
+		let u in 1 .. <Runtime as module_dex::Config>::TradingPathLimit::get() - 1;
+
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = whitelisted_caller();
+
+		let mut route: Vec<RouteHop> = vec![];
+		for i in 1 ..= u as usize {
+			let (cur0, cur1) = (CURRENCY_LIST[i - 1], CURRENCY_LIST[i]);
+			route.push(RouteHop::Dex(cur0, cur1));
+			inject_liquidity(maker.clone(), cur0, cur1, 10_000 * dollar(cur0), 10_000 * dollar(cur1), false)?;
+		}
+		let supply_currency_id = CURRENCY_LIST[0];
+		let target_currency_id = CURRENCY_LIST[u as usize];
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(supply_currency_id, &taker, (10_000 * dollar(supply_currency_id)).unique_saturated_into())?;
+	}: _(RawOrigin::Signed(taker.clone()), supply_currency_id, target_currency_id, vec![(route, 100 * dollar(supply_currency_id))], 0)
+	verify {
+		assert!(Currencies::free_balance(target_currency_id, &taker) > 0);
+	}
 }
 
 #[cfg(test)]
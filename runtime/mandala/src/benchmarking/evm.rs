@@ -17,14 +17,14 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	AccountId, Currencies, CurrencyId, Event, EvmAccounts, GetNativeCurrencyId, NetworkContractSource, Origin, Runtime,
-	System, EVM,
+	AccountId, ContractInactivityPeriod, Currencies, CurrencyId, Event, EvmAccounts, GetNativeCurrencyId,
+	NetworkContractSource, Origin, Runtime, System, UpgradeDelay, EVM,
 };
 
 use super::utils::{dollar, set_balance};
 use frame_support::dispatch::{DispatchError, DispatchResult};
 use frame_system::RawOrigin;
-use module_evm::MaxCodeSize;
+use module_evm::{MaxCodeSize, MaxCompilerSettingsSize, MaxCompilerVersionSize};
 use module_support::AddressMapping;
 use orml_benchmarking::{runtime_benchmarks, whitelist_account};
 use orml_traits::MultiCurrency;
@@ -276,6 +276,147 @@ runtime_benchmarks! {
 
 		whitelist_account!(alice_account);
 	}: _(RawOrigin::Signed(alice_account_id()), contract)
+
+	reclaim_contract {
+		let alice_account = alice_account_id();
+		let bob_account = bob_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+		System::set_block_number(1 + ContractInactivityPeriod::get());
+
+		whitelist_account!(bob_account);
+	}: _(RawOrigin::Signed(bob_account_id()), contract)
+
+	propose_upgrade {
+		let c in 0..MaxCodeSize::get();
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+
+		let new_contract = vec![0; c as usize];
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract, new_contract)
+
+	cancel_upgrade {
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+		EVM::propose_upgrade(Origin::signed(alice_account_id()), contract, vec![0; MaxCodeSize::get() as usize])?;
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract)
+
+	enact_upgrade {
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+		EVM::propose_upgrade(Origin::signed(alice_account_id()), contract, vec![0; MaxCodeSize::get() as usize])?;
+		System::set_block_number(1 + UpgradeDelay::get());
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract)
+
+	deposit_sponsor_budget {
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), 1_000 * dollar(NATIVE))
+
+	withdraw_sponsor_budget {
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		EVM::deposit_sponsor_budget(Origin::signed(alice_account_id()), 1_000 * dollar(NATIVE))?;
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), 1_000 * dollar(NATIVE))
+
+	sponsor_contract {
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract, dollar(NATIVE), 1_000)
+
+	unsponsor_contract {
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+		EVM::sponsor_contract(Origin::signed(alice_account_id()), contract, dollar(NATIVE), 1_000)?;
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract)
+
+	sponsored_call {
+		// Storage.store(1)
+		let input = hex_literal::hex!("6057361d0000000000000000000000000000000000000000000000000000000000000001").to_vec();
+		let alice_account = alice_account_id();
+		let bob_account = bob_account_id();
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		set_balance(NATIVE, &bob_account, dollar(NATIVE));
+
+		// contract address when it gets deployed
+		let contract_address = H160::from(hex_literal::hex!("5e0b4bfa0b55932a3587e648c3552a6515ba56b1"));
+
+		frame_support::assert_ok!(EVM::create(Origin::signed(alice_account.clone()), STORAGE_CONTRACT.to_vec(), 0, 21_000_000, 100_000, vec![]));
+		EVM::publish_free(RawOrigin::Root.into(), contract_address)?;
+		EVM::deposit_sponsor_budget(Origin::signed(alice_account.clone()), 1_000 * dollar(NATIVE))?;
+		EVM::sponsor_contract(Origin::signed(alice_account.clone()), contract_address, dollar(NATIVE), 1_000)?;
+		EVM::set_sponsor_spend_limit(Origin::signed(alice_account.clone()), 1_000 * dollar(NATIVE), 100)?;
+
+		// Storage::number
+		let hashed_key = module_evm::AccountStorages::<Runtime>::hashed_key_for(&contract_address, H256::zero());
+		frame_benchmarking::benchmarking::add_to_whitelist(hashed_key.into());
+
+		whitelist_account!(bob_account);
+	}: _(RawOrigin::Signed(bob_account), alice_account, contract_address, input, 0, 21_000_000, 1_000, vec![])
+	verify {
+		assert_eq!(module_evm::AccountStorages::<Runtime>::get(&contract_address, H256::zero()), H256::from_low_u64_be(1));
+	}
+
+	set_sponsor_spend_limit {
+		let alice_account = alice_account_id();
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), 1_000 * dollar(NATIVE), 100)
+
+	submit_verification {
+		let c in 0..MaxCompilerSettingsSize::get();
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+
+		let compiler_version = vec![0; MaxCompilerVersionSize::get() as usize];
+		let settings = vec![0; c as usize];
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract, compiler_version, settings, H256::zero())
+
+	set_verification_status {
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+		EVM::submit_verification(
+			Origin::signed(alice_account_id()),
+			contract,
+			vec![0; MaxCompilerVersionSize::get() as usize],
+			vec![0; MaxCompilerSettingsSize::get() as usize],
+			H256::zero(),
+		)?;
+		let code_hash = EVM::code_hash_at_address(&contract);
+	}: _(RawOrigin::Root, contract, code_hash, true)
 }
 
 #[cfg(test)]
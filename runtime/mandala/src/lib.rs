@@ -65,7 +65,10 @@ use orml_traits::{
 use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
 use primitives::{
 	define_combined_task,
-	evm::{AccessListItem, EthereumTransactionMessage},
+	evm::{
+		tracing::{Trace, TracerConfig},
+		AccessListInfo, AccessListItem, ContractVerification, EthereumTransactionMessage,
+	},
 	task::TaskResult,
 	unchecked_extrinsic::AcalaUncheckedExtrinsic,
 };
@@ -166,6 +169,7 @@ parameter_types! {
 	pub const TreasuryReservePalletId: PalletId = PalletId(*b"aca/reve");
 	pub const PhragmenElectionPalletId: LockIdentifier = *b"aca/phre";
 	pub const NftPalletId: PalletId = PalletId(*b"aca/aNFT");
+	pub const ArenaPalletId: PalletId = PalletId(*b"aca/arna");
 	pub const NomineesElectionId: LockIdentifier = *b"aca/nome";
 	pub UnreleasedNativeVaultAccountId: AccountId = PalletId(*b"aca/urls").into_account();
 	// This Pallet is only used to payment fee pool, it's not added to whitelist by design.
@@ -192,6 +196,7 @@ pub fn get_all_module_accounts() -> Vec<AccountId> {
 		StarportPalletId::get().into_account(),
 		UnreleasedNativeVaultAccountId::get(),
 		StableAssetPalletId::get().into_account(),
+		ArenaPalletId::get().into_account(),
 	]
 }
 
@@ -1074,6 +1079,7 @@ impl module_emergency_shutdown::Config for Runtime {
 
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (1, 1000);	// 0.1%
+	pub LimitOrderKeeperFee: Ratio = Ratio::saturating_from_rational(1, 1000);	// 0.1%
 	pub EnabledTradingPairs: Vec<TradingPair> = vec![
 		TradingPair::from_currency_ids(AUSD, ACA).unwrap(),
 		TradingPair::from_currency_ids(AUSD, DOT).unwrap(),
@@ -1098,6 +1104,12 @@ impl module_dex::Config for Runtime {
 	type ExtendedProvisioningBlocks = ExtendedProvisioningBlocks;
 	type StableAsset = StableAsset;
 	type OnLiquidityPoolUpdated = ();
+	type PositionNFT = NFT;
+	type MaxInitializedTicks = ConstU32<512>;
+	type MaxLimitOrdersPerAccount = ConstU32<32>;
+	type LimitOrderKeeperFee = LimitOrderKeeperFee;
+	type MaxRouteSplits = ConstU32<4>;
+	type EVM = EVM;
 }
 
 impl module_dex_oracle::Config for Runtime {
@@ -1185,6 +1197,7 @@ impl module_transaction_payment::Config for Runtime {
 	type CustomFeeSurplus = CustomFeeSurplus;
 	type AlternativeFeeSurplus = AlternativeFeeSurplus;
 	type DefaultFeeTokens = DefaultFeeTokens;
+	type FeeSponsor = EVM;
 }
 
 parameter_types! {
@@ -1477,7 +1490,9 @@ impl ecosystem_compound_cash::Config for Runtime {
 parameter_types! {
 	pub const ChainId: u64 = 595;
 	pub NetworkContractSource: H160 = H160::from_low_u64_be(0);
-	pub PrecompilesValue: AllPrecompiles<Runtime> = AllPrecompiles::<_>::mandala();
+	pub PrecompilesValue: AllPrecompiles<Runtime, Arena> = AllPrecompiles::<_, _>::mandala();
+	pub const ContractInactivityPeriod: BlockNumber = 30 * DAYS;
+	pub const UpgradeDelay: BlockNumber = DAYS;
 }
 
 #[cfg(feature = "with-ethereum-compatibility")]
@@ -1528,7 +1543,7 @@ impl module_evm::Config for Runtime {
 	type StorageDepositPerByte = StorageDepositPerByte;
 	type TxFeePerGas = TxFeePerGas;
 	type Event = Event;
-	type PrecompilesType = AllPrecompiles<Self>;
+	type PrecompilesType = AllPrecompiles<Self, Arena>;
 	type PrecompilesValue = PrecompilesValue;
 	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
@@ -1537,8 +1552,11 @@ impl module_evm::Config for Runtime {
 	type NetworkContractSource = NetworkContractSource;
 	type DeveloperDeposit = DeveloperDeposit;
 	type PublicationFee = PublicationFee;
+	type ContractInactivityPeriod = ContractInactivityPeriod;
+	type UpgradeDelay = UpgradeDelay;
 	type TreasuryAccount = TreasuryAccount;
 	type FreePublicationOrigin = EnsureRootOrHalfGeneralCouncil;
+	type VerifierOrigin = EnsureRootOrHalfGeneralCouncil;
 	type Runner = module_evm::runner::stack::Runner<Self>;
 	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
 	type Task = ScheduledTasks;
@@ -1683,6 +1701,28 @@ impl module_idle_scheduler::Config for Runtime {
 	type DisableBlockThreshold = ConstU32<6>;
 }
 
+parameter_types! {
+	pub ArenaEntryStake: Balance = 10 * dollar(ACA);
+	pub ArenaPrizePerRound: Balance = dollar(ACA);
+	pub const ArenaEvictionSlash: Perbill = Perbill::from_percent(10);
+	pub const ArenaContenderGasLimit: u64 = 500_000;
+	pub const ArenaRoundGasBudget: u64 = 50_000_000;
+	pub ArenaOutOfStorageError: sp_runtime::DispatchError = module_evm::Error::<Runtime>::OutOfStorage.into();
+}
+
+/// Randomness of the arena built-in contenders, derived from the parent block hash. It is known
+/// to the block author in advance, which is fine for sparring partners but nothing of value.
+pub struct ArenaRandomness;
+impl Randomness<Hash, BlockNumber> for ArenaRandomness {
+	fn random(subject: &[u8]) -> (Hash, BlockNumber) {
+		let parent_hash = System::parent_hash();
+		(
+			sp_io::hashing::blake2_256(&(parent_hash, subject).encode()).into(),
+			System::block_number().saturating_sub(1),
+		)
+	}
+}
+
 impl module_arena::Config for Runtime {
 	type Event = Event;
 	type EVM = EVM;
@@ -1694,6 +1734,24 @@ impl module_arena::Config for Runtime {
 	type EnqueueCount = ConstU32<5>;
 	type MaxInstancesPerContender = ConstU32<6>;
 	type ContractInvoker = TreasuryAccount;
+	type GameRules = module_arena::RockPaperScissors;
+	type CommitReveal = ConstBool<false>;
+	type RevealDelay = ConstU32<2>;
+	type ContenderGasLimit = ArenaContenderGasLimit;
+	type OutOfStorageError = ArenaOutOfStorageError;
+	type MaxMatchesPerBlock = ConstU32<10>;
+	type MinMatchesPerBlock = ConstU32<1>;
+	type Currency = Balances;
+	type EntryStake = ArenaEntryStake;
+	type PrizePerRound = ArenaPrizePerRound;
+	type EvictionSlash = ArenaEvictionSlash;
+	type PalletId = ArenaPalletId;
+	type LeaderboardSize = ConstU32<20>;
+	type MaxMatchRecords = ConstU32<50>;
+	type RoundGasBudget = ArenaRoundGasBudget;
+	type MaxFailures = ConstU32<10>;
+	type Randomness = ArenaRandomness;
+	type WeightInfo = module_arena::weights::AcalaWeight<Runtime>;
 }
 
 impl cumulus_pallet_aura_ext::Config for Runtime {}
@@ -2215,6 +2273,150 @@ impl_runtime_apis! {
 
 			request.ok_or(sp_runtime::DispatchError::Other("Invalid parameter extrinsic, not evm Call"))
 		}
+
+		fn create_access_list(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+		) -> Result<AccessListInfo, sp_runtime::DispatchError> {
+			module_evm::runner::access_list::create_access_list::<Runtime>(
+				from,
+				to,
+				data,
+				value,
+				gas_limit,
+				storage_limit,
+				access_list.unwrap_or_default(),
+			)
+		}
+
+		fn contract_verification(address: H160) -> Option<ContractVerification> {
+			EVM::current_verification(&address)
+		}
+	}
+
+	impl module_evm_rpc_runtime_api::EVMTraceApi<Block, Balance> for Runtime {
+		fn trace_extrinsic(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			transaction_index: u32,
+			tracer: TracerConfig,
+		) -> Result<Trace, sp_runtime::DispatchError> {
+			#[cfg(feature = "tracing")]
+			{
+				let transaction_index = transaction_index as usize;
+				let transaction = extrinsics
+					.get(transaction_index)
+					.cloned()
+					.ok_or(sp_runtime::DispatchError::Other("Invalid parameter transaction_index"))?;
+
+				for extrinsic in extrinsics.into_iter().take(transaction_index) {
+					let _ = Executive::apply_extrinsic(extrinsic);
+				}
+
+				let (_, trace) = module_evm::runner::tracing::trace(tracer, || Executive::apply_extrinsic(transaction));
+				trace.ok_or(sp_runtime::DispatchError::Other("Invalid parameter transaction_index, not evm transaction"))
+			}
+			#[cfg(not(feature = "tracing"))]
+			{
+				let _ = (extrinsics, transaction_index, tracer);
+				Err(sp_runtime::DispatchError::Other("Runtime is not built with the tracing feature"))
+			}
+		}
+
+		fn trace_call(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			tracer: TracerConfig,
+		) -> Result<Trace, sp_runtime::DispatchError> {
+			#[cfg(feature = "tracing")]
+			{
+				let access_list = access_list.unwrap_or_default().into_iter().map(|v| (v.address, v.storage_keys)).collect();
+				let config = <Runtime as module_evm::Config>::config();
+
+				let (result, trace) = module_evm::runner::tracing::trace(tracer, || match to {
+					Some(to) => module_evm::runner::stack::Runner::<Runtime>::call(
+						from,
+						from,
+						to,
+						data,
+						value,
+						gas_limit,
+						storage_limit,
+						access_list,
+						config,
+					)
+					.map(|_| ()),
+					None => module_evm::runner::stack::Runner::<Runtime>::create(
+						from,
+						data,
+						value,
+						gas_limit,
+						storage_limit,
+						access_list,
+						config,
+					)
+					.map(|_| ()),
+				});
+				result?;
+
+				trace.ok_or(sp_runtime::DispatchError::Other("Execution not traced"))
+			}
+			#[cfg(not(feature = "tracing"))]
+			{
+				let _ = (from, to, data, value, gas_limit, storage_limit, access_list, tracer);
+				Err(sp_runtime::DispatchError::Other("Runtime is not built with the tracing feature"))
+			}
+		}
+	}
+
+	impl module_dex_rpc_runtime_api::DexApi<Block, AccountId, BlockNumber> for Runtime {
+		fn open_limit_orders(
+			who: AccountId,
+		) -> Vec<(module_dex::LimitOrderId, module_dex::LimitOrder<AccountId, BlockNumber>)> {
+			Dex::open_limit_orders(&who)
+		}
+
+		fn get_best_routes(
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			supply_amount: Balance,
+			max_splits: u32,
+		) -> Vec<module_dex::RouteQuote> {
+			Dex::get_best_routes(supply_currency_id, target_currency_id, supply_amount, max_splits)
+		}
+	}
+
+	impl module_arena_rpc_runtime_api::ArenaApi<Block, BlockNumber> for Runtime {
+		fn leaderboard(season: Option<module_arena::SeasonIndex>) -> Vec<(H160, u32)> {
+			Arena::season_leaderboard(season)
+		}
+
+		fn match_history(
+			season: module_arena::SeasonIndex,
+			contract: H160,
+		) -> Vec<module_arena::MatchRecord<BlockNumber>> {
+			Arena::match_history(season, contract)
+		}
+
+		fn contract_rating(contract: H160) -> u32 {
+			Arena::contract_rating(contract)
+		}
+
+		fn dry_run_match(
+			a: module_arena::Contender,
+			b: module_arena::Contender,
+		) -> Result<module_arena::MatchReport, sp_runtime::DispatchError> {
+			Ok(Arena::dry_run_match(a, b))
+		}
 	}
 
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {